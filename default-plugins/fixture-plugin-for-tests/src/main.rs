@@ -12,6 +12,7 @@ struct State {
     received_payload: Option<String>,
     configuration: BTreeMap<String, String>,
     message_to_plugin_payload: Option<String>,
+    plugin_response: Option<PluginResponse>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -65,6 +66,7 @@ impl ZellijPlugin for State {
             EventType::FileSystemCreate,
             EventType::FileSystemUpdate,
             EventType::FileSystemDelete,
+            EventType::PluginResponse,
         ]);
     }
 
@@ -287,6 +289,9 @@ impl ZellijPlugin for State {
                     self.received_payload = Some(payload.clone());
                 }
             },
+            Event::PluginResponse(plugin_response) => {
+                self.plugin_response = Some(plugin_response.clone());
+            },
             Event::SystemClipboardFailure => {
                 // this is just to trigger the worker message
                 post_message_to(PluginMessage {
@@ -302,13 +307,37 @@ impl ZellijPlugin for State {
         should_render
     }
     fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
-        let input_pipe_id = match pipe_message.source {
+        let input_pipe_id = match &pipe_message.source {
             PipeSource::Cli(id) => id.clone(),
             PipeSource::Plugin(id) => format!("{}", id),
         };
         let name = pipe_message.name;
         let payload = pipe_message.payload;
-        if name == "message_name" && payload == Some("message_payload".to_owned()) {
+        if let (Some(request_id), PipeSource::Plugin(requester_plugin_id)) =
+            (pipe_message.request_id, &pipe_message.source)
+        {
+            // requests with any other name are left unanswered so that they time out
+            if name == "plugin_request" {
+                reply_to_plugin_request(
+                    *requester_plugin_id,
+                    PluginResponse::ok(request_id, payload.clone()),
+                );
+            }
+        } else if name == "send_plugin_request" || name == "send_unanswered_plugin_request" {
+            // the payload is the url of the plugin to send the request to
+            let request_name = if name == "send_plugin_request" {
+                "plugin_request"
+            } else {
+                "unanswered_plugin_request"
+            };
+            send_plugin_request(PluginRequest::new(
+                "fixture_request",
+                MessageToPlugin::new(request_name)
+                    .with_plugin_url(payload.clone().unwrap_or_default())
+                    .with_payload("my_request_payload"),
+                std::time::Duration::from_millis(100),
+            ));
+        } else if name == "message_name" && payload == Some("message_payload".to_owned()) {
             unblock_cli_pipe_input(&input_pipe_id);
         } else if name == "message_name_block" {
             block_cli_pipe_input(&input_pipe_id);
@@ -330,6 +359,8 @@ impl ZellijPlugin for State {
             println!("Payload from worker: {:?}", payload);
         } else if let Some(payload) = self.message_to_plugin_payload.take() {
             println!("Payload from self: {:?}", payload);
+        } else if let Some(plugin_response) = self.plugin_response.take() {
+            println!("Plugin response: {:?}", plugin_response);
         } else {
             println!(
                 "Rows: {:?}, Cols: {:?}, Received events: {:?}",
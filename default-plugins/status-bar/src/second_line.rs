@@ -112,6 +112,8 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
     use Direction as Dir;
     use actions::SearchDirection as SDir;
     use actions::SearchOption as SOpt;
+    use actions::CopyModeMotion as CMotion;
+    use actions::CopyModeSelection as CSel;
 
     let mut old_keymap = mi.get_mode_keybinds();
     let s = |string: &str| string.to_string();
@@ -221,7 +223,20 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
             action_key_group(&km, &[&[Action::HalfPageScrollDown], &[Action::HalfPageScrollUp]])),
         (s("Edit scrollback in default editor"), s("Edit"),
             action_key(&km, &[Action::EditScrollback, TO_NORMAL])),
+        (s("Copy mode"), s("Copy"), action_key(&km, &[A::SwitchToMode(IM::Copy)])),
         (s("Select pane"), s("Select"), to_normal_key),
+    ]} else if mi.mode == IM::Copy { vec![
+        (s("Move"), s("Move"), action_key_group(&km, &[
+            &[A::CopyModeMove(CMotion::Left)], &[A::CopyModeMove(CMotion::Down)],
+            &[A::CopyModeMove(CMotion::Up)], &[A::CopyModeMove(CMotion::Right)]])),
+        (s("Word"), s("Word"), action_key_group(&km, &[
+            &[A::CopyModeMove(CMotion::NextWord)], &[A::CopyModeMove(CMotion::PreviousWord)],
+            &[A::CopyModeMove(CMotion::EndOfWord)]])),
+        (s("Select"), s("Select"),
+            action_key(&km, &[A::CopyModeToggleSelection(CSel::Character)])),
+        (s("Select lines"), s("Lines"),
+            action_key(&km, &[A::CopyModeToggleSelection(CSel::Line)])),
+        (s("Copy"), s("Copy"), action_key(&km, &[A::Copy, TO_NORMAL])),
     ]} else if mi.mode == IM::EnterSearch { vec![
         (s("When done"), s("Done"), action_key(&km, &[A::SwitchToMode(IM::Search)])),
         (s("Cancel"), s("Cancel"),
@@ -243,6 +258,8 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
             action_key(&km, &[A::SearchToggleOption(SOpt::Wrap)])),
         (s("Whole words"), s("Whole"),
             action_key(&km, &[A::SearchToggleOption(SOpt::WholeWord)])),
+        (s("Regex"), s("Regex"),
+            action_key(&km, &[A::SearchToggleOption(SOpt::Regex)])),
     ]} else if mi.mode == IM::Session { vec![
         (s("Detach"), s("Detach"), action_key(&km, &[Action::Detach])),
         (s("Session Manager"), s("Manager"), action_key(&km, &[A::LaunchOrFocusPlugin(Default::default(), true, true, false, false), TO_NORMAL])), // not entirely accurate
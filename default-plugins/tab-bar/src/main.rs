@@ -5,7 +5,7 @@ use std::cmp::{max, min};
 use std::collections::BTreeMap;
 use std::convert::TryInto;
 
-use tab::get_tab_to_focus;
+use tab::{get_clicked_line_part, get_tab_to_focus};
 use zellij_tile::prelude::*;
 
 use crate::line::tab_line;
@@ -24,6 +24,8 @@ struct State {
     active_tab_idx: usize,
     mode_info: ModeInfo,
     tab_line: Vec<LinePart>,
+    // the position of the tab the mouse was pressed on, for dragging it elsewhere
+    tab_with_mouse_down: Option<usize>,
 }
 
 static ARROW_SEPARATOR: &str = "";
@@ -65,9 +67,27 @@ impl ZellijPlugin for State {
             },
             Event::Mouse(me) => match me {
                 Mouse::LeftClick(_, col) => {
-                    let tab_to_focus = get_tab_to_focus(&self.tab_line, self.active_tab_idx, col);
-                    if let Some(idx) = tab_to_focus {
-                        switch_tab_to(idx.try_into().unwrap());
+                    self.tab_with_mouse_down =
+                        get_clicked_line_part(&self.tab_line, col).and_then(|p| p.tab_index);
+                },
+                Mouse::Release(_, col) => {
+                    let tab_under_mouse =
+                        get_clicked_line_part(&self.tab_line, col).and_then(|p| p.tab_index);
+                    match (self.tab_with_mouse_down.take(), tab_under_mouse) {
+                        (Some(dragged_tab), Some(dropped_on_tab))
+                            if dragged_tab != dropped_on_tab =>
+                        {
+                            // tabs are indexed starting from 1 so we need to add 1
+                            switch_tab_to(dragged_tab as u32 + 1);
+                            move_tab_to_index(dropped_on_tab as u32 + 1);
+                        },
+                        _ => {
+                            let tab_to_focus =
+                                get_tab_to_focus(&self.tab_line, self.active_tab_idx, col);
+                            if let Some(idx) = tab_to_focus {
+                                switch_tab_to(idx.try_into().unwrap());
+                            }
+                        },
                     }
                 },
                 Mouse::ScrollUp(_) => {
@@ -1,5 +1,13 @@
 use dialoguer::Confirm;
-use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
+use std::{
+    fs::File,
+    io::{self, prelude::*},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::sessions::{
     assert_dead_session, assert_session, assert_session_ne, delete_session as delete_session_impl,
@@ -13,21 +21,26 @@ use zellij_client::{
         config_yaml_to_config_kdl, convert_old_yaml_files, layout_yaml_to_layout_kdl,
     },
     os_input_output::get_client_os_input,
-    start_client as start_client_impl, ClientInfo,
+    start_client as start_client_impl,
+    web_client::{start_web_server as start_web_server_impl, WebAssets},
+    ClientInfo,
 };
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
+    consts::ZELLIJ_WEB_TOKEN_ENV,
     data::ConnectToSession,
     envs,
     input::{
         actions::Action,
         config::{Config, ConfigError},
         options::Options,
+        script::Script,
     },
     miette::{Report, Result},
     nix,
-    setup::Setup,
+    setup::{get_default_data_dir, Setup},
+    uuid::Uuid,
 };
 
 pub(crate) use crate::sessions::list_sessions;
@@ -174,6 +187,44 @@ pub(crate) fn send_action_to_session(
     requested_session_name: Option<String>,
     config: Option<Config>,
 ) {
+    let session_name = session_to_send_actions_to(requested_session_name);
+    attach_with_cli_client(cli_action, &session_name, config);
+}
+
+/// Client entrypoint for `zellij script`
+///
+/// Parses the script, checks session to run it against and attaches with client
+pub(crate) fn run_script(
+    script_file: PathBuf,
+    requested_session_name: Option<String>,
+    config: Option<Config>,
+) {
+    let raw_script = match std::fs::read_to_string(&script_file) {
+        Ok(raw_script) => raw_script,
+        Err(e) => {
+            eprintln!("Failed to read script {}: {}", script_file.display(), e);
+            process::exit(1);
+        },
+    };
+    let script = match Script::from_kdl(&raw_script, script_file.display().to_string(), config) {
+        Ok(script) => script,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    };
+    let session_name = session_to_send_actions_to(requested_session_name);
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    zellij_client::cli_client::start_script_client(Box::new(os_input), &session_name, script);
+}
+
+// exits if the session cannot be determined
+fn session_to_send_actions_to(requested_session_name: Option<String>) -> String {
     match get_active_session() {
         ActiveSession::None => {
             eprintln!("There is no active session!");
@@ -190,7 +241,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            session_name
         },
         ActiveSession::Many => {
             let existing_sessions: Vec<String> = get_sessions()
@@ -200,7 +251,7 @@ pub(crate) fn send_action_to_session(
                 .collect();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    session_name
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
@@ -210,14 +261,14 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                session_name
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
                 list_sessions(false, false);
                 std::process::exit(1);
             }
         },
-    };
+    }
 }
 pub(crate) fn convert_old_config_file(old_config_file: PathBuf) {
     match File::open(&old_config_file) {
@@ -417,6 +468,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                     force_run_commands: false,
                     index: None,
                     options: None,
+                    read_only: false,
                 }));
             } else {
                 opts.command = None;
@@ -436,6 +488,7 @@ pub(crate) fn start_client(opts: CliArgs) {
             force_run_commands,
             index,
             options,
+            ..
         })) = opts.command.clone()
         {
             let config_options = match options.as_deref() {
@@ -598,6 +651,80 @@ pub(crate) fn start_client(opts: CliArgs) {
     }
 }
 
+pub(crate) fn start_web_server(opts: CliArgs, ip: IpAddr, port: u16, token_file: Option<PathBuf>) {
+    let (config, _layout, config_options) = match Setup::from_cli_args(&opts) {
+        Ok(results) => results,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    };
+    let assets_dir = opts
+        .data_dir
+        .clone()
+        .unwrap_or_else(get_default_data_dir)
+        .join("web");
+    let assets = match WebAssets::load(&assets_dir) {
+        Ok(assets) => assets,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+    };
+    let (token, token_is_generated) = match read_web_token(token_file.as_deref()) {
+        Ok(Some(token)) => (token, false),
+        Ok(None) => (Uuid::new_v4().simple().to_string(), true),
+        Err(e) => {
+            eprintln!("Failed to read the token: {}", e);
+            process::exit(1);
+        },
+    };
+    let list_sessions = Arc::new(|| {
+        get_sessions()
+            .map(|sessions| sessions.into_iter().map(|(name, _)| name).collect())
+            .unwrap_or_default()
+    });
+    println!("Serving Zellij sessions on http://{}:{}/", ip, port);
+    if token_is_generated {
+        println!("Log in with the token: {}", token);
+    }
+    if let Err(e) = start_web_server_impl(
+        ip,
+        port,
+        token,
+        assets,
+        config,
+        config_options,
+        list_sessions,
+    ) {
+        eprintln!("Failed to start the web server: {}", e);
+        process::exit(1);
+    }
+}
+
+// the token is never an argument, since the arguments of a process can be seen by other users
+fn read_web_token(token_file: Option<&Path>) -> io::Result<Option<String>> {
+    let token_from_env = std::env::var(ZELLIJ_WEB_TOKEN_ENV).ok();
+    // the sessions started from the browser should not see it
+    std::env::remove_var(ZELLIJ_WEB_TOKEN_ENV);
+    let token = match token_file {
+        Some(token_file) => Some(std::fs::read_to_string(token_file)?),
+        None => token_from_env,
+    };
+    match token.map(|token| token.trim().to_owned()) {
+        Some(token) if token.is_empty() => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the token is empty",
+        )),
+        token => Ok(token),
+    }
+}
+
 fn generate_unique_session_name() -> String {
     let sessions = get_sessions().map(|sessions| {
         sessions
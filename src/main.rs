@@ -29,6 +29,11 @@ fn main() {
             name,
             close_on_exit,
             start_suspended,
+            env,
+            x,
+            y,
+            width,
+            height,
         })) = opts.command
         {
             let skip_plugin_cache = false; // N/A for this action
@@ -42,8 +47,15 @@ fn main() {
                 name,
                 close_on_exit,
                 start_suspended,
+                env,
                 configuration: None,
                 skip_plugin_cache,
+                watch: false,
+                clear_data: false,
+                x,
+                y,
+                width,
+                height,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
@@ -54,6 +66,8 @@ fn main() {
             in_place,
             configuration,
             skip_plugin_cache,
+            watch,
+            clear_data,
         })) = opts.command
         {
             let command_cli_action = CliAction::NewPane {
@@ -66,8 +80,15 @@ fn main() {
                 name: None,
                 close_on_exit: false,
                 start_suspended: false,
+                env: vec![],
                 configuration,
                 skip_plugin_cache,
+                watch,
+                clear_data,
+                x: None,
+                y: None,
+                width: None,
+                height: None,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
@@ -79,6 +100,10 @@ fn main() {
             floating,
             in_place,
             cwd,
+            x,
+            y,
+            width,
+            height,
         })) = opts.command
         {
             let mut file = file;
@@ -95,10 +120,18 @@ fn main() {
                 floating,
                 in_place,
                 cwd,
+                x,
+                y,
+                width,
+                height,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Script { file })) = opts.command {
+            commands::run_script(file, opts.session, config);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
             commands::convert_old_config_file(old_config_file);
             std::process::exit(0);
@@ -159,6 +192,13 @@ fn main() {
     })) = opts.command
     {
         commands::delete_session(target_session, force);
+    } else if let Some(Command::Sessions(Sessions::Web {
+        ip,
+        port,
+        ref token_file,
+    })) = opts.command
+    {
+        commands::start_web_server(opts.clone(), ip, port, token_file.clone());
     } else if let Some(path) = opts.server {
         commands::start_server(path, opts.debug);
     } else {
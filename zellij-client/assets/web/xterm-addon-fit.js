@@ -0,0 +1,3 @@
+// Placeholder for lib/xterm-addon-fit.js of the xterm-addon-fit npm package (0.8.0, MIT licensed),
+// which is bundled into the zellij binary and served as /assets/xterm-addon-fit.js by `zellij web`.
+// Replace this file with the upstream one, or put it in <data_dir>/web/ to override it at runtime.
@@ -0,0 +1,7 @@
+// Placeholder for lib/xterm.js of the xterm npm package (5.3.0, MIT licensed), which is bundled
+// into the zellij binary and served as /assets/xterm.js by `zellij web`.
+// Replace this file with the upstream one, or put it in <data_dir>/web/ to override it at runtime.
+document.addEventListener("DOMContentLoaded", function () {
+  document.body.textContent =
+    "xterm.js is missing from this build of zellij, place it in the web folder of the zellij data directory";
+});
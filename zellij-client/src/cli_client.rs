@@ -3,12 +3,17 @@
 use std::collections::BTreeMap;
 use std::io::BufRead;
 use std::process;
-use std::{fs, path::PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
+use std::{fs, path::PathBuf, thread};
 
 use crate::os_input_output::ClientOsApi;
 use zellij_utils::{
     errors::prelude::*,
-    input::actions::Action,
+    input::{
+        actions::{Action, PaneCondition},
+        script::{Script, ScriptStep},
+    },
     ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
     uuid::Uuid,
 };
@@ -18,6 +23,33 @@ pub fn start_cli_client(
     session_name: &str,
     actions: Vec<Action>,
 ) {
+    let pane_id = connect_to_session(&mut os_input, session_name);
+    for action in actions {
+        match action {
+            Action::CliPipe { .. } => {
+                pipe_client_for_action(&mut os_input, action, pane_id);
+            },
+            action => {
+                single_message_client(&mut os_input, action, pane_id);
+            },
+        }
+    }
+}
+
+/// Runs the steps of a script one after the other, exiting with an error if one of them fails
+pub fn start_script_client(mut os_input: Box<dyn ClientOsApi>, session_name: &str, script: Script) {
+    let pane_id = connect_to_session(&mut os_input, session_name);
+    let result = run_script_steps(&mut os_input, &script.0, pane_id);
+    os_input.send_to_server(ClientToServerMsg::ClientExited);
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(2);
+    }
+    process::exit(0);
+}
+
+// returns the id of the pane we're running in, if any
+fn connect_to_session(os_input: &mut Box<dyn ClientOsApi>, session_name: &str) -> Option<u32> {
     let zellij_ipc_pipe: PathBuf = {
         let mut sock_dir = zellij_utils::consts::ZELLIJ_SOCK_DIR.clone();
         fs::create_dir_all(&sock_dir).unwrap();
@@ -26,48 +58,193 @@ pub fn start_cli_client(
         sock_dir
     };
     os_input.connect_to_server(&*zellij_ipc_pipe);
-    let pane_id = os_input
+    os_input
         .env_variable("ZELLIJ_PANE_ID")
-        .and_then(|e| e.trim().parse().ok());
+        .and_then(|e| e.trim().parse().ok())
+}
 
-    for action in actions {
-        match action {
-            Action::CliPipe {
-                pipe_id,
-                name,
-                payload,
-                plugin,
-                args,
-                configuration,
-                launch_new,
-                skip_cache,
-                floating,
-                in_place,
-                cwd,
-                pane_title,
+fn run_script_steps(
+    os_input: &mut Box<dyn ClientOsApi>,
+    steps: &[ScriptStep],
+    pane_id: Option<u32>,
+) -> Result<(), String> {
+    for step in steps {
+        match step {
+            ScriptStep::Actions(actions) => {
+                for action in actions {
+                    script_action_client(os_input, action.clone(), pane_id)?;
+                }
+            },
+            ScriptStep::WaitForPane {
+                pane,
+                condition,
+                timeout,
             } => {
-                pipe_client(
-                    &mut os_input,
-                    pipe_id,
-                    name,
-                    payload,
-                    plugin,
-                    args,
-                    configuration,
-                    launch_new,
-                    skip_cache,
-                    floating,
-                    in_place,
+                let report =
+                    pane_wait_client(os_input, pane, condition.clone(), true, *timeout, pane_id)?;
+                if report.is_none() {
+                    return Err(match condition {
+                        PaneCondition::OutputMatches(pattern) => format!(
+                            "Pane '{}' was closed before printing a line matching '{}'",
+                            pane, pattern
+                        ),
+                        PaneCondition::Exited => format!("Failed to wait for pane '{}'", pane),
+                    });
+                }
+            },
+            ScriptStep::Sleep(duration) => thread::sleep(*duration),
+            ScriptStep::If {
+                pane,
+                condition,
+                then,
+                otherwise,
+            } => {
+                let report = pane_wait_client(
+                    os_input,
+                    pane,
+                    condition.pane_condition(),
+                    false,
+                    None,
                     pane_id,
-                    cwd,
-                    pane_title,
-                );
+                )?;
+                if condition.is_met(report.as_deref()) {
+                    run_script_steps(os_input, then, pane_id)?;
+                } else {
+                    run_script_steps(os_input, otherwise, pane_id)?;
+                }
             },
-            action => {
-                single_message_client(&mut os_input, action, pane_id);
+        }
+    }
+    Ok(())
+}
+
+// like single_message_client, but returns once the action was handled rather than exiting
+fn script_action_client(
+    os_input: &mut Box<dyn ClientOsApi>,
+    action: Action,
+    pane_id: Option<u32>,
+) -> Result<(), String> {
+    if let Action::CliPipe { .. } = action {
+        pipe_client_for_action(os_input, action, pane_id);
+        return Ok(());
+    }
+    os_input.send_to_server(ClientToServerMsg::Action(action, pane_id, None));
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::UnblockInputThread, _)) => return Ok(()),
+            Some((ServerToClientMsg::Log(log_lines), _)) => {
+                log_lines.iter().for_each(|line| println!("{line}"));
+                return Ok(());
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                return Err(log_lines.join("\n"));
+            },
+            Some((ServerToClientMsg::Exit(ExitReason::Error(e)), _)) => return Err(e),
+            // the rest of the script cannot run without the session
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => {
+                return Err(format!("The session ended: {}", exit_reason))
+            },
+            None => return Err("Lost connection to the session".to_owned()),
+            _ => {},
+        }
+    }
+}
+
+// asks the server about a pane through a cli pipe (see `Action::WaitForPane`), returning what it
+// reported if the condition was met
+fn pane_wait_client(
+    os_input: &mut Box<dyn ClientOsApi>,
+    pane: &str,
+    condition: PaneCondition,
+    block: bool,
+    timeout: Option<Duration>,
+    pane_id: Option<u32>,
+) -> Result<Option<String>, String> {
+    let pipe_id = Uuid::new_v4().to_string();
+    let (done, wait_ended) = channel::<()>();
+    if let Some(timeout) = timeout {
+        let pane = pane.to_owned();
+        thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = wait_ended.recv_timeout(timeout) {
+                eprintln!("Timed out waiting for pane '{}'", pane);
+                process::exit(2);
+            }
+        });
+    }
+    os_input.send_to_server(ClientToServerMsg::Action(
+        Action::WaitForPane {
+            pipe_id: pipe_id.clone(),
+            pane: pane.to_owned(),
+            condition,
+            block,
+        },
+        pane_id,
+        None,
+    ));
+    let mut report = None;
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::CliPipeOutput(pipe_name, output), _))
+                if pipe_name == pipe_id =>
+            {
+                report = Some(output);
+            },
+            Some((ServerToClientMsg::UnblockCliPipeInput(pipe_name), _))
+                if pipe_name == pipe_id =>
+            {
+                break;
             },
+            Some((ServerToClientMsg::Exit(ExitReason::Error(e)), _)) => return Err(e),
+            Some((ServerToClientMsg::Exit(_), _)) | None => {
+                return Err(format!(
+                    "The session ended while waiting for pane '{}'",
+                    pane
+                ));
+            },
+            _ => {},
         }
     }
+    let _ = done.send(());
+    Ok(report)
+}
+
+fn pipe_client_for_action(
+    os_input: &mut Box<dyn ClientOsApi>,
+    action: Action,
+    pane_id: Option<u32>,
+) {
+    if let Action::CliPipe {
+        pipe_id,
+        name,
+        payload,
+        plugin,
+        args,
+        configuration,
+        launch_new,
+        skip_cache,
+        floating,
+        in_place,
+        cwd,
+        pane_title,
+    } = action
+    {
+        pipe_client(
+            os_input,
+            pipe_id,
+            name,
+            payload,
+            plugin,
+            args,
+            configuration,
+            launch_new,
+            skip_cache,
+            floating,
+            in_place,
+            pane_id,
+            cwd,
+            pane_title,
+        );
+    }
 }
 
 fn pipe_client(
@@ -0,0 +1,93 @@
+//! Reloads the configuration of a running client when its config file changes.
+use std::time::Duration;
+
+use crate::InputInstruction;
+use zellij_utils::{
+    channels::SenderWithContext,
+    cli::CliArgs,
+    errors::prelude::*,
+    input::config::{Config, ConfigError},
+    miette::{GraphicalReportHandler, GraphicalTheme},
+    notify_debouncer_full::{
+        new_debouncer,
+        notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher},
+        DebounceEventResult, Debouncer, FileIdMap,
+    },
+    setup::Setup,
+};
+
+const DEBOUNCE_DURATION_MS: u64 = 400;
+
+/// Watches the config file these `opts` point to, sending the reparsed configuration (or the
+/// error that prevented parsing it) to the input thread whenever it is changed.
+///
+/// The parent folder is watched rather than the file itself, because many editors save by
+/// replacing the file, which would otherwise end the watch after the first change.
+pub(crate) fn watch_config_file(
+    opts: CliArgs,
+    send_input_instructions: SenderWithContext<InputInstruction>,
+) -> Result<Option<Debouncer<RecommendedWatcher, FileIdMap>>> {
+    let config_file_path = match Config::config_file_path(&opts) {
+        Some(config_file_path) => config_file_path,
+        None => return Ok(None),
+    };
+    let config_dir = match config_file_path.parent() {
+        Some(config_dir) if config_dir.is_dir() => config_dir.to_path_buf(),
+        _ => return Ok(None),
+    };
+    let watched_file = config_file_path.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let config_file_changed = events.iter().any(|event| {
+                    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                        && event.paths.iter().any(|path| path == &watched_file)
+                });
+                if config_file_changed && watched_file.exists() {
+                    reload_config(&opts, &send_input_instructions);
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("watch error: {error:?}")),
+        },
+    )
+    .with_context(|| format!("failed to watch config file {config_file_path:?}"))?;
+
+    debouncer
+        .watcher()
+        .watch(&config_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("failed to watch config folder {config_dir:?}"))?;
+    Ok(Some(debouncer))
+}
+
+fn reload_config(opts: &CliArgs, send_input_instructions: &SenderWithContext<InputInstruction>) {
+    let instruction = match Setup::reload_config(opts) {
+        Ok((config, config_options)) => {
+            log::info!("Config file changed, reconfiguring client");
+            InputInstruction::Reconfigure(Box::new(config), Box::new(config_options))
+        },
+        Err(e) => {
+            log::error!("Failed to reload config: {e}");
+            InputInstruction::ConfigReloadError(format_config_error(e))
+        },
+    };
+    let _ = send_input_instructions.send(instruction);
+}
+
+fn format_config_error(error: ConfigError) -> String {
+    match error {
+        ConfigError::KdlError(kdl_error) => {
+            let mut out = String::new();
+            match GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+                .render_report(&mut out, &kdl_error)
+            {
+                Ok(()) => out,
+                Err(_) => kdl_error.error_message,
+            }
+        },
+        e => format!("Failed to reload configuration: {e}"),
+    }
+}
@@ -5,12 +5,13 @@ use crate::{
 };
 use zellij_utils::{
     channels::{Receiver, SenderWithContext, OPENCALLS},
-    data::{InputMode, Key},
+    data::{InputMode, Key, KeyEventType, Style},
     errors::{ContextType, ErrorContext, FatalError},
     input::{
         actions::Action,
         cast_termwiz_key,
         config::Config,
+        keybinds::PluginKeybind,
         mouse::{MouseButton, MouseEvent},
         options::Options,
     },
@@ -45,6 +46,8 @@ struct InputHandler {
     receive_input_instructions: Receiver<(InputInstruction, ErrorContext)>,
     holding_mouse: Option<HeldMouseButton>,
     mouse_mode_active: bool,
+    /// Keys bound by plugins, which take precedence over the configured keybinds
+    plugin_keybinds: Vec<PluginKeybind>,
 }
 
 impl InputHandler {
@@ -69,6 +72,7 @@ impl InputHandler {
             receive_input_instructions,
             holding_mouse: None,
             mouse_mode_active: false,
+            plugin_keybinds: vec![],
         }
     }
 
@@ -92,7 +96,7 @@ impl InputHandler {
                     match input_event {
                         InputEvent::Key(key_event) => {
                             let key = cast_termwiz_key(key_event, &raw_bytes);
-                            self.handle_key(&key, raw_bytes);
+                            self.handle_key(&key, raw_bytes, false);
                         },
                         InputEvent::Mouse(mouse_event) => {
                             let mouse_event =
@@ -136,9 +140,22 @@ impl InputHandler {
                         _ => {},
                     }
                 },
+                Ok((InputInstruction::KeyWithModifiersEvent(key, event_type), _error_context)) => {
+                    self.handle_key_event(&key, event_type);
+                },
                 Ok((InputInstruction::SwitchToMode(input_mode), _error_context)) => {
                     self.mode = input_mode;
                 },
+                Ok((InputInstruction::PluginKeybinds(plugin_keybinds), _error_context)) => {
+                    self.plugin_keybinds = plugin_keybinds;
+                },
+                Ok((InputInstruction::Reconfigure(config, options), _error_context)) => {
+                    self.reconfigure(*config, *options);
+                },
+                Ok((InputInstruction::ConfigReloadError(error), _error_context)) => {
+                    self.os_input
+                        .send_to_server(ClientToServerMsg::ConfigReloadError(error));
+                },
                 Ok((
                     InputInstruction::AnsiStdinInstructions(ansi_stdin_instructions),
                     _error_context,
@@ -164,11 +181,57 @@ impl InputHandler {
             }
         }
     }
-    fn handle_key(&mut self, key: &Key, raw_bytes: Vec<u8>) {
+    /// Switches to a reloaded configuration and sends the parts of it that concern the session
+    /// to the server
+    fn reconfigure(&mut self, config: Config, options: Options) {
+        let mouse_mode = options.mouse_mode.unwrap_or(true);
+        if mouse_mode != self.options.mouse_mode.unwrap_or(true) {
+            if mouse_mode {
+                self.os_input.enable_mouse().non_fatal();
+            } else {
+                self.os_input.disable_mouse().non_fatal();
+            }
+            self.mouse_mode_active = mouse_mode;
+        }
+        let style = Style {
+            colors: config
+                .theme_config(&options)
+                .unwrap_or_else(|| self.os_input.load_palette()),
+            rounded_corners: config.ui.pane_frames.rounded_corners,
+            hide_session_name: config.ui.pane_frames.hide_session_name,
+        };
+        self.os_input
+            .send_to_server(ClientToServerMsg::ReconfigureClient(
+                config.keybinds.clone(),
+                style,
+                Box::new(options.clone()),
+            ));
+        self.config = config;
+        self.options = options;
+    }
+    fn handle_key(&mut self, key: &Key, raw_bytes: Vec<u8>, is_kitty_keyboard_protocol: bool) {
         let keybinds = &self.config.keybinds;
-        for action in
-            keybinds.get_actions_for_key_in_mode_or_default_action(&self.mode, key, raw_bytes)
-        {
+        for action in keybinds.get_actions_for_key_with_plugin_keybinds(
+            &self.plugin_keybinds,
+            &self.mode,
+            key,
+            raw_bytes,
+            is_kitty_keyboard_protocol,
+        ) {
+            let should_exit = self.dispatch_action(action, None);
+            if should_exit {
+                self.should_exit = true;
+            }
+        }
+    }
+    fn handle_key_event(&mut self, key: &Key, event_type: KeyEventType) {
+        let keybinds = &self.config.keybinds;
+        for action in keybinds.get_actions_for_key_event_with_plugin_keybinds(
+            &self.plugin_keybinds,
+            &self.mode,
+            key,
+            event_type,
+        ) {
             let should_exit = self.dispatch_action(action, None);
             if should_exit {
                 self.should_exit = true;
@@ -202,6 +265,9 @@ impl InputHandler {
                     .send(ClientInstruction::SetSynchronizedOutput(enabled))
                     .unwrap();
             },
+            AnsiStdinInstruction::KittyKeyboardProtocol => {
+                self.os_input.enable_kitty_keyboard_protocol().non_fatal();
+            },
         }
     }
     fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) {
@@ -0,0 +1,170 @@
+//! Parses the key events terminal emulators send when the kitty keyboard protocol is enabled
+//! (https://sw.kovidgoyal.net/kitty/keyboard-protocol/) as well as the modified key sequences it
+//! shares with the legacy xterm encoding (eg. <ESC>[1;6D for Ctrl+Shift+Left), since termwiz
+//! drops most of the modifiers of these.
+use zellij_utils::data::{BareKey, Key, KeyEventType, KeyModifiers};
+
+const BRACKETED_PASTE_START: &[u8] = b"\x1b[200~";
+const BRACKETED_PASTE_END: &[u8] = b"\x1b[201~";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub event_type: KeyEventType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StdinChunk {
+    // None if this is a key we cannot represent (eg. a modifier key pressed on its own), in which
+    // case it should be dropped rather than sent to the application as is
+    KeyEvent(Option<KeyEvent>),
+    // Anything else, to be parsed by termwiz
+    Bytes(Vec<u8>),
+}
+
+/// Splits the bytes read from STDIN into the key events we parse ourselves and the rest of the
+/// bytes, keeping their order.
+pub fn split_key_events(buf: &[u8]) -> Vec<StdinChunk> {
+    let mut chunks = vec![];
+    let mut bytes = vec![];
+    let mut index = 0;
+    while index < buf.len() {
+        if buf[index..].starts_with(BRACKETED_PASTE_START) {
+            // pasted text is passed on as is, even if it happens to contain key sequences
+            let paste_length = find(&buf[index..], BRACKETED_PASTE_END)
+                .map(|end| end + BRACKETED_PASTE_END.len())
+                .unwrap_or(buf.len() - index);
+            bytes.extend_from_slice(&buf[index..index + paste_length]);
+            index += paste_length;
+            continue;
+        }
+        match parse_key_sequence(&buf[index..]) {
+            Some((key_event, length)) => {
+                if !bytes.is_empty() {
+                    chunks.push(StdinChunk::Bytes(bytes.drain(..).collect()));
+                }
+                chunks.push(StdinChunk::KeyEvent(key_event));
+                index += length;
+            },
+            None => {
+                bytes.push(buf[index]);
+                index += 1;
+            },
+        }
+    }
+    if !bytes.is_empty() {
+        chunks.push(StdinChunk::Bytes(bytes));
+    }
+    chunks
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+// Returns the parsed key event and the length of its sequence if `buf` starts with a sequence we
+// handle ourselves
+fn parse_key_sequence(buf: &[u8]) -> Option<(Option<KeyEvent>, usize)> {
+    if !buf.starts_with(b"\x1b[") {
+        return None;
+    }
+    let parameters_length = buf[2..]
+        .iter()
+        .position(|byte| !(byte.is_ascii_digit() || *byte == b';' || *byte == b':'))?;
+    let parameters = std::str::from_utf8(&buf[2..2 + parameters_length]).ok()?;
+    let terminator = *buf.get(2 + parameters_length)? as char;
+    let sequence_length = 3 + parameters_length;
+    let mut fields = parameters.split(';');
+    let key_code = fields.next().unwrap_or("");
+    let modifiers_and_event_type = fields.next();
+    let (modifiers, event_type) = match modifiers_and_event_type {
+        Some(field) => parse_modifiers_and_event_type(field)?,
+        None => (KeyModifiers::default(), KeyEventType::Press),
+    };
+    let bare_key = match terminator {
+        'u' => {
+            // the key code might be followed by alternate key codes, which we do not request
+            let key_code = key_code.split(':').next()?.parse::<u32>().ok()?;
+            bare_key_from_key_code(key_code)
+        },
+        // these are only sent this way with modifiers, otherwise we leave them to termwiz
+        'A' | 'B' | 'C' | 'D' | 'F' | 'H' | 'P' | 'Q' | 'S'
+            if modifiers_and_event_type.is_some() && (key_code.is_empty() || key_code == "1") =>
+        {
+            match terminator {
+                'A' => Some(BareKey::Up),
+                'B' => Some(BareKey::Down),
+                'C' => Some(BareKey::Right),
+                'D' => Some(BareKey::Left),
+                'F' => Some(BareKey::End),
+                'H' => Some(BareKey::Home),
+                'P' => Some(BareKey::F(1)),
+                'Q' => Some(BareKey::F(2)),
+                _ => Some(BareKey::F(4)),
+            }
+        },
+        '~' if modifiers_and_event_type.is_some() => match key_code.parse::<u32>().ok()? {
+            2 => Some(BareKey::Insert),
+            3 => Some(BareKey::Delete),
+            5 => Some(BareKey::PageUp),
+            6 => Some(BareKey::PageDown),
+            7 => Some(BareKey::Home),
+            8 => Some(BareKey::End),
+            11 => Some(BareKey::F(1)),
+            12 => Some(BareKey::F(2)),
+            13 => Some(BareKey::F(3)),
+            14 => Some(BareKey::F(4)),
+            15 => Some(BareKey::F(5)),
+            17 => Some(BareKey::F(6)),
+            18 => Some(BareKey::F(7)),
+            19 => Some(BareKey::F(8)),
+            20 => Some(BareKey::F(9)),
+            21 => Some(BareKey::F(10)),
+            23 => Some(BareKey::F(11)),
+            24 => Some(BareKey::F(12)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let key_event = bare_key.map(|bare_key| KeyEvent {
+        key: Key::with_modifiers(bare_key, modifiers),
+        event_type,
+    });
+    Some((key_event, sequence_length))
+}
+
+fn parse_modifiers_and_event_type(field: &str) -> Option<(KeyModifiers, KeyEventType)> {
+    let mut parts = field.split(':');
+    let modifiers = match parts.next() {
+        Some("") | None => KeyModifiers::default(),
+        // caps lock and num lock (the higher bits) are ignored here
+        Some(modifiers) => KeyModifiers::decode((modifiers.parse::<u16>().ok()? & 0xff) as u8),
+    };
+    let event_type = match parts.next() {
+        Some("2") => KeyEventType::Repeat,
+        Some("3") => KeyEventType::Release,
+        _ => KeyEventType::Press,
+    };
+    Some((modifiers, event_type))
+}
+
+fn bare_key_from_key_code(key_code: u32) -> Option<BareKey> {
+    match key_code {
+        9 => Some(BareKey::Tab),
+        13 => Some(BareKey::Enter),
+        27 => Some(BareKey::Esc),
+        127 => Some(BareKey::Backspace),
+        // keypad digits
+        57399..=57408 => char::from_digit(key_code - 57399, 10).map(BareKey::Char),
+        // the rest of the private use area is used for keys we don't support (eg. media keys or
+        // modifier keys on their own)
+        57344..=63743 => None,
+        key_code => char::from_u32(key_code).map(BareKey::Char),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/keyboard_parser_tests.rs"]
+mod keyboard_parser_tests;
@@ -2,10 +2,13 @@ pub mod os_input_output;
 
 pub mod cli_client;
 mod command_is_executing;
+mod config_watcher;
 mod input_handler;
+mod keyboard_parser;
 pub mod old_config_converter;
 mod stdin_ansi_parser;
 mod stdin_handler;
+pub mod web_client;
 
 use log::info;
 use std::env::current_exe;
@@ -24,14 +27,17 @@ use crate::{
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     consts::{set_permissions, ZELLIJ_SOCK_DIR},
-    data::{ClientId, ConnectToSession, InputMode, Style},
+    data::{ClientId, ConnectToSession, InputMode, Key, KeyEventType, Style},
     envs,
     errors::{ClientContext, ContextType, ErrorInstruction},
-    input::{config::Config, options::Options},
+    input::{config::Config, keybinds::PluginKeybind, options::Options},
     ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
     termwiz::input::InputEvent,
 };
-use zellij_utils::{cli::CliArgs, input::layout::Layout};
+use zellij_utils::{
+    cli::{CliArgs, Command as CliCommand, Sessions},
+    input::layout::Layout,
+};
 
 /// Instructions related to the client-side application
 #[derive(Debug, Clone)]
@@ -51,6 +57,7 @@ pub(crate) enum ClientInstruction {
     SetSynchronizedOutput(Option<SyncOutput>),
     UnblockCliPipeInput(String),   // String -> pipe name
     CliPipeOutput(String, String), // String -> pipe name, String -> output
+    PluginKeybinds(Vec<PluginKeybind>),
 }
 
 impl From<ServerToClientMsg> for ClientInstruction {
@@ -75,6 +82,9 @@ impl From<ServerToClientMsg> for ClientInstruction {
             ServerToClientMsg::CliPipeOutput(pipe_name, output) => {
                 ClientInstruction::CliPipeOutput(pipe_name, output)
             },
+            ServerToClientMsg::PluginKeybinds(plugin_keybinds) => {
+                ClientInstruction::PluginKeybinds(plugin_keybinds)
+            },
         }
     }
 }
@@ -97,6 +107,7 @@ impl From<&ClientInstruction> for ClientContext {
             ClientInstruction::SetSynchronizedOutput(..) => ClientContext::SetSynchronisedOutput,
             ClientInstruction::UnblockCliPipeInput(..) => ClientContext::UnblockCliPipeInput,
             ClientInstruction::CliPipeOutput(..) => ClientContext::CliPipeOutput,
+            ClientInstruction::PluginKeybinds(..) => ClientContext::PluginKeybinds,
         }
     }
 }
@@ -148,7 +159,11 @@ impl ClientInfo {
 #[derive(Debug, Clone)]
 pub(crate) enum InputInstruction {
     KeyEvent(InputEvent, Vec<u8>),
+    KeyWithModifiersEvent(Key, KeyEventType),
     SwitchToMode(InputMode),
+    PluginKeybinds(Vec<PluginKeybind>),
+    Reconfigure(Box<Config>, Box<Options>),
+    ConfigReloadError(String),
     AnsiStdinInstructions(Vec<AnsiStdinInstruction>),
     StartedParsing,
     DoneParsing,
@@ -203,6 +218,7 @@ pub fn start_client(
             hide_session_name: config.ui.pane_frames.hide_session_name,
         },
         keybinds: config.keybinds.clone(),
+        is_read_only: false,
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -213,15 +229,26 @@ pub fn start_client(
         sock_dir
     };
 
+    let watched_opts = opts.clone();
     let (first_msg, ipc_pipe) = match info {
         ClientInfo::Attach(name, config_options) => {
             envs::set_session_name(name.clone());
             os_input.update_session_name(name);
             let ipc_pipe = create_ipc_pipe();
+            let is_read_only = matches!(
+                opts.command,
+                Some(CliCommand::Sessions(Sessions::Attach {
+                    read_only: true,
+                    ..
+                }))
+            );
 
             (
                 ClientToServerMsg::AttachClient(
-                    client_attributes,
+                    ClientAttributes {
+                        is_read_only,
+                        ..client_attributes
+                    },
                     config_options,
                     tab_position_to_focus,
                     pane_id_to_focus,
@@ -243,6 +270,7 @@ pub fn start_client(
                     Box::new(config_options.clone()),
                     Box::new(layout.unwrap()),
                     Some(config.plugins.clone()),
+                    config.triggers.clone(),
                 ),
                 ipc_pipe,
             )
@@ -281,6 +309,13 @@ pub fn start_client(
         })
     });
 
+    // kept alive for as long as the client runs
+    let _config_watcher =
+        config_watcher::watch_config_file(watched_opts, send_input_instructions.clone())
+            .map_err(|e| log::error!("Failed to watch config file: {e:?}"))
+            .ok()
+            .flatten();
+
     let on_force_close = config_options.on_force_close.unwrap_or_default();
     let stdin_ansi_parser = Arc::new(Mutex::new(StdinAnsiParser::new()));
 
@@ -381,6 +416,7 @@ pub fn start_client(
         let goto_start_of_last_line = format!("\u{1b}[{};{}H", full_screen_ws.rows, 1);
         let restore_snapshot = "\u{1b}[?1049l";
         os_input.disable_mouse().non_fatal();
+        os_input.disable_kitty_keyboard_protocol().non_fatal();
         let error = format!(
             "{}\n{}{}\n",
             restore_snapshot, goto_start_of_last_line, backtrace
@@ -481,6 +517,11 @@ pub fn start_client(
                     .send(InputInstruction::SwitchToMode(input_mode))
                     .unwrap();
             },
+            ClientInstruction::PluginKeybinds(plugin_keybinds) => {
+                send_input_instructions
+                    .send(InputInstruction::PluginKeybinds(plugin_keybinds))
+                    .unwrap();
+            },
             ClientInstruction::Log(lines_to_log) => {
                 for line in lines_to_log {
                     log::info!("{line}");
@@ -516,12 +557,14 @@ pub fn start_client(
         );
 
         os_input.disable_mouse().non_fatal();
+        os_input.disable_kitty_keyboard_protocol().non_fatal();
         info!("{}", exit_msg);
         os_input.unset_raw_mode(0).unwrap();
         let mut stdout = os_input.get_stdout_writer();
         let _ = stdout.write(goodbye_message.as_bytes()).unwrap();
         stdout.flush().unwrap();
     } else {
+        os_input.disable_kitty_keyboard_protocol().non_fatal();
         let clear_screen = "\u{1b}[2J";
         let mut stdout = os_input.get_stdout_writer();
         let _ = stdout.write(clear_screen.as_bytes()).unwrap();
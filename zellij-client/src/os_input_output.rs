@@ -13,6 +13,7 @@ use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::{io, thread, time};
 use zellij_utils::{
+    channels::Receiver,
     data::Palette,
     errors::ErrorContext,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
@@ -21,8 +22,14 @@ use zellij_utils::{
 
 const SIGWINCH_CB_THROTTLE_DURATION: time::Duration = time::Duration::from_millis(50);
 
-const ENABLE_MOUSE_SUPPORT: &str = "\u{1b}[?1000h\u{1b}[?1002h\u{1b}[?1015h\u{1b}[?1006h";
-const DISABLE_MOUSE_SUPPORT: &str = "\u{1b}[?1006l\u{1b}[?1015l\u{1b}[?1002l\u{1b}[?1000l";
+pub(crate) const ENABLE_MOUSE_SUPPORT: &str =
+    "\u{1b}[?1000h\u{1b}[?1002h\u{1b}[?1015h\u{1b}[?1006h";
+pub(crate) const DISABLE_MOUSE_SUPPORT: &str =
+    "\u{1b}[?1006l\u{1b}[?1015l\u{1b}[?1002l\u{1b}[?1000l";
+// push and pop the "disambiguate escape codes" and "report event types" enhancements of the kitty
+// keyboard protocol
+const ENABLE_KITTY_KEYBOARD_PROTOCOL: &str = "\u{1b}[>3u";
+const DISABLE_KITTY_KEYBOARD_PROTOCOL: &str = "\u{1b}[<u";
 
 fn into_raw_mode(pid: RawFd) {
     let mut tio = termios::tcgetattr(pid).expect("could not get terminal attribute");
@@ -113,6 +120,8 @@ pub trait ClientOsApi: Send + Sync {
     fn load_palette(&self) -> Palette;
     fn enable_mouse(&self) -> Result<()>;
     fn disable_mouse(&self) -> Result<()>;
+    fn enable_kitty_keyboard_protocol(&self) -> Result<()>;
+    fn disable_kitty_keyboard_protocol(&self) -> Result<()>;
     // Repeatedly send action, until stdin is readable again
     fn stdin_poller(&self) -> StdinPoller;
     fn env_variable(&self, _name: &str) -> Option<String> {
@@ -284,6 +293,26 @@ impl ClientOsApi for ClientOsInputOutput {
         Ok(())
     }
 
+    fn enable_kitty_keyboard_protocol(&self) -> Result<()> {
+        let err_context = "failed to enable the kitty keyboard protocol";
+        let mut stdout = self.get_stdout_writer();
+        stdout
+            .write_all(ENABLE_KITTY_KEYBOARD_PROTOCOL.as_bytes())
+            .context(err_context)?;
+        stdout.flush().context(err_context)?;
+        Ok(())
+    }
+
+    fn disable_kitty_keyboard_protocol(&self) -> Result<()> {
+        let err_context = "failed to disable the kitty keyboard protocol";
+        let mut stdout = self.get_stdout_writer();
+        stdout
+            .write_all(DISABLE_KITTY_KEYBOARD_PROTOCOL.as_bytes())
+            .context(err_context)?;
+        stdout.flush().context(err_context)?;
+        Ok(())
+    }
+
     fn stdin_poller(&self) -> StdinPoller {
         StdinPoller::default()
     }
@@ -326,43 +355,67 @@ pub fn get_cli_client_os_input() -> Result<ClientOsInputOutput, nix::Error> {
 
 pub const DEFAULT_STDIN_POLL_TIMEOUT_MS: u64 = 10;
 
+enum StdinSource {
+    Fd { poll: Poll, events: Events },
+    // for clients whose input is read from elsewhere and sent to them (eg. the web client), we
+    // check whether some is waiting without taking it from whoever reads it
+    Channel(Receiver<Vec<u8>>),
+}
+
 pub struct StdinPoller {
-    poll: Poll,
-    events: Events,
+    source: StdinSource,
     timeout: time::Duration,
 }
 
 impl StdinPoller {
-    // use mio poll to check if stdin is readable without blocking
-    pub fn ready(&mut self) -> bool {
-        self.poll
-            .poll(&mut self.events, Some(self.timeout))
-            .expect("could not poll stdin for readiness");
-        for event in &self.events {
-            if event.token() == Token(0) && event.is_readable() {
-                return true;
-            }
-        }
-        false
-    }
-}
-
-impl Default for StdinPoller {
-    fn default() -> Self {
-        let stdin = 0;
-        let mut stdin_fd = SourceFd(&stdin);
+    pub fn new(fd: RawFd) -> Self {
+        let mut source_fd = SourceFd(&fd);
         let events = Events::with_capacity(128);
         let poll = Poll::new().unwrap();
         poll.registry()
-            .register(&mut stdin_fd, Token(0), Interest::READABLE)
+            .register(&mut source_fd, Token(0), Interest::READABLE)
             .expect("could not create stdin poll");
 
         let timeout = time::Duration::from_millis(DEFAULT_STDIN_POLL_TIMEOUT_MS);
 
         Self {
-            poll,
-            events,
+            source: StdinSource::Fd { poll, events },
+            timeout,
+        }
+    }
+    pub fn from_receiver(receiver: Receiver<Vec<u8>>) -> Self {
+        let timeout = time::Duration::from_millis(DEFAULT_STDIN_POLL_TIMEOUT_MS);
+        Self {
+            source: StdinSource::Channel(receiver),
             timeout,
         }
     }
+    // use mio poll to check if stdin is readable without blocking
+    pub fn ready(&mut self) -> bool {
+        match &mut self.source {
+            StdinSource::Fd { poll, events } => {
+                poll.poll(events, Some(self.timeout))
+                    .expect("could not poll stdin for readiness");
+                for event in events.iter() {
+                    if event.token() == Token(0) && event.is_readable() {
+                        return true;
+                    }
+                }
+                false
+            },
+            StdinSource::Channel(receiver) => {
+                if receiver.is_empty() {
+                    thread::sleep(self.timeout);
+                }
+                !receiver.is_empty()
+            },
+        }
+    }
+}
+
+impl Default for StdinPoller {
+    fn default() -> Self {
+        let stdin = 0;
+        StdinPoller::new(stdin)
+    }
 }
@@ -64,8 +64,9 @@ impl StdinAnsiParser {
         // <ESC>]11;?<ESC>\ => get background color
         // <ESC>]10;?<ESC>\ => get foreground color
         // <ESC>[?2026$p => get synchronised output mode
+        // <ESC>[?u => get the kitty keyboard protocol flags (only answered if it is supported)
         let mut query_string = String::from(
-            "\u{1b}[14t\u{1b}[16t\u{1b}]11;?\u{1b}\u{5c}\u{1b}]10;?\u{1b}\u{5c}\u{1b}[?2026$p",
+            "\u{1b}[14t\u{1b}[16t\u{1b}]11;?\u{1b}\u{5c}\u{1b}]10;?\u{1b}\u{5c}\u{1b}[?2026$p\u{1b}[?u",
         );
 
         // query colors
@@ -167,6 +168,14 @@ impl StdinAnsiParser {
                 self.pending_events.push(ansi_sequence);
                 self.raw_buffer.clear();
             }
+        } else if byte == b'u' {
+            self.raw_buffer.push(byte);
+            if let Some(ansi_sequence) =
+                AnsiStdinInstruction::kitty_keyboard_protocol_from_bytes(&self.raw_buffer)
+            {
+                self.pending_events.push(ansi_sequence);
+                self.raw_buffer.clear();
+            }
         } else {
             self.raw_buffer.push(byte);
         }
@@ -180,6 +189,7 @@ pub enum AnsiStdinInstruction {
     ForegroundColor(String),
     ColorRegisters(Vec<(usize, String)>),
     SynchronizedOutput(Option<SyncOutput>),
+    KittyKeyboardProtocol,
 }
 
 impl AnsiStdinInstruction {
@@ -281,6 +291,19 @@ impl AnsiStdinInstruction {
             None
         }
     }
+
+    pub fn kitty_keyboard_protocol_from_bytes(bytes: &[u8]) -> Option<Self> {
+        // eg. <ESC>[?0u
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"\u{1b}\[\?\d+u$").unwrap();
+        }
+        let key_string = String::from_utf8_lossy(bytes);
+        if RE.is_match(&key_string) {
+            Some(AnsiStdinInstruction::KittyKeyboardProtocol)
+        } else {
+            None
+        }
+    }
 }
 
 fn color_sequence_from_bytes(bytes: &[u8]) -> Result<(usize, String), &'static str> {
@@ -1,3 +1,4 @@
+use crate::keyboard_parser::{split_key_events, StdinChunk};
 use crate::os_input_output::ClientOsApi;
 use crate::stdin_ansi_parser::StdinAnsiParser;
 use crate::InputInstruction;
@@ -84,43 +85,60 @@ pub(crate) fn stdin_loop(
                         .unwrap()
                         .write_cache(ansi_stdin_events.drain(..).collect());
                 }
-                current_buffer.append(&mut buf.to_vec());
-                let maybe_more = false; // read_from_stdin should (hopefully) always empty the STDIN buffer completely
-                let mut events = vec![];
-                input_parser.parse(
-                    &buf,
-                    |input_event: InputEvent| {
-                        events.push(input_event);
-                    },
-                    maybe_more,
-                );
-
-                let event_count = events.len();
-                for (i, input_event) in events.into_iter().enumerate() {
-                    if holding_mouse && is_mouse_press_or_hold(&input_event) && i == event_count - 1
-                    {
-                        let mut poller = os_input.stdin_poller();
-                        loop {
-                            if poller.ready() {
-                                break;
-                            }
+                for chunk in split_key_events(&buf) {
+                    match chunk {
+                        StdinChunk::KeyEvent(Some(key_event)) => {
                             send_input_instructions
-                                .send(InputInstruction::KeyEvent(
-                                    input_event.clone(),
-                                    current_buffer.clone(),
+                                .send(InputInstruction::KeyWithModifiersEvent(
+                                    key_event.key,
+                                    key_event.event_type,
                                 ))
                                 .unwrap();
-                        }
-                    }
+                        },
+                        StdinChunk::KeyEvent(None) => {},
+                        StdinChunk::Bytes(bytes) => {
+                            current_buffer.extend_from_slice(&bytes);
+                            let maybe_more = false; // read_from_stdin should (hopefully) always empty the STDIN buffer completely
+                            let mut events = vec![];
+                            input_parser.parse(
+                                &bytes,
+                                |input_event: InputEvent| {
+                                    events.push(input_event);
+                                },
+                                maybe_more,
+                            );
+
+                            let event_count = events.len();
+                            for (i, input_event) in events.into_iter().enumerate() {
+                                if holding_mouse
+                                    && is_mouse_press_or_hold(&input_event)
+                                    && i == event_count - 1
+                                {
+                                    let mut poller = os_input.stdin_poller();
+                                    loop {
+                                        if poller.ready() {
+                                            break;
+                                        }
+                                        send_input_instructions
+                                            .send(InputInstruction::KeyEvent(
+                                                input_event.clone(),
+                                                current_buffer.clone(),
+                                            ))
+                                            .unwrap();
+                                    }
+                                }
 
-                    holding_mouse = is_mouse_press_or_hold(&input_event);
+                                holding_mouse = is_mouse_press_or_hold(&input_event);
 
-                    send_input_instructions
-                        .send(InputInstruction::KeyEvent(
-                            input_event,
-                            current_buffer.drain(..).collect(),
-                        ))
-                        .unwrap();
+                                send_input_instructions
+                                    .send(InputInstruction::KeyEvent(
+                                        input_event,
+                                        current_buffer.drain(..).collect(),
+                                    ))
+                                    .unwrap();
+                            }
+                        },
+                    }
                 }
             },
             Err(e) => {
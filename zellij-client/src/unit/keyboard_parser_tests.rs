@@ -0,0 +1,151 @@
+use super::*;
+use zellij_utils::data::CharOrArrow;
+
+fn key_press(key: Key) -> StdinChunk {
+    StdinChunk::KeyEvent(Some(KeyEvent {
+        key,
+        event_type: KeyEventType::Press,
+    }))
+}
+
+#[test]
+pub fn parse_kitty_key_with_multiple_modifiers() {
+    let ctrl_shift_a = "\u{1b}[97;6u".as_bytes();
+    assert_eq!(
+        split_key_events(ctrl_shift_a),
+        vec![key_press(Key::WithModifiers(
+            BareKey::Char('a'),
+            KeyModifiers {
+                shift: true,
+                ctrl: true,
+                ..Default::default()
+            }
+        ))]
+    );
+}
+
+#[test]
+pub fn parse_kitty_key_that_has_a_legacy_representation() {
+    assert_eq!(
+        split_key_events("\u{1b}[97;5u".as_bytes()),
+        vec![key_press(Key::Ctrl('a'))],
+        "Ctrl+a"
+    );
+    assert_eq!(
+        split_key_events("\u{1b}[97;4u".as_bytes()),
+        vec![key_press(Key::Alt(CharOrArrow::Char('A')))],
+        "Alt+Shift+a"
+    );
+    assert_eq!(
+        split_key_events("\u{1b}[27u".as_bytes()),
+        vec![key_press(Key::Esc)],
+        "Esc"
+    );
+}
+
+#[test]
+pub fn parse_kitty_key_with_super_modifier() {
+    let super_x = "\u{1b}[120;9u".as_bytes();
+    assert_eq!(
+        split_key_events(super_x),
+        vec![key_press(Key::WithModifiers(
+            BareKey::Char('x'),
+            KeyModifiers {
+                super_key: true,
+                ..Default::default()
+            }
+        ))]
+    );
+}
+
+#[test]
+pub fn parse_kitty_key_release_and_repeat() {
+    let ctrl_a_repeat_and_release = "\u{1b}[97;5:2u\u{1b}[97;5:3u".as_bytes();
+    assert_eq!(
+        split_key_events(ctrl_a_repeat_and_release),
+        vec![
+            StdinChunk::KeyEvent(Some(KeyEvent {
+                key: Key::Ctrl('a'),
+                event_type: KeyEventType::Repeat,
+            })),
+            StdinChunk::KeyEvent(Some(KeyEvent {
+                key: Key::Ctrl('a'),
+                event_type: KeyEventType::Release,
+            })),
+        ]
+    );
+}
+
+#[test]
+pub fn parse_modified_functional_keys() {
+    let ctrl_shift_left = "\u{1b}[1;6D".as_bytes();
+    assert_eq!(
+        split_key_events(ctrl_shift_left),
+        vec![key_press(Key::WithModifiers(
+            BareKey::Left,
+            KeyModifiers {
+                shift: true,
+                ctrl: true,
+                ..Default::default()
+            }
+        ))]
+    );
+    let ctrl_delete = "\u{1b}[3;5~".as_bytes();
+    assert_eq!(
+        split_key_events(ctrl_delete),
+        vec![key_press(Key::WithModifiers(
+            BareKey::Delete,
+            KeyModifiers {
+                ctrl: true,
+                ..Default::default()
+            }
+        ))]
+    );
+}
+
+#[test]
+pub fn unmodified_keys_are_left_to_termwiz() {
+    let left_arrow_and_page_up = "\u{1b}[D\u{1b}[5~".as_bytes();
+    assert_eq!(
+        split_key_events(left_arrow_and_page_up),
+        vec![StdinChunk::Bytes(left_arrow_and_page_up.to_vec())]
+    );
+}
+
+#[test]
+pub fn text_around_kitty_keys_keeps_its_order() {
+    let text_and_keys = "ab\u{1b}[97;6ucd".as_bytes();
+    assert_eq!(
+        split_key_events(text_and_keys),
+        vec![
+            StdinChunk::Bytes("ab".as_bytes().to_vec()),
+            key_press(Key::WithModifiers(
+                BareKey::Char('a'),
+                KeyModifiers {
+                    shift: true,
+                    ctrl: true,
+                    ..Default::default()
+                }
+            )),
+            StdinChunk::Bytes("cd".as_bytes().to_vec()),
+        ]
+    );
+}
+
+#[test]
+pub fn unsupported_kitty_keys_are_dropped() {
+    let left_shift = "\u{1b}[57441;2u".as_bytes();
+    assert_eq!(
+        split_key_events(left_shift),
+        vec![StdinChunk::KeyEvent(None)]
+    );
+}
+
+#[test]
+pub fn pasted_text_is_not_parsed() {
+    let paste = "\u{1b}[200~\u{1b}[97;6u\u{1b}[201~".as_bytes();
+    assert_eq!(
+        split_key_events(paste),
+        vec![StdinChunk::Bytes(paste.to_vec())]
+    );
+}
@@ -189,6 +189,12 @@ impl ClientOsApi for FakeClientOsApi {
     fn disable_mouse(&self) -> Result<()> {
         Ok(())
     }
+    fn enable_kitty_keyboard_protocol(&self) -> Result<()> {
+        Ok(())
+    }
+    fn disable_kitty_keyboard_protocol(&self) -> Result<()> {
+        Ok(())
+    }
     fn stdin_poller(&self) -> StdinPoller {
         unimplemented!()
     }
@@ -0,0 +1,302 @@
+use super::websocket::{accept_key, write_frame, Message, MessageReader, OpCode};
+use super::{
+    is_authorized, is_same_origin, read_http_request, serve, token_matches, WebAssets, WebServer,
+};
+
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use tempfile::tempdir;
+use zellij_utils::input::{config::Config, options::Options};
+
+fn masked_frame(is_final: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mask = [0x37, 0xfa, 0x21, 0x3d];
+    let mut frame = vec![if is_final { 0x80 } else { 0x00 } | opcode];
+    if payload.len() < 126 {
+        frame.push(0x80 | payload.len() as u8);
+    } else {
+        frame.push(0x80 | 126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    }
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+#[test]
+fn websocket_accept_key() {
+    // the example from RFC 6455, section 1.3
+    assert_eq!(
+        accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+    );
+}
+
+#[test]
+fn read_masked_messages() {
+    let mut bytes = masked_frame(true, 0x1, b"Hello");
+    bytes.extend(masked_frame(true, 0x2, &[0x1b, b'[', b'A']));
+    bytes.extend(masked_frame(true, 0x9, b"ping"));
+    bytes.extend(masked_frame(true, 0x8, &[]));
+    let mut reader = MessageReader::new(Cursor::new(bytes));
+    assert_eq!(
+        reader.read_message().unwrap(),
+        Message::Text("Hello".to_owned())
+    );
+    assert_eq!(
+        reader.read_message().unwrap(),
+        Message::Binary(vec![0x1b, b'[', b'A'])
+    );
+    assert_eq!(
+        reader.read_message().unwrap(),
+        Message::Ping(b"ping".to_vec())
+    );
+    assert_eq!(reader.read_message().unwrap(), Message::Close);
+}
+
+#[test]
+fn read_fragmented_message_with_interleaved_control_frame() {
+    let mut bytes = masked_frame(false, 0x2, b"first ");
+    bytes.extend(masked_frame(true, 0xA, b"pong"));
+    bytes.extend(masked_frame(false, 0x0, b"second "));
+    bytes.extend(masked_frame(true, 0x0, b"third"));
+    let mut reader = MessageReader::new(Cursor::new(bytes));
+    assert_eq!(
+        reader.read_message().unwrap(),
+        Message::Binary(b"first second third".to_vec())
+    );
+}
+
+#[test]
+fn unmasked_client_frames_are_rejected() {
+    let bytes = vec![0x82, 0x01, b'a'];
+    let mut reader = MessageReader::new(Cursor::new(bytes));
+    assert!(reader.read_message().is_err());
+}
+
+#[test]
+fn write_frames_with_extended_lengths() {
+    let mut short = vec![];
+    write_frame(&mut short, OpCode::Text, b"hi").unwrap();
+    assert_eq!(short, vec![0x81, 0x02, b'h', b'i']);
+
+    let mut medium = vec![];
+    write_frame(&mut medium, OpCode::Binary, &[0; 300]).unwrap();
+    assert_eq!(&medium[..4], &[0x82, 126, 0x01, 0x2c]);
+    assert_eq!(medium.len(), 4 + 300);
+
+    let mut long = vec![];
+    write_frame(&mut long, OpCode::Binary, &[0; 70000]).unwrap();
+    assert_eq!(&long[..2], &[0x82, 127]);
+    assert_eq!(&long[2..10], &70000u64.to_be_bytes());
+    assert_eq!(long.len(), 10 + 70000);
+}
+
+#[test]
+fn parse_websocket_upgrade_request() {
+    let request = "GET /ws?session=my%2Fsession&cols=120&rows=40 HTTP/1.1\r\n\
+                   Host: localhost:8082\r\n\
+                   Upgrade: websocket\r\n\
+                   Connection: Upgrade\r\n\
+                   Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                   Sec-WebSocket-Version: 13\r\n\r\n";
+    let request = read_http_request(&mut Cursor::new(request)).unwrap();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/ws");
+    assert_eq!(request.query.get("session").unwrap(), "my/session");
+    assert_eq!(request.query.get("cols").unwrap(), "120");
+    assert_eq!(request.header("Upgrade"), Some("websocket"));
+    assert_eq!(
+        request.header("sec-websocket-key"),
+        Some("dGhlIHNhbXBsZSBub25jZQ==")
+    );
+}
+
+#[test]
+fn oversized_request_is_rejected() {
+    let request = format!(
+        "GET / HTTP/1.1\r\nCookie: {}\r\n\r\n",
+        "a".repeat(16 * 1024)
+    );
+    assert!(read_http_request(&mut Cursor::new(request)).is_err());
+}
+
+#[test]
+fn web_assets_are_bundled_and_can_be_overridden() {
+    let override_dir = tempdir().unwrap();
+    let bundled_assets = WebAssets::load(&override_dir.path().join("web")).unwrap();
+    let (content_type, contents) = bundled_assets.get("xterm.js").unwrap();
+    assert_eq!(*content_type, "text/javascript");
+    assert_eq!(
+        contents.as_slice(),
+        include_bytes!("../../assets/web/xterm.js"),
+        "bundled file is served when there is no override"
+    );
+    assert!(bundled_assets.get("xterm.css").is_some());
+    assert!(bundled_assets.get("xterm-addon-fit.js").is_some());
+
+    fs::write(override_dir.path().join("xterm.css"), "/* overridden */").unwrap();
+    let assets = WebAssets::load(override_dir.path()).unwrap();
+    let (content_type, contents) = assets.get("xterm.css").unwrap();
+    assert_eq!(*content_type, "text/css");
+    assert_eq!(
+        contents.as_slice(),
+        b"/* overridden */",
+        "file is overridden"
+    );
+    assert_eq!(
+        assets.get("xterm.js"),
+        bundled_assets.get("xterm.js"),
+        "other files are still bundled"
+    );
+}
+
+#[test]
+fn token_comparison() {
+    assert!(token_matches("secret-token", "secret-token"));
+    assert!(!token_matches("secret-token", "secret-tokem"));
+    assert!(!token_matches("secret-token", "secret"));
+    assert!(!token_matches("secret-token", ""));
+}
+
+#[test]
+fn token_is_read_from_the_cookie_or_authorization_header() {
+    let with_cookie = "GET /sessions HTTP/1.1\r\n\
+                       Cookie: theme=dark; zellij_web_token=secret-token\r\n\r\n";
+    let request = read_http_request(&mut Cursor::new(with_cookie)).unwrap();
+    assert!(is_authorized(&request, "secret-token"));
+    assert!(!is_authorized(&request, "other-token"));
+
+    let with_header = "GET /sessions HTTP/1.1\r\n\
+                       Authorization: Bearer secret-token\r\n\r\n";
+    let request = read_http_request(&mut Cursor::new(with_header)).unwrap();
+    assert!(is_authorized(&request, "secret-token"));
+
+    let in_query = "GET /sessions?token=secret-token HTTP/1.1\r\n\r\n";
+    let request = read_http_request(&mut Cursor::new(in_query)).unwrap();
+    assert!(
+        !is_authorized(&request, "secret-token"),
+        "tokens in the url are not accepted"
+    );
+}
+
+#[test]
+fn websocket_origin_must_be_the_server() {
+    let request = |origin: &str| {
+        let request = format!(
+            "GET /ws HTTP/1.1\r\nHost: localhost:8082\r\nOrigin: {}\r\n\r\n",
+            origin
+        );
+        read_http_request(&mut Cursor::new(request)).unwrap()
+    };
+    assert!(is_same_origin(&request("http://localhost:8082")));
+    assert!(!is_same_origin(&request("https://example.com")));
+    let without_origin = "GET /ws HTTP/1.1\r\nHost: localhost:8082\r\n\r\n";
+    assert!(is_same_origin(
+        &read_http_request(&mut Cursor::new(without_origin)).unwrap()
+    ));
+}
+
+fn start_test_web_server(sessions: Vec<String>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let web_server = WebServer {
+        token: "secret-token".to_owned(),
+        assets: WebAssets::default(),
+        config: Config::default(),
+        config_options: Options::default(),
+        list_sessions: Arc::new(move || sessions.clone()),
+    };
+    thread::spawn(move || serve(listener, Arc::new(web_server)));
+    address
+}
+
+fn send_request(address: SocketAddr, request: &str) -> Vec<u8> {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = vec![];
+    stream.read_to_end(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn web_server_login_and_websocket_handshake() {
+    let address = start_test_web_server(vec!["test-session".to_owned()]);
+
+    let response = send_request(address, "GET /sessions HTTP/1.1\r\n\r\n");
+    assert!(
+        response.starts_with(b"HTTP/1.1 401 Unauthorized"),
+        "sessions are not listed without the token"
+    );
+
+    let response = send_request(
+        address,
+        "POST /login HTTP/1.1\r\nContent-Length: 11\r\n\r\nwrong-token",
+    );
+    assert!(
+        response.starts_with(b"HTTP/1.1 401 Unauthorized"),
+        "wrong token is refused"
+    );
+
+    let response = String::from_utf8(send_request(
+        address,
+        "POST /login HTTP/1.1\r\nContent-Length: 12\r\n\r\nsecret-token",
+    ))
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 204 No Content"));
+    assert!(
+        response.contains("Set-Cookie: zellij_web_token=secret-token; HttpOnly; SameSite=Strict"),
+        "token is set as a cookie: {}",
+        response
+    );
+
+    let response = String::from_utf8(send_request(
+        address,
+        "GET /sessions HTTP/1.1\r\nCookie: zellij_web_token=secret-token\r\n\r\n",
+    ))
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with("[\"test-session\"]"));
+
+    let cross_origin_upgrade = "GET /ws?session=test-session HTTP/1.1\r\n\
+                                Host: localhost\r\n\
+                                Origin: https://example.com\r\n\
+                                Cookie: zellij_web_token=secret-token\r\n\
+                                Upgrade: websocket\r\n\
+                                Connection: Upgrade\r\n\
+                                Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                                Sec-WebSocket-Version: 13\r\n\r\n";
+    assert!(send_request(address, cross_origin_upgrade).starts_with(b"HTTP/1.1 403 Forbidden"));
+
+    // the session is listed but not actually running, so once the handshake is done the client
+    // fails to connect to it and tells the browser
+    let upgrade = "GET /ws?session=test-session HTTP/1.1\r\n\
+                   Host: localhost\r\n\
+                   Origin: http://localhost\r\n\
+                   Cookie: zellij_web_token=secret-token\r\n\
+                   Upgrade: websocket\r\n\
+                   Connection: Upgrade\r\n\
+                   Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                   Sec-WebSocket-Version: 13\r\n\r\n";
+    let response = send_request(address, upgrade);
+    let head_end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+    let head = String::from_utf8_lossy(&response[..head_end]);
+    assert!(head.starts_with("HTTP/1.1 101 Switching Protocols"));
+    assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+    let frames = &response[head_end..];
+    assert_eq!(frames[0], 0x81, "an unfragmented text frame");
+    let payload_length = frames[1] as usize;
+    let message = String::from_utf8_lossy(&frames[2..2 + payload_length]);
+    assert!(
+        message.contains("\"type\":\"exit\"") && message.contains("Failed to connect"),
+        "exit message: {}",
+        message
+    );
+    assert_eq!(
+        frames[2 + payload_length],
+        0x88,
+        "followed by a close frame"
+    );
+}
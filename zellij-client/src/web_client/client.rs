@@ -0,0 +1,262 @@
+use crate::os_input_output::ClientOsApi;
+use crate::stdin_ansi_parser::StdinAnsiParser;
+use crate::web_client::os_input::WebClientOsApi;
+use crate::{
+    command_is_executing::CommandIsExecuting, input_handler::input_loop, stdin_handler::stdin_loop,
+    ClientInstruction, InputInstruction,
+};
+
+use log::{error, info};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use zellij_utils::{
+    channels::{self, ChannelWithContext, SenderWithContext},
+    data::Style,
+    errors::ContextType,
+    input::{actions::Action, config::Config, options::Options},
+    ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
+};
+
+pub(crate) enum WebClientExit {
+    Exit(String),
+    SwitchSession(String),
+}
+
+/// The browser counterpart of `start_client`: attaches to the session listening on `socket_path`
+/// and runs until the session ends, the client detaches or the browser goes away.
+///
+/// Unlike `start_client` this runs on one of many threads of the web server, so it never touches
+/// the process environment, the panic hook or exits the process.
+pub(crate) fn run_web_client(
+    os_input: WebClientOsApi,
+    socket_path: &Path,
+    config: Config,
+    config_options: Options,
+) -> WebClientExit {
+    info!("Starting Zellij web client!");
+
+    if let Err(e) = os_input.try_connect_to_server(socket_path) {
+        error!("Failed to connect to {}: {}", socket_path.display(), e);
+        return WebClientExit::Exit(format!("Failed to connect to the session: {}", e));
+    }
+    let os_input: Box<dyn ClientOsApi> = Box::new(os_input);
+    let palette = config
+        .theme_config(&config_options)
+        .unwrap_or_else(|| os_input.load_palette());
+    let client_attributes = ClientAttributes {
+        size: os_input.get_terminal_size_using_fd(0),
+        style: Style {
+            colors: palette,
+            rounded_corners: config.ui.pane_frames.rounded_corners,
+            hide_session_name: config.ui.pane_frames.hide_session_name,
+        },
+        keybinds: config.keybinds.clone(),
+        is_read_only: false,
+    };
+
+    os_input.send_to_server(ClientToServerMsg::AttachClient(
+        client_attributes,
+        config_options.clone(),
+        None,
+        None,
+    ));
+
+    let mut command_is_executing = CommandIsExecuting::new();
+    let client_has_exited = Arc::new(AtomicBool::new(false));
+
+    let (send_client_instructions, receive_client_instructions): ChannelWithContext<
+        ClientInstruction,
+    > = channels::bounded(50);
+    let send_client_instructions = SenderWithContext::new(send_client_instructions);
+
+    let (send_input_instructions, receive_input_instructions): ChannelWithContext<
+        InputInstruction,
+    > = channels::bounded(50);
+    let send_input_instructions = SenderWithContext::new(send_input_instructions);
+
+    let stdin_ansi_parser = Arc::new(Mutex::new(StdinAnsiParser::new()));
+
+    let _stdin_thread = thread::Builder::new()
+        .name("web_client_stdin".to_string())
+        .spawn({
+            let os_input = os_input.clone();
+            let send_input_instructions = send_input_instructions.clone();
+            move || stdin_loop(os_input, send_input_instructions, stdin_ansi_parser)
+        });
+
+    let _input_thread = thread::Builder::new()
+        .name("web_client_input".to_string())
+        .spawn({
+            let send_client_instructions = send_client_instructions.clone();
+            let command_is_executing = command_is_executing.clone();
+            let os_input = os_input.clone();
+            let default_mode = config_options.default_mode.unwrap_or_default();
+            move || {
+                input_loop(
+                    os_input,
+                    config,
+                    config_options,
+                    command_is_executing,
+                    send_client_instructions,
+                    default_mode,
+                    receive_input_instructions,
+                )
+            }
+        });
+
+    let _browser_events_thread = thread::Builder::new()
+        .name("web_client_events".to_string())
+        .spawn({
+            let os_input = os_input.clone();
+            let client_has_exited = client_has_exited.clone();
+            move || {
+                os_input.handle_signals(
+                    Box::new({
+                        let os_api = os_input.clone();
+                        move || {
+                            os_api.send_to_server(ClientToServerMsg::TerminalResize(
+                                os_api.get_terminal_size_using_fd(0),
+                            ));
+                        }
+                    }),
+                    Box::new({
+                        let os_api = os_input.clone();
+                        move || {
+                            // closing the tab should never take the session down with it
+                            if !client_has_exited.load(Ordering::SeqCst) {
+                                os_api.send_to_server(ClientToServerMsg::Action(
+                                    Action::Detach,
+                                    None,
+                                    None,
+                                ));
+                            }
+                        }
+                    }),
+                );
+            }
+        });
+
+    let router_thread = thread::Builder::new()
+        .name("web_client_router".to_string())
+        .spawn({
+            let os_input = os_input.clone();
+            move || loop {
+                match os_input.recv_from_server() {
+                    Some((instruction, err_ctx)) => {
+                        err_ctx.update_thread_ctx();
+                        let should_break = matches!(instruction, ServerToClientMsg::Exit(_));
+                        if send_client_instructions.send(instruction.into()).is_err()
+                            || should_break
+                        {
+                            break;
+                        }
+                    },
+                    None => {
+                        let _ =
+                            send_client_instructions.send(ClientInstruction::UnblockInputThread);
+                        let _ = send_client_instructions.send(ClientInstruction::Error(
+                            "Received empty message from server".to_string(),
+                        ));
+                        break;
+                    },
+                }
+            }
+        })
+        .unwrap();
+
+    let mut stdout = os_input.get_stdout_writer();
+    let _ = stdout.write_all("\u{1b}[1m\u{1b}[HLoading Zellij\u{1b}[m\n\r".as_bytes());
+
+    let mut web_client_exit = WebClientExit::Exit(String::new());
+    let mut loading = true;
+    let mut pending_instructions = vec![];
+    loop {
+        let (client_instruction, mut err_ctx) = if !loading && !pending_instructions.is_empty() {
+            pending_instructions.remove(0)
+        } else {
+            match receive_client_instructions.recv() {
+                Ok(instruction) => instruction,
+                Err(_) => break,
+            }
+        };
+
+        if loading {
+            // the stdin thread queries xterm.js for its colors and pixel size on startup, we wait
+            // for that to finish (or time out) before forwarding anything
+            match client_instruction {
+                ClientInstruction::DoneParsingStdinQuery => {
+                    loading = false;
+                },
+                ClientInstruction::StartedParsingStdinQuery => {},
+                instruction => {
+                    pending_instructions.push((instruction, err_ctx));
+                },
+            }
+            continue;
+        }
+
+        err_ctx.add_call(ContextType::Client((&client_instruction).into()));
+
+        match client_instruction {
+            ClientInstruction::Exit(reason) => {
+                client_has_exited.store(true, Ordering::SeqCst);
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                web_client_exit = WebClientExit::Exit(reason.to_string());
+                break;
+            },
+            ClientInstruction::Error(backtrace) => {
+                error!("{}", backtrace);
+                client_has_exited.store(true, Ordering::SeqCst);
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                web_client_exit = WebClientExit::Exit(backtrace);
+                break;
+            },
+            ClientInstruction::Render(output) => {
+                if stdout.write_all(output.as_bytes()).is_err() {
+                    // the browser is gone, our events thread will detach us
+                    continue;
+                }
+            },
+            ClientInstruction::UnblockInputThread => {
+                command_is_executing.unblock_input_thread();
+            },
+            ClientInstruction::SwitchToMode(input_mode) => {
+                let _ = send_input_instructions.send(InputInstruction::SwitchToMode(input_mode));
+            },
+            ClientInstruction::PluginKeybinds(plugin_keybinds) => {
+                let _ =
+                    send_input_instructions.send(InputInstruction::PluginKeybinds(plugin_keybinds));
+            },
+            ClientInstruction::Log(lines_to_log) => {
+                for line in lines_to_log {
+                    info!("{line}");
+                }
+            },
+            ClientInstruction::LogError(lines_to_log) => {
+                for line in lines_to_log {
+                    error!("{line}");
+                }
+            },
+            ClientInstruction::SwitchSession(connect_to_session) => {
+                client_has_exited.store(true, Ordering::SeqCst);
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                web_client_exit = match connect_to_session.name {
+                    Some(name) => WebClientExit::SwitchSession(name),
+                    None => WebClientExit::Exit(
+                        "New sessions cannot be started from the browser".to_owned(),
+                    ),
+                };
+                break;
+            },
+            _ => {},
+        }
+    }
+
+    let _ = router_thread.join();
+    let _ = send_input_instructions.send(InputInstruction::Exit);
+
+    web_client_exit
+}
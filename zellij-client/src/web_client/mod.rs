@@ -0,0 +1,562 @@
+//! Serves a page running xterm.js and bridges its WebSocket to a running session, so that a
+//! session can be attached to from a browser tab.
+//!
+//! Every WebSocket connection gets a full client of its own (see [`run_web_client`]) with the
+//! browser standing in for the terminal: keystrokes arrive as binary messages, renders are sent
+//! back as binary messages and resizes arrive as small JSON text messages.
+//!
+//! The browser logs in by posting the token to `/login`, which sets it as an `HttpOnly` cookie
+//! so that it never appears in a URL. Other clients can send it as a bearer token instead.
+
+mod client;
+pub mod os_input;
+pub mod websocket;
+
+use client::{run_web_client, WebClientExit};
+use os_input::{BrowserEvent, WebClientOsApi};
+use websocket::{accept_key, write_frame, Message, MessageReader, OpCode};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Shutdown, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use zellij_utils::{
+    channels,
+    consts::ZELLIJ_SOCK_DIR,
+    input::{config::Config, options::Options},
+    pane_size::Size,
+};
+
+const INDEX_HTML: &str = include_str!("../../assets/web/index.html");
+const MAX_REQUEST_HEAD_SIZE: usize = 8 * 1024;
+const MAX_LOGIN_BODY_SIZE: usize = 1024;
+const DEFAULT_SIZE: Size = Size { rows: 24, cols: 80 };
+const AUTH_COOKIE: &str = "zellij_web_token";
+// the files of xterm.js the page loads (with their content types), they are bundled and served
+// by us rather than a CDN so that no third party script runs in a page that can type into sessions
+const WEB_ASSETS: &[(&str, &str, &[u8])] = &[
+    (
+        "xterm.js",
+        "text/javascript",
+        include_bytes!("../../assets/web/xterm.js"),
+    ),
+    (
+        "xterm.css",
+        "text/css",
+        include_bytes!("../../assets/web/xterm.css"),
+    ),
+    (
+        "xterm-addon-fit.js",
+        "text/javascript",
+        include_bytes!("../../assets/web/xterm-addon-fit.js"),
+    ),
+];
+
+/// Lists the names of the sessions that are currently running
+pub type ListSessions = Arc<dyn Fn() -> Vec<String> + Send + Sync>;
+
+/// The xterm.js files served under `/assets/`, by file name
+#[derive(Debug, Clone, Default)]
+pub struct WebAssets(HashMap<String, (&'static str, Vec<u8>)>);
+
+impl WebAssets {
+    /// The bundled files listed in `WEB_ASSETS`, each replaced by the file of the same name in
+    /// `override_dir` if there is one
+    pub fn load(override_dir: &Path) -> io::Result<Self> {
+        let mut assets = HashMap::new();
+        for (name, content_type, bundled_contents) in WEB_ASSETS {
+            let path = override_dir.join(name);
+            let contents = match fs::read(&path) {
+                Ok(contents) => {
+                    info!("Serving {} instead of the bundled {}", path.display(), name);
+                    contents
+                },
+                Err(e) if e.kind() == io::ErrorKind::NotFound => bundled_contents.to_vec(),
+                Err(e) => {
+                    return Err(io::Error::new(
+                        e.kind(),
+                        format!("failed to read {}: {}", path.display(), e),
+                    ));
+                },
+            };
+            assets.insert(name.to_string(), (*content_type, contents));
+        }
+        Ok(WebAssets(assets))
+    }
+    fn get(&self, name: &str) -> Option<&(&'static str, Vec<u8>)> {
+        self.0.get(name)
+    }
+}
+
+/// What every connection to the web server shares
+pub(crate) struct WebServer {
+    token: String,
+    assets: WebAssets,
+    config: Config,
+    config_options: Options,
+    list_sessions: ListSessions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(|h| h.as_str())
+    }
+    fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(|p| p.as_str())
+    }
+    fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")?
+            .split(';')
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(cookie_name, _)| *cookie_name == name)
+            .map(|(_, value)| value)
+    }
+    fn bearer_token(&self) -> Option<&str> {
+        self.header("authorization")?.strip_prefix("Bearer ")
+    }
+}
+
+/// Messages the page sends us as WebSocket text messages
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BrowserMessage {
+    Resize { cols: usize, rows: usize },
+}
+
+/// Messages we send the page as WebSocket text messages
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Exit { message: String },
+    SwitchSession { name: String },
+}
+
+/// Listens on `ip`:`port` until the process is killed, serving the web client to anyone who
+/// presents `token`.
+pub fn start_web_server(
+    ip: IpAddr,
+    port: u16,
+    token: String,
+    assets: WebAssets,
+    config: Config,
+    config_options: Options,
+    list_sessions: ListSessions,
+) -> io::Result<()> {
+    let listener = TcpListener::bind((ip, port))?;
+    info!("Web client listening on {}:{}", ip, port);
+    let web_server = WebServer {
+        token,
+        assets,
+        config,
+        config_options,
+        list_sessions,
+    };
+    serve(listener, Arc::new(web_server));
+    Ok(())
+}
+
+pub(crate) fn serve(listener: TcpListener, web_server: Arc<WebServer>) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                error!("Failed to accept web client connection: {}", e);
+                continue;
+            },
+        };
+        let web_server = web_server.clone();
+        let _ = thread::Builder::new()
+            .name("web_client".to_string())
+            .spawn(move || {
+                if let Err(e) = handle_connection(stream, &web_server) {
+                    error!("Web client connection failed: {}", e);
+                }
+            });
+    }
+}
+
+fn handle_connection(stream: TcpStream, web_server: &WebServer) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let request = match read_http_request(&mut reader) {
+        Ok(request) => request,
+        Err(e) => {
+            write_http_response(&mut writer, "400 Bad Request", "text/plain", b"Bad request")?;
+            return Err(e);
+        },
+    };
+    match (request.method.as_str(), request.path.as_str()) {
+        // neither the page nor xterm.js contain anything secret, they are served to everyone so
+        // that the page can ask for the token
+        ("GET", "/") => {
+            return write_http_response(
+                &mut writer,
+                "200 OK",
+                "text/html; charset=utf-8",
+                INDEX_HTML.as_bytes(),
+            );
+        },
+        ("GET", path) if path.starts_with("/assets/") => {
+            return match web_server.assets.get(&path["/assets/".len()..]) {
+                Some((content_type, contents)) => {
+                    write_http_response(&mut writer, "200 OK", content_type, contents)
+                },
+                None => {
+                    write_http_response(&mut writer, "404 Not Found", "text/plain", b"Not found")
+                },
+            };
+        },
+        ("POST", "/login") => {
+            let body = read_http_body(&mut reader, &request, MAX_LOGIN_BODY_SIZE)?;
+            let provided = String::from_utf8_lossy(&body);
+            return if token_matches(&web_server.token, provided.trim()) {
+                let cookie = format!(
+                    "{}={}; HttpOnly; SameSite=Strict; Path=/",
+                    AUTH_COOKIE, web_server.token
+                );
+                write_http_response_with_headers(
+                    &mut writer,
+                    "204 No Content",
+                    &[("Set-Cookie", &cookie)],
+                    "text/plain",
+                    b"",
+                )
+            } else {
+                write_http_response(
+                    &mut writer,
+                    "401 Unauthorized",
+                    "text/plain",
+                    b"Unauthorized",
+                )
+            };
+        },
+        ("GET", _) => {},
+        _ => {
+            return write_http_response(
+                &mut writer,
+                "405 Method Not Allowed",
+                "text/plain",
+                b"Method not allowed",
+            );
+        },
+    }
+    if !is_authorized(&request, &web_server.token) {
+        return write_http_response(
+            &mut writer,
+            "401 Unauthorized",
+            "text/plain",
+            b"Unauthorized",
+        );
+    }
+    let list_sessions = web_server.list_sessions.clone();
+    match request.path.as_str() {
+        "/sessions" => {
+            let sessions = serde_json::to_vec(&list_sessions()).unwrap_or_default();
+            write_http_response(&mut writer, "200 OK", "application/json", &sessions)
+        },
+        "/ws" => {
+            // browsers send cookies along with WebSocket connections opened by any page, so we
+            // make sure it was opened by ours
+            if !is_same_origin(&request) {
+                return write_http_response(
+                    &mut writer,
+                    "403 Forbidden",
+                    "text/plain",
+                    b"Cross-origin WebSocket connections are not allowed",
+                );
+            }
+            let session_name = match request.query_param("session") {
+                Some(session_name) if list_sessions().iter().any(|s| s == session_name) => {
+                    session_name.to_owned()
+                },
+                _ => {
+                    return write_http_response(
+                        &mut writer,
+                        "404 Not Found",
+                        "text/plain",
+                        b"No such session",
+                    );
+                },
+            };
+            let key = match websocket_key(&request) {
+                Some(key) => key,
+                None => {
+                    return write_http_response(
+                        &mut writer,
+                        "400 Bad Request",
+                        "text/plain",
+                        b"Expected a WebSocket upgrade",
+                    );
+                },
+            };
+            let response = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept_key(key)
+            );
+            writer.write_all(response.as_bytes())?;
+            let size = initial_size(&request);
+            serve_session(
+                reader,
+                writer,
+                session_name,
+                size,
+                web_server.config.clone(),
+                web_server.config_options.clone(),
+                list_sessions,
+            )
+        },
+        _ => write_http_response(&mut writer, "404 Not Found", "text/plain", b"Not found"),
+    }
+}
+
+/// Attaches a client to `session_name` for the lifetime of the WebSocket
+fn serve_session(
+    reader: BufReader<TcpStream>,
+    stream: TcpStream,
+    session_name: String,
+    size: Size,
+    config: Config,
+    config_options: Options,
+    list_sessions: ListSessions,
+) -> io::Result<()> {
+    let websocket = Arc::new(Mutex::new(stream));
+    let size = Arc::new(Mutex::new(size));
+    let (send_browser_input, browser_input) = channels::unbounded();
+    let (send_browser_events, browser_events) = channels::unbounded();
+
+    let _browser_reader_thread = thread::Builder::new()
+        .name("web_client_reader".to_string())
+        .spawn({
+            let websocket = websocket.clone();
+            let size = size.clone();
+            move || {
+                let mut messages = MessageReader::new(reader);
+                loop {
+                    match messages.read_message() {
+                        Ok(Message::Binary(bytes)) => {
+                            if send_browser_input.send(bytes).is_err() {
+                                break;
+                            }
+                        },
+                        Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                            Ok(BrowserMessage::Resize { cols, rows }) => {
+                                if cols > 0 && rows > 0 {
+                                    *size.lock().unwrap() = Size { rows, cols };
+                                    let _ = send_browser_events.send(BrowserEvent::Resize);
+                                }
+                            },
+                            Err(e) => {
+                                error!("Unknown message from browser: {}", e);
+                            },
+                        },
+                        Ok(Message::Ping(payload)) => {
+                            let mut websocket = websocket.lock().unwrap();
+                            let _ = write_frame(&mut *websocket, OpCode::Pong, &payload);
+                        },
+                        Ok(Message::Close) => break,
+                        Err(e) => {
+                            if e.kind() != io::ErrorKind::UnexpectedEof {
+                                error!("Failed to read from browser: {}", e);
+                            }
+                            break;
+                        },
+                    }
+                }
+                let _ = send_browser_events.send(BrowserEvent::Closed);
+            }
+        });
+
+    let os_input = WebClientOsApi::new(size, websocket.clone(), browser_input, browser_events);
+    let socket_path = ZELLIJ_SOCK_DIR.join(session_name);
+    let server_message = match run_web_client(os_input, &socket_path, config, config_options) {
+        WebClientExit::SwitchSession(name) if list_sessions().contains(&name) => {
+            ServerMessage::SwitchSession { name }
+        },
+        WebClientExit::SwitchSession(_) => ServerMessage::Exit {
+            message: "Only running sessions can be switched to from the browser".to_owned(),
+        },
+        WebClientExit::Exit(message) => ServerMessage::Exit { message },
+    };
+    let mut websocket = websocket.lock().unwrap();
+    let server_message = serde_json::to_string(&server_message).unwrap_or_default();
+    let _ = write_frame(&mut *websocket, OpCode::Text, server_message.as_bytes());
+    let _ = write_frame(&mut *websocket, OpCode::Close, &[]);
+    websocket.shutdown(Shutdown::Both)
+}
+
+pub(crate) fn read_http_request(reader: &mut impl BufRead) -> io::Result<HttpRequest> {
+    let mut lines = vec![];
+    let mut head_size = 0;
+    loop {
+        let mut line = String::new();
+        let remaining = (MAX_REQUEST_HEAD_SIZE - head_size) as u64;
+        let read = reader.by_ref().take(remaining).read_line(&mut line)?;
+        head_size += read;
+        if !line.ends_with('\n') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "incomplete or oversized request",
+            ));
+        }
+        let line = line.trim_end().to_owned();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+    let mut lines = lines.into_iter();
+    let request_line = lines.next().unwrap_or_default();
+    let mut request_line = request_line.split_whitespace();
+    let (method, target) = match (request_line.next(), request_line.next()) {
+        (Some(method), Some(target)) => (method.to_owned(), target.to_owned()),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "malformed request line",
+            ));
+        },
+    };
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let query = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    let headers = lines
+        .filter_map(|line| {
+            line.split_once(':')
+                .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+        })
+        .collect();
+    Ok(HttpRequest {
+        method,
+        path: path.to_owned(),
+        query,
+        headers,
+    })
+}
+
+/// Reads the body of `request`, which must not be larger than `max_size`
+fn read_http_body(
+    reader: &mut impl Read,
+    request: &HttpRequest,
+    max_size: usize,
+) -> io::Result<Vec<u8>> {
+    let content_length = request
+        .header("content-length")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request body is too large",
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
+
+fn write_http_response(
+    writer: &mut impl Write,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> io::Result<()> {
+    write_http_response_with_headers(writer, status, &[], content_type, body)
+}
+
+fn write_http_response_with_headers(
+    writer: &mut impl Write,
+    status: &str,
+    headers: &[(&str, &str)],
+    content_type: &str,
+    body: &[u8],
+) -> io::Result<()> {
+    let mut head = format!("HTTP/1.1 {}\r\n", status);
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        content_type,
+        body.len()
+    ));
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(body)?;
+    writer.flush()
+}
+
+fn is_authorized(request: &HttpRequest, token: &str) -> bool {
+    request
+        .cookie(AUTH_COOKIE)
+        .or_else(|| request.bearer_token())
+        .map(|provided| token_matches(token, provided))
+        .unwrap_or(false)
+}
+
+/// Whether the `Origin` of the request (if any, only browsers send it) is the server itself
+fn is_same_origin(request: &HttpRequest) -> bool {
+    match (request.header("origin"), request.header("host")) {
+        (None, _) => true,
+        (Some(origin), Some(host)) => origin
+            .split_once("://")
+            .map(|(_scheme, origin_host)| origin_host == host)
+            .unwrap_or(false),
+        (Some(_), None) => false,
+    }
+}
+
+fn websocket_key(request: &HttpRequest) -> Option<&str> {
+    let is_upgrade = request
+        .header("upgrade")
+        .map(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false);
+    let is_supported_version = request.header("sec-websocket-version") == Some("13");
+    if is_upgrade && is_supported_version {
+        request.header("sec-websocket-key")
+    } else {
+        None
+    }
+}
+
+fn initial_size(request: &HttpRequest) -> Size {
+    let dimension = |name| {
+        request
+            .query_param(name)
+            .and_then(|d| d.parse::<usize>().ok())
+            .filter(|d| *d > 0)
+    };
+    match (dimension("cols"), dimension("rows")) {
+        (Some(cols), Some(rows)) => Size { rows, cols },
+        _ => DEFAULT_SIZE,
+    }
+}
+
+/// Compares tokens in constant time so that the token cannot be guessed by timing responses
+pub(crate) fn token_matches(expected: &str, provided: &str) -> bool {
+    let (expected, provided) = (expected.as_bytes(), provided.as_bytes());
+    expected.len() == provided.len()
+        && expected
+            .iter()
+            .zip(provided)
+            .fold(0, |difference, (e, p)| difference | (e ^ p))
+            == 0
+}
+
+#[cfg(test)]
+#[path = "../unit/web_client_tests.rs"]
+mod web_client_tests;
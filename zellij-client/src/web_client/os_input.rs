@@ -0,0 +1,187 @@
+use crate::os_input_output::{
+    ClientOsApi, StdinPoller, DISABLE_MOUSE_SUPPORT, ENABLE_MOUSE_SUPPORT,
+};
+use crate::web_client::websocket::{write_frame, OpCode};
+
+use interprocess::local_socket::LocalSocketStream;
+use std::io;
+use std::net::TcpStream;
+use std::os::unix::io::RawFd;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use zellij_utils::anyhow::Result;
+use zellij_utils::channels::Receiver;
+use zellij_utils::pane_size::Size;
+use zellij_utils::{
+    data::Palette,
+    errors::ErrorContext,
+    ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    shared::default_palette,
+};
+use zellij_utils::{interprocess, nix};
+
+const CONNECT_TO_SERVER_ATTEMPTS: usize = 20;
+const CONNECT_TO_SERVER_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Events from the browser that a native client would receive as signals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserEvent {
+    Resize,
+    Closed,
+}
+
+/// Sends everything written to it to the browser as binary WebSocket frames
+struct WebSocketWriter {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+impl io::Write for WebSocketWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut stream = self.stream.lock().unwrap();
+        write_frame(&mut *stream, OpCode::Binary, buf)?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`ClientOsApi`] for a client whose terminal is an xterm.js instance in a browser tab.
+///
+/// STDIN is whatever the browser sends us over the WebSocket, STDOUT is sent back over it, and
+/// the browser's resize and close events stand in for SIGWINCH and SIGHUP.
+#[derive(Clone)]
+pub struct WebClientOsApi {
+    size: Arc<Mutex<Size>>,
+    websocket: Arc<Mutex<TcpStream>>,
+    browser_input: Receiver<Vec<u8>>,
+    browser_events: Receiver<BrowserEvent>,
+    send_instructions_to_server: Arc<Mutex<Option<IpcSenderWithContext<ClientToServerMsg>>>>,
+    receive_instructions_from_server: Arc<Mutex<Option<IpcReceiverWithContext<ServerToClientMsg>>>>,
+}
+
+impl WebClientOsApi {
+    pub fn new(
+        size: Arc<Mutex<Size>>,
+        websocket: Arc<Mutex<TcpStream>>,
+        browser_input: Receiver<Vec<u8>>,
+        browser_events: Receiver<BrowserEvent>,
+    ) -> Self {
+        WebClientOsApi {
+            size,
+            websocket,
+            browser_input,
+            browser_events,
+            send_instructions_to_server: Arc::new(Mutex::new(None)),
+            receive_instructions_from_server: Arc::new(Mutex::new(None)),
+        }
+    }
+    /// Connects to the session listening on `path`, giving up after a few attempts since unlike
+    /// a native client we only ever attach to sessions that are already running
+    pub fn try_connect_to_server(&self, path: &Path) -> io::Result<()> {
+        let mut attempts = 0;
+        let socket = loop {
+            match LocalSocketStream::connect(path) {
+                Ok(socket) => break socket,
+                Err(e) => {
+                    attempts += 1;
+                    if attempts >= CONNECT_TO_SERVER_ATTEMPTS {
+                        return Err(e);
+                    }
+                    std::thread::sleep(CONNECT_TO_SERVER_RETRY_INTERVAL);
+                },
+            }
+        };
+        let sender = IpcSenderWithContext::new(socket);
+        let receiver = sender.get_receiver();
+        *self.send_instructions_to_server.lock().unwrap() = Some(sender);
+        *self.receive_instructions_from_server.lock().unwrap() = Some(receiver);
+        Ok(())
+    }
+    fn write_to_browser(&self, bytes: &[u8]) -> Result<()> {
+        let mut stream = self.websocket.lock().unwrap();
+        write_frame(&mut *stream, OpCode::Binary, bytes)?;
+        Ok(())
+    }
+}
+
+impl ClientOsApi for WebClientOsApi {
+    fn get_terminal_size_using_fd(&self, _fd: RawFd) -> Size {
+        *self.size.lock().unwrap()
+    }
+    fn set_raw_mode(&mut self, _fd: RawFd) {
+        // xterm.js hands us raw input to begin with
+    }
+    fn unset_raw_mode(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        Ok(())
+    }
+    fn get_stdout_writer(&self) -> Box<dyn io::Write> {
+        Box::new(WebSocketWriter {
+            stream: self.websocket.clone(),
+        })
+    }
+    fn get_stdin_reader(&self) -> Box<dyn io::BufRead> {
+        Box::new(io::empty())
+    }
+    fn update_session_name(&mut self, _new_session_name: String) {
+        // switching sessions is done by the browser reconnecting, see `run_web_client`
+    }
+    fn read_from_stdin(&mut self) -> Result<Vec<u8>, &'static str> {
+        self.browser_input
+            .recv()
+            .map_err(|_| "Browser disconnected")
+    }
+    fn box_clone(&self) -> Box<dyn ClientOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_server(&self, msg: ClientToServerMsg) {
+        if let Some(sender) = self.send_instructions_to_server.lock().unwrap().as_mut() {
+            let _ = sender.send(msg);
+        }
+    }
+    fn recv_from_server(&self) -> Option<(ServerToClientMsg, ErrorContext)> {
+        self.receive_instructions_from_server
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|receiver| receiver.recv())
+    }
+    fn handle_signals(&self, sigwinch_cb: Box<dyn Fn()>, quit_cb: Box<dyn Fn()>) {
+        loop {
+            match self.browser_events.recv() {
+                Ok(BrowserEvent::Resize) => sigwinch_cb(),
+                Ok(BrowserEvent::Closed) | Err(_) => {
+                    quit_cb();
+                    break;
+                },
+            }
+        }
+    }
+    fn connect_to_server(&self, path: &Path) {
+        if let Err(e) = self.try_connect_to_server(path) {
+            log::error!("Failed to connect to {}: {}", path.display(), e);
+        }
+    }
+    fn load_palette(&self) -> Palette {
+        default_palette()
+    }
+    fn enable_mouse(&self) -> Result<()> {
+        self.write_to_browser(ENABLE_MOUSE_SUPPORT.as_bytes())
+    }
+    fn disable_mouse(&self) -> Result<()> {
+        self.write_to_browser(DISABLE_MOUSE_SUPPORT.as_bytes())
+    }
+    fn enable_kitty_keyboard_protocol(&self) -> Result<()> {
+        // xterm.js does not support the kitty keyboard protocol
+        Ok(())
+    }
+    fn disable_kitty_keyboard_protocol(&self) -> Result<()> {
+        Ok(())
+    }
+    fn stdin_poller(&self) -> StdinPoller {
+        // the WebSocket is read by the thread that sends us the browser's input, polling its fd
+        // here would race that thread for the data
+        StdinPoller::from_receiver(self.browser_input.clone())
+    }
+}
@@ -0,0 +1,204 @@
+//! A minimal server-side implementation of the WebSocket protocol (RFC 6455).
+//!
+//! This only covers what the web client needs: the opening handshake, reading (masked) client
+//! frames including fragmented messages, and writing unmasked server frames. Extensions and
+//! subprotocols are not negotiated.
+
+use sha1::{Digest, Sha1};
+use std::io::{self, Read, Write};
+
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The largest message we are willing to buffer from a browser. We only ever receive keystrokes,
+/// pastes and small control messages, so anything bigger than this is treated as an error.
+pub const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpCode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl OpCode {
+    fn from_u8(byte: u8) -> Option<Self> {
+        match byte {
+            0x0 => Some(OpCode::Continuation),
+            0x1 => Some(OpCode::Text),
+            0x2 => Some(OpCode::Binary),
+            0x8 => Some(OpCode::Close),
+            0x9 => Some(OpCode::Ping),
+            0xA => Some(OpCode::Pong),
+            _ => None,
+        }
+    }
+    fn as_u8(&self) -> u8 {
+        match self {
+            OpCode::Continuation => 0x0,
+            OpCode::Text => 0x1,
+            OpCode::Binary => 0x2,
+            OpCode::Close => 0x8,
+            OpCode::Ping => 0x9,
+            OpCode::Pong => 0xA,
+        }
+    }
+    fn is_control(&self) -> bool {
+        matches!(self, OpCode::Close | OpCode::Ping | OpCode::Pong)
+    }
+}
+
+/// A complete message received from the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Close,
+}
+
+/// The value of the `Sec-WebSocket-Accept` header for the given `Sec-WebSocket-Key`
+pub fn accept_key(client_key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.input(client_key.trim().as_bytes());
+    hasher.input(WEBSOCKET_GUID.as_bytes());
+    base64::encode(hasher.result())
+}
+
+/// Writes a single, unfragmented and unmasked (as required for servers) frame
+pub fn write_frame(writer: &mut impl Write, opcode: OpCode, payload: &[u8]) -> io::Result<()> {
+    let mut header = Vec::with_capacity(10);
+    header.push(0x80 | opcode.as_u8());
+    let len = payload.len();
+    if len < 126 {
+        header.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        header.push(126);
+        header.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        header.push(127);
+        header.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+    writer.write_all(&header)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+struct Frame {
+    is_final: bool,
+    opcode: OpCode,
+    payload: Vec<u8>,
+}
+
+/// Reads messages from a browser, reassembling fragmented ones
+pub struct MessageReader<R: Read> {
+    reader: R,
+    fragmented_opcode: Option<OpCode>,
+    fragments: Vec<u8>,
+}
+
+impl<R: Read> MessageReader<R> {
+    pub fn new(reader: R) -> Self {
+        MessageReader {
+            reader,
+            fragmented_opcode: None,
+            fragments: vec![],
+        }
+    }
+    /// Blocks until a complete message is received. Pong frames are skipped.
+    pub fn read_message(&mut self) -> io::Result<Message> {
+        loop {
+            let frame = self.read_frame()?;
+            let (opcode, payload) = match frame.opcode {
+                OpCode::Continuation => {
+                    let opcode = self
+                        .fragmented_opcode
+                        .ok_or_else(|| invalid_data("continuation frame without a message"))?;
+                    if self.fragments.len() + frame.payload.len() > MAX_MESSAGE_SIZE {
+                        return Err(invalid_data("message too large"));
+                    }
+                    self.fragments.extend_from_slice(&frame.payload);
+                    if !frame.is_final {
+                        continue;
+                    }
+                    self.fragmented_opcode = None;
+                    (opcode, self.fragments.drain(..).collect())
+                },
+                OpCode::Text | OpCode::Binary => {
+                    if self.fragmented_opcode.is_some() {
+                        return Err(invalid_data("new message before previous one ended"));
+                    }
+                    if !frame.is_final {
+                        self.fragmented_opcode = Some(frame.opcode);
+                        self.fragments = frame.payload;
+                        continue;
+                    }
+                    (frame.opcode, frame.payload)
+                },
+                OpCode::Pong => continue,
+                opcode => (opcode, frame.payload),
+            };
+            return match opcode {
+                OpCode::Text => String::from_utf8(payload)
+                    .map(Message::Text)
+                    .map_err(|_| invalid_data("text message is not valid utf-8")),
+                OpCode::Binary => Ok(Message::Binary(payload)),
+                OpCode::Ping => Ok(Message::Ping(payload)),
+                _ => Ok(Message::Close),
+            };
+        }
+    }
+    fn read_frame(&mut self) -> io::Result<Frame> {
+        let mut header = [0; 2];
+        self.reader.read_exact(&mut header)?;
+        let is_final = header[0] & 0x80 != 0;
+        if header[0] & 0x70 != 0 {
+            return Err(invalid_data(
+                "reserved bits set without a negotiated extension",
+            ));
+        }
+        let opcode =
+            OpCode::from_u8(header[0] & 0x0F).ok_or_else(|| invalid_data("unknown opcode"))?;
+        let is_masked = header[1] & 0x80 != 0;
+        if !is_masked {
+            return Err(invalid_data("client frames must be masked"));
+        }
+        let len = match header[1] & 0x7F {
+            126 => {
+                let mut len = [0; 2];
+                self.reader.read_exact(&mut len)?;
+                u16::from_be_bytes(len) as u64
+            },
+            127 => {
+                let mut len = [0; 8];
+                self.reader.read_exact(&mut len)?;
+                u64::from_be_bytes(len)
+            },
+            len => len as u64,
+        };
+        if opcode.is_control() && (len > 125 || !is_final) {
+            return Err(invalid_data("invalid control frame"));
+        }
+        if len > MAX_MESSAGE_SIZE as u64 {
+            return Err(invalid_data("message too large"));
+        }
+        let mut mask = [0; 4];
+        self.reader.read_exact(&mut mask)?;
+        let mut payload = vec![0; len as usize];
+        self.reader.read_exact(&mut payload)?;
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+        Ok(Frame {
+            is_final,
+            opcode,
+            payload,
+        })
+    }
+}
@@ -5,12 +5,13 @@ use zellij_utils::consts::{
 };
 use zellij_utils::data::{Event, HttpVerb, SessionInfo};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
+use zellij_utils::session_serialization::is_pane_snapshot_file_name;
 use zellij_utils::surf::{
     http::{Method, Url},
     RequestBuilder,
 };
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::Write;
 use std::os::unix::fs::FileTypeExt;
@@ -21,6 +22,7 @@ use std::sync::{
 };
 use std::time::{Duration, Instant};
 
+use crate::panes::grid_snapshot::{GridSnapshot, GridSnapshotDelta};
 use crate::panes::PaneId;
 use crate::plugins::{PluginId, PluginInstruction};
 use crate::screen::ScreenInstruction;
@@ -35,6 +37,9 @@ pub enum BackgroundJob {
     ReadAllSessionInfosOnMachine,                         // u32 - plugin_id
     ReportSessionInfo(String, SessionInfo),               // String - session name
     ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
+    // BTreeMap<file_name, snapshot> - None if it did not change since it was last reported,
+    // Option<usize> - scrollback_lines_to_serialize
+    ReportPaneSnapshots(BTreeMap<String, Option<GridSnapshotDelta>>, Option<usize>),
     RunCommand(
         PluginId,
         ClientId,
@@ -69,6 +74,7 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             },
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
+            BackgroundJob::ReportPaneSnapshots(..) => BackgroundJobContext::ReportPaneSnapshots,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
@@ -91,6 +97,9 @@ pub(crate) fn background_jobs_main(
     let current_session_name = Arc::new(Mutex::new(String::default()));
     let current_session_info = Arc::new(Mutex::new(SessionInfo::default()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
+    // None until the first report, so that we don't remove the snapshots of a resurrected session
+    // before this one had a chance to write its own
+    let current_pane_snapshots: Arc<Mutex<Option<PaneSnapshots>>> = Arc::new(Mutex::new(None));
     let last_serialization_time = Arc::new(Mutex::new(Instant::now()));
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
                                                                            // milliseconds
@@ -154,6 +163,13 @@ pub(crate) fn background_jobs_main(
             BackgroundJob::ReportLayoutInfo(session_layout) => {
                 *current_session_layout.lock().unwrap() = session_layout;
             },
+            BackgroundJob::ReportPaneSnapshots(pane_snapshots, scrollback_lines_to_serialize) => {
+                current_pane_snapshots
+                    .lock()
+                    .unwrap()
+                    .get_or_insert_with(PaneSnapshots::default)
+                    .update(pane_snapshots, scrollback_lines_to_serialize);
+            },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 // this job should only be run once and it keeps track of other sessions (as well
                 // as this one's) infos (metadata mostly) and sends it to the screen which in turn
@@ -167,6 +183,7 @@ pub(crate) fn background_jobs_main(
                     let current_session_info = current_session_info.clone();
                     let current_session_name = current_session_name.clone();
                     let current_session_layout = current_session_layout.clone();
+                    let current_pane_snapshots = current_pane_snapshots.clone();
                     let last_serialization_time = last_serialization_time.clone();
                     async move {
                         loop {
@@ -180,6 +197,19 @@ pub(crate) fn background_jobs_main(
                                 current_session_info,
                                 current_session_layout,
                             );
+                            let pane_snapshots_to_write = current_pane_snapshots
+                                .lock()
+                                .unwrap()
+                                .as_mut()
+                                .map(|pane_snapshots| pane_snapshots.take_snapshots_to_write());
+                            if let Some((live_file_names, pane_snapshots)) = pane_snapshots_to_write
+                            {
+                                write_pane_snapshots_to_disk(
+                                    &current_session_name,
+                                    live_file_names,
+                                    pane_snapshots,
+                                );
+                            }
                             let session_infos_on_machine =
                                 read_other_live_session_states(&current_session_name);
                             let resurrectable_sessions =
@@ -188,6 +218,8 @@ pub(crate) fn background_jobs_main(
                                 session_infos_on_machine,
                                 resurrectable_sessions,
                             ));
+                            let _ = senders
+                                .send_to_screen(ScreenInstruction::ReportTerminalCwdsAndCommands);
                             if last_serialization_time
                                 .lock()
                                 .unwrap()
@@ -382,6 +414,97 @@ fn write_session_state_to_disk(
     }
 }
 
+// the full snapshots of all terminal panes by their file name, built from the deltas reported
+// by the screen
+#[derive(Default)]
+struct PaneSnapshots {
+    snapshots: BTreeMap<String, GridSnapshot>,
+    unwritten_file_names: BTreeSet<String>,
+    scrollback_lines_to_serialize: Option<usize>,
+}
+
+impl PaneSnapshots {
+    pub fn update(
+        &mut self,
+        pane_snapshots: BTreeMap<String, Option<GridSnapshotDelta>>,
+        scrollback_lines_to_serialize: Option<usize>,
+    ) {
+        // panes that were not reported have since been closed
+        self.snapshots
+            .retain(|file_name, _| pane_snapshots.contains_key(file_name));
+        self.unwritten_file_names
+            .retain(|file_name| pane_snapshots.contains_key(file_name));
+        if scrollback_lines_to_serialize != self.scrollback_lines_to_serialize {
+            self.scrollback_lines_to_serialize = scrollback_lines_to_serialize;
+            self.unwritten_file_names
+                .extend(self.snapshots.keys().cloned());
+        }
+        for (file_name, delta) in pane_snapshots {
+            if let Some(delta) = delta {
+                self.snapshots
+                    .entry(file_name.clone())
+                    .or_default()
+                    .apply_delta(delta);
+                self.unwritten_file_names.insert(file_name);
+            }
+        }
+    }
+    // returns the file names of all the snapshots we know about and the contents of those that
+    // have not yet been written
+    pub fn take_snapshots_to_write(&mut self) -> (BTreeSet<String>, BTreeMap<String, Vec<u8>>) {
+        let live_file_names = self.snapshots.keys().cloned().collect();
+        let mut pane_snapshots_to_write = BTreeMap::new();
+        for file_name in std::mem::take(&mut self.unwritten_file_names) {
+            let snapshot = self
+                .snapshots
+                .get(&file_name)
+                .map(|snapshot| snapshot.to_bytes(self.scrollback_lines_to_serialize));
+            match snapshot {
+                Some(Ok(snapshot)) => {
+                    pane_snapshots_to_write.insert(file_name, snapshot);
+                },
+                Some(Err(e)) => {
+                    log::error!("Failed to serialize pane snapshot {}: {:?}", file_name, e)
+                },
+                None => {},
+            }
+        }
+        (live_file_names, pane_snapshots_to_write)
+    }
+}
+
+fn write_pane_snapshots_to_disk(
+    current_session_name: &str,
+    live_file_names: BTreeSet<String>,
+    pane_snapshots: BTreeMap<String, Vec<u8>>,
+) {
+    let session_info_folder = session_info_folder_for_session(current_session_name);
+    if let Err(e) = std::fs::create_dir_all(&session_info_folder) {
+        log::error!("Failed to create session info folder: {:?}", e);
+        return;
+    }
+    for (file_name, snapshot) in pane_snapshots {
+        // written to a temporary file first so that a resurrection never reads half a snapshot
+        let snapshot_file = session_info_folder.join(&file_name);
+        let temporary_file = session_info_folder.join(format!("{}.tmp", file_name));
+        std::fs::write(&temporary_file, snapshot)
+            .and_then(|_| std::fs::rename(&temporary_file, &snapshot_file))
+            .unwrap_or_else(|e| {
+                log::error!("Failed to write pane snapshot {}: {:?}", file_name, e);
+            });
+    }
+    // remove the snapshots of panes that have since been closed
+    if let Ok(files) = fs::read_dir(&session_info_folder) {
+        for file in files.filter_map(|f| f.ok()) {
+            if let Ok(file_name) = file.file_name().into_string() {
+                if is_pane_snapshot_file_name(&file_name) && !live_file_names.contains(&file_name) {
+                    let _ = std::fs::remove_file(file.path());
+                }
+            }
+        }
+    }
+}
+
 fn read_other_live_session_states(current_session_name: &str) -> BTreeMap<String, SessionInfo> {
     let mut other_session_names = vec![];
     let mut session_infos_on_machine = BTreeMap::new();
@@ -5,6 +5,7 @@ pub mod tab;
 
 mod background_jobs;
 mod logging_pipe;
+mod pane_waits;
 mod plugins;
 mod pty;
 mod pty_writer;
@@ -32,31 +33,39 @@ use wasmer::Store;
 
 use crate::{
     os_input_output::ServerOsApi,
+    panes::PaneId,
     plugins::{plugin_thread_main, PluginInstruction},
     pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
     screen::{screen_thread_main, ScreenInstruction},
     thread_bus::{Bus, ThreadSenders},
 };
-use route::route_thread_main;
+use route::{route_action, route_thread_main};
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
     consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
-    data::{ConnectToSession, Event, PluginCapabilities},
+    data::{ConnectToSession, Event, PluginCapabilities, Style},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
     home::get_default_data_dir,
     input::{
+        actions::Action,
         command::{RunCommand, TerminalAction},
         get_mode_info,
+        keybinds::{Keybinds, PluginKeybind},
         layout::Layout,
         options::Options,
         plugins::PluginsConfig,
+        triggers::Triggers,
     },
     ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
 };
 
 pub type ClientId = u16;
 
+// client ids are given out starting from 1, this one is used for actions that are not run on
+// behalf of any connected client
+const NO_CLIENT_ID: ClientId = 0;
+
 /// Instructions related to server-side application
 #[derive(Debug, Clone)]
 pub enum ServerInstruction {
@@ -67,6 +76,7 @@ pub enum ServerInstruction {
         Box<Layout>,
         ClientId,
         Option<PluginsConfig>,
+        Triggers,
     ),
     Render(Option<HashMap<ClientId, String>>),
     UnblockInputThread,
@@ -93,6 +103,9 @@ pub enum ServerInstruction {
         pipe_id: String,
         client_id: ClientId,
     },
+    RunTriggerActions(Vec<Action>, u32), // u32 is the id of the terminal pane that matched
+    PluginKeybinds(Vec<PluginKeybind>, Option<ClientId>), // None => all clients
+    ReconfigureClient(Keybinds, Style, Box<Options>, ClientId),
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -117,6 +130,9 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::AssociatePipeWithClient { .. } => {
                 ServerContext::AssociatePipeWithClient
             },
+            ServerInstruction::RunTriggerActions(..) => ServerContext::RunTriggerActions,
+            ServerInstruction::PluginKeybinds(..) => ServerContext::PluginKeybinds,
+            ServerInstruction::ReconfigureClient(..) => ServerContext::ReconfigureClient,
         }
     }
 }
@@ -131,6 +147,7 @@ pub(crate) struct SessionMetaData {
     pub senders: ThreadSenders,
     pub capabilities: PluginCapabilities,
     pub client_attributes: ClientAttributes,
+    pub client_keybinds: HashMap<ClientId, Keybinds>, // keybinds of clients that reloaded their config
     pub default_shell: Option<TerminalAction>,
     pub layout: Box<Layout>,
     screen_thread: Option<thread::JoinHandle<()>>,
@@ -140,6 +157,18 @@ pub(crate) struct SessionMetaData {
     background_jobs_thread: Option<thread::JoinHandle<()>>,
 }
 
+impl SessionMetaData {
+    pub fn client_attributes_for(&self, client_id: ClientId) -> ClientAttributes {
+        match self.client_keybinds.get(&client_id) {
+            Some(keybinds) => ClientAttributes {
+                keybinds: keybinds.clone(),
+                ..self.client_attributes.clone()
+            },
+            None => self.client_attributes.clone(),
+        }
+    }
+}
+
 impl Drop for SessionMetaData {
     fn drop(&mut self) {
         let _ = self.senders.send_to_pty(PtyInstruction::Exit);
@@ -199,6 +228,7 @@ macro_rules! send_to_client {
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct SessionState {
     clients: HashMap<ClientId, Option<Size>>,
+    read_only_clients: HashSet<ClientId>,
     pipes: HashMap<String, ClientId>, // String => pipe_id
 }
 
@@ -206,6 +236,7 @@ impl SessionState {
     pub fn new() -> Self {
         SessionState {
             clients: HashMap::new(),
+            read_only_clients: HashSet::new(),
             pipes: HashMap::new(),
         }
     }
@@ -227,24 +258,31 @@ impl SessionState {
     }
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.clients.remove(&client_id);
+        self.read_only_clients.remove(&client_id);
         self.pipes.retain(|_p_id, c_id| c_id != &client_id);
     }
     pub fn set_client_size(&mut self, client_id: ClientId, size: Size) {
         self.clients.insert(client_id, Some(size));
     }
+    pub fn set_client_read_only(&mut self, client_id: ClientId) {
+        self.read_only_clients.insert(client_id);
+    }
     pub fn min_client_terminal_size(&self) -> Option<Size> {
         // None if there are no client sizes
-        let mut rows: Vec<usize> = self
+        // read-only clients do not get to shrink the session for everyone else, unless they are
+        // the only ones with a size
+        let mut client_sizes: Vec<Size> = self
             .clients
-            .values()
-            .filter_map(|size| size.map(|size| size.rows))
+            .iter()
+            .filter(|(client_id, _)| !self.read_only_clients.contains(client_id))
+            .filter_map(|(_, size)| *size)
             .collect();
+        if client_sizes.is_empty() {
+            client_sizes = self.clients.values().filter_map(|size| *size).collect();
+        }
+        let mut rows: Vec<usize> = client_sizes.iter().map(|size| size.rows).collect();
         rows.sort_unstable();
-        let mut cols: Vec<usize> = self
-            .clients
-            .values()
-            .filter_map(|size| size.map(|size| size.cols))
-            .collect();
+        let mut cols: Vec<usize> = client_sizes.iter().map(|size| size.cols).collect();
         cols.sort_unstable();
         let min_rows = rows.first();
         let min_cols = cols.first();
@@ -362,6 +400,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 layout,
                 client_id,
                 plugins,
+                triggers,
             ) => {
                 let session = init_session(
                     os_input.clone(),
@@ -371,6 +410,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         opts,
                         layout: layout.clone(),
                         plugins,
+                        triggers,
                         config_options: config_options.clone(),
                     },
                 );
@@ -461,8 +501,19 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 pane_id_to_focus,
                 client_id,
             ) => {
+                // client ids are reused, so this one should not get the keybinds of a previous
+                // client that reloaded its config
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    session_data.client_keybinds.remove(&client_id);
+                }
                 let rlock = session_data.read().unwrap();
                 let session_data = rlock.as_ref().unwrap();
+                if attrs.is_read_only {
+                    session_state
+                        .write()
+                        .unwrap()
+                        .set_client_read_only(client_id);
+                }
                 session_state
                     .write()
                     .unwrap()
@@ -482,6 +533,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         client_id,
                         tab_position_to_focus,
                         pane_id_to_focus,
+                        attrs.is_read_only,
                     ))
                     .unwrap();
                 session_data
@@ -790,6 +842,70 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .unwrap()
                     .associate_pipe_with_client(pipe_id, client_id);
             },
+            ServerInstruction::RunTriggerActions(actions, terminal_id) => {
+                // triggers are not sent by any client, so we run their actions on behalf of the
+                // longest connected one, when no client is connected we still run them so that
+                // those that do not need one (eg. pipes or acting on the matched pane) happen
+                let client_id = session_state
+                    .read()
+                    .unwrap()
+                    .client_ids()
+                    .into_iter()
+                    .min()
+                    .unwrap_or(NO_CLIENT_ID);
+                match session_data.read().unwrap().as_ref() {
+                    Some(session_data) => {
+                        for action in actions {
+                            if let Err(e) = route_action(
+                                action,
+                                client_id,
+                                Some(PaneId::Terminal(terminal_id)),
+                                session_data.senders.clone(),
+                                session_data.capabilities,
+                                session_data.client_attributes_for(client_id),
+                                session_data.default_shell.clone(),
+                                session_data.layout.clone(),
+                                None,
+                            ) {
+                                log::error!("Failed to run trigger action: {:?}", e);
+                            }
+                        }
+                    },
+                    None => {
+                        log::info!("No session, not running trigger actions");
+                    },
+                }
+            },
+            ServerInstruction::PluginKeybinds(plugin_keybinds, client_id) => {
+                let client_ids = match client_id {
+                    Some(client_id) => vec![client_id],
+                    None => session_state.read().unwrap().client_ids(),
+                };
+                for client_id in client_ids {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::PluginKeybinds(plugin_keybinds.clone()),
+                        session_state
+                    );
+                }
+            },
+            ServerInstruction::ReconfigureClient(keybinds, style, options, client_id) => {
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    // actions routed from now on (eg. mode switches) use the new keybinds of this
+                    // client and the new style when updating the status bar
+                    session_data
+                        .client_keybinds
+                        .insert(client_id, keybinds.clone());
+                    session_data.client_attributes.style = style;
+                    session_data
+                        .senders
+                        .send_to_screen(ScreenInstruction::Reconfigure(
+                            keybinds, style, options, client_id,
+                        ))
+                        .unwrap();
+                }
+            },
         }
     }
 
@@ -804,6 +920,7 @@ pub struct SessionOptions {
     pub config_options: Box<Options>,
     pub layout: Box<Layout>,
     pub plugins: Option<PluginsConfig>,
+    pub triggers: Triggers,
 }
 
 fn init_session(
@@ -817,6 +934,7 @@ fn init_session(
         config_options,
         layout,
         plugins,
+        triggers,
     } = options;
 
     let _ = SCROLL_BUFFER_SIZE.set(
@@ -882,6 +1000,7 @@ fn init_session(
                 ),
                 opts.debug,
                 config_options.scrollback_editor.clone(),
+                triggers,
             );
 
             move || pty_thread_main(pty, layout.clone()).fatal()
@@ -1005,6 +1124,7 @@ fn init_session(
         capabilities,
         default_shell,
         client_attributes,
+        client_keybinds: HashMap::new(),
         layout,
         screen_thread: Some(screen_thread),
         pty_thread: Some(pty_thread),
@@ -183,6 +183,7 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(&cmd.env)
                 .env("ZELLIJ_PANE_ID", &format!("{}", terminal_id))
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
@@ -337,6 +338,7 @@ fn spawn_terminal(
                 cwd,
                 hold_on_close: false,
                 hold_on_start: false,
+                env: BTreeMap::new(),
             }
         },
         TerminalAction::RunCommand(command) => command,
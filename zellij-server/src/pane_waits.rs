@@ -0,0 +1,146 @@
+//! Panes a cli client (eg. `zellij script`) is waiting on through `Action::WaitForPane`, they are
+//! checked whenever the state of the session changes and whenever a terminal pane prints
+//! something.
+use crate::panes::PaneId;
+use crate::terminal_bytes::TriggerMatcher;
+use crate::ClientId;
+use zellij_utils::{
+    data::{PaneContents, PaneInfo, PaneManifest},
+    errors::prelude::*,
+    input::{
+        actions::PaneCondition,
+        triggers::{Trigger, Triggers},
+    },
+    regex::Regex,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PaneWaitResult {
+    Pending,
+    /// The condition was met, this is reported to the client through the pipe
+    Met(String),
+    /// The condition can no longer be met (eg. the pane was closed before printing the line)
+    Unmet,
+}
+
+pub(crate) struct PaneWait {
+    pub pipe_id: String,
+    pub client_id: ClientId, // the cli client waiting, the wait is dropped when it disconnects
+    pane: String,            // the name or terminal id of the pane as the client gave it
+    condition: PaneCondition,
+    pane_id: Option<PaneId>, // once the pane was found, it might not have been created yet
+    output_pattern: Option<Regex>,
+    output_matcher: TriggerMatcher,
+}
+
+impl PaneWait {
+    pub fn new(
+        pipe_id: String,
+        pane: String,
+        condition: PaneCondition,
+        client_id: ClientId,
+    ) -> Result<Self> {
+        let (output_pattern, output_matcher) = match &condition {
+            PaneCondition::OutputMatches(pattern) => {
+                let output_pattern = Regex::new(pattern)
+                    .with_context(|| format!("invalid pattern to wait for: {}", pattern))?;
+                let output_matcher = TriggerMatcher::new(&Triggers(vec![Trigger {
+                    pattern: pattern.clone(),
+                    ..Default::default()
+                }]));
+                (Some(output_pattern), output_matcher)
+            },
+            PaneCondition::Exited => (None, TriggerMatcher::default()),
+        };
+        Ok(PaneWait {
+            pipe_id,
+            client_id,
+            pane,
+            condition,
+            pane_id: None,
+            output_pattern,
+            output_matcher,
+        })
+    }
+    /// Checks the wait against the current state of the session, `pane_contents` is used to look
+    /// for output that was printed before we started waiting
+    pub fn check_session_state(
+        &mut self,
+        pane_manifest: &PaneManifest,
+        pane_contents: impl Fn(PaneId) -> Option<PaneContents>,
+    ) -> PaneWaitResult {
+        let mut pane_infos = pane_manifest.panes.values().flatten();
+        match self.pane_id {
+            None => {
+                let pane_info = match pane_infos.find(|p| self.is_pane(p)) {
+                    Some(pane_info) => pane_info,
+                    None => return PaneWaitResult::Pending,
+                };
+                let pane_id = pane_id_of(pane_info);
+                self.pane_id = Some(pane_id);
+                match &self.output_pattern {
+                    Some(output_pattern) => pane_contents(pane_id)
+                        .and_then(|pane_contents| {
+                            pane_contents
+                                .lines_above_viewport
+                                .into_iter()
+                                .chain(pane_contents.viewport)
+                                .find(|line| output_pattern.is_match(line))
+                        })
+                        .map(PaneWaitResult::Met)
+                        .unwrap_or(PaneWaitResult::Pending),
+                    None => self.check_exit(pane_info),
+                }
+            },
+            Some(pane_id) => match pane_infos.find(|p| pane_id_of(p) == pane_id) {
+                Some(pane_info) => match self.condition {
+                    PaneCondition::Exited => self.check_exit(pane_info),
+                    PaneCondition::OutputMatches(_) => PaneWaitResult::Pending,
+                },
+                None => match self.condition {
+                    // the pane was closed, its exit status is unknown
+                    PaneCondition::Exited => PaneWaitResult::Met(String::new()),
+                    PaneCondition::OutputMatches(_) => PaneWaitResult::Unmet,
+                },
+            },
+        }
+    }
+    /// Checks the wait against what a terminal pane printed
+    pub fn check_output(&mut self, terminal_id: u32, bytes: &[u8]) -> PaneWaitResult {
+        if self.pane_id != Some(PaneId::Terminal(terminal_id)) {
+            return PaneWaitResult::Pending;
+        }
+        match self.output_matcher.match_bytes(bytes).drain(..).next() {
+            Some((_trigger, line)) => PaneWaitResult::Met(line),
+            None => PaneWaitResult::Pending,
+        }
+    }
+    fn check_exit(&self, pane_info: &PaneInfo) -> PaneWaitResult {
+        if pane_info.exited {
+            PaneWaitResult::Met(
+                pane_info
+                    .exit_status
+                    .map(|exit_status| exit_status.to_string())
+                    .unwrap_or_default(),
+            )
+        } else {
+            PaneWaitResult::Pending
+        }
+    }
+    fn is_pane(&self, pane_info: &PaneInfo) -> bool {
+        let is_terminal_id = !pane_info.is_plugin && self.pane == pane_info.id.to_string();
+        is_terminal_id || self.pane == pane_info.title
+    }
+}
+
+fn pane_id_of(pane_info: &PaneInfo) -> PaneId {
+    if pane_info.is_plugin {
+        PaneId::Plugin(pane_info.id)
+    } else {
+        PaneId::Terminal(pane_info.id)
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pane_waits_tests.rs"]
+mod pane_waits_tests;
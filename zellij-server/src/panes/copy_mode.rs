@@ -0,0 +1,348 @@
+use crate::panes::{Grid, Row};
+use zellij_utils::input::actions::{CopyModeMotion, CopyModeSelection};
+use zellij_utils::position::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharacterClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+impl CharacterClass {
+    fn of(character: Option<char>) -> Self {
+        match character {
+            Some(c) if c.is_alphanumeric() || c == '_' => CharacterClass::Word,
+            Some(c) if !c.is_whitespace() => CharacterClass::Punctuation,
+            _ => CharacterClass::Whitespace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CopyModeCursor {
+    // Relative to the viewport, just like the mouse position when selecting
+    pub position: Position,
+    // Where we started selecting, this moves along with the content when scrolling
+    anchor: Option<(CopyModeSelection, Position)>,
+    // The previous motion was vi's `g` prefix
+    pending_go_prefix: bool,
+}
+
+impl CopyModeCursor {
+    pub fn move_up(&mut self, lines: usize) {
+        if let Some((_, anchor)) = self.anchor.as_mut() {
+            anchor.line.0 -= lines as isize;
+        }
+    }
+
+    pub fn move_down(&mut self, lines: usize) {
+        if let Some((_, anchor)) = self.anchor.as_mut() {
+            anchor.line.0 += lines as isize;
+        }
+    }
+}
+
+// The (column, character) pairs of a row, the column taking wide characters into account
+fn columns_of_row(row: &Row) -> Vec<(usize, char)> {
+    let mut column = 0;
+    row.columns
+        .iter()
+        .map(|terminal_character| {
+            let column_and_character = (column, terminal_character.character);
+            column += terminal_character.width();
+            column_and_character
+        })
+        .collect()
+}
+
+impl Grid {
+    pub fn enter_copy_mode(&mut self) {
+        let line = self.cursor.y.min(self.last_copy_mode_line());
+        let column = self.cursor.x.min(self.width.saturating_sub(1));
+        self.copy_mode = Some(CopyModeCursor {
+            position: Position::new(line as i32, column as u16),
+            anchor: None,
+            pending_go_prefix: false,
+        });
+        self.reset_selection();
+    }
+
+    pub fn exit_copy_mode(&mut self) {
+        if self.copy_mode.take().is_some() {
+            self.reset_selection();
+        }
+    }
+
+    pub fn copy_mode_move(&mut self, motion: CopyModeMotion) {
+        let (position, pending_go_prefix) = match self.copy_mode.as_mut() {
+            Some(copy_mode) => (
+                copy_mode.position,
+                std::mem::replace(&mut copy_mode.pending_go_prefix, false),
+            ),
+            None => return,
+        };
+        let motion = match motion {
+            CopyModeMotion::GoPrefix if pending_go_prefix => CopyModeMotion::Top,
+            CopyModeMotion::GoPrefix => {
+                if let Some(copy_mode) = self.copy_mode.as_mut() {
+                    copy_mode.pending_go_prefix = true;
+                }
+                return;
+            },
+            motion => motion,
+        };
+        let (line, column) = (position.line() as usize, position.column());
+        let new_position = match motion {
+            CopyModeMotion::Left => (line, column.saturating_sub(1)),
+            CopyModeMotion::Right => (line, (column + 1).min(self.width.saturating_sub(1))),
+            CopyModeMotion::Up => {
+                if line == 0 {
+                    self.scroll_up_one_line();
+                    (line, column)
+                } else {
+                    (line - 1, column)
+                }
+            },
+            CopyModeMotion::Down => {
+                if line >= self.last_copy_mode_line() {
+                    self.scroll_down_one_line();
+                    (self.last_copy_mode_line(), column)
+                } else {
+                    (line + 1, column)
+                }
+            },
+            CopyModeMotion::NextWord => self.next_word_start(line, column),
+            CopyModeMotion::PreviousWord => self.previous_word_start(line, column),
+            CopyModeMotion::EndOfWord => self.next_word_end(line, column),
+            CopyModeMotion::LineStart => (line, 0),
+            CopyModeMotion::LineEnd => {
+                let last_column = self
+                    .viewport
+                    .get(line)
+                    .map(columns_of_row)
+                    .and_then(|columns| {
+                        columns
+                            .iter()
+                            .rev()
+                            .find(|(_, c)| !c.is_whitespace())
+                            .map(|(column, _)| *column)
+                    })
+                    .unwrap_or(0);
+                (line, last_column)
+            },
+            CopyModeMotion::Top => {
+                self.scroll_to_top();
+                (0, 0)
+            },
+            CopyModeMotion::Bottom => {
+                self.scroll_to_bottom();
+                (self.last_copy_mode_line(), 0)
+            },
+            // handled above
+            CopyModeMotion::GoPrefix => (line, column),
+        };
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.position = Position::new(new_position.0 as i32, new_position.1 as u16);
+        }
+        self.update_copy_mode_selection();
+        self.mark_for_rerender();
+    }
+
+    pub fn copy_mode_toggle_selection(&mut self, selection: CopyModeSelection) {
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.anchor = match copy_mode.anchor {
+                // Pressing the same key again stops selecting, the other one switches the kind
+                // of selection and keeps the start of the selection
+                Some((current_selection, _)) if current_selection == selection => None,
+                Some((_, anchor)) => Some((selection, anchor)),
+                None => Some((selection, copy_mode.position)),
+            };
+        }
+        self.update_copy_mode_selection();
+        self.mark_for_rerender();
+    }
+
+    pub fn copy_mode_cursor_coordinates(&self) -> Option<(usize, usize)> {
+        self.copy_mode.map(|copy_mode| {
+            (
+                copy_mode.position.column(),
+                copy_mode.position.line() as usize,
+            )
+        })
+    }
+
+    fn update_copy_mode_selection(&mut self) {
+        let (anchor, cursor) = match self.copy_mode {
+            Some(CopyModeCursor {
+                position,
+                anchor: Some(anchor),
+                ..
+            }) => (anchor, position),
+            _ => {
+                self.reset_selection();
+                return;
+            },
+        };
+        let (start, end) = match anchor {
+            (CopyModeSelection::Character, anchor) => {
+                // Both the anchor and the cursor are part of the selection
+                let (first, last) = if anchor <= cursor {
+                    (anchor, cursor)
+                } else {
+                    (cursor, anchor)
+                };
+                let end = Position::new(last.line() as i32, (last.column() + 1) as u16);
+                (first, end)
+            },
+            (CopyModeSelection::Line, anchor) => {
+                let first_line = anchor.line().min(cursor.line());
+                let last_line = anchor.line().max(cursor.line());
+                (
+                    Position::new(first_line as i32, 0),
+                    Position::new(last_line as i32, self.width as u16),
+                )
+            },
+        };
+        self.start_selection(&start);
+        self.end_selection(&end);
+    }
+
+    fn last_copy_mode_line(&self) -> usize {
+        self.viewport.len().saturating_sub(1)
+    }
+
+    fn character_at(&self, line: usize, column: usize) -> Option<char> {
+        self.viewport.get(line).and_then(|row| {
+            columns_of_row(row)
+                .into_iter()
+                .find(|(c, _)| *c == column)
+                .map(|(_, character)| character)
+        })
+    }
+
+    // Returns the next position and whether we moved past a line break (the beginning of a
+    // canonical line) to get there. Scrolls down if needed and `scroll` is true.
+    fn step_forward(
+        &mut self,
+        line: usize,
+        column: usize,
+        scroll: bool,
+    ) -> Option<(usize, usize, bool)> {
+        let columns = columns_of_row(self.viewport.get(line)?);
+        if let Some((next_column, _)) = columns.iter().find(|(c, _)| *c > column) {
+            return Some((line, *next_column, false));
+        }
+        if line < self.last_copy_mode_line() {
+            Some((line + 1, 0, self.viewport[line + 1].is_canonical))
+        } else if scroll && !self.lines_below.is_empty() {
+            self.scroll_down_one_line();
+            let line = self.last_copy_mode_line();
+            Some((line, 0, self.viewport[line].is_canonical))
+        } else {
+            None
+        }
+    }
+
+    // Same as `step_forward`, but in the other direction
+    fn step_backward(&mut self, line: usize, column: usize) -> Option<(usize, usize, bool)> {
+        if column > 0 {
+            let columns = columns_of_row(self.viewport.get(line)?);
+            let previous_column = columns
+                .iter()
+                .rev()
+                .find(|(c, _)| *c < column)
+                .map(|(c, _)| *c)
+                .unwrap_or(0);
+            return Some((line, previous_column, false));
+        }
+        let (line, crossed_line_break) = if line > 0 {
+            (line - 1, self.viewport.get(line)?.is_canonical)
+        } else if !self.lines_above.is_empty() {
+            self.scroll_up_one_line();
+            (0, self.viewport.get(1)?.is_canonical)
+        } else {
+            return None;
+        };
+        let last_column = self
+            .viewport
+            .get(line)
+            .and_then(|row| columns_of_row(row).last().map(|(c, _)| *c))
+            .unwrap_or(0);
+        Some((line, last_column, crossed_line_break))
+    }
+
+    // vi's `w`
+    fn next_word_start(&mut self, mut line: usize, mut column: usize) -> (usize, usize) {
+        let start_class = CharacterClass::of(self.character_at(line, column));
+        let mut passed_whitespace = false;
+        while let Some((next_line, next_column, crossed_line_break)) =
+            self.step_forward(line, column, true)
+        {
+            line = next_line;
+            column = next_column;
+            let class = CharacterClass::of(self.character_at(line, column));
+            if crossed_line_break || class == CharacterClass::Whitespace {
+                passed_whitespace = true;
+            }
+            if class != CharacterClass::Whitespace && (passed_whitespace || class != start_class) {
+                break;
+            }
+        }
+        (line, column)
+    }
+
+    // vi's `e`
+    fn next_word_end(&mut self, mut line: usize, mut column: usize) -> (usize, usize) {
+        // we always move at least one character and skip the whitespace after it
+        while let Some((next_line, next_column, _)) = self.step_forward(line, column, true) {
+            line = next_line;
+            column = next_column;
+            if CharacterClass::of(self.character_at(line, column)) != CharacterClass::Whitespace {
+                break;
+            }
+        }
+        let class = CharacterClass::of(self.character_at(line, column));
+        while let Some((next_line, next_column, crossed_line_break)) =
+            self.step_forward(line, column, false)
+        {
+            if crossed_line_break
+                || CharacterClass::of(self.character_at(next_line, next_column)) != class
+            {
+                break;
+            }
+            line = next_line;
+            column = next_column;
+        }
+        (line, column)
+    }
+
+    // vi's `b`
+    fn previous_word_start(&mut self, mut line: usize, mut column: usize) -> (usize, usize) {
+        // we always move at least one character and skip the whitespace before it
+        while let Some((previous_line, previous_column, _)) = self.step_backward(line, column) {
+            line = previous_line;
+            column = previous_column;
+            if CharacterClass::of(self.character_at(line, column)) != CharacterClass::Whitespace {
+                break;
+            }
+        }
+        let class = CharacterClass::of(self.character_at(line, column));
+        // we stop at the top of the viewport rather than scrolling to find the beginning of the word
+        while column > 0 || (line > 0 && !self.viewport[line].is_canonical) {
+            let (previous_line, previous_column, crossed_line_break) =
+                match self.step_backward(line, column) {
+                    Some(previous) => previous,
+                    None => break,
+                };
+            if crossed_line_break
+                || CharacterClass::of(self.character_at(previous_line, previous_column)) != class
+            {
+                break;
+            }
+            line = previous_line;
+            column = previous_column;
+        }
+        (line, column)
+    }
+}
@@ -1,6 +1,6 @@
 mod floating_pane_grid;
 use zellij_utils::{
-    data::{Direction, PaneInfo, ResizeStrategy},
+    data::{Direction, FloatingPaneCoordinates, PaneInfo, ResizeStrategy},
     position::Position,
 };
 
@@ -25,7 +25,7 @@ use zellij_utils::{
     data::{ModeInfo, Style},
     errors::prelude::*,
     input::command::RunCommand,
-    input::layout::{FloatingPaneLayout, Run, RunPlugin},
+    input::layout::{FloatingPaneLayout, PercentOrFixed, Run, RunPlugin},
     pane_size::{Dimension, Offset, PaneGeom, Size, SizeInPixels, Viewport},
 };
 
@@ -87,9 +87,9 @@ impl FloatingPanes {
         }
     }
     pub fn stack(&self) -> Option<FloatingPanesStack> {
-        if self.panes_are_visible() {
+        if self.panes_are_visible() || self.has_pinned_panes() {
             let layers = self
-                .z_indices
+                .rendered_pane_ids()
                 .iter()
                 .map(|pane_id| self.panes.get(pane_id).unwrap().position_and_size())
                 .collect();
@@ -98,6 +98,36 @@ impl FloatingPanes {
             None
         }
     }
+    fn rendered_pane_ids(&self) -> Vec<PaneId> {
+        // pinned panes are rendered above all other floating panes, and are the only ones
+        // rendered when the floating panes are hidden
+        let mut rendered_pane_ids: Vec<PaneId> = self
+            .z_indices
+            .iter()
+            .filter(|pane_id| {
+                self.show_panes
+                    || self
+                        .panes
+                        .get(pane_id)
+                        .map(|p| p.is_pinned())
+                        .unwrap_or(false)
+            })
+            .copied()
+            .collect();
+        rendered_pane_ids.sort_by_key(|pane_id| {
+            self.panes
+                .get(pane_id)
+                .map(|p| p.is_pinned())
+                .unwrap_or(false)
+        });
+        rendered_pane_ids
+    }
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        for pane in self.panes.values_mut() {
+            pane.update_style(style);
+        }
+    }
     pub fn pane_ids(&self) -> impl Iterator<Item = &PaneId> {
         self.panes.keys()
     }
@@ -201,6 +231,9 @@ impl FloatingPanes {
     pub fn has_panes(&self) -> bool {
         !self.panes.is_empty()
     }
+    pub fn has_pinned_panes(&self) -> bool {
+        self.panes.values().any(|p| p.is_pinned())
+    }
     pub fn active_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
         self.active_panes.get(&client_id).copied()
     }
@@ -241,44 +274,59 @@ impl FloatingPanes {
         &mut self,
         floating_pane_layout: &FloatingPaneLayout,
     ) -> PaneGeom {
-        let display_area = *self.display_area.borrow();
         let viewport = *self.viewport.borrow();
-        let floating_pane_grid = FloatingPaneGrid::new(
-            &mut self.panes,
-            &mut self.desired_pane_positions,
-            display_area,
+        let position = self.find_room_for_new_pane().unwrap(); // TODO: no unwrap
+        apply_coordinates_to_geom(
+            position,
+            floating_pane_layout.x.as_ref(),
+            floating_pane_layout.y.as_ref(),
+            floating_pane_layout.width.as_ref(),
+            floating_pane_layout.height.as_ref(),
+            viewport,
+        )
+    }
+    pub fn find_room_for_new_pane_with_coordinates(
+        &mut self,
+        coordinates: &FloatingPaneCoordinates,
+    ) -> Option<PaneGeom> {
+        let viewport = *self.viewport.borrow();
+        self.find_room_for_new_pane().map(|position| {
+            apply_coordinates_to_geom(
+                position,
+                coordinates.x.as_ref(),
+                coordinates.y.as_ref(),
+                coordinates.width.as_ref(),
+                coordinates.height.as_ref(),
+                viewport,
+            )
+        })
+    }
+    pub fn change_pane_coordinates(
+        &mut self,
+        pane_id: PaneId,
+        coordinates: &FloatingPaneCoordinates,
+    ) -> Result<()> {
+        let err_context = || format!("failed to change coordinates of floating pane {pane_id:?}");
+        let viewport = *self.viewport.borrow();
+        let pane = self
+            .panes
+            .get_mut(&pane_id)
+            .ok_or_else(|| anyhow!("floating pane not found"))
+            .with_context(err_context)?;
+        let new_geom = apply_coordinates_to_geom(
+            pane.position_and_size(),
+            coordinates.x.as_ref(),
+            coordinates.y.as_ref(),
+            coordinates.width.as_ref(),
+            coordinates.height.as_ref(),
             viewport,
         );
-        let mut position = floating_pane_grid.find_room_for_new_pane().unwrap(); // TODO: no unwrap
-        if let Some(x) = &floating_pane_layout.x {
-            position.x = x.to_position(viewport.cols);
-        }
-        if let Some(y) = &floating_pane_layout.y {
-            position.y = y.to_position(viewport.rows);
-        }
-        if let Some(width) = &floating_pane_layout.width {
-            position.cols = Dimension::fixed(width.to_position(viewport.cols));
-        }
-        if let Some(height) = &floating_pane_layout.height {
-            position.rows = Dimension::fixed(height.to_position(viewport.rows));
-        }
-        if position.cols.as_usize() > viewport.cols {
-            position.cols = Dimension::fixed(viewport.cols);
-        }
-        if position.rows.as_usize() > viewport.rows {
-            position.rows = Dimension::fixed(viewport.rows);
-        }
-        if position.x + position.cols.as_usize() > viewport.cols {
-            position.x = position
-                .x
-                .saturating_sub((position.x + position.cols.as_usize()) - viewport.cols);
-        }
-        if position.y + position.rows.as_usize() > viewport.rows {
-            position.y = position
-                .y
-                .saturating_sub((position.y + position.rows.as_usize()) - viewport.rows);
-        }
-        position
+        pane.set_geom(new_geom);
+        resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+            .with_context(err_context)?;
+        self.desired_pane_positions.insert(pane_id, new_geom);
+        self.set_force_render();
+        Ok(())
     }
     pub fn first_floating_pane_id(&self) -> Option<PaneId> {
         self.panes.keys().next().copied()
@@ -317,28 +365,17 @@ impl FloatingPanes {
         let err_context = || "failed to render output";
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        let mut floating_panes: Vec<_> = self.panes.iter_mut().collect();
-        floating_panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
-            self.z_indices
-                .iter()
-                .position(|id| id == *a_id)
-                .with_context(err_context)
-                .fatal()
-                .cmp(
-                    &self
-                        .z_indices
-                        .iter()
-                        .position(|id| id == *b_id)
-                        .with_context(err_context)
-                        .fatal(),
-                )
-        });
-
-        for (z_index, (kind, pane)) in floating_panes.iter_mut().enumerate() {
-            let mut active_panes = self.active_panes.clone_active_panes();
+        for (z_index, kind) in self.rendered_pane_ids().into_iter().enumerate() {
+            let mut active_panes = if self.show_panes {
+                self.active_panes.clone_active_panes()
+            } else {
+                // pinned panes do not take focus while the floating panes are hidden
+                HashMap::new()
+            };
             let multiple_users_exist_in_session =
                 { self.connected_clients_in_app.borrow().len() > 1 };
             active_panes.retain(|c_id, _| self.connected_clients.borrow().contains(c_id));
+            let pane = self.panes.get_mut(&kind).with_context(err_context)?;
             let mut pane_contents_and_ui = PaneContentsAndUi::new(
                 pane,
                 output,
@@ -351,14 +388,19 @@ impl FloatingPanes {
                 true,
             );
             for client_id in &connected_clients {
-                let client_mode = self
-                    .mode_info
-                    .borrow()
-                    .get(client_id)
-                    .unwrap_or(&self.default_mode_info)
-                    .mode;
+                let (client_mode, client_is_read_only) = {
+                    let mode_info = self.mode_info.borrow();
+                    let client_mode_info =
+                        mode_info.get(client_id).unwrap_or(&self.default_mode_info);
+                    (client_mode_info.mode, client_mode_info.is_read_only)
+                };
                 pane_contents_and_ui
-                    .render_pane_frame(*client_id, client_mode, self.session_is_mirrored)
+                    .render_pane_frame(
+                        *client_id,
+                        client_mode,
+                        client_is_read_only,
+                        self.session_is_mirrored,
+                    )
                     .with_context(err_context)?;
                 if let PaneId::Plugin(..) = kind {
                     pane_contents_and_ui
@@ -915,3 +957,44 @@ impl FloatingPanes {
         }
     }
 }
+
+fn apply_coordinates_to_geom(
+    mut position: PaneGeom,
+    x: Option<&PercentOrFixed>,
+    y: Option<&PercentOrFixed>,
+    width: Option<&PercentOrFixed>,
+    height: Option<&PercentOrFixed>,
+    viewport: Viewport,
+) -> PaneGeom {
+    if let Some(x) = x {
+        position.x = x.to_position(viewport.cols);
+    }
+    if let Some(y) = y {
+        position.y = y.to_position(viewport.rows);
+    }
+    // a pane cannot be smaller than a single cell, eg. if its size is 0 or a percentage of a
+    // small viewport
+    if let Some(width) = width {
+        position.cols = Dimension::fixed(std::cmp::max(width.to_position(viewport.cols), 1));
+    }
+    if let Some(height) = height {
+        position.rows = Dimension::fixed(std::cmp::max(height.to_position(viewport.rows), 1));
+    }
+    if position.cols.as_usize() > viewport.cols {
+        position.cols = Dimension::fixed(viewport.cols);
+    }
+    if position.rows.as_usize() > viewport.rows {
+        position.rows = Dimension::fixed(viewport.rows);
+    }
+    if position.x + position.cols.as_usize() > viewport.cols {
+        position.x = position
+            .x
+            .saturating_sub((position.x + position.cols.as_usize()) - viewport.cols);
+    }
+    if position.y + position.rows.as_usize() > viewport.rows {
+        position.y = position
+            .y
+            .saturating_sub((position.y + position.rows.as_usize()) - viewport.rows);
+    }
+    position
+}
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
-use zellij_utils::data::Style;
+use zellij_utils::data::{PaneContents, Style};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::regex::Regex;
 
@@ -24,12 +24,23 @@ use zellij_utils::{
 
 const TABSTOP_WIDTH: usize = 8; // TODO: is this always right?
 pub const MAX_TITLE_STACK_SIZE: usize = 1000;
+pub const MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE: usize = 16;
+// the kitty keyboard protocol enhancements we support, "disambiguate escape codes" and "report
+// event types" (the others are masked out, so applications querying the flags know they are off)
+const KITTY_KEYBOARD_DISAMBIGUATE_ESCAPE_CODES: u8 = 0b1;
+const KITTY_KEYBOARD_REPORT_EVENT_TYPES: u8 = 0b10;
+const SUPPORTED_KITTY_KEYBOARD_FLAGS: u8 =
+    KITTY_KEYBOARD_DISAMBIGUATE_ESCAPE_CODES | KITTY_KEYBOARD_REPORT_EVENT_TYPES;
 
 use vte::{Params, Perform};
 use zellij_utils::{consts::VERSION, shared::version_number};
 
 use crate::output::{CharacterChunk, OutputBuffer, SixelImageChunk};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
+use crate::panes::copy_mode::CopyModeCursor;
+use crate::panes::grid_snapshot::{
+    AlternateScreenSnapshot, GridSnapshot, GridSnapshotDelta, ScrollbackChanges,
+};
 use crate::panes::link_handler::LinkHandler;
 use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
@@ -115,6 +126,21 @@ fn get_top_canonical_row_and_wraps(rows: &mut Vec<Row>) -> Vec<Row> {
     }
 }
 
+// Merges wrapped rows into their canonical lines and wraps those again to the given width
+fn wrap_rows(rows: impl Iterator<Item = Row>, width: usize) -> Vec<Row> {
+    let mut canonical_lines: Vec<Vec<Row>> = vec![];
+    for row in rows {
+        match canonical_lines.last_mut() {
+            Some(canonical_line) if !row.is_canonical => canonical_line.push(row),
+            _ => canonical_lines.push(vec![row]),
+        }
+    }
+    canonical_lines
+        .into_iter()
+        .flat_map(|canonical_line| Row::from_rows(canonical_line).split_to_rows_of_length(width))
+        .collect()
+}
+
 fn transfer_rows_from_lines_above_to_viewport(
     lines_above: &mut VecDeque<Row>,
     viewport: &mut Vec<Row>,
@@ -162,6 +188,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     viewport: &mut Vec<Row>,
     lines_above: &mut VecDeque<Row>,
     sixel_grid: &mut SixelGrid,
+    scrollback_changes: &mut Option<ScrollbackChanges>,
     count: usize,
     max_viewport_width: usize,
 ) -> isize {
@@ -175,12 +202,18 @@ fn transfer_rows_from_viewport_to_lines_above(
             let mut bottom_canonical_row_and_wraps_in_dst =
                 get_lines_above_bottom_canonical_row_and_wraps(lines_above);
             next_lines.append(&mut bottom_canonical_row_and_wraps_in_dst);
+            if let Some(scrollback_changes) = scrollback_changes.as_mut() {
+                scrollback_changes.lines_removed_from_bottom(lines_above.len());
+            }
         }
         next_lines.push(next_line);
         let dropped_line_width = bounded_push(lines_above, sixel_grid, Row::from_rows(next_lines));
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
+            if let Some(scrollback_changes) = scrollback_changes.as_mut() {
+                scrollback_changes.line_dropped_from_top();
+            }
         }
     }
     transferred_rows_count
@@ -316,7 +349,7 @@ pub struct Grid {
     pub(crate) lines_below: Vec<Row>,
     horizontal_tabstops: BTreeSet<usize>,
     alternate_screen_state: Option<AlternateScreenState>,
-    cursor: Cursor,
+    pub(crate) cursor: Cursor,
     cursor_is_hidden: bool,
     saved_cursor_position: Option<Cursor>,
     // FIXME: change scroll_region to be (usize, usize) - where the top line is always the first
@@ -330,6 +363,7 @@ pub struct Grid {
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     pub(crate) output_buffer: OutputBuffer,
     title_stack: Vec<String>,
+    kitty_keyboard_flags_stack: Vec<u8>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     sixel_grid: SixelGrid,
     pub changed_colors: Option<[Option<AnsiCode>; 256]>,
@@ -352,11 +386,15 @@ pub struct Grid {
     pub link_handler: Rc<RefCell<LinkHandler>>,
     pub ring_bell: bool,
     scrollback_buffer_lines: usize,
+    // None if the scrollback changed in a way that needs it to be snapshotted whole
+    scrollback_changes: Option<ScrollbackChanges>,
     pub mouse_mode: MouseMode,
     pub mouse_tracking: MouseTracking,
     pub focus_event_tracking: bool,
     pub search_results: SearchResult,
+    pub copy_mode: Option<CopyModeCursor>,
     pub pending_clipboard_update: Option<String>,
+    pub last_command_exit_code: Option<i32>, // as reported by the shell with OSC 133;D
     ui_component_bytes: Option<Vec<u8>>,
     style: Style,
     debug: bool,
@@ -486,19 +524,23 @@ impl Grid {
             output_buffer: Default::default(),
             selection: Default::default(),
             title_stack: vec![],
+            kitty_keyboard_flags_stack: vec![],
             title: None,
             changed_colors: None,
             is_scrolled: false,
             link_handler,
             ring_bell: false,
             scrollback_buffer_lines: 0,
+            scrollback_changes: None,
             mouse_mode: MouseMode::default(),
             mouse_tracking: MouseTracking::default(),
             focus_event_tracking: false,
             character_cell_size,
             search_results: Default::default(),
+            copy_mode: None,
             sixel_grid,
             pending_clipboard_update: None,
+            last_command_exit_code: None,
             ui_component_bytes: None,
             style,
             debug,
@@ -665,6 +707,7 @@ impl Grid {
         let mut found_something = false;
         if !self.lines_above.is_empty() && self.viewport.len() == self.height {
             self.is_scrolled = true;
+            self.scrollback_changes = None;
             let line_to_push_down = self.viewport.pop().unwrap();
             self.lines_below.insert(0, line_to_push_down);
 
@@ -680,10 +723,13 @@ impl Grid {
                 .saturating_sub(transferred_rows_height);
 
             self.selection.move_down(1);
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_down(1);
+            }
             // Move all search-selections down one line as well
-            found_something = self
-                .search_results
-                .move_down(1, &self.viewport, self.height);
+            found_something =
+                self.search_results
+                    .move_down(1, &self.viewport, &self.lines_above, self.height);
         }
         self.output_buffer.update_all_lines();
         found_something
@@ -691,6 +737,7 @@ impl Grid {
     pub fn scroll_down_one_line(&mut self) -> bool {
         let mut found_something = false;
         if !self.lines_below.is_empty() && self.viewport.len() == self.height {
+            self.scrollback_changes = None;
             let mut line_to_push_up = self.viewport.remove(0);
 
             self.scrollback_buffer_lines +=
@@ -722,10 +769,18 @@ impl Grid {
             );
 
             self.selection.move_up(1);
+
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_up(1);
+            }
             // Move all search-selections up one line as well
-            found_something =
-                self.search_results
-                    .move_up(1, &self.viewport, &self.lines_below, self.height);
+            found_something = self.search_results.move_up(
+                1,
+                &self.viewport,
+                &self.lines_above,
+                &self.lines_below,
+                self.height,
+            );
             self.output_buffer.update_all_lines();
         }
         if self.lines_below.is_empty() {
@@ -756,6 +811,7 @@ impl Grid {
         if new_columns == 0 || new_rows == 0 {
             return;
         }
+        self.scrollback_changes = None;
         if self.alternate_screen_state.is_some() {
             // in alternate screen we do nothing but log the new size, the program in the terminal
             // is in control now...
@@ -952,6 +1008,7 @@ impl Grid {
                         &mut self.viewport,
                         &mut self.lines_above,
                         &mut self.sixel_grid,
+                        &mut self.scrollback_changes,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -1039,6 +1096,46 @@ impl Grid {
 
         (changed_character_chunks, changed_sixel_image_chunks)
     }
+    pub fn rows_above_viewport(&self) -> usize {
+        self.scrollback_buffer_lines
+    }
+    pub fn render_scrolled_up(
+        &self,
+        rows_to_scroll: usize,
+        x_offset: usize,
+        y_offset: usize,
+    ) -> Vec<CharacterChunk> {
+        // this renders the viewport as it would be if it were scrolled up, without scrolling it
+        // (eg. for a read-only client looking at the scrollback on its own)
+        let mut rows_above: Vec<Row> = vec![];
+        for canonical_line in self.lines_above.iter().rev() {
+            if rows_above.len() >= rows_to_scroll {
+                break;
+            }
+            // rows in lines_above are unwrapped
+            let mut wrapped_rows = canonical_line.clone().split_to_rows_of_length(self.width);
+            wrapped_rows.append(&mut rows_above);
+            rows_above = wrapped_rows;
+        }
+        let rows: Vec<Row> = rows_above
+            .iter()
+            .skip(rows_above.len().saturating_sub(rows_to_scroll))
+            .chain(self.viewport.iter())
+            .take(self.height)
+            .cloned()
+            .collect();
+        let mut character_chunks = OutputBuffer::default().changed_chunks_in_viewport(
+            &rows,
+            self.width,
+            self.height,
+            x_offset,
+            y_offset,
+        );
+        for character_chunk in character_chunks.iter_mut() {
+            character_chunk.add_changed_colors(self.changed_colors);
+        }
+        character_chunks
+    }
     pub fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         match scrollback_lines_to_serialize {
             Some(scrollback_lines_to_serialize) => {
@@ -1061,6 +1158,104 @@ impl Grid {
             None => self.output_buffer.serialize(&self.viewport).ok(),
         }
     }
+    /// A snapshot of the styled contents of this grid to be applied to its previous one, which
+    /// includes only the scrollback lines added since then if nothing else changed in it
+    pub fn snapshot(&mut self) -> GridSnapshotDelta {
+        let mut snapshot = GridSnapshot::new(self.width, self.height);
+        let mut scrollback_changes = self.scrollback_changes.take().unwrap_or_default();
+        let (lines_above, viewport, cursor): (Vec<&Row>, Vec<&Row>, &Cursor) =
+            match &self.alternate_screen_state {
+                Some(alternate_screen_state) => {
+                    snapshot.alternate_screen = Some(AlternateScreenSnapshot {
+                        viewport: self
+                            .viewport
+                            .iter()
+                            .map(|row| snapshot.snapshot_row(row))
+                            .collect(),
+                        cursor: (self.cursor.x, self.cursor.y),
+                    });
+                    scrollback_changes = ScrollbackChanges::default();
+                    (
+                        alternate_screen_state.lines_above.iter().collect(),
+                        alternate_screen_state.viewport.iter().collect(),
+                        &alternate_screen_state.cursor,
+                    )
+                },
+                None if self.is_scrolled => {
+                    // the bottom of the viewport is in lines_below
+                    let mut lines_above: Vec<&Row> = self
+                        .lines_above
+                        .iter()
+                        .chain(self.viewport.iter())
+                        .chain(self.lines_below.iter())
+                        .collect();
+                    let viewport = lines_above
+                        .split_off(lines_above.len().saturating_sub(self.viewport.len()));
+                    scrollback_changes = ScrollbackChanges::default();
+                    (lines_above, viewport, &self.cursor)
+                },
+                None => (
+                    self.lines_above
+                        .iter()
+                        .skip(scrollback_changes.unchanged_lines())
+                        .collect(),
+                    self.viewport.iter().collect(),
+                    &self.cursor,
+                ),
+            };
+        snapshot.lines_above = lines_above
+            .iter()
+            .map(|row| snapshot.snapshot_row(row))
+            .collect();
+        snapshot.viewport = viewport
+            .iter()
+            .map(|row| snapshot.snapshot_row(row))
+            .collect();
+        snapshot.cursor = (cursor.x, cursor.y);
+        // the next snapshot can only build on this one if the scrollback we took is the real one
+        if self.alternate_screen_state.is_none() && !self.is_scrolled {
+            self.scrollback_changes = Some(ScrollbackChanges::new(self.lines_above.len()));
+        }
+        scrollback_changes.delta(snapshot)
+    }
+    /// Replaces the contents of this grid with those of a serialized `GridSnapshot`, reflowing
+    /// them to the current size of the grid
+    pub fn restore_snapshot(&mut self, bytes: &[u8]) -> Result<()> {
+        let snapshot = GridSnapshot::from_bytes(bytes)?;
+        let (height, width) = (self.height, self.width);
+        // the program that was using the alternate screen did not survive, so we only restore
+        // the primary screen
+        let mut lines_above: VecDeque<Row> = snapshot
+            .lines_above
+            .iter()
+            .map(|row| snapshot.restore_row(row))
+            .collect();
+        let max_lines_above = *SCROLL_BUFFER_SIZE.get().unwrap();
+        while lines_above.len() > max_lines_above {
+            lines_above.pop_front();
+        }
+        let mut viewport: Vec<Row> = snapshot
+            .viewport
+            .iter()
+            .map(|row| snapshot.restore_row(row))
+            .collect();
+        if viewport.is_empty() {
+            viewport.push(Row::new().canonical());
+        }
+        self.lines_above = lines_above;
+        self.scrollback_changes = None;
+        self.lines_below.clear();
+        self.is_scrolled = false;
+        self.cursor.x = snapshot.cursor.0;
+        self.cursor.y = std::cmp::min(snapshot.cursor.1, viewport.len() - 1);
+        self.viewport = viewport;
+        self.width = std::cmp::max(snapshot.width, 1);
+        self.height = std::cmp::max(snapshot.height, 1);
+        self.change_size(height, width);
+        self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        self.output_buffer.update_all_lines();
+        Ok(())
+    }
     pub fn render(
         &mut self,
         content_x: usize,
@@ -1130,7 +1325,9 @@ impl Grid {
         )));
     }
     pub fn cursor_coordinates(&self) -> Option<(usize, usize)> {
-        if self.cursor_is_hidden {
+        if self.copy_mode.is_some() {
+            self.copy_mode_cursor_coordinates()
+        } else if self.cursor_is_hidden {
             None
         } else {
             Some((self.cursor.x, self.cursor.y))
@@ -1158,6 +1355,16 @@ impl Grid {
         scrollback.push_str(&viewport);
         scrollback
     }
+    pub fn pane_contents(&self, scrollback_lines: usize) -> PaneContents {
+        let to_lines = |dump: String| -> Vec<String> { dump.lines().map(String::from).collect() };
+        let first_index = self.lines_above.len().saturating_sub(scrollback_lines);
+        let lines_above: Vec<&Row> = self.lines_above.iter().skip(first_index).collect();
+        PaneContents {
+            viewport: to_lines(dump_screen!(self.viewport)),
+            lines_above_viewport: to_lines(dump_screen!(lines_above)),
+            lines_below_viewport: to_lines(dump_screen!(self.lines_below)),
+        }
+    }
     pub fn move_viewport_up(&mut self, count: usize) {
         for _ in 0..count {
             self.scroll_up_one_line();
@@ -1170,6 +1377,52 @@ impl Grid {
         }
         self.output_buffer.update_all_lines();
     }
+    // Moves the viewport to the top of the scrollback in one go rather than one line at a time
+    pub fn scroll_to_top(&mut self) {
+        if self.lines_above.is_empty() || self.viewport.len() != self.height {
+            return;
+        }
+        self.scrollback_changes = None;
+        let viewport_len = self.viewport.len();
+        let mut rows = wrap_rows(
+            self.lines_above.drain(..).chain(self.viewport.drain(..)),
+            self.width,
+        );
+        let scrolled_by = rows.len().saturating_sub(viewport_len);
+        let mut lines_below = rows.split_off(self.height.min(rows.len()));
+        lines_below.append(&mut self.lines_below);
+        self.viewport = rows;
+        self.lines_below = lines_below;
+        self.scrollback_buffer_lines = 0;
+        self.is_scrolled = !self.lines_below.is_empty();
+        self.selection.move_down(scrolled_by);
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.move_down(scrolled_by);
+        }
+        self.search_whole_viewport_again();
+        self.output_buffer.update_all_lines();
+    }
+    // Moves the viewport to the bottom of the scrollback in one go rather than one line at a time
+    pub fn scroll_to_bottom(&mut self) {
+        if self.lines_below.is_empty() || self.viewport.len() != self.height {
+            return;
+        }
+        self.scrollback_changes = None;
+        let viewport_len = self.viewport.len();
+        self.viewport = wrap_rows(
+            self.viewport.drain(..).chain(self.lines_below.drain(..)),
+            self.width,
+        );
+        let scrolled_by = self.viewport.len().saturating_sub(viewport_len);
+        self.transfer_rows_to_lines_above(self.viewport.len().saturating_sub(self.height));
+        self.is_scrolled = false;
+        self.selection.move_up(scrolled_by);
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.move_up(scrolled_by);
+        }
+        self.search_whole_viewport_again();
+        self.output_buffer.update_all_lines();
+    }
     pub fn reset_viewport(&mut self) {
         let max_lines_to_scroll = *SCROLL_BUFFER_SIZE.get().unwrap() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
         let mut lines_scrolled = 0;
@@ -1258,6 +1511,9 @@ impl Grid {
                     let columns = VecDeque::from(vec![pad_character; self.width]);
                     self.viewport.push(Row::from_columns(columns).canonical());
                     self.selection.move_up(1);
+                    if let Some(copy_mode) = self.copy_mode.as_mut() {
+                        copy_mode.move_up(1);
+                    }
                 } else {
                     self.viewport.remove(scroll_region_top);
                     let mut pad_character = EMPTY_TERMINAL_CHARACTER;
@@ -1291,6 +1547,10 @@ impl Grid {
                 }
 
                 self.selection.move_up(1);
+
+                if let Some(copy_mode) = self.copy_mode.as_mut() {
+                    copy_mode.move_up(1);
+                }
             }
             self.output_buffer.update_all_lines();
         } else {
@@ -1441,6 +1701,9 @@ impl Grid {
             let wrapped_row = Row::new();
             self.viewport.push(wrapped_row);
             self.selection.move_up(1);
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_up(1);
+            }
             self.output_buffer.update_all_lines();
         } else {
             self.cursor.y += 1;
@@ -1453,6 +1716,7 @@ impl Grid {
     }
     fn clear_lines_above(&mut self) {
         self.lines_above.clear();
+        self.scrollback_changes = None;
         self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
     }
 
@@ -1683,15 +1947,21 @@ impl Grid {
         self.add_canonical_line();
         self.mark_for_rerender();
     }
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        self.mark_for_rerender();
+    }
     pub fn mark_for_rerender(&mut self) {
         self.should_render = true;
     }
     pub fn reset_terminal_state(&mut self) {
         self.lines_above = VecDeque::new();
+        self.scrollback_changes = None;
         self.lines_below = vec![];
         self.viewport = vec![Row::new().canonical()];
         self.alternate_screen_state = None;
         self.cursor_key_mode = false;
+        self.kitty_keyboard_flags_stack.clear();
         self.scroll_region = None;
         self.clear_viewport_before_rendering = true;
         self.cursor = Cursor::new(0, 0, self.styled_underlines);
@@ -1824,6 +2094,109 @@ impl Grid {
             Some(selection.join("\n"))
         }
     }
+    pub fn scroll_to_previous_prompt(&mut self) {
+        let has_previous_prompt = self
+            .lines_above
+            .iter()
+            .any(|row| row.prompt_marks.prompt_start);
+        if !has_previous_prompt {
+            return;
+        }
+        while !self.lines_above.is_empty() && self.viewport.len() == self.height {
+            self.scroll_up_one_line();
+            if self.top_row_is_prompt() {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+    }
+    pub fn scroll_to_next_prompt(&mut self) {
+        let has_next_prompt = self
+            .viewport
+            .iter()
+            .skip(1)
+            .chain(self.lines_below.iter())
+            .any(|row| row.prompt_marks.prompt_start);
+        if !has_next_prompt {
+            return;
+        }
+        while !self.lines_below.is_empty() && self.viewport.len() == self.height {
+            self.scroll_down_one_line();
+            if self.top_row_is_prompt() {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+    }
+    /// Selects the output of the last command that finished running (the rows between its
+    /// output mark and the current prompt) and returns it as text
+    pub fn select_last_command_output(&mut self) -> Option<String> {
+        let rows: Vec<&Row> = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+            .collect();
+        let current_prompt = rows.iter().rposition(|row| row.prompt_marks.prompt_start)?;
+        let previous_prompt = rows[..current_prompt]
+            .iter()
+            .rposition(|row| row.prompt_marks.prompt_start)?;
+        let output_start = (previous_prompt..current_prompt)
+            .find(|i| rows[*i].prompt_marks.output_start)
+            .map(|i| {
+                // if the shell marked the output on the prompt row itself, the output starts on
+                // the next one
+                if i == previous_prompt {
+                    i + 1
+                } else {
+                    i
+                }
+            })?;
+        if output_start >= current_prompt {
+            return None;
+        }
+        let mut lines: Vec<String> = vec![];
+        for row in &rows[output_start..current_prompt] {
+            let line: String = row.columns.iter().map(|c| c.character).collect();
+            match lines.last_mut() {
+                Some(previous_line) if !row.is_canonical => previous_line.push_str(&line),
+                _ => lines.push(line),
+            }
+        }
+        let mut lines: Vec<&str> = lines.iter().map(|l| l.trim_end()).collect();
+        while lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            return None;
+        }
+
+        let lines_above_count = self.lines_above.len() as i32;
+        let old_selection = self.selection;
+        self.selection
+            .start(Position::new(output_start as i32 - lines_above_count, 0));
+        self.selection.end(Position::new(
+            current_prompt as i32 - 1 - lines_above_count,
+            self.width as u16,
+        ));
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+        Some(lines.join("\n"))
+    }
+    fn top_row_is_prompt(&self) -> bool {
+        self.viewport
+            .first()
+            .map(|row| row.prompt_marks.prompt_start)
+            .unwrap_or(false)
+    }
+    fn mark_current_row(&mut self, mark: impl FnOnce(&mut PromptMarks)) {
+        if self.viewport.get(self.cursor.y).is_none() {
+            self.pad_lines_until(self.cursor.y, EMPTY_TERMINAL_CHARACTER);
+        }
+        if let Some(current_row) = self.viewport.get_mut(self.cursor.y) {
+            mark(&mut current_row.prompt_marks);
+        }
+    }
     pub fn absolute_position_in_scrollback(&self) -> usize {
         self.lines_above.len() + self.cursor.y
     }
@@ -1849,11 +2222,45 @@ impl Grid {
             self.title = Some(popped_title);
         }
     }
+    pub fn kitty_keyboard_flags(&self) -> u8 {
+        self.kitty_keyboard_flags_stack.last().copied().unwrap_or(0)
+    }
+    pub fn supports_kitty_keyboard_protocol(&self) -> bool {
+        self.kitty_keyboard_flags() != 0
+    }
+    pub fn reports_kitty_key_event_types(&self) -> bool {
+        self.kitty_keyboard_flags() & KITTY_KEYBOARD_REPORT_EVENT_TYPES != 0
+    }
+    fn push_kitty_keyboard_flags(&mut self, flags: u8) {
+        if self.kitty_keyboard_flags_stack.len() >= MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE {
+            self.kitty_keyboard_flags_stack.remove(0);
+        }
+        self.kitty_keyboard_flags_stack
+            .push(flags & SUPPORTED_KITTY_KEYBOARD_FLAGS);
+    }
+    fn pop_kitty_keyboard_flags(&mut self, count: usize) {
+        let remaining = self.kitty_keyboard_flags_stack.len().saturating_sub(count);
+        self.kitty_keyboard_flags_stack.truncate(remaining);
+    }
+    fn set_kitty_keyboard_flags(&mut self, flags: u8, mode: usize) {
+        let flags = flags & SUPPORTED_KITTY_KEYBOARD_FLAGS;
+        let current_flags = self.kitty_keyboard_flags();
+        let new_flags = match mode {
+            2 => current_flags | flags,
+            3 => current_flags & !flags,
+            _ => flags,
+        };
+        match self.kitty_keyboard_flags_stack.last_mut() {
+            Some(current_flags) => *current_flags = new_flags,
+            None => self.kitty_keyboard_flags_stack.push(new_flags),
+        }
+    }
     fn transfer_rows_to_lines_above(&mut self, count: usize) {
         let transferred_rows_count = transfer_rows_from_viewport_to_lines_above(
             &mut self.viewport,
             &mut self.lines_above,
             &mut self.sixel_grid,
+            &mut self.scrollback_changes,
             count,
             self.width,
         );
@@ -2134,6 +2541,7 @@ impl Grid {
     }
     pub fn delete_viewport_and_scroll(&mut self) {
         self.lines_above.clear();
+        self.scrollback_changes = None;
         self.viewport.clear();
         self.lines_below.clear();
     }
@@ -2385,6 +2793,30 @@ impl Perform for Grid {
                 }
             },
 
+            // Semantic prompt marks (shell integration).
+            b"133" => {
+                if self.alternate_screen_state.is_some() {
+                    // marks only make sense in the shell's own scrollback
+                    return;
+                }
+                match params.get(1).and_then(|p| p.first()) {
+                    Some(b'A') => self.mark_current_row(|marks| marks.prompt_start = true),
+                    Some(b'B') => self.mark_current_row(|marks| marks.command_start = true),
+                    Some(b'C') => self.mark_current_row(|marks| marks.output_start = true),
+                    Some(b'D') => {
+                        self.last_command_exit_code = params
+                            .get(2)
+                            .and_then(|exit_code| str::from_utf8(exit_code).ok())
+                            .and_then(|exit_code| exit_code.parse().ok());
+                    },
+                    _ => {
+                        if self.debug {
+                            log::warn!("Unhandled semantic prompt mark: {:?}", params);
+                        }
+                    },
+                }
+            },
+
             // Reset color index.
             b"104" => {
                 // Reset all color indexes when no parameters are given.
@@ -2546,6 +2978,7 @@ impl Perform for Grid {
                                 );
                             }
                             self.alternate_screen_state = None;
+                            self.scrollback_changes = None;
                             self.clear_viewport_before_rendering = true;
                             self.force_change_size(self.height, self.width); // the alternative_viewport might have been of a different size...
                             self.mark_for_rerender();
@@ -2628,6 +3061,7 @@ impl Perform for Grid {
                         },
                         1049 => {
                             // enter alternate buffer
+                            self.scrollback_changes = None;
                             let current_lines_above =
                                 std::mem::replace(&mut self.lines_above, VecDeque::new());
                             let current_viewport =
@@ -2826,7 +3260,30 @@ impl Perform for Grid {
         } else if c == 's' {
             self.save_cursor_position();
         } else if c == 'u' {
-            self.restore_cursor_position();
+            // kitty keyboard protocol, see https://sw.kovidgoyal.net/kitty/keyboard-protocol/
+            match intermediates.first() {
+                Some(b'>') => {
+                    let flags = next_param_or(0);
+                    self.push_kitty_keyboard_flags(flags as u8);
+                },
+                Some(b'<') => {
+                    let count = next_param_or(1);
+                    self.pop_kitty_keyboard_flags(count);
+                },
+                Some(b'=') => {
+                    let flags = next_param_or(0);
+                    let mode = next_param_or(1);
+                    self.set_kitty_keyboard_flags(flags as u8, mode);
+                },
+                Some(b'?') => {
+                    let flags_report = format!("\u{1b}[?{}u", self.kitty_keyboard_flags());
+                    self.pending_messages_to_pty
+                        .push(flags_report.as_bytes().to_vec());
+                },
+                _ => {
+                    self.restore_cursor_position();
+                },
+            }
         } else if c == '@' {
             let count = next_param_or(1);
             for _ in 0..count {
@@ -3117,9 +3574,26 @@ impl AlternateScreenState {
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub prompt_marks: PromptMarks,
     width: Option<usize>,
 }
 
+/// Semantic prompt marks (OSC 133) sent by the shell while the cursor was on a row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PromptMarks {
+    pub prompt_start: bool,  // A
+    pub command_start: bool, // B
+    pub output_start: bool,  // C
+}
+
+impl PromptMarks {
+    pub fn merge(&mut self, other: PromptMarks) {
+        self.prompt_start |= other.prompt_start;
+        self.command_start |= other.command_start;
+        self.output_start |= other.output_start;
+    }
+}
+
 impl Debug for Row {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         for character in &self.columns {
@@ -3134,6 +3608,7 @@ impl Row {
         Row {
             columns: VecDeque::new(),
             is_canonical: false,
+            prompt_marks: PromptMarks::default(),
             width: None,
         }
     }
@@ -3141,6 +3616,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            prompt_marks: PromptMarks::default(),
             width: None,
         }
     }
@@ -3151,6 +3627,7 @@ impl Row {
             let mut first_row = rows.remove(0);
             for row in &mut rows {
                 first_row.append(&mut row.columns);
+                first_row.prompt_marks.merge(row.prompt_marks);
             }
             first_row
         }
@@ -3441,6 +3918,9 @@ impl Row {
                 part.is_canonical = true;
             }
         }
+        if let Some(part) = parts.get_mut(0) {
+            part.prompt_marks = self.prompt_marks;
+        }
         if parts.is_empty() {
             parts.push(self.clone());
         }
@@ -0,0 +1,314 @@
+//! A binary snapshot of the contents of a [`Grid`](super::Grid), written to disk alongside the
+//! session layout so that the scrollback of panes (including its styles) survives a resurrection.
+//!
+//! Snapshots are taken on the screen thread as deltas containing only the scrollback lines that
+//! changed since the previous one. These are applied to the full snapshot of the pane that the
+//! background jobs thread keeps, which is also where it is serialized and written to disk.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+use zellij_utils::errors::prelude::*;
+use zellij_utils::serde;
+
+use crate::panes::grid::Row;
+use crate::panes::terminal_character::{
+    CharacterStyles, LinkAnchor, RcCharacterStyles, TerminalCharacter, DEFAULT_STYLES,
+};
+
+// snapshots written with a different version are ignored rather than misinterpreted, so this
+// should be bumped whenever the format changes
+const GRID_SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+pub struct GridSnapshot {
+    version: u32,
+    pub width: usize,
+    pub height: usize,
+    styles: StyleTable,
+    pub lines_above: Vec<SnapshotRow>,
+    pub viewport: Vec<SnapshotRow>,
+    pub cursor: (usize, usize), // (x, y) in the viewport
+    pub alternate_screen: Option<AlternateScreenSnapshot>,
+}
+
+/// The contents of the alternate screen if the snapshot was taken while it was active, in which
+/// case the rest of the snapshot holds the primary screen that was saved when entering it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+pub struct AlternateScreenSnapshot {
+    pub viewport: Vec<SnapshotRow>,
+    pub cursor: (usize, usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+pub struct SnapshotRow {
+    is_canonical: bool,
+    text: String,
+    style_runs: Vec<(u32, u32)>, // (character count, index in styles)
+}
+
+/// Every distinct style in a snapshot, rows refer to them by index
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(
+    crate = "self::serde",
+    from = "Vec<CharacterStyles>",
+    into = "Vec<CharacterStyles>"
+)]
+struct StyleTable {
+    styles: Vec<CharacterStyles>,
+    indices: HashMap<CharacterStyles, u32>,
+}
+
+/// A snapshot of the grid to be applied to its previous one: only the scrollback lines that were
+/// added since then are included, unless the scrollback changed in some other way in which case
+/// none of the previous lines are kept
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GridSnapshotDelta {
+    pub dropped_lines_above: usize, // from the top of the previous snapshot's scrollback
+    pub unchanged_lines_above: usize, // of the previous snapshot's scrollback after dropping
+    pub snapshot: GridSnapshot,
+}
+
+/// How the scrollback of a grid changed since its last snapshot, tracked only as long as lines
+/// are added to its bottom or dropped from its top
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollbackChanges {
+    dropped_lines: usize,
+    unchanged_lines: usize,
+}
+
+impl GridSnapshot {
+    pub fn new(width: usize, height: usize) -> Self {
+        GridSnapshot {
+            version: GRID_SNAPSHOT_VERSION,
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let snapshot: GridSnapshot =
+            rmp_serde::from_slice(bytes).context("failed to deserialize grid snapshot")?;
+        if snapshot.version != GRID_SNAPSHOT_VERSION {
+            return Err(anyhow!(
+                "unsupported grid snapshot version {} (expected {})",
+                snapshot.version,
+                GRID_SNAPSHOT_VERSION
+            ));
+        }
+        Ok(snapshot)
+    }
+    /// Serializes this snapshot with only the last scrollback_lines_to_serialize lines of its
+    /// scrollback (all of them if this is Some(0), none of them if it is None)
+    pub fn to_bytes(&self, scrollback_lines_to_serialize: Option<usize>) -> Result<Vec<u8>> {
+        // rows in lines_above are unwrapped, so rows that were wrapped into the viewport (if the
+        // grid was scrolled up) are joined back into their canonical line
+        let mut lines_above: Vec<SnapshotRow> = vec![];
+        for row in &self.lines_above {
+            match lines_above.last_mut() {
+                Some(canonical_line) if !row.is_canonical => canonical_line.append(row),
+                _ => lines_above.push(row.clone()),
+            }
+        }
+        let lines_above_to_serialize = match scrollback_lines_to_serialize {
+            Some(0) => lines_above.len(),
+            Some(scrollback_lines_to_serialize) => scrollback_lines_to_serialize,
+            None => 0,
+        };
+        let first_index = lines_above.len().saturating_sub(lines_above_to_serialize);
+        // copying the rows leaves out the styles of the lines that are no longer in the snapshot
+        let mut serialized = GridSnapshot::default();
+        for row in &lines_above[first_index..] {
+            let row = serialized.copy_row(self, row);
+            serialized.lines_above.push(row);
+        }
+        serialized.copy_screen(self);
+        rmp_serde::to_vec(&serialized).context("failed to serialize grid snapshot")
+    }
+    pub fn apply_delta(&mut self, delta: GridSnapshotDelta) {
+        let GridSnapshotDelta {
+            dropped_lines_above,
+            unchanged_lines_above,
+            snapshot,
+        } = delta;
+        let dropped_lines_above = std::cmp::min(dropped_lines_above, self.lines_above.len());
+        self.lines_above.drain(..dropped_lines_above);
+        self.lines_above.truncate(unchanged_lines_above);
+        for row in &snapshot.lines_above {
+            let row = self.copy_row(&snapshot, row);
+            self.lines_above.push(row);
+        }
+        self.copy_screen(&snapshot);
+    }
+    pub fn snapshot_row(&mut self, row: &Row) -> SnapshotRow {
+        let mut text = String::with_capacity(row.columns.len());
+        let mut style_runs: Vec<(u32, u32)> = vec![];
+        for character in &row.columns {
+            text.push(character.character);
+            let mut styles: CharacterStyles = *character.styles;
+            if let Some(LinkAnchor::Start(_)) = styles.link_anchor {
+                // links live in the LinkHandler of this session and cannot be restored
+                styles.link_anchor = None;
+            }
+            push_style_run(&mut style_runs, 1, self.styles.index_of(styles));
+        }
+        SnapshotRow {
+            is_canonical: row.is_canonical,
+            text,
+            style_runs,
+        }
+    }
+    pub fn restore_row(&self, snapshot_row: &SnapshotRow) -> Row {
+        let mut styles = snapshot_row.style_runs.iter().flat_map(|(count, index)| {
+            let styles: RcCharacterStyles = self.styles.get(*index).into();
+            std::iter::repeat(styles).take(*count as usize)
+        });
+        let columns: VecDeque<TerminalCharacter> = snapshot_row
+            .text
+            .chars()
+            .map(|character| {
+                TerminalCharacter::new_styled(character, styles.next().unwrap_or_default())
+            })
+            .collect();
+        let row = Row::from_columns(columns);
+        if snapshot_row.is_canonical {
+            row.canonical()
+        } else {
+            row
+        }
+    }
+    // everything but the scrollback
+    fn copy_screen(&mut self, other: &GridSnapshot) {
+        self.viewport = other
+            .viewport
+            .iter()
+            .map(|row| self.copy_row(other, row))
+            .collect();
+        self.alternate_screen =
+            other
+                .alternate_screen
+                .as_ref()
+                .map(|alternate_screen| AlternateScreenSnapshot {
+                    viewport: alternate_screen
+                        .viewport
+                        .iter()
+                        .map(|row| self.copy_row(other, row))
+                        .collect(),
+                    cursor: alternate_screen.cursor,
+                });
+        self.version = other.version;
+        self.width = other.width;
+        self.height = other.height;
+        self.cursor = other.cursor;
+    }
+    // a row of another snapshot, with its styles indexed in this one
+    fn copy_row(&mut self, other: &GridSnapshot, row: &SnapshotRow) -> SnapshotRow {
+        let mut style_runs: Vec<(u32, u32)> = vec![];
+        for (count, index) in &row.style_runs {
+            let index = self.styles.index_of(other.styles.get(*index));
+            push_style_run(&mut style_runs, *count, index);
+        }
+        SnapshotRow {
+            is_canonical: row.is_canonical,
+            text: row.text.clone(),
+            style_runs,
+        }
+    }
+}
+
+impl SnapshotRow {
+    /// Appends the contents of a row that was wrapped from this one
+    fn append(&mut self, wrapped_row: &SnapshotRow) {
+        self.text.push_str(&wrapped_row.text);
+        for (count, index) in &wrapped_row.style_runs {
+            push_style_run(&mut self.style_runs, *count, *index);
+        }
+    }
+}
+
+impl StyleTable {
+    fn index_of(&mut self, styles: CharacterStyles) -> u32 {
+        let next_index = self.styles.len() as u32;
+        let index = *self.indices.entry(styles).or_insert(next_index);
+        if index == next_index {
+            self.styles.push(styles);
+        }
+        index
+    }
+    fn get(&self, index: u32) -> CharacterStyles {
+        self.styles
+            .get(index as usize)
+            .copied()
+            .unwrap_or(DEFAULT_STYLES)
+    }
+}
+
+impl From<Vec<CharacterStyles>> for StyleTable {
+    fn from(styles: Vec<CharacterStyles>) -> Self {
+        let mut indices = HashMap::new();
+        for (index, styles) in styles.iter().enumerate() {
+            indices.entry(*styles).or_insert(index as u32);
+        }
+        StyleTable { styles, indices }
+    }
+}
+
+impl From<StyleTable> for Vec<CharacterStyles> {
+    fn from(style_table: StyleTable) -> Self {
+        style_table.styles
+    }
+}
+
+// the indices are derived from the styles
+impl PartialEq for StyleTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.styles == other.styles
+    }
+}
+
+impl Eq for StyleTable {}
+
+impl Hash for StyleTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.styles.hash(state);
+    }
+}
+
+impl ScrollbackChanges {
+    pub fn new(lines: usize) -> Self {
+        ScrollbackChanges {
+            dropped_lines: 0,
+            unchanged_lines: lines,
+        }
+    }
+    pub fn line_dropped_from_top(&mut self) {
+        self.dropped_lines += 1;
+        self.unchanged_lines = self.unchanged_lines.saturating_sub(1);
+    }
+    pub fn lines_removed_from_bottom(&mut self, remaining_lines: usize) {
+        self.unchanged_lines = std::cmp::min(self.unchanged_lines, remaining_lines);
+    }
+    pub fn unchanged_lines(&self) -> usize {
+        self.unchanged_lines
+    }
+    pub fn delta(&self, snapshot: GridSnapshot) -> GridSnapshotDelta {
+        GridSnapshotDelta {
+            dropped_lines_above: self.dropped_lines,
+            unchanged_lines_above: self.unchanged_lines,
+            snapshot,
+        }
+    }
+}
+
+fn push_style_run(style_runs: &mut Vec<(u32, u32)>, count: u32, style_index: u32) {
+    match style_runs.last_mut() {
+        Some((last_count, last_style_index)) if *last_style_index == style_index => {
+            *last_count += count;
+        },
+        _ => style_runs.push((count, style_index)),
+    }
+}
@@ -1,11 +1,13 @@
 pub mod alacritty_functions;
 pub mod grid;
+pub mod grid_snapshot;
 pub mod link_handler;
 pub mod selection;
 pub mod sixel;
 pub mod terminal_character;
 
 mod active_panes;
+mod copy_mode;
 mod floating_panes;
 mod plugin_pane;
 mod search;
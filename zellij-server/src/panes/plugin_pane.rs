@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::Instant;
 
 use crate::output::{CharacterChunk, SixelImageChunk};
@@ -83,6 +83,8 @@ pub(crate) struct PluginPane {
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
     exclude_from_sync: bool,
+    pinned: bool,
+    in_pane_group_of: HashSet<ClientId>, // the clients whose pane group this pane is in
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
     loading_indication: LoadingIndication,
@@ -130,6 +132,8 @@ impl PluginPane {
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             exclude_from_sync: false,
+            pinned: false,
+            in_pane_group_of: HashSet::new(),
             link_handler,
             character_cell_size,
             sixel_image_store,
@@ -540,6 +544,14 @@ impl Pane for PluginPane {
     fn set_frame(&mut self, _frame: bool) {
         self.frame.clear();
     }
+    fn update_style(&mut self, style: Style) {
+        self.style = style;
+        for grid in self.grids.values_mut() {
+            grid.update_style(style);
+        }
+        self.frame.clear();
+        self.set_should_render(true);
+    }
     fn set_content_offset(&mut self, offset: Offset) {
         self.content_offset = offset;
         self.resize_grids();
@@ -568,6 +580,25 @@ impl Pane for PluginPane {
     fn exclude_from_sync(&self) -> bool {
         self.exclude_from_sync
     }
+    fn set_pinned(&mut self, should_be_pinned: bool) {
+        self.pinned = should_be_pinned;
+    }
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+    fn set_in_group(&mut self, client_id: ClientId, in_group: bool) {
+        if in_group {
+            self.in_pane_group_of.insert(client_id);
+        } else {
+            self.in_pane_group_of.remove(&client_id);
+        }
+    }
+    fn is_in_group(&self, client_id: ClientId) -> bool {
+        self.in_pane_group_of.contains(&client_id)
+    }
+    fn is_in_any_group(&self) -> bool {
+        !self.in_pane_group_of.is_empty()
+    }
     fn handle_right_click(&mut self, to: &Position, client_id: ClientId) {
         self.send_plugin_instructions
             .send(PluginInstruction::Update(vec![(
@@ -2,15 +2,57 @@ use crate::panes::selection::Selection;
 use crate::panes::terminal_character::TerminalCharacter;
 use crate::panes::{Grid, Row};
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
+use zellij_utils::regex::{Regex, RegexBuilder};
 
 // If char is neither alphanumeric nor an underscore do we consider it a word-boundary
 fn is_word_boundary(x: &Option<char>) -> bool {
     x.map_or(true, |c| !c.is_ascii_alphanumeric() && c != '_')
 }
 
+// The tail may have not been wrapped yet (when coming from lines_below),
+// so it could be that the end extends across more characters than the row is wide.
+// Therefore we need to reflow the end:
+fn reflow_selection_ends(selections: &mut [Selection], row_width: usize) {
+    for s in selections.iter_mut() {
+        while s.end.column() > row_width {
+            s.end.column.0 -= row_width;
+            s.end.line.0 += 1;
+        }
+    }
+}
+
+// Collect the rows above `viewport[ridx]` that belong to the same canonical line.
+// If that line starts above the viewport, we keep walking back into the lines above until we reach
+// its canonical row (wrapped rows are usually merged when they are moved above the viewport, but
+// we don't rely on that here).
+fn head_of_row<'a>(
+    ridx: usize,
+    viewport: &'a [Row],
+    lines_above: &'a VecDeque<Row>,
+) -> Vec<&'a Row> {
+    let mut head = Vec::new();
+    let mut rows_above = viewport[..ridx]
+        .iter()
+        .rev()
+        .chain(lines_above.iter().rev());
+    let mut current = &viewport[ridx];
+    while !current.is_canonical {
+        match rows_above.next() {
+            Some(row) => {
+                head.push(row);
+                current = row;
+            },
+            None => break,
+        }
+    }
+    head.reverse();
+    head
+}
+
 #[derive(Debug)]
 enum SearchSource<'a> {
     Main(&'a Row),
@@ -98,6 +140,10 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Interpret the needle as a regular expression
+    pub regex_search: bool,
+    // The compiled needle when searching with regular expressions (None if it is not a valid regex)
+    regex: Option<Regex>,
 }
 
 impl SearchResult {
@@ -141,7 +187,20 @@ impl SearchResult {
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search || self.whole_word_only || self.case_insensitive || self.regex_search
+    }
+
+    /// Needs to be called whenever the needle, the case sensitivity or the regex option changes.
+    /// An invalid regex (eg. one that is still being typed) simply doesn't match anything.
+    pub(crate) fn update_regex(&mut self) {
+        self.regex = if self.regex_search && !self.needle.is_empty() {
+            RegexBuilder::new(&self.needle)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .ok()
+        } else {
+            None
+        };
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -176,11 +235,24 @@ impl SearchResult {
 
     /// Search a row and its tail.
     /// The tail are all the non-canonical lines below `row`, with `row` not necessarily being canonical itself.
-    pub(crate) fn search_row(&self, mut ridx: usize, row: &Row, tail: &[&Row]) -> Vec<Selection> {
+    /// The head are all the lines above `row` that belong to the same canonical line, if `row` is not canonical.
+    /// It is only used as context when searching with a regex.
+    pub(crate) fn search_row(
+        &self,
+        mut ridx: usize,
+        head: &[&Row],
+        row: &Row,
+        tail: &[&Row],
+    ) -> Vec<Selection> {
         let mut res = Vec::new();
         if self.needle.is_empty() || row.columns.is_empty() {
             return res;
         }
+        if self.regex_search {
+            res = self.search_row_with_regex(ridx, head, row, tail);
+            reflow_selection_ends(&mut res, row.width());
+            return res;
+        }
 
         let mut tailit = tail.iter();
         let mut source = SearchSource::Main(row); // Where we currently get the haystack-characters from
@@ -267,13 +339,72 @@ impl SearchResult {
             }
         }
 
-        // The tail may have not been wrapped yet (when coming from lines_below),
-        // so it could be that the end extends across more characters than the row is wide.
-        // Therefore we need to reflow the end:
-        for s in res.iter_mut() {
-            while s.end.column() > row.width() {
-                s.end.column.0 -= row.width();
-                s.end.line.0 += 1;
+        reflow_selection_ends(&mut res, row.width());
+        res
+    }
+
+    /// Match the regex against the whole canonical line `row` belongs to (`head`, `row` and `tail` joined together).
+    /// Only matches starting in `row` are returned, as the others will be found when searching their rows.
+    fn search_row_with_regex(
+        &self,
+        ridx: usize,
+        head: &[&Row],
+        row: &Row,
+        tail: &[&Row],
+    ) -> Vec<Selection> {
+        let mut res = Vec::new();
+        let regex = match &self.regex {
+            Some(regex) => regex,
+            None => return res,
+        };
+
+        // For every haystack char we remember its byte offset in the haystack and its position
+        // in the grid (the head is only context for the regex, so it doesn't get a position)
+        let mut haystack = String::new();
+        let mut haystack_chars: Vec<(usize, char, Option<Position>)> = Vec::new();
+        for head_row in head {
+            for column in &head_row.columns {
+                haystack_chars.push((haystack.len(), column.character, None));
+                haystack.push(column.character);
+            }
+        }
+        for (line_offset, current_row) in
+            std::iter::once(row).chain(tail.iter().copied()).enumerate()
+        {
+            for (hidx, column) in current_row.columns.iter().enumerate() {
+                let position = Position::new((ridx + line_offset) as i32, hidx as u16);
+                haystack_chars.push((haystack.len(), column.character, Some(position)));
+                haystack.push(column.character);
+            }
+        }
+
+        for found in regex.find_iter(&haystack) {
+            if found.start() == found.end() {
+                // Empty matches (eg. `a*`) cannot be highlighted
+                continue;
+            }
+            let start_idx = haystack_chars.partition_point(|(offset, ..)| *offset < found.start());
+            let end_idx = haystack_chars.partition_point(|(offset, ..)| *offset < found.end());
+            let start = match haystack_chars[start_idx].2 {
+                Some(start) if start.line() as usize == ridx => start,
+                _ => continue,
+            };
+            if self.whole_word_only {
+                let prev_haystack_char = start_idx
+                    .checked_sub(1)
+                    .map(|prev_idx| haystack_chars[prev_idx].1);
+                let next_haystack_char = haystack_chars.get(end_idx).map(|(_, c, _)| *c);
+                if !is_word_boundary(&prev_haystack_char) || !is_word_boundary(&next_haystack_char)
+                {
+                    continue;
+                }
+            }
+            // The last char of the match comes after the start, so it always has a position
+            if let Some(end) = haystack_chars[end_idx - 1].2 {
+                let mut selection = Selection::default();
+                selection.start(start);
+                selection.end(Position::new(end.line() as i32, (end.column() + 1) as u16));
+                res.push(selection);
             }
         }
         res
@@ -318,6 +449,7 @@ impl SearchResult {
         &mut self,
         amount: usize,
         viewport: &[Row],
+        lines_above: &VecDeque<Row>,
         grid_height: usize,
     ) -> bool {
         let mut found_something = false;
@@ -341,7 +473,8 @@ impl SearchResult {
                         break;
                     }
                 }
-                let selections = self.search_row(0, row, &tail);
+                let head = head_of_row(0, viewport, lines_above);
+                let selections = self.search_row(0, &head, row, &tail);
                 for selection in selections.iter().rev() {
                     self.selections.insert(0, *selection);
                     found_something = true;
@@ -355,6 +488,7 @@ impl SearchResult {
         &mut self,
         amount: usize,
         viewport: &[Row],
+        lines_above: &VecDeque<Row>,
         lines_below: &[Row],
         grid_height: usize,
     ) -> bool {
@@ -370,7 +504,8 @@ impl SearchResult {
         if !self.needle.is_empty() {
             if let Some(row) = viewport.last() {
                 let tail: Vec<&Row> = lines_below.iter().take_while(|r| !r.is_canonical).collect();
-                let selections = self.search_row(viewport.len() - 1, row, &tail);
+                let head = head_of_row(viewport.len() - 1, viewport, lines_above);
+                let selections = self.search_row(viewport.len() - 1, &head, row, &tail);
                 for selection in selections {
                     // We are only interested in results that start in the this new row
                     if selection.start.line() as usize == viewport.len() - 1 {
@@ -412,6 +547,7 @@ impl Grid {
 
     pub fn set_search_string(&mut self, needle: &str) {
         self.search_results.needle = needle.to_string();
+        self.search_results.update_regex();
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
@@ -439,7 +575,8 @@ impl Grid {
                     break;
                 }
             }
-            let selections = self.search_results.search_row(ridx, row, &tail);
+            let head = head_of_row(ridx, &self.viewport, &self.lines_above);
+            let selections = self.search_results.search_row(ridx, &head, row, &tail);
             for sel in &selections {
                 // Cast works because we can' be negative here
                 self.output_buffer
@@ -452,8 +589,19 @@ impl Grid {
         }
     }
 
+    // eg. after the viewport jumped to a different part of the scrollback
+    pub fn search_whole_viewport_again(&mut self) {
+        if self.search_results.needle.is_empty() {
+            return;
+        }
+        self.search_results.selections.clear();
+        self.search_viewport();
+        self.search_results.unset_active_selection_if_nonexistent();
+    }
+
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -467,6 +615,19 @@ impl Grid {
         self.search_results.wrap_search = !self.search_results.wrap_search;
     }
 
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex_search = !self.search_results.regex_search;
+        self.search_results.update_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.active = None;
+        self.search_viewport();
+        // Maybe the selection we had is now gone
+        self.search_results.unset_active_selection_if_nonexistent();
+    }
+
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
         for line in self.search_results.selections.drain(..) {
@@ -1,13 +1,16 @@
 use std::convert::From;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, IndexMut};
 use std::rc::Rc;
 use unicode_width::UnicodeWidthChar;
 
+use serde::{Deserialize, Serialize};
 use unicode_width::UnicodeWidthStr;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::{
     data::{PaletteColor, Style},
+    serde,
     vte::ParamsIter,
 };
 
@@ -60,7 +63,8 @@ thread_local! {
         RcCharacterStyles::Rc(Rc::new(DEFAULT_STYLES));
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum AnsiCode {
     On,
     Reset,
@@ -70,7 +74,8 @@ pub enum AnsiCode {
     Underline(Option<AnsiStyledUnderline>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum AnsiStyledUnderline {
     Double,
     Undercurl,
@@ -87,7 +92,8 @@ impl From<PaletteColor> for AnsiCode {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum NamedColor {
     Black,
     Red,
@@ -209,7 +215,8 @@ impl RcCharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub struct CharacterStyles {
     pub foreground: Option<AnsiCode>,
     pub background: Option<AnsiCode>,
@@ -245,6 +252,27 @@ impl PartialEq for CharacterStyles {
     }
 }
 
+impl Eq for CharacterStyles {}
+
+// consistent with PartialEq, which ignores styled_underlines_enabled
+impl Hash for CharacterStyles {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.foreground.hash(state);
+        self.background.hash(state);
+        self.underline_color.hash(state);
+        self.strike.hash(state);
+        self.hidden.hash(state);
+        self.reverse.hash(state);
+        self.slow_blink.hash(state);
+        self.fast_blink.hash(state);
+        self.underline.hash(state);
+        self.bold.hash(state);
+        self.dim.hash(state);
+        self.italic.hash(state);
+        self.link_anchor.hash(state);
+    }
+}
+
 impl CharacterStyles {
     pub fn foreground(mut self, foreground_code: Option<AnsiCode>) -> Self {
         self.foreground = foreground_code;
@@ -762,7 +790,8 @@ impl Display for CharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum LinkAnchor {
     Start(u16),
     End,
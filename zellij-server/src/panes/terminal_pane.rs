@@ -3,6 +3,7 @@ use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
 use crate::panes::{
     grid::Grid,
+    grid_snapshot::GridSnapshotDelta,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
 use crate::pty::VteBytes;
@@ -11,13 +12,18 @@ use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::path::Path;
 use std::rc::Rc;
 use std::time::{self, Instant};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
-    data::{InputMode, Palette, PaletteColor, PaneId as ZellijUtilsPaneId, Style},
+    data::{
+        InputMode, Key, KeyEventType, Palette, PaletteColor, PaneContents,
+        PaneId as ZellijUtilsPaneId, Style,
+    },
     errors::prelude::*,
+    input::actions::{CopyModeMotion, CopyModeSelection},
     input::layout::Run,
     pane_size::PaneGeom,
     pane_size::SizeInPixels,
@@ -95,6 +101,15 @@ impl From<ZellijUtilsPaneId> for PaneId {
     }
 }
 
+impl From<PaneId> for ZellijUtilsPaneId {
+    fn from(pane_id: PaneId) -> Self {
+        match pane_id {
+            PaneId::Terminal(id) => ZellijUtilsPaneId::Terminal(id),
+            PaneId::Plugin(id) => ZellijUtilsPaneId::Plugin(id),
+        }
+    }
+}
+
 type IsFirstRun = bool;
 
 // FIXME: This should hold an os_api handle so that terminal panes can set their own size via FD in
@@ -117,6 +132,9 @@ pub struct TerminalPane {
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
     exclude_from_sync: bool,
+    pinned: bool,
+    in_pane_group_of: HashSet<ClientId>, // the clients whose pane group this pane is in
+    viewer_scroll_positions: HashMap<ClientId, usize>, // read-only clients scrolled on their own => the scrollback row at the top of their view
     fake_cursor_locations: HashSet<(usize, usize)>, // (x, y) - these hold a record of previous fake cursors which we need to clear on render
     search_term: String,
     is_held: Option<(Option<i32>, IsFirstRun, RunCommand)>, // a "held" pane means that its command has either exited and the pane is waiting for a
@@ -127,6 +145,7 @@ pub struct TerminalPane {
     invoked_with: Option<Run>,
     #[allow(dead_code)]
     arrow_fonts: bool,
+    changed_since_last_snapshot: bool,
 }
 
 impl Pane for TerminalPane {
@@ -175,6 +194,7 @@ impl Pane for TerminalPane {
     }
     fn handle_pty_bytes(&mut self, bytes: VteBytes) {
         self.set_should_render(true);
+        self.changed_since_last_snapshot = true;
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
         }
@@ -278,6 +298,17 @@ impl Pane for TerminalPane {
             Some(AdjustedInput::WriteBytesToTerminal(input_bytes))
         }
     }
+    fn serialize_key(&self, key: &Key, event_type: KeyEventType) -> Option<Vec<u8>> {
+        if self.grid.reports_kitty_key_event_types() {
+            key.serialize_kitty_event(event_type)
+        } else if event_type == KeyEventType::Release {
+            None
+        } else if self.grid.supports_kitty_keyboard_protocol() {
+            key.serialize_kitty()
+        } else {
+            key.serialize_legacy()
+        }
+    }
     fn position_and_size(&self) -> PaneGeom {
         self.geom
     }
@@ -368,6 +399,9 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex_search {
+                    modifiers.push("r")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
@@ -382,9 +416,20 @@ impl Pane for TerminalPane {
         };
 
         let frame_geom = self.current_geom();
+        let scrollback_position_and_length = match self.viewer_scroll_positions.get(&client_id) {
+            Some(scroll_position) => {
+                let (position, length) = self.grid.scrollback_position_and_length();
+                let rows_scrolled_up = self
+                    .grid
+                    .rows_above_viewport()
+                    .saturating_sub(*scroll_position);
+                (position + rows_scrolled_up, length)
+            },
+            None => self.grid.scrollback_position_and_length(),
+        };
         let mut frame = PaneFrame::new(
             frame_geom.into(),
-            self.grid.scrollback_position_and_length(),
+            scrollback_position_and_length,
             pane_title,
             frame_params,
         );
@@ -524,7 +569,11 @@ impl Pane for TerminalPane {
     fn dump_screen(&mut self, _client_id: ClientId, full: bool) -> String {
         self.grid.dump_screen(full)
     }
+    fn pane_contents(&self, scrollback_lines: usize) -> PaneContents {
+        self.grid.pane_contents(scrollback_lines)
+    }
     fn clear_screen(&mut self) {
+        self.changed_since_last_snapshot = true;
         self.grid.clear_screen()
     }
     fn scroll_up(&mut self, count: usize, _client_id: ClientId) {
@@ -542,6 +591,72 @@ impl Pane for TerminalPane {
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
+    fn scroll_up_for_viewer(&mut self, count: usize, client_id: ClientId) {
+        let rows_above_viewport = self.grid.rows_above_viewport();
+        let scroll_position = self
+            .viewer_scroll_positions
+            .get(&client_id)
+            .copied()
+            .unwrap_or(rows_above_viewport)
+            .saturating_sub(count);
+        if scroll_position < rows_above_viewport {
+            self.viewer_scroll_positions
+                .insert(client_id, scroll_position);
+            self.set_should_render(true);
+        }
+    }
+    fn scroll_down_for_viewer(&mut self, count: usize, client_id: ClientId) {
+        if let Some(scroll_position) = self.viewer_scroll_positions.get(&client_id).copied() {
+            let scroll_position = scroll_position.saturating_add(count);
+            if scroll_position < self.grid.rows_above_viewport() {
+                self.viewer_scroll_positions
+                    .insert(client_id, scroll_position);
+                self.set_should_render(true);
+            } else {
+                self.clear_scroll_for_viewer(client_id);
+            }
+        }
+    }
+    fn clear_scroll_for_viewer(&mut self, client_id: ClientId) {
+        if self.viewer_scroll_positions.remove(&client_id).is_some() {
+            // the client did not get the changes made to the pane while it was scrolled
+            self.render_full_viewport();
+        }
+    }
+    fn is_scrolled_for_viewer(&self, client_id: ClientId) -> bool {
+        self.viewer_scroll_positions.contains_key(&client_id)
+    }
+    fn render_for_viewer(&self, client_id: ClientId) -> Option<Vec<CharacterChunk>> {
+        let scroll_position = self.viewer_scroll_positions.get(&client_id)?;
+        if self.get_content_rows() < 1 || self.get_content_columns() < 1 {
+            return None;
+        }
+        let rows_scrolled_up = self
+            .grid
+            .rows_above_viewport()
+            .saturating_sub(*scroll_position);
+        Some(self.grid.render_scrolled_up(
+            rows_scrolled_up,
+            self.get_content_x(),
+            self.get_content_y(),
+        ))
+    }
+    fn scroll_to_previous_prompt(&mut self) {
+        self.grid.scroll_to_previous_prompt();
+        self.set_should_render(true);
+    }
+    fn scroll_to_next_prompt(&mut self) {
+        self.grid.scroll_to_next_prompt();
+        self.set_should_render(true);
+    }
+    fn select_last_command_output(&mut self) -> Option<String> {
+        let output = self.grid.select_last_command_output();
+        self.set_should_render(true);
+        output
+    }
+    fn last_command_exit_code(&self) -> Option<i32> {
+        self.grid.last_command_exit_code
+    }
 
     fn active_at(&self) -> Instant {
         self.active_at
@@ -603,6 +718,12 @@ impl Pane for TerminalPane {
     fn set_frame(&mut self, _frame: bool) {
         self.frame.clear();
     }
+    fn update_style(&mut self, style: Style) {
+        self.style = style;
+        self.grid.update_style(style);
+        self.frame.clear();
+        self.set_should_render(true);
+    }
 
     fn set_content_offset(&mut self, offset: Offset) {
         self.content_offset = offset;
@@ -634,6 +755,25 @@ impl Pane for TerminalPane {
     fn exclude_from_sync(&self) -> bool {
         self.exclude_from_sync
     }
+    fn set_pinned(&mut self, should_be_pinned: bool) {
+        self.pinned = should_be_pinned;
+    }
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+    fn set_in_group(&mut self, client_id: ClientId, in_group: bool) {
+        if in_group {
+            self.in_pane_group_of.insert(client_id);
+        } else {
+            self.in_pane_group_of.remove(&client_id);
+        }
+    }
+    fn is_in_group(&self, client_id: ClientId) -> bool {
+        self.in_pane_group_of.contains(&client_id)
+    }
+    fn is_in_any_group(&self) -> bool {
+        !self.in_pane_group_of.is_empty()
+    }
 
     fn mouse_left_click(&self, position: &Position, is_held: bool) -> Option<String> {
         self.grid.mouse_left_click_signal(position, is_held)
@@ -713,6 +853,26 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
+    fn enter_copy_mode(&mut self) {
+        self.grid.enter_copy_mode();
+        self.set_should_render(true);
+    }
+    fn exit_copy_mode(&mut self) {
+        self.grid.exit_copy_mode();
+        self.set_should_render(true);
+    }
+    fn copy_mode_move(&mut self, motion: CopyModeMotion) {
+        self.grid.copy_mode_move(motion);
+        self.set_should_render(true);
+    }
+    fn copy_mode_toggle_selection(&mut self, selection: CopyModeSelection) {
+        self.grid.copy_mode_toggle_selection(selection);
+        self.set_should_render(true);
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
@@ -784,6 +944,13 @@ impl Pane for TerminalPane {
     fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         self.grid.serialize(scrollback_lines_to_serialize)
     }
+    fn take_snapshot(&mut self) -> Option<GridSnapshotDelta> {
+        if !self.changed_since_last_snapshot {
+            return None;
+        }
+        self.changed_since_last_snapshot = false;
+        Some(self.grid.snapshot())
+    }
 }
 
 impl TerminalPane {
@@ -822,6 +989,7 @@ impl TerminalPane {
         );
         TerminalPane {
             frame: HashMap::new(),
+            viewer_scroll_positions: HashMap::new(),
             content_offset: Offset::default(),
             pid,
             grid,
@@ -837,6 +1005,8 @@ impl TerminalPane {
             prev_pane_name: pane_name,
             borderless: false,
             exclude_from_sync: false,
+            pinned: false,
+            in_pane_group_of: HashSet::new(),
             fake_cursor_locations: HashSet::new(),
             search_term: String::new(),
             is_held: None,
@@ -844,6 +1014,20 @@ impl TerminalPane {
             pane_frame_color_override: None,
             invoked_with,
             arrow_fonts,
+            changed_since_last_snapshot: true,
+        }
+    }
+    pub fn restore_snapshot(&mut self, snapshot_file: &Path) {
+        let restored = std::fs::read(snapshot_file)
+            .context("failed to read pane snapshot")
+            .and_then(|snapshot| self.grid.restore_snapshot(&snapshot));
+        match restored {
+            Ok(()) => self.set_should_render(true),
+            Err(e) => log::error!(
+                "Failed to restore pane snapshot {:?}: {:?}",
+                snapshot_file,
+                e
+            ),
         }
     }
     pub fn get_x(&self) -> usize {
@@ -919,3 +1103,7 @@ mod grid_tests;
 #[cfg(test)]
 #[path = "./unit/search_in_pane_tests.rs"]
 mod search_tests;
+
+#[cfg(test)]
+#[path = "./unit/copy_mode_in_pane_tests.rs"]
+mod copy_mode_tests;
@@ -53,6 +53,14 @@ fn pane_content_offset(position_and_size: &PaneGeom, viewport: &Viewport) -> (us
     (columns_offset, rows_offset)
 }
 
+/// The geometry of all tiled panes in a tab at a certain point in time, used to undo layout
+/// changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledPanesGeometry {
+    viewport: Viewport,
+    pane_geoms: BTreeMap<PaneId, PaneGeom>,
+}
+
 pub struct TiledPanes {
     pub panes: BTreeMap<PaneId, Box<dyn Pane>>,
     display_area: Rc<RefCell<Size>>,
@@ -297,10 +305,51 @@ impl TiledPanes {
 
         self.set_pane_frames(self.draw_pane_frames);
     }
+    pub fn geometry(&self) -> TiledPanesGeometry {
+        TiledPanesGeometry {
+            viewport: *self.viewport.borrow(),
+            pane_geoms: self
+                .panes
+                .iter()
+                .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+                .collect(),
+        }
+    }
+    pub fn can_restore_geometry(&self, geometry: &TiledPanesGeometry) -> bool {
+        // we can only restore the geometry of the same panes in the same space, otherwise it
+        // would leave gaps or overlaps
+        geometry.viewport == *self.viewport.borrow()
+            && geometry.pane_geoms.len() == self.panes.len()
+            && geometry
+                .pane_geoms
+                .keys()
+                .all(|pane_id| self.panes.contains_key(pane_id))
+    }
+    pub fn restore_geometry(&mut self, geometry: &TiledPanesGeometry) -> bool {
+        // returns true if the geometry was restored
+        if !self.can_restore_geometry(geometry) {
+            return false;
+        }
+        self.unset_fullscreen();
+        for (pane_id, pane_geom) in &geometry.pane_geoms {
+            if let Some(pane) = self.panes.get_mut(pane_id) {
+                pane.set_geom(*pane_geom);
+            }
+        }
+        self.reapply_pane_frames();
+        self.set_force_render();
+        true
+    }
     pub fn reapply_pane_frames(&mut self) {
         // same as set_pane_frames except it reapplies the current situation
         self.set_pane_frames(self.draw_pane_frames);
     }
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        for pane in self.panes.values_mut() {
+            pane.update_style(style);
+        }
+    }
     pub fn set_pane_frames(&mut self, draw_pane_frames: bool) {
         self.draw_pane_frames = draw_pane_frames;
         let viewport = *self.viewport.borrow();
@@ -677,12 +726,12 @@ impl TiledPanes {
                     should_draw_pane_frames,
                 );
                 for client_id in &connected_clients {
-                    let client_mode = self
-                        .mode_info
-                        .borrow()
-                        .get(client_id)
-                        .unwrap_or(&self.default_mode_info)
-                        .mode;
+                    let (client_mode, client_is_read_only) = {
+                        let mode_info = self.mode_info.borrow();
+                        let client_mode_info =
+                            mode_info.get(client_id).unwrap_or(&self.default_mode_info);
+                        (client_mode_info.mode, client_mode_info.is_read_only)
+                    };
                     let err_context =
                         || format!("failed to render tiled panes for client {client_id}");
                     if let PaneId::Plugin(..) = kind {
@@ -692,13 +741,23 @@ impl TiledPanes {
                     }
                     if self.draw_pane_frames {
                         pane_contents_and_ui
-                            .render_pane_frame(*client_id, client_mode, self.session_is_mirrored)
+                            .render_pane_frame(
+                                *client_id,
+                                client_mode,
+                                client_is_read_only,
+                                self.session_is_mirrored,
+                            )
                             .with_context(err_context)?;
                     } else if pane_is_stacked {
                         // if we have no pane frames but the pane is stacked, we need to render its
                         // frame which will amount to only rendering the title line
                         pane_contents_and_ui
-                            .render_pane_frame(*client_id, client_mode, self.session_is_mirrored)
+                            .render_pane_frame(
+                                *client_id,
+                                client_mode,
+                                client_is_read_only,
+                                self.session_is_mirrored,
+                            )
                             .with_context(err_context)?;
                         // we also need to render its boundaries as normal
                         let boundaries = client_id_to_boundaries
@@ -1285,6 +1344,87 @@ impl TiledPanes {
         current_position.set_should_render(true);
         self.set_pane_frames(self.draw_pane_frames);
     }
+    /// Exchanges the positions of two tiled panes, returning false if either of them is not here
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) -> bool {
+        if first_pane_id == second_pane_id
+            || !self.panes.contains_key(&first_pane_id)
+            || !self.panes.contains_key(&second_pane_id)
+        {
+            return false;
+        }
+        for pane_id in [first_pane_id, second_pane_id] {
+            if self
+                .panes
+                .get(&pane_id)
+                .map(|p| p.current_geom().is_stacked)
+                .unwrap_or(false)
+            {
+                let _ = StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide)
+                    .expand_pane(&pane_id);
+                self.reapply_pane_frames();
+            }
+        }
+        let first_pane = self.panes.get(&first_pane_id).unwrap();
+        let first_geom = first_pane.position_and_size();
+        let first_geom_override = first_pane.geom_override();
+
+        let second_pane = self.panes.get_mut(&second_pane_id).unwrap();
+        let second_geom = second_pane.position_and_size();
+        let second_geom_override = second_pane.geom_override();
+        second_pane.set_geom(first_geom);
+        if let Some(geom) = first_geom_override {
+            second_pane.set_geom_override(geom);
+        }
+        resize_pty!(
+            second_pane,
+            self.os_api,
+            self.senders,
+            self.character_cell_size
+        )
+        .unwrap();
+        second_pane.set_should_render(true);
+
+        let first_pane = self.panes.get_mut(&first_pane_id).unwrap();
+        first_pane.set_geom(second_geom);
+        if let Some(geom) = second_geom_override {
+            first_pane.set_geom_override(geom);
+        }
+        resize_pty!(
+            first_pane,
+            self.os_api,
+            self.senders,
+            self.character_cell_size
+        )
+        .unwrap();
+        first_pane.set_should_render(true);
+        self.set_pane_frames(self.draw_pane_frames);
+        true
+    }
+    pub fn swap_active_pane_with(&mut self, direction: Direction, client_id: ClientId) {
+        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
+            let pane_to_swap_with = {
+                let pane_grid = TiledPaneGrid::new(
+                    &mut self.panes,
+                    &self.panes_to_hide,
+                    *self.display_area.borrow(),
+                    *self.viewport.borrow(),
+                );
+                match direction {
+                    Direction::Left => {
+                        pane_grid.next_selectable_pane_id_to_the_left(&active_pane_id)
+                    },
+                    Direction::Right => {
+                        pane_grid.next_selectable_pane_id_to_the_right(&active_pane_id)
+                    },
+                    Direction::Up => pane_grid.next_selectable_pane_id_above(&active_pane_id),
+                    Direction::Down => pane_grid.next_selectable_pane_id_below(&active_pane_id),
+                }
+            };
+            if let Some(pane_to_swap_with) = pane_to_swap_with {
+                self.swap_panes(active_pane_id, pane_to_swap_with);
+            }
+        }
+    }
     pub fn move_active_pane_down(&mut self, client_id: ClientId) {
         if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
             let mut pane_grid = TiledPaneGrid::new(
@@ -0,0 +1,217 @@
+use super::super::TerminalPane;
+use crate::panes::sixel::SixelImageStore;
+use crate::panes::LinkHandler;
+use crate::tab::Pane;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use zellij_utils::data::{Palette, Style};
+use zellij_utils::input::actions::{CopyModeMotion, CopyModeSelection};
+use zellij_utils::pane_size::PaneGeom;
+
+fn create_pane(rows: usize, cols: usize, content: &str) -> TerminalPane {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(cols);
+    fake_win_size.rows.set_inner(rows);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        None,
+        None,
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    ); // 0 is the pane index
+    terminal_pane.handle_pty_bytes(content.as_bytes().to_vec());
+    terminal_pane
+}
+
+#[test]
+pub fn copy_mode_cursor_starts_at_terminal_cursor() {
+    let mut terminal_pane = create_pane(5, 20, "first line\n\rsecond");
+    terminal_pane.enter_copy_mode();
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((6, 1)));
+    terminal_pane.copy_mode_move(CopyModeMotion::Up);
+    terminal_pane.copy_mode_move(CopyModeMotion::Left);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((5, 0)));
+    terminal_pane.exit_copy_mode();
+    assert_eq!(
+        terminal_pane.grid.cursor_coordinates(),
+        Some((6, 1)),
+        "terminal cursor is restored when leaving copy mode"
+    );
+}
+
+#[test]
+pub fn copy_mode_word_motions() {
+    let mut terminal_pane = create_pane(5, 40, "cargo build --release\n\rnext line");
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_move(CopyModeMotion::Top);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::NextWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((6, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::NextWord);
+    assert_eq!(
+        terminal_pane.grid.cursor_coordinates(),
+        Some((12, 0)),
+        "punctuation is a word of its own"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::NextWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((14, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::NextWord);
+    assert_eq!(
+        terminal_pane.grid.cursor_coordinates(),
+        Some((0, 1)),
+        "moved to the next line"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::PreviousWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((14, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::PreviousWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((12, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::EndOfWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((13, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::EndOfWord);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((20, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::LineStart);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 0)));
+    terminal_pane.copy_mode_move(CopyModeMotion::LineEnd);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((20, 0)));
+}
+
+#[test]
+pub fn copy_mode_character_selection() {
+    let mut terminal_pane = create_pane(5, 40, "cargo build --release\n\rnext line");
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_move(CopyModeMotion::Top);
+    terminal_pane.copy_mode_move(CopyModeMotion::NextWord);
+    terminal_pane.copy_mode_toggle_selection(CopyModeSelection::Character);
+    terminal_pane.copy_mode_move(CopyModeMotion::EndOfWord);
+    assert_eq!(terminal_pane.get_selected_text(), Some("build".to_owned()));
+    terminal_pane.copy_mode_move(CopyModeMotion::Down);
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        Some("build --release\nnext line".to_owned())
+    );
+    terminal_pane.copy_mode_toggle_selection(CopyModeSelection::Character);
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        None,
+        "toggling again stops selecting"
+    );
+}
+
+#[test]
+pub fn copy_mode_line_selection() {
+    let mut terminal_pane = create_pane(5, 40, "first line\n\rsecond line\n\rthird line");
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_toggle_selection(CopyModeSelection::Line);
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        Some("third line".to_owned())
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::Up);
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        Some("second line\nthird line".to_owned())
+    );
+    terminal_pane.exit_copy_mode();
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        None,
+        "selection is cleared when leaving copy mode"
+    );
+}
+
+#[test]
+pub fn copy_mode_scrolls_the_viewport() {
+    let content: Vec<String> = (1..=10).map(|i| format!("line {}", i)).collect();
+    let mut terminal_pane = create_pane(3, 20, &content.join("\n\r"));
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_toggle_selection(CopyModeSelection::Line);
+    for _ in 0..3 {
+        terminal_pane.copy_mode_move(CopyModeMotion::Up);
+    }
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((7, 0)));
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        Some("line 7\nline 8\nline 9\nline 10".to_owned()),
+        "selection is kept when scrolling"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::Top);
+    assert_eq!(
+        terminal_pane.grid.dump_screen(false),
+        "line 1\nline 2\nline 3"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::Bottom);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 2)));
+    assert_eq!(
+        terminal_pane.grid.dump_screen(false),
+        "line 8\nline 9\nline 10"
+    );
+}
+
+#[test]
+pub fn copy_mode_go_prefix_jumps_to_the_top() {
+    let content: Vec<String> = (1..=10).map(|i| format!("line {}", i)).collect();
+    let mut terminal_pane = create_pane(3, 20, &content.join("\n\r"));
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_move(CopyModeMotion::GoPrefix);
+    terminal_pane.copy_mode_move(CopyModeMotion::Up);
+    terminal_pane.copy_mode_move(CopyModeMotion::GoPrefix);
+    assert_eq!(
+        terminal_pane.grid.cursor_coordinates(),
+        Some((7, 1)),
+        "a single g does not move"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::GoPrefix);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 0)));
+    assert_eq!(
+        terminal_pane.grid.dump_screen(false),
+        "line 1\nline 2\nline 3"
+    );
+}
+
+#[test]
+pub fn copy_mode_top_and_bottom_keep_wrapped_lines_and_selection() {
+    let mut terminal_pane = create_pane(
+        3,
+        10,
+        "aaaaaaaaaabbbbbbbbbbcc\n\rline 2\n\rline 3\n\rline 4\n\rline 5",
+    );
+    terminal_pane.enter_copy_mode();
+    terminal_pane.copy_mode_toggle_selection(CopyModeSelection::Line);
+    terminal_pane.copy_mode_move(CopyModeMotion::Top);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 0)));
+    assert_eq!(
+        terminal_pane.grid.dump_screen(false),
+        "aaaaaaaaaabbbbbbbbbbcc",
+        "the wrapped line fills the whole viewport"
+    );
+    assert_eq!(
+        terminal_pane.get_selected_text(),
+        Some("aaaaaaaaaabbbbbbbbbbcc\nline 2\nline 3\nline 4\nline 5".to_owned()),
+        "selection moves along with the content"
+    );
+    terminal_pane.copy_mode_move(CopyModeMotion::Bottom);
+    assert_eq!(terminal_pane.grid.cursor_coordinates(), Some((0, 2)));
+    assert_eq!(
+        terminal_pane.grid.dump_screen(false),
+        "line 3\nline 4\nline 5"
+    );
+}
@@ -1,5 +1,6 @@
 use super::super::Grid;
 use crate::panes::grid::SixelImageStore;
+use crate::panes::grid_snapshot::GridSnapshot;
 use crate::panes::link_handler::LinkHandler;
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
@@ -24,6 +25,27 @@ fn read_fixture(fixture_name: &str) -> Vec<u8> {
         .unwrap_or_else(|_| panic!("could not read fixture {:?}", &fixture_name))
 }
 
+fn create_grid(rows: usize, columns: usize) -> Grid {
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    )
+}
+
 #[test]
 fn vttest1_0() {
     let mut vte_parser = vte::Parser::new();
@@ -3636,3 +3658,302 @@ fn text_ui_component_with_coordinates() {
     }
     assert_snapshot!(format!("{:?}", grid));
 }
+
+#[test]
+fn pane_contents_with_scrollback() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(3, 20);
+    let content = "line 1\n\rline 2\n\rline 3\n\rline 4\n\rline 5";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let pane_contents = grid.pane_contents(1);
+    assert_eq!(
+        pane_contents.viewport,
+        vec!["line 3", "line 4", "line 5"],
+        "viewport contains the visible lines"
+    );
+    assert_eq!(
+        pane_contents.lines_above_viewport,
+        vec!["line 2"],
+        "only the requested amount of scrollback lines is included"
+    );
+    assert!(
+        pane_contents.lines_below_viewport.is_empty(),
+        "no lines below viewport"
+    );
+}
+
+fn run_command_with_shell_integration(command: &str, output: &[&str], exit_code: i32) -> String {
+    // the sequences a shell emits with OSC 133 integration: A (prompt), B (command), C (output)
+    // and D (command finished)
+    let mut content = format!("\u{1b}]133;A\u{7}$ \u{1b}]133;B\u{7}{}\n\r", command);
+    content.push_str("\u{1b}]133;C\u{7}");
+    for line in output {
+        content.push_str(line);
+        content.push_str("\n\r");
+    }
+    content.push_str(&format!("\u{1b}]133;D;{}\u{7}", exit_code));
+    content
+}
+
+#[test]
+fn scroll_to_previous_and_next_prompt() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(3, 20);
+    let mut content = String::new();
+    content.push_str(&run_command_with_shell_integration(
+        "first",
+        &["out 1", "out 2"],
+        0,
+    ));
+    content.push_str(&run_command_with_shell_integration(
+        "second",
+        &["out 3", "out 4"],
+        0,
+    ));
+    content.push_str("\u{1b}]133;A\u{7}$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.scroll_to_previous_prompt();
+    assert_eq!(
+        grid.pane_contents(0).viewport,
+        vec!["$ second", "out 3", "out 4"],
+        "scrolled up to the previous prompt"
+    );
+    grid.scroll_to_previous_prompt();
+    assert_eq!(
+        grid.pane_contents(0).viewport,
+        vec!["$ first", "out 1", "out 2"],
+        "scrolled up to the first prompt"
+    );
+    grid.scroll_to_previous_prompt();
+    assert_eq!(
+        grid.pane_contents(0).viewport,
+        vec!["$ first", "out 1", "out 2"],
+        "no prompt above the first one, viewport stays in place"
+    );
+    grid.scroll_to_next_prompt();
+    assert_eq!(
+        grid.pane_contents(0).viewport,
+        vec!["$ second", "out 3", "out 4"],
+        "scrolled down to the next prompt"
+    );
+}
+
+#[test]
+fn select_last_command_output() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(10, 20);
+    let mut content = String::new();
+    content.push_str(&run_command_with_shell_integration("first", &["out 1"], 0));
+    content.push_str(&run_command_with_shell_integration(
+        "second",
+        &["out 2   ", "out 3"],
+        0,
+    ));
+    content.push_str("\u{1b}]133;A\u{7}$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let output = grid.select_last_command_output();
+    assert_eq!(
+        output,
+        Some("out 2\nout 3".to_owned()),
+        "output of last command"
+    );
+    assert_eq!(
+        grid.get_selected_text(),
+        Some("out 2\nout 3".to_owned()),
+        "output of last command is selected"
+    );
+}
+
+#[test]
+fn select_last_command_output_without_shell_integration() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(10, 20);
+    let content = "$ first\n\rout 1\n\r$ ";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.select_last_command_output(), None);
+}
+
+#[test]
+fn last_command_exit_code() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(10, 20);
+    assert_eq!(grid.last_command_exit_code, None, "no command ran yet");
+    let content = run_command_with_shell_integration("false", &[], 1);
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.last_command_exit_code, Some(1));
+    let content = run_command_with_shell_integration("true", &[], 0);
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.last_command_exit_code, Some(0));
+}
+
+fn styled_scrollback_content() -> String {
+    let mut content = String::new();
+    for i in 0..10 {
+        content.push_str(&format!(
+            "\u{1b}[1;31mline {}\u{1b}[m and \u{1b}[38;2;10;20;30mrgb\u{1b}[m\n\r",
+            i
+        ));
+    }
+    content.push_str("$ ");
+    content
+}
+
+fn serialized_snapshot(grid: &mut Grid, scrollback_lines_to_serialize: Option<usize>) -> Vec<u8> {
+    let mut snapshot = GridSnapshot::default();
+    snapshot.apply_delta(grid.snapshot());
+    snapshot.to_bytes(scrollback_lines_to_serialize).unwrap()
+}
+
+#[test]
+fn snapshot_and_restore_styled_scrollback() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    for byte in styled_scrollback_content().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let snapshot = serialized_snapshot(&mut grid, Some(0));
+
+    let mut restored_grid = create_grid(5, 30);
+    restored_grid.restore_snapshot(&snapshot).unwrap();
+    assert_eq!(
+        restored_grid.serialize(Some(0)),
+        grid.serialize(Some(0)),
+        "styled contents including the scrollback were restored"
+    );
+    assert_eq!(
+        restored_grid.cursor_coordinates(),
+        grid.cursor_coordinates(),
+        "cursor position was restored"
+    );
+}
+
+#[test]
+fn snapshot_respects_scrollback_lines_to_serialize() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    for byte in styled_scrollback_content().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let mut restored_grid = create_grid(5, 30);
+    restored_grid
+        .restore_snapshot(&serialized_snapshot(&mut grid, Some(2)))
+        .unwrap();
+    assert_eq!(
+        restored_grid.lines_above.len(),
+        2,
+        "last two scrollback lines"
+    );
+
+    let mut restored_grid = create_grid(5, 30);
+    restored_grid
+        .restore_snapshot(&serialized_snapshot(&mut grid, None))
+        .unwrap();
+    assert_eq!(restored_grid.lines_above.len(), 0, "only the viewport");
+}
+
+#[test]
+fn restore_snapshot_reflows_to_current_size() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    for byte in styled_scrollback_content().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let mut restored_grid = create_grid(4, 10);
+    restored_grid
+        .restore_snapshot(&serialized_snapshot(&mut grid, Some(0)))
+        .unwrap();
+    assert_snapshot!(format!("{:?}", restored_grid));
+}
+
+#[test]
+fn snapshot_in_alternate_screen_restores_primary_screen() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    let content = "$ vim\n\r\u{1b}[?1049h\u{1b}[Hin the editor";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let mut restored_grid = create_grid(5, 30);
+    restored_grid
+        .restore_snapshot(&serialized_snapshot(&mut grid, Some(0)))
+        .unwrap();
+    let restored_contents = format!("{:?}", restored_grid);
+    assert!(
+        restored_contents.contains("$ vim") && !restored_contents.contains("in the editor"),
+        "primary screen was restored: {}",
+        restored_contents
+    );
+}
+
+#[test]
+fn restore_snapshot_rejects_garbage() {
+    let mut grid = create_grid(5, 30);
+    assert!(grid.restore_snapshot(b"not a snapshot").is_err());
+}
+
+#[test]
+fn snapshot_deltas_only_contain_new_scrollback_lines() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    for byte in styled_scrollback_content().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let mut snapshot = GridSnapshot::default();
+    snapshot.apply_delta(grid.snapshot());
+    let lines_above = snapshot.lines_above.len();
+
+    // a line long enough to wrap, the rows of which are joined when they reach the scrollback
+    let content = format!("{}\n\r\u{1b}[32mnew line\u{1b}[m\n\r$ ", "x".repeat(50));
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let delta = grid.snapshot();
+    assert_eq!(delta.dropped_lines_above, 0);
+    assert_eq!(
+        delta.unchanged_lines_above, lines_above,
+        "all the previous lines above are kept"
+    );
+    assert!(
+        delta.snapshot.lines_above.len() < lines_above,
+        "only new lines were taken: {:?}",
+        delta.snapshot.lines_above
+    );
+    snapshot.apply_delta(delta);
+
+    let mut whole_grid = grid.clone();
+    whole_grid.scrollback_changes = None;
+    let mut full_snapshot = GridSnapshot::default();
+    full_snapshot.apply_delta(whole_grid.snapshot());
+    assert_eq!(
+        snapshot.to_bytes(Some(0)).unwrap(),
+        full_snapshot.to_bytes(Some(0)).unwrap(),
+        "applying the delta gives the same snapshot as taking it whole"
+    );
+}
+
+#[test]
+fn scrolling_takes_the_whole_scrollback_in_the_next_snapshot() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(5, 30);
+    for byte in styled_scrollback_content().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.snapshot();
+    grid.scroll_up_one_line();
+    grid.scroll_down_one_line();
+    let delta = grid.snapshot();
+    assert_eq!(delta.unchanged_lines_above, 0);
+    assert_eq!(delta.snapshot.lines_above.len(), grid.lines_above.len());
+}
@@ -419,3 +419,86 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn searching_with_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("qu[a-z]m");
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "regex is searched literally by default"
+    );
+
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_quam_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+
+    terminal_pane.toggle_search_whole_words();
+    assert_snapshot!(
+        "grid_copy_quam_whole_word_only",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_invalid_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("quam(");
+    terminal_pane.toggle_search_regex();
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "invalid regex does not match anything"
+    );
+}
+
+#[test]
+pub fn searching_with_regex_across_line_wrap() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.handle_pty_bytes(
+        "error[E0308]: mismatched types error[E42]\n\rerror[E1] error[] warning[E0308]"
+            .as_bytes()
+            .to_vec(),
+    );
+    terminal_pane.grid.change_size(20, 5);
+    terminal_pane.update_search_term(r"^error\[E\d+\]|warning\[E\d+\]");
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_multiline_regex",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_in_line_wrapped_from_above_viewport() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.grid.change_size(4, 10);
+    // a canonical line wrapped across 3 rows, the first 2 of which end up above the viewport
+    terminal_pane.handle_pty_bytes(
+        "\n\r\n\r\n\r\n\raaaaaaaaaabbbbbbbbbberror-tail\n\rone\n\rtwo\n\rthree"
+            .as_bytes()
+            .to_vec(),
+    );
+    assert_eq!(
+        terminal_pane.grid.viewport[0]
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>(),
+        "error-tail"
+    );
+    // `^` needs to be anchored at the start of the canonical line (2 rows above the viewport)
+    // rather than at the start of the previous row for the anchored match to stop before "error"
+    // and for "error" to be found
+    terminal_pane.update_search_term(r"^.{12}|error");
+    terminal_pane.toggle_search_regex();
+    let matches: Vec<(isize, usize)> = terminal_pane
+        .grid
+        .search_results
+        .selections
+        .iter()
+        .map(|s| (s.start.line(), s.start.column()))
+        .collect();
+    assert_eq!(matches, vec![(0, 0)]);
+}
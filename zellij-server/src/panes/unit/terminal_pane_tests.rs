@@ -7,7 +7,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use zellij_utils::{
-    data::{Palette, Style},
+    data::{BareKey, Key, KeyEventType, KeyModifiers, Palette, Style},
     pane_size::{Offset, PaneGeom, SizeInPixels},
     position::Position,
 };
@@ -740,3 +740,129 @@ pub fn frameless_pane_position_is_on_frame() {
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 130)));
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 131)));
 }
+
+#[test]
+pub fn keys_are_serialized_according_to_the_kitty_keyboard_protocol_flags() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_colors = Rc::new(RefCell::new(Palette::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        terminal_emulator_colors,
+        terminal_emulator_color_codes,
+        None,
+        None,
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    ); // 0 is the pane index
+    let ctrl_shift_a = Key::WithModifiers(
+        BareKey::Char('a'),
+        KeyModifiers {
+            ctrl: true,
+            shift: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Press),
+        Some(vec![1]),
+        "legacy encoding before the application asks for the kitty keyboard protocol"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Esc, KeyEventType::Press),
+        Some(vec![27]),
+        "legacy encoding for Esc"
+    );
+
+    // push the "disambiguate escape codes" flag and query the current flags
+    terminal_pane.handle_pty_bytes("\u{1b}[>1u\u{1b}[?u".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.grid.pending_messages_to_pty,
+        vec!["\u{1b}[?1u".as_bytes().to_vec()],
+        "current flags reported"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Press),
+        Some("\u{1b}[97;6u".as_bytes().to_vec()),
+        "kitty encoding once the application asked for it"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Esc, KeyEventType::Press),
+        Some("\u{1b}[27u".as_bytes().to_vec()),
+        "Esc is disambiguated"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Char('a'), KeyEventType::Press),
+        Some(vec![b'a']),
+        "text is still sent as is"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Release),
+        None,
+        "releasing keys is not reported without the report event types flag"
+    );
+
+    // also report event types
+    terminal_pane.handle_pty_bytes("\u{1b}[=3;2u\u{1b}[?u".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.grid.pending_messages_to_pty.last(),
+        Some(&"\u{1b}[?3u".as_bytes().to_vec()),
+        "report event types flag is supported"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Press),
+        Some("\u{1b}[97;6u".as_bytes().to_vec())
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Repeat),
+        Some("\u{1b}[97;6:2u".as_bytes().to_vec())
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Release),
+        Some("\u{1b}[97;6:3u".as_bytes().to_vec())
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Char('A'), KeyEventType::Repeat),
+        Some(vec![b'A']),
+        "repeated text is sent as text"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Char('A'), KeyEventType::Release),
+        Some("\u{1b}[97;2:3u".as_bytes().to_vec()),
+        "releasing text is reported with the un-shifted key"
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Left, KeyEventType::Release),
+        Some("\u{1b}[1;1:3D".as_bytes().to_vec())
+    );
+    assert_eq!(
+        terminal_pane.serialize_key(&Key::Char('\n'), KeyEventType::Release),
+        None,
+        "releasing Enter is only reported with the report all keys flag"
+    );
+
+    // pop the flags
+    terminal_pane.handle_pty_bytes("\u{1b}[<u".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.serialize_key(&ctrl_shift_a, KeyEventType::Press),
+        Some(vec![1]),
+        "legacy encoding after the application popped the flags"
+    );
+}
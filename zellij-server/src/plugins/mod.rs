@@ -1,6 +1,7 @@
 mod pipes;
 mod plugin_loader;
 mod plugin_map;
+mod plugin_store;
 mod plugin_worker;
 mod wasm_bridge;
 mod watch_filesystem;
@@ -11,7 +12,7 @@ use std::{
     fs,
     path::PathBuf,
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use wasmer::Store;
 
@@ -27,11 +28,12 @@ use zellij_utils::{
     async_std::{channel, future::timeout, task},
     data::{
         Event, EventType, MessageToPlugin, PermissionStatus, PermissionType, PipeMessage,
-        PipeSource, PluginCapabilities,
+        PipeSource, PluginCapabilities, PluginRequest, PluginResponse, StoreScope,
     },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
         command::TerminalAction,
+        keybinds::PluginKeybind,
         layout::{
             FloatingPaneLayout, Layout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation,
             TiledPaneLayout,
@@ -130,6 +132,25 @@ pub enum PluginInstruction {
         message: MessageToPlugin,
     },
     UnblockCliPipes(Vec<PluginRenderAsset>),
+    RegisterKeybind(PluginKeybind),
+    PluginRequest {
+        requester_plugin_id: PluginId,
+        requester_client_id: ClientId,
+        request: PluginRequest,
+    },
+    PluginResponse {
+        requester_plugin_id: PluginId,
+        responder_plugin_id: PluginId,
+        response: PluginResponse,
+    },
+    PluginRequestTimedOut {
+        requester_plugin_id: PluginId,
+        request_id: String,
+    },
+    StoreSet(PluginId, StoreScope, String, Option<String>), // scope, key, value
+    StoreGet(PluginId, ClientId, StoreScope, String),       // scope, key
+    StoreWatch(PluginId, ClientId, StoreScope, String),     // scope, key
+    WatchedPluginFilesChanged(Vec<PathBuf>),
     Exit,
 }
 
@@ -165,6 +186,16 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::CachePluginEvents { .. } => PluginContext::CachePluginEvents,
             PluginInstruction::MessageFromPlugin { .. } => PluginContext::MessageFromPlugin,
             PluginInstruction::UnblockCliPipes { .. } => PluginContext::UnblockCliPipes,
+            PluginInstruction::RegisterKeybind(..) => PluginContext::RegisterKeybind,
+            PluginInstruction::PluginRequest { .. } => PluginContext::PluginRequest,
+            PluginInstruction::PluginResponse { .. } => PluginContext::PluginResponse,
+            PluginInstruction::PluginRequestTimedOut { .. } => PluginContext::PluginRequestTimedOut,
+            PluginInstruction::StoreSet(..) => PluginContext::StoreSet,
+            PluginInstruction::StoreGet(..) => PluginContext::StoreGet,
+            PluginInstruction::StoreWatch(..) => PluginContext::StoreWatch,
+            PluginInstruction::WatchedPluginFilesChanged(..) => {
+                PluginContext::WatchedPluginFilesChanged
+            },
         }
     }
 }
@@ -511,59 +542,14 @@ pub(crate) fn plugin_thread_main(
                 source_plugin_id,
                 message,
             } => {
-                let cwd = message.new_plugin_args.as_ref().and_then(|n| n.cwd.clone());
                 let mut pipe_messages = vec![];
-                let skip_cache = message
-                    .new_plugin_args
-                    .as_ref()
-                    .map(|n| n.skip_cache)
-                    .unwrap_or(false);
-                let should_float = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.should_float)
-                    .unwrap_or(true);
-                let pane_title = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.pane_title.clone());
-                let pane_id_to_replace = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.pane_id_to_replace);
-                match message.plugin_url {
-                    Some(plugin_url) => {
-                        // send to specific plugin(s)
-                        pipe_to_specific_plugins(
-                            PipeSource::Plugin(source_plugin_id),
-                            &plugin_url,
-                            &Some(message.plugin_config),
-                            &cwd,
-                            skip_cache,
-                            should_float,
-                            &pane_id_to_replace.map(|p| p.into()),
-                            &pane_title,
-                            None,
-                            &mut pipe_messages,
-                            &message.message_name,
-                            &message.message_payload,
-                            &Some(message.message_args),
-                            &bus,
-                            &mut wasm_bridge,
-                        );
-                    },
-                    None => {
-                        // send to all plugins
-                        pipe_to_all_plugins(
-                            PipeSource::Plugin(source_plugin_id),
-                            &message.message_name,
-                            &message.message_payload,
-                            &Some(message.message_args),
-                            &mut wasm_bridge,
-                            &mut pipe_messages,
-                        );
-                    },
-                }
+                pipe_message_from_plugin(
+                    source_plugin_id,
+                    message,
+                    &mut pipe_messages,
+                    &bus,
+                    &mut wasm_bridge,
+                );
                 wasm_bridge.pipe_messages(pipe_messages, shutdown_send.clone())?;
             },
             PluginInstruction::UnblockCliPipes(pipes_to_unblock) => {
@@ -575,6 +561,62 @@ pub(crate) fn plugin_thread_main(
                         .context("failed to unblock input pipe");
                 }
             },
+            PluginInstruction::RegisterKeybind(plugin_keybind) => {
+                wasm_bridge.register_keybind(plugin_keybind);
+            },
+            PluginInstruction::PluginRequest {
+                requester_plugin_id,
+                requester_client_id,
+                request,
+            } => {
+                send_plugin_request(
+                    requester_plugin_id,
+                    requester_client_id,
+                    request,
+                    &bus,
+                    &mut wasm_bridge,
+                    shutdown_send.clone(),
+                )?;
+            },
+            PluginInstruction::PluginResponse {
+                requester_plugin_id,
+                responder_plugin_id,
+                response,
+            } => {
+                wasm_bridge.respond_to_plugin_request(
+                    requester_plugin_id,
+                    responder_plugin_id,
+                    response,
+                    shutdown_send.clone(),
+                )?;
+            },
+            PluginInstruction::PluginRequestTimedOut {
+                requester_plugin_id,
+                request_id,
+            } => {
+                wasm_bridge.time_out_plugin_request(
+                    requester_plugin_id,
+                    request_id,
+                    shutdown_send.clone(),
+                )?;
+            },
+            // eg. a plugin exceeding the size limit of its store should not bring down the thread
+            PluginInstruction::StoreSet(plugin_id, scope, key, value) => {
+                wasm_bridge
+                    .store_set(plugin_id, scope, key, value, shutdown_send.clone())
+                    .non_fatal();
+            },
+            PluginInstruction::StoreGet(plugin_id, client_id, scope, key) => {
+                wasm_bridge
+                    .store_get(plugin_id, client_id, scope, key, shutdown_send.clone())
+                    .non_fatal();
+            },
+            PluginInstruction::StoreWatch(plugin_id, client_id, scope, key) => {
+                wasm_bridge.store_watch(plugin_id, client_id, scope, key);
+            },
+            PluginInstruction::WatchedPluginFilesChanged(changed_paths) => {
+                wasm_bridge.reload_watched_plugins(changed_paths);
+            },
             PluginInstruction::Exit => {
                 break;
             },
@@ -625,6 +667,138 @@ fn populate_session_layout_metadata(
     session_layout_metadata.update_plugin_cmds(plugin_ids_to_cmds);
 }
 
+fn send_plugin_request(
+    requester_plugin_id: PluginId,
+    requester_client_id: ClientId,
+    request: PluginRequest,
+    bus: &Bus<PluginInstruction>,
+    wasm_bridge: &mut WasmBridge,
+    shutdown_send: channel::Sender<()>,
+) -> Result<()> {
+    let request_id = request.request_id;
+    let respond_immediately = |wasm_bridge: &mut WasmBridge, response: PluginResponse| {
+        let updates = vec![(
+            Some(requester_plugin_id),
+            Some(requester_client_id),
+            Event::PluginResponse(response),
+        )];
+        wasm_bridge.update_plugins(updates, shutdown_send.clone())
+    };
+    if wasm_bridge.plugin_request_is_pending(requester_plugin_id, &request_id) {
+        return respond_immediately(
+            wasm_bridge,
+            PluginResponse::error(request_id, "A request with this id is already pending"),
+        );
+    }
+    let mut pipe_messages = vec![];
+    if request.message.plugin_url.is_some() {
+        // requests are only sent to a specific plugin, never broadcast
+        pipe_message_from_plugin(
+            requester_plugin_id,
+            request.message,
+            &mut pipe_messages,
+            bus,
+            wasm_bridge,
+        );
+    }
+    let responders: HashSet<PluginId> = pipe_messages
+        .iter()
+        .filter_map(|(plugin_id, _, _)| *plugin_id)
+        .collect();
+    if responders.is_empty() {
+        return respond_immediately(wasm_bridge, PluginResponse::unavailable(request_id));
+    }
+    wasm_bridge.add_pending_plugin_request(
+        requester_plugin_id,
+        requester_client_id,
+        &request_id,
+        responders,
+        Instant::now() + request.timeout,
+    );
+    let senders = bus.senders.clone();
+    let timeout = request.timeout;
+    let timed_out_request_id = request_id.clone();
+    task::spawn(async move {
+        task::sleep(timeout).await;
+        let _ = senders.send_to_plugin(PluginInstruction::PluginRequestTimedOut {
+            requester_plugin_id,
+            request_id: timed_out_request_id,
+        });
+    });
+    let pipe_messages = pipe_messages
+        .into_iter()
+        .map(|(plugin_id, client_id, pipe_message)| {
+            (
+                plugin_id,
+                client_id,
+                pipe_message.with_request_id(request_id.clone()),
+            )
+        })
+        .collect();
+    wasm_bridge.pipe_messages(pipe_messages, shutdown_send)
+}
+
+fn pipe_message_from_plugin(
+    source_plugin_id: u32,
+    message: MessageToPlugin,
+    pipe_messages: &mut Vec<(Option<PluginId>, Option<ClientId>, PipeMessage)>,
+    bus: &Bus<PluginInstruction>,
+    wasm_bridge: &mut WasmBridge,
+) {
+    let cwd = message.new_plugin_args.as_ref().and_then(|n| n.cwd.clone());
+    let skip_cache = message
+        .new_plugin_args
+        .as_ref()
+        .map(|n| n.skip_cache)
+        .unwrap_or(false);
+    let should_float = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.should_float)
+        .unwrap_or(true);
+    let pane_title = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.pane_title.clone());
+    let pane_id_to_replace = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.pane_id_to_replace);
+    match message.plugin_url {
+        Some(plugin_url) => {
+            // send to specific plugin(s)
+            pipe_to_specific_plugins(
+                PipeSource::Plugin(source_plugin_id),
+                &plugin_url,
+                &Some(message.plugin_config),
+                &cwd,
+                skip_cache,
+                should_float,
+                &pane_id_to_replace.map(|p| p.into()),
+                &pane_title,
+                None,
+                pipe_messages,
+                &message.message_name,
+                &message.message_payload,
+                &Some(message.message_args),
+                bus,
+                wasm_bridge,
+            );
+        },
+        None => {
+            // send to all plugins
+            pipe_to_all_plugins(
+                PipeSource::Plugin(source_plugin_id),
+                &message.message_name,
+                &message.message_payload,
+                &Some(message.message_args),
+                wasm_bridge,
+                pipe_messages,
+            );
+        },
+    }
+}
+
 fn pipe_to_all_plugins(
     pipe_source: PipeSource,
     name: &str,
@@ -3,6 +3,7 @@ use crate::plugins::plugin_map::RunningPlugin;
 use crate::plugins::wasm_bridge::PluginRenderAsset;
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use wasmer::Value;
 use zellij_utils::data::{PipeMessage, PipeSource};
 use zellij_utils::plugin_api::pipe_message::ProtobufPipeMessage;
@@ -138,6 +139,112 @@ impl PendingPipeInfo {
     }
 }
 
+// requests sent from one plugin to another that were not yet answered, keyed by the id of the
+// requesting plugin and the id it chose for the request
+#[derive(Debug, Clone, Default)]
+pub struct PendingPluginRequests {
+    requests: HashMap<(PluginId, String), PendingPluginRequestInfo>,
+}
+
+impl PendingPluginRequests {
+    pub fn is_pending(&self, requester_plugin_id: PluginId, request_id: &str) -> bool {
+        self.requests
+            .contains_key(&(requester_plugin_id, request_id.to_owned()))
+    }
+    pub fn add(
+        &mut self,
+        requester_plugin_id: PluginId,
+        requester_client_id: ClientId,
+        request_id: &str,
+        responders: HashSet<PluginId>,
+        deadline: Instant,
+    ) {
+        self.requests.insert(
+            (requester_plugin_id, request_id.to_owned()),
+            PendingPluginRequestInfo {
+                requester_client_id,
+                responders,
+                deadline,
+            },
+        );
+    }
+    // returns the client id of the requesting plugin if the responder was asked to answer this
+    // request and it is still pending - the first response answers the request
+    pub fn respond(
+        &mut self,
+        requester_plugin_id: PluginId,
+        request_id: &str,
+        responder_plugin_id: PluginId,
+    ) -> Option<ClientId> {
+        let key = (requester_plugin_id, request_id.to_owned());
+        let is_responder = self
+            .requests
+            .get(&key)
+            .map(|pending_request| pending_request.responders.contains(&responder_plugin_id))
+            .unwrap_or(false);
+        if is_responder {
+            self.requests
+                .remove(&key)
+                .map(|pending_request| pending_request.requester_client_id)
+        } else {
+            None
+        }
+    }
+    // returns the client id of the requesting plugin if the request is still pending and its
+    // deadline passed (a request with the same id might have been sent again since this timeout
+    // was set, in which case its deadline would be later)
+    pub fn time_out(
+        &mut self,
+        requester_plugin_id: PluginId,
+        request_id: &str,
+        now: Instant,
+    ) -> Option<ClientId> {
+        let key = (requester_plugin_id, request_id.to_owned());
+        let deadline_passed = self
+            .requests
+            .get(&key)
+            .map(|pending_request| pending_request.deadline <= now)
+            .unwrap_or(false);
+        if deadline_passed {
+            self.requests
+                .remove(&key)
+                .map(|pending_request| pending_request.requester_client_id)
+        } else {
+            None
+        }
+    }
+    // returns the requests that can no longer be answered because all the plugins they were sent
+    // to were unloaded, as (requester_plugin_id, requester_client_id, request_id)
+    pub fn unload_plugin(&mut self, plugin_id: &PluginId) -> Vec<(PluginId, ClientId, String)> {
+        let mut unanswerable_requests = vec![];
+        self.requests
+            .retain(|(requester_plugin_id, request_id), pending_request| {
+                if requester_plugin_id == plugin_id {
+                    return false;
+                }
+                pending_request.responders.remove(plugin_id);
+                if pending_request.responders.is_empty() {
+                    unanswerable_requests.push((
+                        *requester_plugin_id,
+                        pending_request.requester_client_id,
+                        request_id.to_owned(),
+                    ));
+                    false
+                } else {
+                    true
+                }
+            });
+        unanswerable_requests
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingPluginRequestInfo {
+    requester_client_id: ClientId,
+    responders: HashSet<PluginId>,
+    deadline: Instant,
+}
+
 pub fn apply_pipe_message_to_plugin(
     plugin_id: PluginId,
     client_id: ClientId,
@@ -255,3 +362,7 @@ pub fn pipes_to_block_or_unblock(
     }
     pipe_state_changes
 }
+
+#[cfg(test)]
+#[path = "./unit/pipes_tests.rs"]
+mod pipes_tests;
@@ -248,6 +248,8 @@ impl PluginMap {
                         .clone();
                     Some(RunPlugin {
                         _allow_exec_host_cmd: false,
+                        _watch: false,
+                        _watch_clear_data: false,
                         location: run_plugin_location,
                         configuration: run_plugin_configuration,
                     })
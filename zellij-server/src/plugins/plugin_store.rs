@@ -0,0 +1,251 @@
+//! Key-value storage for plugins (`PluginCommand::StoreSet`, `StoreGet` and `StoreWatch`). The
+//! plugin and global scopes are kept in json files under the zellij data dir so that they persist
+//! across sessions, the session scope only lives as long as the session.
+use super::PluginId;
+use crate::ClientId;
+use highway::{HighwayHash, PortableHash};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{self, File},
+    os::unix::io::AsRawFd,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use zellij_utils::{
+    data::StoreScope,
+    errors::prelude::*,
+    nix::fcntl::{flock, FlockArg},
+};
+
+/// The most bytes (of keys and values) a single namespace can hold
+const MAX_NAMESPACE_SIZE: usize = 1024 * 1024;
+
+/// The keys a plugin can see in a given scope
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StoreNamespace {
+    Plugin(String), // the location of the plugin
+    Session,
+    Global,
+}
+
+impl StoreNamespace {
+    pub fn new(scope: StoreScope, plugin_location: &str) -> Self {
+        match scope {
+            StoreScope::Plugin => StoreNamespace::Plugin(plugin_location.to_owned()),
+            StoreScope::Session => StoreNamespace::Session,
+            StoreScope::Global => StoreNamespace::Global,
+        }
+    }
+    pub fn scope(&self) -> StoreScope {
+        match self {
+            StoreNamespace::Plugin(_) => StoreScope::Plugin,
+            StoreNamespace::Session => StoreScope::Session,
+            StoreNamespace::Global => StoreScope::Global,
+        }
+    }
+}
+
+pub struct PluginStore {
+    store_dir: PathBuf,
+    session_values: BTreeMap<String, String>,
+    persisted_values: HashMap<PathBuf, PersistedValues>,
+    watchers: HashMap<(StoreNamespace, String), HashSet<(PluginId, ClientId)>>,
+}
+
+// the values of a store file as of its last modification time, so that getting a value only
+// reads the file again if another session changed it
+#[derive(Debug, Default)]
+struct PersistedValues {
+    values: BTreeMap<String, String>,
+    modified: Option<SystemTime>,
+}
+
+impl PluginStore {
+    pub fn new(store_dir: PathBuf) -> Self {
+        PluginStore {
+            store_dir,
+            session_values: BTreeMap::new(),
+            persisted_values: HashMap::new(),
+            watchers: HashMap::new(),
+        }
+    }
+    pub fn get(&mut self, namespace: &StoreNamespace, key: &str) -> Result<Option<String>> {
+        match self.file_path(namespace) {
+            Some(file_path) => Ok(self.persisted_values(&file_path)?.values.get(key).cloned()),
+            None => Ok(self.session_values.get(key).cloned()),
+        }
+    }
+    // returns the plugins watching this key, to be notified if its value changed
+    pub fn set(
+        &mut self,
+        namespace: &StoreNamespace,
+        key: &str,
+        value: Option<String>,
+    ) -> Result<Vec<(PluginId, ClientId)>> {
+        let err_context = || format!("failed to set key {} of plugin store {:?}", key, namespace);
+        let previous_value = match self.file_path(namespace) {
+            Some(file_path) => {
+                // other sessions might be changing this file at the same time, so it is locked
+                // until it is written
+                let _lock = lock_store_file(&file_path).with_context(err_context)?;
+                // always read the file here rather than trusting the cache: modification times
+                // are too coarse to tell apart two writes that happened in quick succession
+                let mut values = read_values(&file_path).with_context(err_context)?;
+                check_namespace_size(&values, key, &value).with_context(err_context)?;
+                let previous_value = set_value(&mut values, key, value.clone());
+                if previous_value != value {
+                    write_values(&file_path, &values).with_context(err_context)?;
+                }
+                let modified = modification_time(&file_path).with_context(err_context)?;
+                self.persisted_values
+                    .insert(file_path, PersistedValues { values, modified });
+                previous_value
+            },
+            None => {
+                check_namespace_size(&self.session_values, key, &value)
+                    .with_context(err_context)?;
+                set_value(&mut self.session_values, key, value.clone())
+            },
+        };
+        if previous_value == value {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .watchers
+            .get(&(namespace.clone(), key.to_owned()))
+            .map(|watchers| watchers.iter().copied().collect())
+            .unwrap_or_default())
+    }
+    pub fn watch(
+        &mut self,
+        namespace: StoreNamespace,
+        key: String,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) {
+        self.watchers
+            .entry((namespace, key))
+            .or_default()
+            .insert((plugin_id, client_id));
+    }
+    pub fn unload_plugin(&mut self, plugin_id: PluginId) {
+        self.watchers.retain(|_, watchers| {
+            watchers.retain(|(watcher_plugin_id, _)| *watcher_plugin_id != plugin_id);
+            !watchers.is_empty()
+        });
+    }
+    fn persisted_values(&mut self, file_path: &Path) -> Result<&PersistedValues> {
+        let modified = modification_time(file_path)?;
+        let persisted_values = self
+            .persisted_values
+            .entry(file_path.to_path_buf())
+            .or_default();
+        if persisted_values.modified != modified {
+            persisted_values.values = read_values(file_path)?;
+            persisted_values.modified = modified;
+        }
+        Ok(persisted_values)
+    }
+    fn file_path(&self, namespace: &StoreNamespace) -> Option<PathBuf> {
+        match namespace {
+            StoreNamespace::Plugin(plugin_location) => {
+                let file_name: String = PortableHash::default()
+                    .hash128(plugin_location.as_bytes())
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                Some(
+                    self.store_dir
+                        .join("plugins")
+                        .join(format!("{}.json", file_name)),
+                )
+            },
+            StoreNamespace::Session => None,
+            StoreNamespace::Global => Some(self.store_dir.join("global.json")),
+        }
+    }
+}
+
+// returns the previous value of the key
+fn set_value(
+    values: &mut BTreeMap<String, String>,
+    key: &str,
+    value: Option<String>,
+) -> Option<String> {
+    match value {
+        Some(value) => values.insert(key.to_owned(), value),
+        None => values.remove(key),
+    }
+}
+
+// checks the size the namespace would have after setting this key
+fn check_namespace_size(
+    values: &BTreeMap<String, String>,
+    key: &str,
+    value: &Option<String>,
+) -> Result<()> {
+    let other_values_size: usize = values
+        .iter()
+        .filter(|(other_key, _)| other_key.as_str() != key)
+        .map(|(other_key, other_value)| other_key.len() + other_value.len())
+        .sum();
+    let value_size = value
+        .as_ref()
+        .map(|value| key.len() + value.len())
+        .unwrap_or(0);
+    if other_values_size + value_size > MAX_NAMESPACE_SIZE {
+        Err(anyhow!(
+            "plugin store would exceed its limit of {} bytes",
+            MAX_NAMESPACE_SIZE
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// the lock is held until the returned file is dropped
+fn lock_store_file(file_path: &Path) -> Result<File> {
+    let lock_file_path = file_path.with_extension("json.lock");
+    let err_context = || format!("failed to lock plugin store at {:?}", lock_file_path);
+    if let Some(parent) = lock_file_path.parent() {
+        fs::create_dir_all(parent).with_context(err_context)?;
+    }
+    let lock_file = File::create(&lock_file_path).with_context(err_context)?;
+    flock(lock_file.as_raw_fd(), FlockArg::LockExclusive).with_context(err_context)?;
+    Ok(lock_file)
+}
+
+fn modification_time(file_path: &Path) -> Result<Option<SystemTime>> {
+    match fs::metadata(file_path).and_then(|metadata| metadata.modified()) {
+        Ok(modified) => Ok(Some(modified)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("failed to read plugin store at {:?}", file_path)),
+    }
+}
+
+fn read_values(file_path: &Path) -> Result<BTreeMap<String, String>> {
+    match fs::read_to_string(file_path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse plugin store at {:?}", file_path)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e).with_context(|| format!("failed to read plugin store at {:?}", file_path)),
+    }
+}
+
+fn write_values(file_path: &Path, values: &BTreeMap<String, String>) -> Result<()> {
+    let err_context = || format!("failed to write plugin store at {:?}", file_path);
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).with_context(err_context)?;
+    }
+    // write to a temporary file and rename it so that a session reading the store at the same
+    // time never sees a partially written file
+    let temp_file_path = file_path.with_extension(format!("json.{}", std::process::id()));
+    let contents = serde_json::to_string(values).with_context(err_context)?;
+    fs::write(&temp_file_path, contents).with_context(err_context)?;
+    fs::rename(&temp_file_path, file_path).with_context(err_context)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "./unit/plugin_store_tests.rs"]
+mod plugin_store_tests;
@@ -0,0 +1,115 @@
+use super::*;
+use std::time::Duration;
+
+fn responders(plugin_ids: &[PluginId]) -> HashSet<PluginId> {
+    plugin_ids.iter().copied().collect()
+}
+
+#[test]
+fn plugin_request_is_answered_once_by_one_of_its_responders() {
+    let mut pending_plugin_requests = PendingPluginRequests::default();
+    let requester_plugin_id = 1;
+    let requester_client_id = 10;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    pending_plugin_requests.add(
+        requester_plugin_id,
+        requester_client_id,
+        "request_1",
+        responders(&[2, 3]),
+        deadline,
+    );
+    assert!(pending_plugin_requests.is_pending(requester_plugin_id, "request_1"));
+    assert_eq!(
+        pending_plugin_requests.respond(requester_plugin_id, "request_1", 4),
+        None,
+        "plugin the request was not sent to cannot answer it"
+    );
+    assert_eq!(
+        pending_plugin_requests.respond(requester_plugin_id, "request_1", 3),
+        Some(requester_client_id)
+    );
+    assert!(!pending_plugin_requests.is_pending(requester_plugin_id, "request_1"));
+    assert_eq!(
+        pending_plugin_requests.respond(requester_plugin_id, "request_1", 2),
+        None,
+        "request was already answered"
+    );
+    assert_eq!(
+        pending_plugin_requests.time_out(requester_plugin_id, "request_1", deadline),
+        None,
+        "answered request does not time out"
+    );
+}
+
+#[test]
+fn plugin_request_times_out_only_after_its_deadline() {
+    let mut pending_plugin_requests = PendingPluginRequests::default();
+    let requester_plugin_id = 1;
+    let requester_client_id = 10;
+    let now = Instant::now();
+    let deadline = now + Duration::from_secs(10);
+    pending_plugin_requests.add(
+        requester_plugin_id,
+        requester_client_id,
+        "request_1",
+        responders(&[2]),
+        deadline,
+    );
+    assert_eq!(
+        pending_plugin_requests.time_out(requester_plugin_id, "request_1", now),
+        None
+    );
+    assert!(pending_plugin_requests.is_pending(requester_plugin_id, "request_1"));
+    assert_eq!(
+        pending_plugin_requests.time_out(requester_plugin_id, "request_1", deadline),
+        Some(requester_client_id)
+    );
+    assert!(!pending_plugin_requests.is_pending(requester_plugin_id, "request_1"));
+    assert_eq!(
+        pending_plugin_requests.respond(requester_plugin_id, "request_1", 2),
+        None,
+        "request that timed out cannot be answered"
+    );
+}
+
+#[test]
+fn unloading_plugins_cleans_up_their_plugin_requests() {
+    let mut pending_plugin_requests = PendingPluginRequests::default();
+    let requester_client_id = 10;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    pending_plugin_requests.add(
+        1,
+        requester_client_id,
+        "request_1",
+        responders(&[2]),
+        deadline,
+    );
+    pending_plugin_requests.add(
+        1,
+        requester_client_id,
+        "request_2",
+        responders(&[2, 3]),
+        deadline,
+    );
+    pending_plugin_requests.add(
+        2,
+        requester_client_id,
+        "request_3",
+        responders(&[3]),
+        deadline,
+    );
+
+    // request_1 can no longer be answered, request_2 can still be answered by plugin 3 and
+    // request_3 was sent by the unloaded plugin
+    assert_eq!(
+        pending_plugin_requests.unload_plugin(&2),
+        vec![(1, requester_client_id, "request_1".to_owned())]
+    );
+    assert!(!pending_plugin_requests.is_pending(1, "request_1"));
+    assert!(pending_plugin_requests.is_pending(1, "request_2"));
+    assert!(!pending_plugin_requests.is_pending(2, "request_3"));
+    assert_eq!(
+        pending_plugin_requests.respond(1, "request_2", 3),
+        Some(requester_client_id)
+    );
+}
@@ -0,0 +1,179 @@
+use super::*;
+use tempfile::tempdir;
+
+#[test]
+fn plugin_and_global_values_persist_across_stores() {
+    let store_dir = tempdir().unwrap();
+    let git_status = StoreNamespace::new(StoreScope::Plugin, "file:/plugins/git-status.wasm");
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(&git_status, "recent_files", Some("src/main.rs".to_owned()))
+        .unwrap();
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", Some("dracula".to_owned()))
+        .unwrap();
+    plugin_store
+        .set(&StoreNamespace::Session, "bookmarks", Some("1".to_owned()))
+        .unwrap();
+
+    // eg. a new session
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    assert_eq!(
+        plugin_store.get(&git_status, "recent_files").unwrap(),
+        Some("src/main.rs".to_owned())
+    );
+    assert_eq!(
+        plugin_store.get(&StoreNamespace::Global, "theme").unwrap(),
+        Some("dracula".to_owned())
+    );
+    assert_eq!(
+        plugin_store
+            .get(&StoreNamespace::Session, "bookmarks")
+            .unwrap(),
+        None,
+        "session values are not persisted"
+    );
+}
+
+#[test]
+fn plugin_scope_is_not_shared_between_plugins() {
+    let store_dir = tempdir().unwrap();
+    let git_status = StoreNamespace::new(StoreScope::Plugin, "file:/plugins/git-status.wasm");
+    let project_index = StoreNamespace::new(StoreScope::Plugin, "file:/plugins/project-index.wasm");
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(&git_status, "recent_files", Some("src/main.rs".to_owned()))
+        .unwrap();
+    assert_eq!(
+        plugin_store.get(&project_index, "recent_files").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn removing_a_key() {
+    let store_dir = tempdir().unwrap();
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", Some("dracula".to_owned()))
+        .unwrap();
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", None)
+        .unwrap();
+    assert_eq!(
+        plugin_store.get(&StoreNamespace::Global, "theme").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn watchers_are_returned_only_when_the_value_changes() {
+    let store_dir = tempdir().unwrap();
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store.watch(StoreNamespace::Session, "bookmarks".to_owned(), 1, 1);
+    plugin_store.watch(StoreNamespace::Session, "bookmarks".to_owned(), 2, 1);
+    plugin_store.watch(StoreNamespace::Session, "other_key".to_owned(), 3, 1);
+    let mut watchers = plugin_store
+        .set(&StoreNamespace::Session, "bookmarks", Some("1".to_owned()))
+        .unwrap();
+    watchers.sort();
+    assert_eq!(watchers, vec![(1, 1), (2, 1)]);
+    let watchers = plugin_store
+        .set(&StoreNamespace::Session, "bookmarks", Some("1".to_owned()))
+        .unwrap();
+    assert!(watchers.is_empty(), "value did not change");
+    plugin_store.unload_plugin(1);
+    let watchers = plugin_store
+        .set(&StoreNamespace::Session, "bookmarks", None)
+        .unwrap();
+    assert_eq!(watchers, vec![(2, 1)]);
+}
+
+#[test]
+fn changes_from_other_sessions_are_seen() {
+    let store_dir = tempdir().unwrap();
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let mut other_session_plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", Some("dracula".to_owned()))
+        .unwrap();
+    assert_eq!(
+        other_session_plugin_store
+            .get(&StoreNamespace::Global, "theme")
+            .unwrap(),
+        Some("dracula".to_owned())
+    );
+    other_session_plugin_store
+        .set(&StoreNamespace::Global, "font", Some("hack".to_owned()))
+        .unwrap();
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", Some("nord".to_owned()))
+        .unwrap();
+    assert_eq!(
+        other_session_plugin_store
+            .get(&StoreNamespace::Global, "theme")
+            .unwrap(),
+        Some("nord".to_owned())
+    );
+    assert_eq!(
+        plugin_store.get(&StoreNamespace::Global, "font").unwrap(),
+        Some("hack".to_owned()),
+        "value set by the other session was not overwritten"
+    );
+}
+
+#[test]
+fn namespace_size_is_limited() {
+    let store_dir = tempdir().unwrap();
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let half_of_the_limit = "a".repeat(MAX_NAMESPACE_SIZE / 2);
+    for namespace in [StoreNamespace::Global, StoreNamespace::Session] {
+        plugin_store
+            .set(&namespace, "first", Some(half_of_the_limit.clone()))
+            .unwrap();
+        assert!(plugin_store
+            .set(&namespace, "second", Some(half_of_the_limit.clone()))
+            .is_err());
+        assert_eq!(plugin_store.get(&namespace, "second").unwrap(), None);
+        // replacing a value only counts its new size
+        plugin_store
+            .set(&namespace, "first", Some(half_of_the_limit.clone()))
+            .unwrap();
+        plugin_store.set(&namespace, "first", None).unwrap();
+        plugin_store
+            .set(&namespace, "second", Some(half_of_the_limit.clone()))
+            .unwrap();
+    }
+}
+
+#[test]
+fn setting_a_value_does_not_lose_changes_made_within_the_same_modification_time() {
+    let store_dir = tempdir().unwrap();
+    let global_store_file = store_dir.path().join("global.json");
+    let mut plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let mut other_session_plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(&StoreNamespace::Global, "theme", Some("dracula".to_owned()))
+        .unwrap();
+    let modified = modification_time(&global_store_file).unwrap().unwrap();
+    other_session_plugin_store
+        .set(&StoreNamespace::Global, "font", Some("hack".to_owned()))
+        .unwrap();
+    // eg. a filesystem with a coarse modification time
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&global_store_file)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    plugin_store
+        .set(&StoreNamespace::Global, "font_size", Some("12".to_owned()))
+        .unwrap();
+    let mut new_session_plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    assert_eq!(
+        new_session_plugin_store
+            .get(&StoreNamespace::Global, "font")
+            .unwrap(),
+        Some("hack".to_owned())
+    );
+}
@@ -1,11 +1,12 @@
 use super::plugin_thread_main;
 use crate::screen::ScreenInstruction;
-use crate::{channels::SenderWithContext, thread_bus::Bus, ServerInstruction};
+use crate::{channels::SenderWithContext, thread_bus::Bus, ClientId, ServerInstruction};
 use insta::assert_snapshot;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tempfile::tempdir;
 use wasmer::Store;
+use zellij_utils::consts::ZELLIJ_SESSION_CACHE_DIR;
 use zellij_utils::data::{Event, Key, PermissionStatus, PermissionType, PluginCapabilities};
 use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation};
@@ -14,6 +15,7 @@ use zellij_utils::input::plugins::PluginsConfig;
 use zellij_utils::ipc::ClientAttributes;
 use zellij_utils::lazy_static::lazy_static;
 use zellij_utils::pane_size::Size;
+use zellij_utils::url::Url;
 
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
@@ -540,6 +542,13 @@ lazy_static! {
     );
 }
 
+fn plugin_data_dir(client_id: ClientId) -> PathBuf {
+    // the fixture plugin is the first plugin loaded in these tests, so its id is 0
+    ZELLIJ_SESSION_CACHE_DIR
+        .join(Url::from(&RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE))).to_string())
+        .join(format!("{}-{}", 0, client_id))
+}
+
 #[test]
 #[ignore]
 pub fn load_new_plugin_from_hd() {
@@ -556,6 +565,8 @@ pub fn load_new_plugin_from_hd() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -631,6 +642,8 @@ pub fn plugin_workers() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -710,6 +723,8 @@ pub fn plugin_workers_persist_state() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -799,6 +814,8 @@ pub fn can_subscribe_to_hd_events() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -876,6 +893,8 @@ pub fn switch_to_mode_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -947,6 +966,8 @@ pub fn switch_to_mode_plugin_command_permission_denied() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1018,6 +1039,8 @@ pub fn new_tabs_with_layout_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1103,6 +1126,8 @@ pub fn new_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1174,6 +1199,8 @@ pub fn go_to_next_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1244,6 +1271,8 @@ pub fn go_to_previous_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1314,6 +1343,8 @@ pub fn resize_focused_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1384,6 +1415,8 @@ pub fn resize_focused_pane_with_direction_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1454,6 +1487,8 @@ pub fn focus_next_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1524,6 +1559,8 @@ pub fn focus_previous_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1594,6 +1631,8 @@ pub fn move_focus_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1664,6 +1703,8 @@ pub fn move_focus_or_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1734,6 +1775,8 @@ pub fn edit_scrollback_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1804,6 +1847,8 @@ pub fn write_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1874,6 +1919,8 @@ pub fn write_chars_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -1944,6 +1991,8 @@ pub fn toggle_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2014,6 +2063,8 @@ pub fn move_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2084,6 +2135,8 @@ pub fn move_pane_with_direction_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2154,6 +2207,8 @@ pub fn clear_screen_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2225,6 +2280,8 @@ pub fn scroll_up_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2296,6 +2353,8 @@ pub fn scroll_down_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2366,6 +2425,8 @@ pub fn scroll_to_top_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2436,6 +2497,8 @@ pub fn scroll_to_bottom_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2506,6 +2569,8 @@ pub fn page_scroll_up_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2576,6 +2641,8 @@ pub fn page_scroll_down_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2646,6 +2713,8 @@ pub fn toggle_focus_fullscreen_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2716,6 +2785,8 @@ pub fn toggle_pane_frames_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2786,6 +2857,8 @@ pub fn toggle_pane_embed_or_eject_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2856,6 +2929,8 @@ pub fn undo_rename_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2926,6 +3001,8 @@ pub fn close_focus_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -2996,6 +3073,8 @@ pub fn toggle_active_tab_sync_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3066,6 +3145,8 @@ pub fn close_focused_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3136,6 +3217,8 @@ pub fn undo_rename_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3206,6 +3289,8 @@ pub fn previous_swap_layout_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3276,6 +3361,8 @@ pub fn next_swap_layout_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3346,6 +3433,8 @@ pub fn go_to_tab_name_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3416,6 +3505,8 @@ pub fn focus_or_create_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3486,6 +3577,8 @@ pub fn go_to_tab() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3556,6 +3649,8 @@ pub fn start_or_reload_plugin() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3626,6 +3721,8 @@ pub fn quit_zellij_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3703,6 +3800,8 @@ pub fn detach_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3780,6 +3879,8 @@ pub fn open_file_floating_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3857,6 +3958,8 @@ pub fn open_file_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -3934,6 +4037,8 @@ pub fn open_file_with_line_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4012,6 +4117,8 @@ pub fn open_file_with_line_floating_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4089,6 +4196,8 @@ pub fn open_terminal_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4166,6 +4275,8 @@ pub fn open_terminal_floating_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4243,6 +4354,8 @@ pub fn open_command_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4320,6 +4433,8 @@ pub fn open_command_pane_floating_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4397,6 +4512,8 @@ pub fn switch_to_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4467,6 +4584,8 @@ pub fn hide_self_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4537,6 +4656,8 @@ pub fn show_self_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4606,6 +4727,8 @@ pub fn close_terminal_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4676,6 +4799,8 @@ pub fn close_plugin_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4746,6 +4871,8 @@ pub fn focus_terminal_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4816,6 +4943,8 @@ pub fn focus_plugin_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4886,6 +5015,8 @@ pub fn rename_terminal_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -4956,6 +5087,8 @@ pub fn rename_plugin_pane_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5026,6 +5159,8 @@ pub fn rename_tab_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5105,6 +5240,8 @@ pub fn send_configuration_to_plugins() {
     );
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: PluginUserConfiguration::new(configuration),
     };
@@ -5176,6 +5313,8 @@ pub fn request_plugin_permissions() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5242,6 +5381,8 @@ pub fn granted_permission_request_result() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5332,6 +5473,8 @@ pub fn denied_permission_request_result() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5415,6 +5558,8 @@ pub fn run_command_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5492,6 +5637,8 @@ pub fn run_command_with_env_vars_and_cwd_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5569,6 +5716,8 @@ pub fn web_request_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5646,6 +5795,8 @@ pub fn unblock_input_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5727,6 +5878,8 @@ pub fn block_input_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5809,6 +5962,8 @@ pub fn pipe_output_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5898,6 +6053,8 @@ pub fn pipe_message_to_plugin_plugin_command() {
     let plugin_title = Some("test_plugin".to_owned());
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
     };
@@ -5970,3 +6127,349 @@ pub fn pipe_message_to_plugin_plugin_command() {
         });
     assert_snapshot!(format!("{:#?}", plugin_bytes_event));
 }
+
+#[test]
+#[ignore]
+pub fn send_plugin_request_plugin_command() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+    };
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::PluginBytes,
+        screen_receiver,
+        3,
+        &PermissionType::MessageAndLaunchOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        tab_index,
+        None,
+        client_id,
+        size,
+        None,
+        false,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "send_plugin_request".to_owned(),
+        // the plugin sends the request to itself
+        payload: Some(format!("file:{}", &*PLUGIN_FIXTURE)),
+        plugin: None, // broadcast
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_response = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::PluginBytes(plugin_render_assets) = i {
+                for plugin_render_asset in plugin_render_assets {
+                    let plugin_bytes = plugin_render_asset.bytes.clone();
+                    let plugin_bytes = String::from_utf8_lossy(plugin_bytes.as_slice()).to_string();
+                    if plugin_bytes.contains("Plugin response:") {
+                        return Some(plugin_bytes);
+                    }
+                }
+            }
+            None
+        });
+    assert!(
+        plugin_response
+            .unwrap()
+            .contains(r#"PluginResponse { request_id: "fixture_request", status: Ok, payload: Some("my_request_payload") }"#),
+        "requesting plugin received the response"
+    );
+}
+
+#[test]
+#[ignore]
+pub fn send_plugin_request_times_out_plugin_command() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+    };
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::PluginBytes,
+        screen_receiver,
+        3,
+        &PermissionType::MessageAndLaunchOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        tab_index,
+        None,
+        client_id,
+        size,
+        None,
+        false,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "send_unanswered_plugin_request".to_owned(),
+        // the plugin sends the request to itself
+        payload: Some(format!("file:{}", &*PLUGIN_FIXTURE)),
+        plugin: None, // broadcast
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_response = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::PluginBytes(plugin_render_assets) = i {
+                for plugin_render_asset in plugin_render_assets {
+                    let plugin_bytes = plugin_render_asset.bytes.clone();
+                    let plugin_bytes = String::from_utf8_lossy(plugin_bytes.as_slice()).to_string();
+                    if plugin_bytes.contains("Plugin response:") {
+                        return Some(plugin_bytes);
+                    }
+                }
+            }
+            None
+        });
+    assert!(
+        plugin_response.unwrap().contains(
+            r#"PluginResponse { request_id: "fixture_request", status: TimedOut, payload: None }"#
+        ),
+        "requesting plugin received the response"
+    );
+}
+
+#[test]
+#[ignore]
+pub fn watched_plugin_is_reloaded_when_its_file_changes() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        _watch: true,
+        _watch_clear_data: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+    };
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::RefreshPluginVisibility,
+        screen_receiver,
+        1,
+        &PermissionType::ChangeApplicationState,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        tab_index,
+        None,
+        client_id,
+        size,
+        None,
+        false,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let plugin_data_dir = plugin_data_dir(client_id);
+    std::fs::create_dir_all(&plugin_data_dir).unwrap();
+    std::fs::write(plugin_data_dir.join("kept_across_reloads"), "").unwrap();
+    // the path the filesystem watcher reports is not necessarily the one the plugin was loaded
+    // from (this one has a "..")
+    let _ = plugin_thread_sender.send(PluginInstruction::WatchedPluginFilesChanged(vec![
+        PathBuf::from(&*PLUGIN_FIXTURE),
+    ]));
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    teardown();
+    // once the plugin is reloaded, the screen sends it an Event::Visible
+    let refreshed_plugin_ids = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::RefreshPluginVisibility(plugin_ids) = i {
+                Some(plugin_ids.clone())
+            } else {
+                None
+            }
+        });
+    assert_eq!(refreshed_plugin_ids, Some(vec![0]), "plugin was reloaded");
+    assert!(
+        plugin_data_dir.join("kept_across_reloads").exists(),
+        "/data folder is kept across reloads"
+    );
+}
+
+#[test]
+#[ignore]
+pub fn watched_plugin_data_is_cleared_before_reload_with_clear_data() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        _watch: true,
+        _watch_clear_data: true,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+    };
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::RefreshPluginVisibility,
+        screen_receiver,
+        1,
+        &PermissionType::ChangeApplicationState,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        tab_index,
+        None,
+        client_id,
+        size,
+        None,
+        false,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let plugin_data_dir = plugin_data_dir(client_id);
+    std::fs::create_dir_all(&plugin_data_dir).unwrap();
+    std::fs::write(plugin_data_dir.join("cleared_before_reload"), "").unwrap();
+    // the path the filesystem watcher reports is not necessarily the one the plugin was loaded
+    // from (this one has a "..")
+    let _ = plugin_thread_sender.send(PluginInstruction::WatchedPluginFilesChanged(vec![
+        PathBuf::from(&*PLUGIN_FIXTURE),
+    ]));
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    teardown();
+    // once the plugin is reloaded, the screen sends it an Event::Visible
+    let refreshed_plugin_ids = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::RefreshPluginVisibility(plugin_ids) = i {
+                Some(plugin_ids.clone())
+            } else {
+                None
+            }
+        });
+    assert_eq!(refreshed_plugin_ids, Some(vec![0]), "plugin was reloaded");
+    assert!(
+        plugin_data_dir.exists(),
+        "/data folder is created again when the plugin is reloaded"
+    );
+    assert!(
+        !plugin_data_dir.join("cleared_before_reload").exists(),
+        "/data folder is emptied before the reload"
+    );
+}
@@ -1,11 +1,13 @@
 use super::{PluginId, PluginInstruction};
 use crate::plugins::pipes::{
-    apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PipeStateChange,
+    apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PendingPluginRequests,
+    PipeStateChange,
 };
 use crate::plugins::plugin_loader::PluginLoader;
 use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions};
+use crate::plugins::plugin_store::{PluginStore, StoreNamespace};
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::watch_filesystem::watch_filesystem;
+use crate::plugins::watch_filesystem::{watch_filesystem, watch_plugin_files};
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use highway::{HighwayHash, PortableHash};
 use log::info;
@@ -14,15 +16,21 @@ use std::{
     path::PathBuf,
     str::FromStr,
     sync::{Arc, Mutex},
+    time::Instant,
 };
 use wasmer::{Module, Store, Value};
 use zellij_utils::async_channel::Sender;
 use zellij_utils::async_std::task::{self, JoinHandle};
-use zellij_utils::consts::ZELLIJ_CACHE_DIR;
-use zellij_utils::data::{PermissionStatus, PermissionType, PipeMessage, PipeSource};
+use zellij_utils::consts::{ZELLIJ_CACHE_DIR, ZELLIJ_PLUGIN_STORE_DIR};
+use zellij_utils::data::{
+    PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginResponse, StoreScope,
+};
 use zellij_utils::downloader::Downloader;
 use zellij_utils::input::permission::PermissionCache;
-use zellij_utils::notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
+use zellij_utils::notify_debouncer_full::{
+    notify::{RecommendedWatcher, RecursiveMode, Watcher},
+    Debouncer, FileIdMap,
+};
 use zellij_utils::plugin_api::event::ProtobufEvent;
 
 use zellij_utils::prost::Message;
@@ -37,6 +45,7 @@ use zellij_utils::{
     errors::prelude::*,
     input::{
         command::TerminalAction,
+        keybinds::PluginKeybind,
         layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation},
         plugins::PluginsConfig,
     },
@@ -94,6 +103,8 @@ pub struct WasmBridge {
     pending_plugin_reloads: HashSet<RunPlugin>,
     path_to_default_shell: PathBuf,
     watcher: Option<Debouncer<RecommendedWatcher, FileIdMap>>,
+    plugin_file_watcher: Option<Debouncer<RecommendedWatcher, FileIdMap>>,
+    watched_plugins: HashMap<PathBuf, HashSet<RunPlugin>>, // wasm file => plugins loaded from it
     zellij_cwd: PathBuf,
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
@@ -102,6 +113,9 @@ pub struct WasmBridge {
     cached_plugin_map:
         HashMap<RunPluginLocation, HashMap<PluginUserConfiguration, Vec<(PluginId, ClientId)>>>,
     pending_pipes: PendingPipes,
+    pending_plugin_requests: PendingPluginRequests,
+    plugin_store: PluginStore,
+    plugin_keybinds: Vec<PluginKeybind>,
 }
 
 impl WasmBridge {
@@ -132,6 +146,8 @@ impl WasmBridge {
             plugin_map,
             path_to_default_shell,
             watcher,
+            plugin_file_watcher: None,
+            watched_plugins: HashMap::new(),
             next_plugin_id: 0,
             cached_events_for_pending_plugins: HashMap::new(),
             plugin_ids_waiting_for_permission_request: HashSet::new(),
@@ -146,6 +162,9 @@ impl WasmBridge {
             default_layout,
             cached_plugin_map: HashMap::new(),
             pending_pipes: Default::default(),
+            pending_plugin_requests: Default::default(),
+            plugin_store: PluginStore::new(ZELLIJ_PLUGIN_STORE_DIR.to_path_buf()),
+            plugin_keybinds: vec![],
         }
     }
     pub fn load_plugin(
@@ -182,6 +201,9 @@ impl WasmBridge {
             .with_context(|| format!("failed to resolve plugin {run:?}"))
             .with_context(err_context)?;
         let plugin_name = run.location.to_string();
+        if run._watch {
+            self.watch_plugin_file(run);
+        }
 
         self.cached_events_for_pending_plugins
             .insert(plugin_id, vec![]);
@@ -280,7 +302,9 @@ impl WasmBridge {
                 log::error!("Failed to remove cache dir for plugin: {:?}", e);
             }
         }
+        drop(plugin_map);
         self.cached_plugin_map.clear();
+        self.unwatch_stopped_plugins();
         let mut pipes_to_unblock = self.pending_pipes.unload_plugin(&pid);
         for pipe_name in pipes_to_unblock.drain(..) {
             let _ = self
@@ -288,8 +312,53 @@ impl WasmBridge {
                 .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_name))
                 .context("failed to unblock input pipe");
         }
+        let unanswerable_requests = self.pending_plugin_requests.unload_plugin(&pid);
+        if !unanswerable_requests.is_empty() {
+            let updates = unanswerable_requests
+                .into_iter()
+                .map(|(requester_plugin_id, requester_client_id, request_id)| {
+                    (
+                        Some(requester_plugin_id),
+                        Some(requester_client_id),
+                        Event::PluginResponse(PluginResponse::unavailable(request_id)),
+                    )
+                })
+                .collect();
+            let _ = self
+                .senders
+                .send_to_plugin(PluginInstruction::Update(updates))
+                .context("failed to respond to plugin requests");
+        }
+        self.plugin_store.unload_plugin(pid);
+        let keybind_count = self.plugin_keybinds.len();
+        self.plugin_keybinds.retain(|k| k.plugin_id != pid);
+        if self.plugin_keybinds.len() != keybind_count {
+            self.send_plugin_keybinds_to_clients(None);
+        }
         Ok(())
     }
+    pub fn register_keybind(&mut self, plugin_keybind: PluginKeybind) {
+        // every instance of the plugin (one per client) registers the same keys
+        self.plugin_keybinds.retain(|k| {
+            (k.plugin_id, k.mode, k.key)
+                != (
+                    plugin_keybind.plugin_id,
+                    plugin_keybind.mode,
+                    plugin_keybind.key,
+                )
+        });
+        self.plugin_keybinds.push(plugin_keybind);
+        self.send_plugin_keybinds_to_clients(None);
+    }
+    fn send_plugin_keybinds_to_clients(&self, client_id: Option<ClientId>) {
+        let _ = self
+            .senders
+            .send_to_server(ServerInstruction::PluginKeybinds(
+                self.plugin_keybinds.clone(),
+                client_id,
+            ))
+            .context("failed to send plugin keybinds to clients");
+    }
     pub fn reload_plugin(&mut self, run_plugin: &RunPlugin) -> Result<()> {
         if self.plugin_is_currently_being_loaded(&run_plugin.location) {
             self.pending_plugin_reloads.insert(run_plugin.clone());
@@ -342,6 +411,9 @@ impl WasmBridge {
                 ) {
                     Ok(_) => {
                         handle_plugin_successful_loading(&senders, first_plugin_id);
+                        let _ = senders.send_to_screen(ScreenInstruction::RefreshPluginVisibility(
+                            plugin_ids.clone(),
+                        ));
                         for plugin_id in &plugin_ids {
                             if plugin_id == &first_plugin_id {
                                 // no need to reload the plugin we just reloaded
@@ -419,6 +491,9 @@ impl WasmBridge {
                 let _ = self
                     .senders
                     .send_to_screen(ScreenInstruction::RequestStateUpdateForPlugins);
+                if !self.plugin_keybinds.is_empty() {
+                    self.send_plugin_keybinds_to_clients(Some(client_id));
+                }
                 Ok(())
             },
             Err(e) => Err(e),
@@ -1027,6 +1102,149 @@ impl WasmBridge {
             };
         }
     }
+    // a reloaded plugin keeps its id, and so also its /data folder - unless it was started with
+    // _watch_clear_data, in which case the folder is emptied before the reload
+    pub fn reload_watched_plugins(&mut self, changed_paths: Vec<PathBuf>) {
+        for changed_path in changed_paths {
+            // watched paths are canonical (see watch_plugin_file)
+            let changed_path = changed_path.canonicalize().unwrap_or(changed_path);
+            let run_plugins = match self.watched_plugins.get(&changed_path) {
+                Some(run_plugins) => run_plugins.clone(),
+                None => continue,
+            };
+            for run_plugin in run_plugins {
+                log::info!("{} changed, reloading plugin", changed_path.display());
+                if run_plugin._watch_clear_data {
+                    self.clear_plugin_data_dirs(&run_plugin);
+                }
+                if let Err(e) = self.reload_plugin(&run_plugin) {
+                    log::error!("Failed to reload plugin: {:?}", e);
+                }
+            }
+        }
+    }
+    fn clear_plugin_data_dirs(&mut self, run_plugin: &RunPlugin) {
+        let data_dirs: Vec<PathBuf> = self
+            .plugin_map
+            .lock()
+            .unwrap()
+            .running_plugins_and_subscriptions()
+            .iter()
+            .filter_map(|(_, _, running_plugin, _)| {
+                let running_plugin = running_plugin.lock().unwrap();
+                let plugin = &running_plugin.plugin_env.plugin;
+                if plugin.location == run_plugin.location
+                    && plugin.userspace_configuration == run_plugin.configuration
+                {
+                    Some(running_plugin.plugin_env.plugin_own_data_dir.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for data_dir in data_dirs {
+            // the folder is created again when the plugin is loaded
+            if let Err(e) = std::fs::remove_dir_all(&data_dir) {
+                log::error!(
+                    "Failed to clear data folder {} of plugin: {:?}",
+                    data_dir.display(),
+                    e
+                );
+            }
+        }
+    }
+    fn watch_plugin_file(&mut self, run_plugin: &RunPlugin) {
+        let wasm_path = match &run_plugin.location {
+            RunPluginLocation::File(path)
+                if !path.exists() && path.with_extension("wasm").exists() =>
+            {
+                path.with_extension("wasm")
+            },
+            RunPluginLocation::File(path) => path.clone(),
+            _ => {
+                log::warn!(
+                    "Only file: plugins can be watched, not watching {}",
+                    run_plugin.location
+                );
+                return;
+            },
+        };
+        // so that a relative path has a parent directory to watch and matches the absolute
+        // paths of the changes
+        let wasm_path = match wasm_path.canonicalize() {
+            Ok(wasm_path) => wasm_path,
+            Err(e) => {
+                log::error!("Failed to watch {}: {:?}", wasm_path.display(), e);
+                return;
+            },
+        };
+        if self.plugin_file_watcher.is_none() {
+            self.plugin_file_watcher = match watch_plugin_files(self.senders.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    log::error!("Failed to watch plugin files: {:?}", e);
+                    return;
+                },
+            };
+        }
+        let directory_is_watched = self
+            .watched_plugins
+            .keys()
+            .any(|watched_path| watched_path.parent() == wasm_path.parent());
+        if let (false, Some(directory), Some(plugin_file_watcher)) = (
+            directory_is_watched,
+            wasm_path.parent(),
+            self.plugin_file_watcher.as_mut(),
+        ) {
+            if let Err(e) = plugin_file_watcher
+                .watcher()
+                .watch(directory, RecursiveMode::NonRecursive)
+            {
+                log::error!("Failed to watch {}: {:?}", directory.display(), e);
+                return;
+            }
+        }
+        self.watched_plugins
+            .entry(wasm_path)
+            .or_default()
+            .insert(run_plugin.clone());
+    }
+    fn unwatch_stopped_plugins(&mut self) {
+        let mut unwatched_paths = vec![];
+        {
+            let plugin_map = self.plugin_map.lock().unwrap();
+            for (wasm_path, run_plugins) in self.watched_plugins.iter_mut() {
+                run_plugins.retain(|run_plugin| {
+                    plugin_map
+                        .all_plugin_ids_for_plugin_location(
+                            &run_plugin.location,
+                            &run_plugin.configuration,
+                        )
+                        .is_ok()
+                });
+                if run_plugins.is_empty() {
+                    unwatched_paths.push(wasm_path.clone());
+                }
+            }
+        }
+        for wasm_path in unwatched_paths {
+            self.unwatch_plugin_file(&wasm_path);
+        }
+    }
+    fn unwatch_plugin_file(&mut self, wasm_path: &PathBuf) {
+        self.watched_plugins.remove(wasm_path);
+        let directory_is_watched = self
+            .watched_plugins
+            .keys()
+            .any(|watched_path| watched_path.parent() == wasm_path.parent());
+        if let (false, Some(directory), Some(plugin_file_watcher)) = (
+            directory_is_watched,
+            wasm_path.parent(),
+            self.plugin_file_watcher.as_mut(),
+        ) {
+            let _ = plugin_file_watcher.watcher().unwatch(directory);
+        }
+    }
     pub fn cache_plugin_permissions(
         &mut self,
         plugin_id: PluginId,
@@ -1134,6 +1352,156 @@ impl WasmBridge {
             all_plugin_ids
         }
     }
+    pub fn plugin_request_is_pending(
+        &self,
+        requester_plugin_id: PluginId,
+        request_id: &str,
+    ) -> bool {
+        self.pending_plugin_requests
+            .is_pending(requester_plugin_id, request_id)
+    }
+    pub fn add_pending_plugin_request(
+        &mut self,
+        requester_plugin_id: PluginId,
+        requester_client_id: ClientId,
+        request_id: &str,
+        responders: HashSet<PluginId>,
+        deadline: Instant,
+    ) {
+        self.pending_plugin_requests.add(
+            requester_plugin_id,
+            requester_client_id,
+            request_id,
+            responders,
+            deadline,
+        );
+    }
+    pub fn respond_to_plugin_request(
+        &mut self,
+        requester_plugin_id: PluginId,
+        responder_plugin_id: PluginId,
+        response: PluginResponse,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        match self.pending_plugin_requests.respond(
+            requester_plugin_id,
+            &response.request_id,
+            responder_plugin_id,
+        ) {
+            Some(requester_client_id) => self.update_plugins(
+                vec![(
+                    Some(requester_plugin_id),
+                    Some(requester_client_id),
+                    Event::PluginResponse(response),
+                )],
+                shutdown_sender,
+            ),
+            None => {
+                log::warn!(
+                    "Plugin {} responded to request {} of plugin {} that is not pending",
+                    responder_plugin_id,
+                    response.request_id,
+                    requester_plugin_id
+                );
+                Ok(())
+            },
+        }
+    }
+    pub fn time_out_plugin_request(
+        &mut self,
+        requester_plugin_id: PluginId,
+        request_id: String,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        match self.pending_plugin_requests.time_out(
+            requester_plugin_id,
+            &request_id,
+            Instant::now(),
+        ) {
+            Some(requester_client_id) => self.update_plugins(
+                vec![(
+                    Some(requester_plugin_id),
+                    Some(requester_client_id),
+                    Event::PluginResponse(PluginResponse::timed_out(request_id)),
+                )],
+                shutdown_sender,
+            ),
+            None => Ok(()),
+        }
+    }
+    pub fn store_set(
+        &mut self,
+        plugin_id: PluginId,
+        scope: StoreScope,
+        key: String,
+        value: Option<String>,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set {:?} store key {} of plugin", scope, key);
+        let namespace = self
+            .store_namespace(plugin_id, scope)
+            .with_context(err_context)?;
+        let watchers = self
+            .plugin_store
+            .set(&namespace, &key, value.clone())
+            .with_context(err_context)?;
+        let updates = watchers
+            .into_iter()
+            .map(|(plugin_id, client_id)| {
+                (
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::StoreChanged(scope, key.clone(), value.clone()),
+                )
+            })
+            .collect();
+        self.update_plugins(updates, shutdown_sender)
+    }
+    pub fn store_get(
+        &mut self,
+        plugin_id: PluginId,
+        client_id: ClientId,
+        scope: StoreScope,
+        key: String,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to get {:?} store key {} of plugin", scope, key);
+        let namespace = self
+            .store_namespace(plugin_id, scope)
+            .with_context(err_context)?;
+        let value = self
+            .plugin_store
+            .get(&namespace, &key)
+            .with_context(err_context)?;
+        self.update_plugins(
+            vec![(
+                Some(plugin_id),
+                Some(client_id),
+                Event::StoreValue(namespace.scope(), key, value),
+            )],
+            shutdown_sender,
+        )
+    }
+    pub fn store_watch(
+        &mut self,
+        plugin_id: PluginId,
+        client_id: ClientId,
+        scope: StoreScope,
+        key: String,
+    ) {
+        match self.store_namespace(plugin_id, scope) {
+            Ok(namespace) => self
+                .plugin_store
+                .watch(namespace, key, plugin_id, client_id),
+            Err(e) => log::error!("Failed to watch store key: {:?}", e),
+        }
+    }
+    fn store_namespace(&self, plugin_id: PluginId, scope: StoreScope) -> Result<StoreNamespace> {
+        let run_plugin = self
+            .run_plugin_of_plugin_id(plugin_id)
+            .ok_or_else(|| anyhow!("plugin with id {} not found", plugin_id))?;
+        Ok(StoreNamespace::new(scope, &run_plugin.location.to_string()))
+    }
     pub fn clear_plugin_map_cache(&mut self) {
         self.cached_plugin_map.clear();
     }
@@ -1221,7 +1589,9 @@ fn check_event_permission(
         | Event::SessionUpdate(..)
         | Event::CopyToClipboard(..)
         | Event::SystemClipboardFailure
-        | Event::InputReceived => PermissionType::ReadApplicationState,
+        | Event::InputReceived
+        | Event::ActionJournal(..) => PermissionType::ReadApplicationState,
+        Event::PaneContents(..) | Event::PaneOutputMatched(..) => PermissionType::ReadPaneContents,
         _ => return (PermissionStatus::Granted, None),
     };
 
@@ -108,3 +108,35 @@ pub fn watch_filesystem(
         .watch(zellij_cwd, RecursiveMode::Recursive)?;
     Ok(debouncer)
 }
+
+// watches the wasm files of plugins loaded with `_watch`, the directories to watch are added by the
+// caller (we watch the parent directory rather than the file itself because build tools often
+// replace the file rather than writing to it)
+pub fn watch_plugin_files(
+    senders: ThreadSenders,
+) -> Result<Debouncer<RecommendedWatcher, FileIdMap>> {
+    let debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let mut changed_paths: Vec<PathBuf> = events
+                    .iter()
+                    .filter(|e| matches!(e.kind, EventKind::Create(_) | EventKind::Modify(_)))
+                    .flat_map(|e| e.paths.iter().cloned())
+                    .collect();
+                changed_paths.sort();
+                changed_paths.dedup();
+                if !changed_paths.is_empty() {
+                    let _ = senders.send_to_plugin(PluginInstruction::WatchedPluginFilesChanged(
+                        changed_paths,
+                    ));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("plugin watch error: {error:?}")),
+        },
+    )?;
+    Ok(debouncer)
+}
@@ -18,8 +18,9 @@ use std::{
 use wasmer::{imports, AsStoreMut, Function, FunctionEnv, FunctionEnvMut, Imports};
 use wasmer_wasi::WasiEnv;
 use zellij_utils::data::{
-    CommandType, ConnectToSession, HttpVerb, MessageToPlugin, PermissionStatus, PermissionType,
-    PluginPermission,
+    CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, MessageToPlugin,
+    PaneId as ZellijUtilsPaneId, PermissionStatus, PermissionType, PluginPermission, PluginRequest,
+    PluginResponse, StoreScope, TabIndexOrName,
 };
 use zellij_utils::input::permission::PermissionCache;
 
@@ -30,13 +31,14 @@ use crate::{panes::PaneId, screen::ScreenInstruction};
 use zellij_utils::{
     consts::{VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR},
     data::{
-        CommandToRun, Direction, Event, EventType, FileToOpen, InputMode, PluginCommand, PluginIds,
-        PluginMessage, Resize, ResizeStrategy,
+        CommandToRun, Direction, Event, EventType, FileToOpen, InputMode, Key, PluginCommand,
+        PluginIds, PluginMessage, Resize, ResizeStrategy,
     },
     errors::prelude::*,
     input::{
         actions::Action,
         command::{RunCommand, RunCommandAction, TerminalAction},
+        keybinds::PluginKeybind,
         layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation},
         plugins::PluginType,
     },
@@ -113,18 +115,18 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                     PluginCommand::GetPluginIds => get_plugin_ids(env),
                     PluginCommand::GetZellijVersion => get_zellij_version(env),
                     PluginCommand::OpenFile(file_to_open) => open_file(env, file_to_open),
-                    PluginCommand::OpenFileFloating(file_to_open) => {
-                        open_file_floating(env, file_to_open)
+                    PluginCommand::OpenFileFloating(file_to_open, coordinates) => {
+                        open_file_floating(env, file_to_open, coordinates)
                     },
                     PluginCommand::OpenTerminal(cwd) => open_terminal(env, cwd.path.try_into()?),
-                    PluginCommand::OpenTerminalFloating(cwd) => {
-                        open_terminal_floating(env, cwd.path.try_into()?)
+                    PluginCommand::OpenTerminalFloating(cwd, coordinates) => {
+                        open_terminal_floating(env, cwd.path.try_into()?, coordinates)
                     },
                     PluginCommand::OpenCommandPane(command_to_run) => {
                         open_command_pane(env, command_to_run)
                     },
-                    PluginCommand::OpenCommandPaneFloating(command_to_run) => {
-                        open_command_pane_floating(env, command_to_run)
+                    PluginCommand::OpenCommandPaneFloating(command_to_run, coordinates) => {
+                        open_command_pane_floating(env, command_to_run, coordinates)
                     },
                     PluginCommand::SwitchTabTo(tab_index) => switch_tab_to(env, tab_index),
                     PluginCommand::SetTimeout(seconds) => set_timeout(env, seconds),
@@ -192,6 +194,18 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                     PluginCommand::GoToTabName(tab_name) => go_to_tab_name(env, tab_name),
                     PluginCommand::FocusOrCreateTab(tab_name) => focus_or_create_tab(env, tab_name),
                     PluginCommand::GoToTab(tab_index) => go_to_tab(env, tab_index),
+                    PluginCommand::MoveTab(direction) => move_tab(env, direction),
+                    PluginCommand::MoveTabToIndex(tab_index) => move_tab_to_index(env, tab_index),
+                    PluginCommand::SwapPanes(first_pane_id, second_pane_id) => {
+                        swap_panes(env, first_pane_id, second_pane_id)
+                    },
+                    PluginCommand::SwapPaneWith(direction) => swap_pane_with(env, direction),
+                    PluginCommand::ChangeFloatingPaneCoordinates(pane_id, coordinates) => {
+                        change_floating_pane_coordinates(env, pane_id, coordinates)
+                    },
+                    PluginCommand::SetPanePinned(pane_id, should_be_pinned) => {
+                        set_pane_pinned(env, pane_id, should_be_pinned)
+                    },
                     PluginCommand::StartOrReloadPlugin(plugin_url) => {
                         start_or_reload_plugin(env, &plugin_url)?
                     },
@@ -252,6 +266,33 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                         cli_pipe_output(env, pipe_name, output)?
                     },
                     PluginCommand::MessageToPlugin(message) => message_to_plugin(env, message)?,
+                    PluginCommand::RegisterKeybind(mode, key, intercept) => {
+                        register_keybind(env, mode, key, intercept)?
+                    },
+                    PluginCommand::GetPaneScrollback(pane_id, scrollback_lines) => {
+                        get_pane_scrollback(env, pane_id.into(), scrollback_lines)?
+                    },
+                    PluginCommand::GetActionJournal => get_action_journal(env)?,
+                    PluginCommand::SendPluginRequest(plugin_request) => {
+                        send_plugin_request(env, plugin_request)?
+                    },
+                    PluginCommand::ReplyToPluginRequest(requester_plugin_id, response) => {
+                        reply_to_plugin_request(env, requester_plugin_id, response)?
+                    },
+                    PluginCommand::StoreSet(scope, key, value) => {
+                        store_set(env, scope, key, value)?
+                    },
+                    PluginCommand::StoreGet(scope, key) => store_get(env, scope, key)?,
+                    PluginCommand::StoreWatch(scope, key) => store_watch(env, scope, key)?,
+                    PluginCommand::PipePaneToFile(terminal_pane_id, path, strip_ansi) => {
+                        pipe_pane_to_file(env, terminal_pane_id, path, strip_ansi)
+                    },
+                    PluginCommand::StopPipePaneToFile(terminal_pane_id) => {
+                        stop_pipe_pane_to_file(env, terminal_pane_id)
+                    },
+                    PluginCommand::MovePaneToTab(pane_id, tab, should_float) => {
+                        move_pane_to_tab(env, pane_id.into(), tab, should_float)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -317,6 +358,116 @@ fn message_to_plugin(env: &ForeignFunctionEnv, message_to_plugin: MessageToPlugi
         .context("failed to send message to plugin")
 }
 
+fn register_keybind(
+    env: &ForeignFunctionEnv,
+    mode: InputMode,
+    key: Key,
+    intercept: bool,
+) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::RegisterKeybind(PluginKeybind {
+            plugin_id: env.plugin_env.plugin_id,
+            mode,
+            key,
+            intercept,
+        }))
+        .context("failed to register keybind")
+}
+
+fn get_pane_scrollback(
+    env: &ForeignFunctionEnv,
+    pane_id: PaneId,
+    scrollback_lines: usize,
+) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::GetPaneScrollback(
+            pane_id,
+            scrollback_lines,
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+        ))
+        .context("failed to request pane scrollback")
+}
+
+fn get_action_journal(env: &ForeignFunctionEnv) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::GetActionJournal(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+        ))
+        .context("failed to request action journal")
+}
+
+fn send_plugin_request(env: &ForeignFunctionEnv, plugin_request: PluginRequest) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::PluginRequest {
+            requester_plugin_id: env.plugin_env.plugin_id,
+            requester_client_id: env.plugin_env.client_id,
+            request: plugin_request,
+        })
+        .context("failed to send plugin request")
+}
+
+fn reply_to_plugin_request(
+    env: &ForeignFunctionEnv,
+    requester_plugin_id: u32,
+    response: PluginResponse,
+) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::PluginResponse {
+            requester_plugin_id,
+            responder_plugin_id: env.plugin_env.plugin_id,
+            response,
+        })
+        .context("failed to reply to plugin request")
+}
+
+fn store_set(
+    env: &ForeignFunctionEnv,
+    scope: StoreScope,
+    key: String,
+    value: Option<String>,
+) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::StoreSet(
+            env.plugin_env.plugin_id,
+            scope,
+            key,
+            value,
+        ))
+        .context("failed to set store key")
+}
+
+fn store_get(env: &ForeignFunctionEnv, scope: StoreScope, key: String) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::StoreGet(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            scope,
+            key,
+        ))
+        .context("failed to get store key")
+}
+
+fn store_watch(env: &ForeignFunctionEnv, scope: StoreScope, key: String) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::StoreWatch(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            scope,
+            key,
+        ))
+        .context("failed to watch store key")
+}
+
 fn unsubscribe(env: &ForeignFunctionEnv, event_list: HashSet<EventType>) -> Result<()> {
     env.subscriptions
         .lock()
@@ -439,11 +590,16 @@ fn open_file(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
         None,
         floating,
         in_place,
+        None,
     );
     apply_action!(action, error_msg, env);
 }
 
-fn open_file_floating(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
+fn open_file_floating(
+    env: &ForeignFunctionEnv,
+    file_to_open: FileToOpen,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
     let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
     let floating = true;
     let in_place = false;
@@ -458,6 +614,7 @@ fn open_file_floating(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
         None,
         floating,
         in_place,
+        coordinates,
     );
     apply_action!(action, error_msg, env);
 }
@@ -477,6 +634,7 @@ fn open_file_in_place(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
         None,
         floating,
         in_place,
+        None,
     );
     apply_action!(action, error_msg, env);
 }
@@ -498,7 +656,11 @@ fn open_terminal(env: &ForeignFunctionEnv, cwd: PathBuf) {
     apply_action!(action, error_msg, env);
 }
 
-fn open_terminal_floating(env: &ForeignFunctionEnv, cwd: PathBuf) {
+fn open_terminal_floating(
+    env: &ForeignFunctionEnv,
+    cwd: PathBuf,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
     let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
     let cwd = env.plugin_env.plugin_cwd.join(cwd);
     let mut default_shell = env
@@ -511,7 +673,7 @@ fn open_terminal_floating(env: &ForeignFunctionEnv, cwd: PathBuf) {
         TerminalAction::RunCommand(run_command) => Some(run_command.into()),
         _ => None,
     };
-    let action = Action::NewFloatingPane(run_command_action, None);
+    let action = Action::NewFloatingPane(run_command_action, None, coordinates);
     apply_action!(action, error_msg, env);
 }
 
@@ -550,12 +712,17 @@ fn open_command_pane(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
         direction,
         hold_on_close,
         hold_on_start,
+        env: command_to_run.env,
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
 }
 
-fn open_command_pane_floating(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
+fn open_command_pane_floating(
+    env: &ForeignFunctionEnv,
+    command_to_run: CommandToRun,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
     let error_msg = || format!("failed to open command in plugin {}", env.plugin_env.name());
     let command = command_to_run.path;
     let cwd = command_to_run
@@ -573,8 +740,9 @@ fn open_command_pane_floating(env: &ForeignFunctionEnv, command_to_run: CommandT
         direction,
         hold_on_close,
         hold_on_start,
+        env: command_to_run.env,
     };
-    let action = Action::NewFloatingPane(Some(run_command_action), name);
+    let action = Action::NewFloatingPane(Some(run_command_action), name, coordinates);
     apply_action!(action, error_msg, env);
 }
 
@@ -596,6 +764,7 @@ fn open_command_pane_in_place(env: &ForeignFunctionEnv, command_to_run: CommandT
         direction,
         hold_on_close,
         hold_on_start,
+        env: command_to_run.env,
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1189,6 +1358,60 @@ fn go_to_tab(env: &ForeignFunctionEnv, tab_index: u32) {
     apply_action!(action, error_msg, env);
 }
 
+fn move_tab(env: &ForeignFunctionEnv, direction: Direction) {
+    let error_msg = || format!("failed to move tab in plugin {}", env.plugin_env.name());
+    let action = Action::MoveTab(direction);
+    apply_action!(action, error_msg, env);
+}
+
+fn move_tab_to_index(env: &ForeignFunctionEnv, tab_index: u32) {
+    let error_msg = || format!("failed to move tab in plugin {}", env.plugin_env.name());
+    let action = Action::MoveTabToIndex(tab_index as usize);
+    apply_action!(action, error_msg, env);
+}
+
+fn swap_panes(
+    env: &ForeignFunctionEnv,
+    first_pane_id: ZellijUtilsPaneId,
+    second_pane_id: ZellijUtilsPaneId,
+) {
+    let error_msg = || format!("failed to swap panes in plugin {}", env.plugin_env.name());
+    let action = Action::SwapPanes(first_pane_id, second_pane_id);
+    apply_action!(action, error_msg, env);
+}
+
+fn swap_pane_with(env: &ForeignFunctionEnv, direction: Direction) {
+    let error_msg = || format!("failed to swap panes in plugin {}", env.plugin_env.name());
+    let action = Action::SwapPaneWith(direction);
+    apply_action!(action, error_msg, env);
+}
+
+fn change_floating_pane_coordinates(
+    env: &ForeignFunctionEnv,
+    pane_id: ZellijUtilsPaneId,
+    coordinates: FloatingPaneCoordinates,
+) {
+    let error_msg = || {
+        format!(
+            "failed to change floating pane coordinates in plugin {}",
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::ChangeFloatingPaneCoordinates(pane_id, coordinates);
+    apply_action!(action, error_msg, env);
+}
+
+fn set_pane_pinned(env: &ForeignFunctionEnv, pane_id: ZellijUtilsPaneId, should_be_pinned: bool) {
+    let error_msg = || {
+        format!(
+            "failed to set pane pinned in plugin {}",
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::SetPanePinned(pane_id, should_be_pinned);
+    apply_action!(action, error_msg, env);
+}
+
 fn start_or_reload_plugin(env: &ForeignFunctionEnv, url: &str) -> Result<()> {
     let error_msg = || {
         format!(
@@ -1203,6 +1426,8 @@ fn start_or_reload_plugin(env: &ForeignFunctionEnv, url: &str) -> Result<()> {
     let run_plugin = RunPlugin {
         location: run_plugin_location,
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         configuration: PluginUserConfiguration::new(BTreeMap::new()), // TODO: allow passing configuration
     };
     let action = Action::StartOrReloadPlugin(run_plugin);
@@ -1255,6 +1480,35 @@ fn rename_terminal_pane(env: &ForeignFunctionEnv, terminal_pane_id: u32, new_nam
     apply_action!(rename_pane_action, error_msg, env);
 }
 
+fn pipe_pane_to_file(
+    env: &ForeignFunctionEnv,
+    terminal_pane_id: u32,
+    path: PathBuf,
+    strip_ansi: bool,
+) {
+    let error_msg = || "Failed to pipe pane to file".to_string();
+    let path = env.plugin_env.plugin_cwd.join(path);
+    let pipe_pane_to_file_action = Action::PipePaneToFile(terminal_pane_id, path, strip_ansi);
+    apply_action!(pipe_pane_to_file_action, error_msg, env);
+}
+
+fn stop_pipe_pane_to_file(env: &ForeignFunctionEnv, terminal_pane_id: u32) {
+    let error_msg = || "Failed to stop piping pane to file".to_string();
+    let stop_pipe_pane_to_file_action = Action::StopPipePaneToFile(terminal_pane_id);
+    apply_action!(stop_pipe_pane_to_file_action, error_msg, env);
+}
+
+fn move_pane_to_tab(
+    env: &ForeignFunctionEnv,
+    pane_id: PaneId,
+    tab: TabIndexOrName,
+    should_float: bool,
+) {
+    let error_msg = || "Failed to move pane to tab".to_string();
+    let move_pane_to_tab_action = Action::MovePaneToTab(pane_id.into(), tab, should_float);
+    apply_action!(move_pane_to_tab_action, error_msg, env);
+}
+
 fn rename_plugin_pane(env: &ForeignFunctionEnv, plugin_pane_id: u32, new_name: &str) {
     let error_msg = || format!("Failed to rename plugin pane");
     let rename_pane_action = Action::RenamePluginPane(plugin_pane_id, new_name.as_bytes().to_vec());
@@ -1396,6 +1650,12 @@ fn check_command_permission(
         | PluginCommand::GoToTabName(..)
         | PluginCommand::FocusOrCreateTab(..)
         | PluginCommand::GoToTab(..)
+        | PluginCommand::MoveTab(..)
+        | PluginCommand::MoveTabToIndex(..)
+        | PluginCommand::SwapPanes(..)
+        | PluginCommand::SwapPaneWith(..)
+        | PluginCommand::ChangeFloatingPaneCoordinates(..)
+        | PluginCommand::SetPanePinned(..)
         | PluginCommand::CloseTerminalPane(..)
         | PluginCommand::ClosePluginPane(..)
         | PluginCommand::FocusTerminalPane(..)
@@ -1406,15 +1666,43 @@ fn check_command_permission(
         | PluginCommand::DeleteDeadSession(..)
         | PluginCommand::DeleteAllDeadSessions
         | PluginCommand::RenameSession(..)
-        | PluginCommand::RenameTab(..) => PermissionType::ChangeApplicationState,
+        | PluginCommand::RenameTab(..)
+        | PluginCommand::MovePaneToTab(..) => PermissionType::ChangeApplicationState,
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
-        PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
+        PluginCommand::MessageToPlugin(..) | PluginCommand::SendPluginRequest(..) => {
+            PermissionType::MessageAndLaunchOtherPlugins
+        },
+        PluginCommand::GetPaneScrollback(..) => PermissionType::ReadPaneContents,
+        PluginCommand::PipePaneToFile(..) | PluginCommand::StopPipePaneToFile(..) => {
+            PermissionType::WriteToFiles
+        },
+        PluginCommand::RegisterKeybind(..) => PermissionType::InterceptKeys,
+        PluginCommand::GetActionJournal => PermissionType::ReadApplicationState,
+        // a plugin can always use its own store, like its own data folder
+        PluginCommand::StoreSet(StoreScope::Session | StoreScope::Global, ..)
+        | PluginCommand::StoreGet(StoreScope::Session | StoreScope::Global, ..)
+        | PluginCommand::StoreWatch(StoreScope::Session | StoreScope::Global, ..) => {
+            PermissionType::ShareDataWithOtherPlugins
+        },
         _ => return (PermissionStatus::Granted, None),
     };
+    // piping a pane to a file writes its contents to disk, so reading them is also required
+    let additional_permission = match command {
+        PluginCommand::PipePaneToFile(..) | PluginCommand::StopPipePaneToFile(..) => {
+            Some(PermissionType::ReadPaneContents)
+        },
+        _ => None,
+    };
 
     if let Some(permissions) = plugin_env.permissions.lock().unwrap().as_ref() {
+        match additional_permission {
+            Some(additional_permission) if !permissions.contains(&additional_permission) => {
+                return (PermissionStatus::Denied, Some(additional_permission));
+            },
+            _ => {},
+        }
         if permissions.contains(&permission) {
             return (PermissionStatus::Granted, None);
         }
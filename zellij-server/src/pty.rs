@@ -1,5 +1,5 @@
 use crate::background_jobs::BackgroundJob;
-use crate::terminal_bytes::TerminalBytes;
+use crate::terminal_bytes::{PaneOutputLog, PaneOutputLogs, TerminalBytes, TriggerMatcher};
 use crate::{
     panes::PaneId,
     plugins::PluginInstruction,
@@ -9,10 +9,15 @@ use crate::{
     ClientId, ServerInstruction,
 };
 use async_std::task::{self, JoinHandle};
-use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    os::unix::io::RawFd,
+    path::PathBuf,
+};
 use zellij_utils::nix::unistd::Pid;
 use zellij_utils::{
     async_std,
+    data::FloatingPaneCoordinates,
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
@@ -21,6 +26,7 @@ use zellij_utils::{
             FloatingPaneLayout, Layout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation,
             TiledPaneLayout,
         },
+        triggers::Triggers,
     },
     pane_size::Size,
     session_serialization,
@@ -43,6 +49,7 @@ pub enum PtyInstruction {
         Option<TerminalAction>,
         Option<bool>,
         Option<String>,
+        Option<FloatingPaneCoordinates>,
         ClientTabIndexOrPaneId,
     ), // bool (if Some) is
     // should_float, String is an optional pane name
@@ -90,6 +97,9 @@ pub enum PtyInstruction {
         Size,
         bool, // skip cache
     ),
+    ReportTerminalCwdsAndCommands(Vec<u32>), // terminal ids
+    PipePaneToFile(u32, PathBuf, bool, ClientId), // u32 is the terminal id, bool is strip_ansi
+    StopPipePaneToFile(u32, ClientId),       // u32 is the terminal id
     Exit,
 }
 
@@ -111,6 +121,11 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::DumpLayout(..) => PtyContext::DumpLayout,
             PtyInstruction::LogLayoutToHd(..) => PtyContext::LogLayoutToHd,
             PtyInstruction::FillPluginCwd(..) => PtyContext::FillPluginCwd,
+            PtyInstruction::ReportTerminalCwdsAndCommands(..) => {
+                PtyContext::ReportTerminalCwdsAndCommands
+            },
+            PtyInstruction::PipePaneToFile(..) => PtyContext::PipePaneToFile,
+            PtyInstruction::StopPipePaneToFile(..) => PtyContext::StopPipePaneToFile,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -123,6 +138,10 @@ pub(crate) struct Pty {
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
     default_editor: Option<PathBuf>,
+    // terminal_id => env variables it was spawned with (for serialization)
+    id_to_env: HashMap<u32, BTreeMap<String, String>>,
+    trigger_matcher: TriggerMatcher, // cloned into the TerminalBytes of each new terminal
+    pane_output_logs: PaneOutputLogs,
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -134,6 +153,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                 terminal_action,
                 should_float,
                 name,
+                floating_pane_coordinates,
                 client_or_tab_index,
             ) => {
                 let err_context =
@@ -175,6 +195,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                 should_float,
                                 hold_for_command,
                                 invoked_with,
+                                floating_pane_coordinates,
                                 client_or_tab_index,
                             ))
                             .with_context(err_context)?;
@@ -191,6 +212,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                         should_float,
                                         hold_for_command,
                                         invoked_with,
+                                        floating_pane_coordinates,
                                         client_or_tab_index,
                                     ))
                                     .with_context(err_context)?;
@@ -668,6 +690,41 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     skip_cache,
                 )?;
             },
+            PtyInstruction::ReportTerminalCwdsAndCommands(terminal_ids) => {
+                let err_context = || "Failed to report terminal cwds and commands";
+                let (terminal_ids_to_cwds, terminal_ids_to_commands) =
+                    pty.get_terminal_cwds_and_commands(terminal_ids.clone());
+                let terminal_ids_to_running_commands = terminal_ids_to_commands
+                    .into_iter()
+                    .map(|(terminal_id, command)| (terminal_id, command.join(" ")))
+                    .collect();
+                pty.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::UpdateTerminalCwdsAndCommands(
+                        terminal_ids,
+                        terminal_ids_to_cwds,
+                        terminal_ids_to_running_commands,
+                    ))
+                    .with_context(err_context)?;
+            },
+            PtyInstruction::PipePaneToFile(terminal_id, path, strip_ansi, client_id) => {
+                pty.pipe_pane_to_file(terminal_id, path, strip_ansi, client_id)
+                    .and_then(|_| {
+                        pty.bus
+                            .senders
+                            .send_to_server(ServerInstruction::UnblockInputThread)
+                    })
+                    .with_context(|| format!("failed to pipe pane {terminal_id} to file"))?;
+            },
+            PtyInstruction::StopPipePaneToFile(terminal_id, client_id) => {
+                pty.stop_pipe_pane_to_file(terminal_id, client_id)
+                    .and_then(|_| {
+                        pty.bus
+                            .senders
+                            .send_to_server(ServerInstruction::UnblockInputThread)
+                    })
+                    .with_context(|| format!("failed to stop piping pane {terminal_id} to file"))?;
+            },
             PtyInstruction::Exit => break,
         }
     }
@@ -679,6 +736,7 @@ impl Pty {
         bus: Bus<PtyInstruction>,
         debug_to_file: bool,
         default_editor: Option<PathBuf>,
+        triggers: Triggers,
     ) -> Self {
         Pty {
             active_panes: HashMap::new(),
@@ -687,6 +745,9 @@ impl Pty {
             debug_to_file,
             task_handles: HashMap::new(),
             default_editor,
+            id_to_env: HashMap::new(),
+            trigger_matcher: TriggerMatcher::new(&triggers),
+            pane_output_logs: PaneOutputLogs::new(),
         }
     }
     pub fn get_default_terminal(
@@ -723,10 +784,18 @@ impl Pty {
                     cwd, // note: this might also be filled by the calling function, eg. spawn_terminal
                     hold_on_close: false,
                     hold_on_start: false,
+                    env: BTreeMap::new(),
                 })
             },
         }
     }
+    fn remember_env(&mut self, terminal_id: u32, terminal_action: &TerminalAction) {
+        if let TerminalAction::RunCommand(run_command) = terminal_action {
+            if !run_command.env.is_empty() {
+                self.id_to_env.insert(terminal_id, run_command.env.clone());
+            }
+        }
+    }
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
@@ -803,6 +872,7 @@ impl Pty {
                 .context("couldn't get mutable reference to OS interface")
                 .and_then(|os_input| os_input.reserve_terminal_id())
                 .with_context(err_context)?;
+            self.remember_env(terminal_id, &terminal_action);
             return Ok((terminal_id, starts_held));
         }
 
@@ -828,9 +898,14 @@ impl Pty {
             .as_mut()
             .context("no OS I/O interface found")
             .and_then(|os_input| {
-                os_input.spawn_terminal(terminal_action, quit_cb, self.default_editor.clone())
+                os_input.spawn_terminal(
+                    terminal_action.clone(),
+                    quit_cb,
+                    self.default_editor.clone(),
+                )
             })
             .with_context(err_context)?;
+        self.remember_env(terminal_id, &terminal_action);
         let terminal_bytes = task::spawn({
             let err_context =
                 |terminal_id: u32| format!("failed to run async task for terminal {terminal_id}");
@@ -843,12 +918,22 @@ impl Pty {
                 .fatal()
                 .clone();
             let debug_to_file = self.debug_to_file;
+            let trigger_matcher = self.trigger_matcher.clone();
+            let pane_output_logs = self.pane_output_logs.clone();
             async move {
-                TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, terminal_id)
-                    .listen()
-                    .await
-                    .with_context(|| err_context(terminal_id))
-                    .fatal();
+                TerminalBytes::new(
+                    pid_primary,
+                    senders,
+                    os_input,
+                    debug_to_file,
+                    terminal_id,
+                    trigger_matcher,
+                    pane_output_logs,
+                )
+                .listen()
+                .await
+                .with_context(|| err_context(terminal_id))
+                .fatal();
             }
         });
 
@@ -872,11 +957,11 @@ impl Pty {
         let mut default_shell =
             default_shell.unwrap_or_else(|| self.get_default_terminal(cwd, None));
         self.fill_cwd(&mut default_shell, client_id);
-        let extracted_run_instructions = layout.extract_run_instructions();
+        let extracted_run_instructions = layout.extract_run_instructions_with_env();
         let extracted_floating_run_instructions = floating_panes_layout
             .iter()
             .filter(|f| !f.already_running)
-            .map(|f| f.run.clone());
+            .map(|f| (f.run.clone(), f.env.clone()));
         let mut new_pane_pids: Vec<(u32, bool, Option<RunCommand>, Result<RawFd>)> = vec![]; // (terminal_id,
                                                                                              // starts_held,
                                                                                              // run_command,
@@ -885,16 +970,16 @@ impl Pty {
             vec![]; // same
                     // as
                     // new_pane_pids
-        for run_instruction in extracted_run_instructions {
+        for (run_instruction, env) in extracted_run_instructions {
             if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone(), tab_index)?
+                self.apply_run_instruction(run_instruction, env, default_shell.clone(), tab_index)?
             {
                 new_pane_pids.push(new_pane_data);
             }
         }
-        for run_instruction in extracted_floating_run_instructions {
+        for (run_instruction, env) in extracted_floating_run_instructions {
             if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone(), tab_index)?
+                self.apply_run_instruction(run_instruction, env, default_shell.clone(), tab_index)?
             {
                 new_floating_panes_pids.push(new_pane_data);
             }
@@ -951,6 +1036,8 @@ impl Pty {
                             .with_context(err_context)?
                             .clone();
                         let debug_to_file = self.debug_to_file;
+                        let trigger_matcher = self.trigger_matcher.clone();
+                        let pane_output_logs = self.pane_output_logs.clone();
                         async move {
                             TerminalBytes::new(
                                 pid_primary,
@@ -958,6 +1045,8 @@ impl Pty {
                                 os_input,
                                 debug_to_file,
                                 terminal_id,
+                                trigger_matcher,
+                                pane_output_logs,
                             )
                             .listen()
                             .await
@@ -994,6 +1083,7 @@ impl Pty {
     fn apply_run_instruction(
         &mut self,
         run_instruction: Option<Run>,
+        env: BTreeMap<String, String>,
         default_shell: TerminalAction,
         tab_index: usize,
     ) -> Result<Option<(u32, bool, Option<RunCommand>, Result<i32>)>> {
@@ -1034,6 +1124,7 @@ impl Pty {
                         command.cwd = cmd.cwd;
                     }
                 }
+                command.env.extend(env);
                 let cmd = TerminalAction::RunCommand(command.clone());
                 if starts_held {
                     // we don't actually open a terminal in this case, just wait for the user to run it
@@ -1046,6 +1137,7 @@ impl Pty {
                         .reserve_terminal_id()
                     {
                         Ok(terminal_id) => {
+                            self.remember_env(terminal_id, &cmd);
                             Ok(Some((
                                 terminal_id,
                                 starts_held,
@@ -1063,11 +1155,12 @@ impl Pty {
                         .as_mut()
                         .context("no OS I/O interface found")
                         .with_context(err_context)?
-                        .spawn_terminal(cmd, quit_cb, self.default_editor.clone())
+                        .spawn_terminal(cmd.clone(), quit_cb, self.default_editor.clone())
                         .with_context(err_context)
                     {
                         Ok((terminal_id, pid_primary, child_fd)) => {
                             self.id_to_child_pid.insert(terminal_id, child_fd);
+                            self.remember_env(terminal_id, &cmd);
                             Ok(Some((
                                 terminal_id,
                                 starts_held,
@@ -1088,18 +1181,20 @@ impl Pty {
             },
             Some(Run::Cwd(cwd)) => {
                 let starts_held = false; // we do not hold Cwd panes
-                let shell = self.get_default_terminal(Some(cwd), Some(default_shell.clone()));
+                let mut shell = self.get_default_terminal(Some(cwd), Some(default_shell.clone()));
+                add_env_to_terminal_action(&mut shell, env);
                 match self
                     .bus
                     .os_input
                     .as_mut()
                     .context("no OS I/O interface found")
                     .with_context(err_context)?
-                    .spawn_terminal(shell, quit_cb, self.default_editor.clone())
+                    .spawn_terminal(shell.clone(), quit_cb, self.default_editor.clone())
                     .with_context(err_context)
                 {
                     Ok((terminal_id, pid_primary, child_fd)) => {
                         self.id_to_child_pid.insert(terminal_id, child_fd);
+                        self.remember_env(terminal_id, &shell);
                         Ok(Some((terminal_id, starts_held, None, Ok(pid_primary))))
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
@@ -1139,17 +1234,20 @@ impl Pty {
             },
             None => {
                 let starts_held = false;
+                let mut shell = default_shell.clone();
+                add_env_to_terminal_action(&mut shell, env);
                 match self
                     .bus
                     .os_input
                     .as_mut()
                     .context("no OS I/O interface found")
                     .with_context(err_context)?
-                    .spawn_terminal(default_shell.clone(), quit_cb, self.default_editor.clone())
+                    .spawn_terminal(shell.clone(), quit_cb, self.default_editor.clone())
                     .with_context(err_context)
                 {
                     Ok((terminal_id, pid_primary, child_fd)) => {
                         self.id_to_child_pid.insert(terminal_id, child_fd);
+                        self.remember_env(terminal_id, &shell);
                         Ok(Some((terminal_id, starts_held, None, Ok(pid_primary))))
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
@@ -1164,11 +1262,86 @@ impl Pty {
             Some(Run::Plugin(_)) => Ok(None),
         }
     }
+    pub fn pipe_pane_to_file(
+        &mut self,
+        terminal_id: u32,
+        path: PathBuf,
+        strip_ansi: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to pipe pane {terminal_id} to file");
+        if !self.task_handles.contains_key(&terminal_id) {
+            return self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::LogError(
+                    vec![format!("No terminal pane with id {}", terminal_id)],
+                    client_id,
+                ))
+                .with_context(err_context);
+        }
+        match PaneOutputLog::new(path, strip_ansi) {
+            Ok(output_log) => {
+                let path = output_log.path().to_path_buf();
+                self.pane_output_logs
+                    .start(terminal_id, output_log)
+                    .with_context(err_context)?;
+                self.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::UpdatePaneOutputLog(
+                        terminal_id,
+                        Some(path),
+                    ))
+                    .with_context(err_context)?;
+            },
+            Err(e) => {
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::LogError(
+                        vec![format!("{:?}", e)],
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
+            },
+        }
+        Ok(())
+    }
+    pub fn stop_pipe_pane_to_file(&mut self, terminal_id: u32, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to stop piping pane {terminal_id} to file");
+        let was_logged = self
+            .pane_output_logs
+            .stop(terminal_id)
+            .with_context(err_context)?;
+        if was_logged {
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::UpdatePaneOutputLog(terminal_id, None))
+                .with_context(err_context)
+        } else {
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::LogError(
+                    vec![format!(
+                        "The output of pane {} is not being piped to a file",
+                        terminal_id
+                    )],
+                    client_id,
+                ))
+                .with_context(err_context)
+        }
+    }
     pub fn close_pane(&mut self, id: PaneId) -> Result<()> {
         let err_context = || format!("failed to close for pane {id:?}");
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.id_to_env.remove(&id);
+                if self.pane_output_logs.stop(id).unwrap_or(false) {
+                    self.bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::UpdatePaneOutputLog(id, None))
+                        .with_context(err_context)?;
+                }
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -1260,12 +1433,22 @@ impl Pty {
                         .fatal()
                         .clone();
                     let debug_to_file = self.debug_to_file;
+                    let trigger_matcher = self.trigger_matcher.clone();
+                    let pane_output_logs = self.pane_output_logs.clone();
                     async move {
-                        TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, id)
-                            .listen()
-                            .await
-                            .with_context(|| err_context(pane_id))
-                            .fatal();
+                        TerminalBytes::new(
+                            pid_primary,
+                            senders,
+                            os_input,
+                            debug_to_file,
+                            id,
+                            trigger_matcher,
+                            pane_output_logs,
+                        )
+                        .listen()
+                        .await
+                        .with_context(|| err_context(pane_id))
+                        .fatal();
                     }
                 });
 
@@ -1281,6 +1464,24 @@ impl Pty {
         session_layout_metadata: &mut SessionLayoutMetadata,
     ) {
         let terminal_ids = session_layout_metadata.all_terminal_ids();
+        let (terminal_ids_to_cwds, terminal_ids_to_commands) =
+            self.get_terminal_cwds_and_commands(terminal_ids);
+        session_layout_metadata.update_default_shell(get_default_shell());
+        session_layout_metadata.update_terminal_commands(terminal_ids_to_commands);
+        session_layout_metadata.update_terminal_cwds(terminal_ids_to_cwds);
+        session_layout_metadata.update_terminal_envs(
+            self.id_to_env
+                .iter()
+                .map(|(terminal_id, env)| (*terminal_id, env.clone()))
+                .collect(),
+        );
+    }
+    fn get_terminal_cwds_and_commands(
+        &self,
+        terminal_ids: Vec<u32>,
+    ) -> (HashMap<u32, PathBuf>, HashMap<u32, Vec<String>>) {
+        // the commands are those running in the foreground of each terminal (eg. the child
+        // process of its shell)
         let mut terminal_ids_to_commands: HashMap<u32, Vec<String>> = HashMap::new();
         let mut terminal_ids_to_cwds: HashMap<u32, PathBuf> = HashMap::new();
 
@@ -1317,9 +1518,7 @@ impl Pty {
                 terminal_ids_to_cwds.insert(terminal_id, cwd.clone());
             }
         }
-        session_layout_metadata.update_default_shell(get_default_shell());
-        session_layout_metadata.update_terminal_commands(terminal_ids_to_commands);
-        session_layout_metadata.update_terminal_cwds(terminal_ids_to_cwds);
+        (terminal_ids_to_cwds, terminal_ids_to_commands)
     }
     pub fn fill_plugin_cwd(
         &self,
@@ -1401,6 +1600,13 @@ fn send_command_not_found_to_screen(
     Ok(())
 }
 
+fn add_env_to_terminal_action(terminal_action: &mut TerminalAction, env: BTreeMap<String, String>) {
+    // editor panes are opened without them
+    if let TerminalAction::RunCommand(run_command) = terminal_action {
+        run_command.env.extend(env);
+    }
+}
+
 pub fn get_default_shell() -> PathBuf {
     PathBuf::from(std::env::var("SHELL").unwrap_or_else(|_| {
         log::warn!("Cannot read SHELL env, falling back to use /bin/sh");
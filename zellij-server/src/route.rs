@@ -12,7 +12,7 @@ use crate::{
 };
 use zellij_utils::{
     channels::SenderWithContext,
-    data::{Direction, Event, PluginCapabilities, ResizeStrategy},
+    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
     errors::prelude::*,
     input::{
         actions::{Action, SearchDirection, SearchOption},
@@ -36,7 +36,7 @@ pub(crate) fn route_action(
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
     default_layout: Box<Layout>,
-    mut seen_cli_pipes: Option<&mut HashSet<String>>,
+    seen_cli_pipes: Option<&mut HashSet<String>>,
 ) -> Result<bool> {
     let mut should_break = false;
     let err_context = || format!("failed to route action for client {client_id}");
@@ -57,6 +57,15 @@ pub(crate) fn route_action(
         },
     }
 
+    if action.is_structural() {
+        senders
+            .send_to_screen(ScreenInstruction::RecordActionInJournal(
+                action.clone(),
+                client_id,
+            ))
+            .with_context(err_context)?;
+    }
+
     match action {
         Action::ToggleTab => {
             senders
@@ -71,6 +80,14 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::WriteCharacter(val, client_id))
                 .with_context(err_context)?;
         },
+        Action::WriteKey(key, event_type) => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearScroll(client_id))
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::WriteKey(key, event_type, client_id))
+                .with_context(err_context)?;
+        },
         Action::WriteChars(val) => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScroll(client_id))
@@ -164,6 +181,79 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::MovePaneBackwards(client_id))
                 .with_context(err_context)?;
         },
+        Action::SwapPanes(first_pane_id, second_pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::SwapPanes(
+                    first_pane_id.into(),
+                    second_pane_id.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SwapPaneWith(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::SwapPaneWith(direction, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ChangeFloatingPaneCoordinates(pane_id, floating_pane_coordinates) => {
+            senders
+                .send_to_screen(ScreenInstruction::ChangeFloatingPaneCoordinates(
+                    pane_id.into(),
+                    floating_pane_coordinates,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::TogglePanePinned => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePanePinned(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetPanePinned(pane_id, should_be_pinned) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetPanePinned(
+                    pane_id.into(),
+                    should_be_pinned,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneInGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneInGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ClearPaneGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearPaneGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneGroupBroadcast => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneGroupBroadcast(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ClosePaneGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::ClosePaneGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BreakPaneGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::BreakPaneGroup(
+                    default_layout.clone(),
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneGroupEmbedOrFloating => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneGroupEmbedOrFloating(client_id))
+                .with_context(err_context)?;
+        },
+        Action::RenamePaneGroup(name) => {
+            senders
+                .send_to_screen(ScreenInstruction::RenamePaneGroup(name, client_id))
+                .with_context(err_context)?;
+        },
         Action::ClearScreen => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScreen(client_id))
@@ -174,6 +264,24 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
                 .with_context(err_context)?;
         },
+        Action::PipePaneToFile(terminal_pane_id, path, strip_ansi) => {
+            senders
+                .send_to_pty(PtyInstruction::PipePaneToFile(
+                    terminal_pane_id,
+                    path,
+                    strip_ansi,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::StopPipePaneToFile(terminal_pane_id) => {
+            senders
+                .send_to_pty(PtyInstruction::StopPipePaneToFile(
+                    terminal_pane_id,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::DumpLayout => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
@@ -218,6 +326,21 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollToTop(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollToPreviousPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToPreviousPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToNextPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToNextPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyLastCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyLastCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
         Action::PageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::PageScrollUp(client_id))
@@ -268,6 +391,7 @@ pub(crate) fn route_action(
                     shell,
                     None,
                     name,
+                    None,
                     ClientTabIndexOrPaneId::ClientId(client_id),
                 ),
             };
@@ -280,6 +404,7 @@ pub(crate) fn route_action(
             split_direction,
             should_float,
             should_open_in_place,
+            floating_pane_coordinates,
         ) => {
             let title = format!("Editing: {}", path_to_file.display());
             let open_file = TerminalAction::OpenFile(path_to_file, line_number, cwd);
@@ -319,6 +444,7 @@ pub(crate) fn route_action(
                     Some(open_file),
                     Some(should_float),
                     Some(title),
+                    floating_pane_coordinates,
                     ClientTabIndexOrPaneId::ClientId(client_id),
                 ),
             };
@@ -341,7 +467,7 @@ pub(crate) fn route_action(
                 )))
                 .with_context(err_context)?;
         },
-        Action::NewFloatingPane(run_command, name) => {
+        Action::NewFloatingPane(run_command, name, floating_pane_coordinates) => {
             let should_float = true;
             let run_cmd = run_command
                 .map(|cmd| TerminalAction::RunCommand(cmd.into()))
@@ -351,6 +477,7 @@ pub(crate) fn route_action(
                     run_cmd,
                     Some(should_float),
                     name,
+                    floating_pane_coordinates,
                     ClientTabIndexOrPaneId::ClientId(client_id),
                 ))
                 .with_context(err_context)?;
@@ -403,6 +530,7 @@ pub(crate) fn route_action(
                     run_cmd,
                     Some(should_float),
                     name,
+                    None,
                     ClientTabIndexOrPaneId::ClientId(client_id),
                 ),
             };
@@ -451,6 +579,7 @@ pub(crate) fn route_action(
                     run_cmd,
                     None,
                     None,
+                    None,
                     ClientTabIndexOrPaneId::ClientId(client_id),
                 ),
             };
@@ -510,6 +639,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::GoToTab(i, Some(client_id)))
                 .with_context(err_context)?;
         },
+        Action::MoveTab(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTab(direction, client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveTabToIndex(tab_index) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTabToIndex(tab_index, client_id))
+                .with_context(err_context)?;
+        },
         Action::GoToTabName(name, create) => {
             let shell = default_shell.clone();
             let swap_tiled_layouts = default_layout.swap_tiled_layouts.clone();
@@ -638,11 +777,24 @@ pub(crate) fn route_action(
                 },
                 SearchOption::WholeWord => ScreenInstruction::SearchToggleWholeWord(client_id),
                 SearchOption::Wrap => ScreenInstruction::SearchToggleWrap(client_id),
+                SearchOption::Regex => ScreenInstruction::SearchToggleRegex(client_id),
             };
             senders
                 .send_to_screen(instruction)
                 .with_context(err_context)?;
         },
+        Action::CopyModeMove(motion) => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyModeMove(motion, client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyModeToggleSelection(selection) => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyModeToggleSelection(
+                    selection, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::ToggleMouseMode => {}, // Handled client side
         Action::PreviousSwapLayout => {
             senders
@@ -654,6 +806,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::NextSwapLayout(client_id))
                 .with_context(err_context)?;
         },
+        Action::QueryActionJournal => {
+            senders
+                .send_to_screen(ScreenInstruction::QueryActionJournal(client_id))
+                .with_context(err_context)?;
+        },
+        Action::UndoLayoutChange => {
+            senders
+                .send_to_screen(ScreenInstruction::UndoLayoutChange(client_id))
+                .with_context(err_context)?;
+        },
         Action::QueryTabNames => {
             senders
                 .send_to_screen(ScreenInstruction::QueryTabNames(client_id))
@@ -799,6 +961,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::BreakPaneLeft(client_id))
                 .with_context(err_context)?;
         },
+        Action::MovePaneToTab(pane_id, tab_index_or_name, should_float) => {
+            senders
+                .send_to_screen(ScreenInstruction::MovePaneToTab(
+                    pane_id.into(),
+                    tab_index_or_name,
+                    should_float,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::RenameSession(name) => {
             senders
                 .send_to_screen(ScreenInstruction::RenameSession(name, client_id))
@@ -818,17 +990,8 @@ pub(crate) fn route_action(
             pane_title,
             ..
         } => {
-            if let Some(seen_cli_pipes) = seen_cli_pipes.as_mut() {
-                if !seen_cli_pipes.contains(&pipe_id) {
-                    seen_cli_pipes.insert(pipe_id.clone());
-                    senders
-                        .send_to_server(ServerInstruction::AssociatePipeWithClient {
-                            pipe_id: pipe_id.clone(),
-                            client_id,
-                        })
-                        .with_context(err_context)?;
-                }
-            }
+            associate_pipe_with_client(&pipe_id, client_id, seen_cli_pipes, &senders)
+                .with_context(err_context)?;
             if let Some(name) = name.take() {
                 let should_open_in_place = in_place.unwrap_or(false);
                 if should_open_in_place && pane_id.is_none() {
@@ -855,10 +1018,87 @@ pub(crate) fn route_action(
                 log::error!("Message must have a name");
             }
         },
+        Action::WaitForPane {
+            pipe_id,
+            pane,
+            condition,
+            block,
+        } => {
+            associate_pipe_with_client(&pipe_id, client_id, seen_cli_pipes, &senders)
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::WaitForPane(
+                    pipe_id, pane, condition, block, client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SendKeyToPlugin(plugin_id, key) => {
+            senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::Key(key),
+                )]))
+                .with_context(err_context)?;
+        },
     }
     Ok(should_break)
 }
 
+// so that whatever is sent down the pipe reaches the cli client that opened it rather than all
+// clients
+fn associate_pipe_with_client(
+    pipe_id: &str,
+    client_id: ClientId,
+    seen_cli_pipes: Option<&mut HashSet<String>>,
+    senders: &ThreadSenders,
+) -> Result<()> {
+    if let Some(seen_cli_pipes) = seen_cli_pipes {
+        if seen_cli_pipes.insert(pipe_id.to_owned()) {
+            senders.send_to_server(ServerInstruction::AssociatePipeWithClient {
+                pipe_id: pipe_id.to_owned(),
+                client_id,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn action_is_allowed_for_read_only_client(action: &Action) -> bool {
+    // read-only clients can scroll, move between tabs and leave the session, but not change it
+    // they scroll their own view of a pane, since its viewport is shared by all clients, and their
+    // mouse scrolls are never forwarded to panes that track the mouse
+    // the other modes whose actions act on the focused pane (eg. copy mode or searching) are not
+    // allowed
+    match action {
+        Action::SwitchToMode(
+            InputMode::Normal
+            | InputMode::Locked
+            | InputMode::Scroll
+            | InputMode::Tab
+            | InputMode::Session,
+        )
+        | Action::ScrollUp
+        | Action::ScrollUpAt(_)
+        | Action::ScrollDown
+        | Action::ScrollDownAt(_)
+        | Action::ScrollToTop
+        | Action::ScrollToBottom
+        | Action::PageScrollUp
+        | Action::PageScrollDown
+        | Action::HalfPageScrollUp
+        | Action::HalfPageScrollDown
+        | Action::GoToNextTab
+        | Action::GoToPreviousTab
+        | Action::GoToTab(_)
+        | Action::ToggleTab
+        | Action::Detach
+        | Action::NoOp => true,
+        Action::GoToTabName(_, create) => !create,
+        _ => false,
+    }
+}
+
 // this should only be used for one-off startup instructions
 macro_rules! send_to_screen_or_retry_queue {
     ($rlocked_sessions:expr, $message:expr, $instruction: expr, $retry_queue:expr) => {{
@@ -886,6 +1126,7 @@ pub(crate) fn route_thread_main(
     let mut retry_queue = VecDeque::new();
     let err_context = || format!("failed to handle instruction for client {client_id}");
     let mut seen_cli_pipes = HashSet::new();
+    let mut client_is_read_only = false;
     'route_loop: loop {
         match receiver.recv() {
             Some((instruction, err_ctx)) => {
@@ -900,6 +1141,11 @@ pub(crate) fn route_thread_main(
                     match instruction {
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
                             let client_id = maybe_client_id.unwrap_or(client_id);
+                            if client_is_read_only
+                                && !action_is_allowed_for_read_only_client(&action)
+                            {
+                                return Ok(should_break);
+                            }
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 if let Action::SwitchToMode(input_mode) = action {
                                     let send_res = os_input.send_to_client(
@@ -918,7 +1164,10 @@ pub(crate) fn route_thread_main(
                                     maybe_pane_id.map(|p| PaneId::Terminal(p)),
                                     rlocked_sessions.senders.clone(),
                                     rlocked_sessions.capabilities.clone(),
-                                    rlocked_sessions.client_attributes.clone(),
+                                    ClientAttributes {
+                                        is_read_only: client_is_read_only,
+                                        ..rlocked_sessions.client_attributes_for(client_id)
+                                    },
                                     rlocked_sessions.default_shell.clone(),
                                     rlocked_sessions.layout.clone(),
                                     Some(&mut seen_cli_pipes),
@@ -996,6 +1245,7 @@ pub(crate) fn route_thread_main(
                             opts,
                             layout,
                             plugin_config,
+                            triggers,
                         ) => {
                             let new_client_instruction = ServerInstruction::NewClient(
                                 client_attributes,
@@ -1004,6 +1254,7 @@ pub(crate) fn route_thread_main(
                                 layout,
                                 client_id,
                                 plugin_config,
+                                triggers,
                             );
                             to_server
                                 .send(new_client_instruction)
@@ -1015,6 +1266,7 @@ pub(crate) fn route_thread_main(
                             tab_position_to_focus,
                             pane_id_to_focus,
                         ) => {
+                            client_is_read_only = client_attributes.is_read_only;
                             let attach_client_instruction = ServerInstruction::AttachClient(
                                 client_attributes,
                                 opts,
@@ -1048,6 +1300,26 @@ pub(crate) fn route_thread_main(
                         ClientToServerMsg::ListClients => {
                             let _ = to_server.send(ServerInstruction::ActiveClients(client_id));
                         },
+                        ClientToServerMsg::ReconfigureClient(keybinds, style, opts) => {
+                            // the configuration also changes the session itself (eg. its theme),
+                            // which read-only clients are not allowed to do
+                            if !client_is_read_only {
+                                to_server
+                                    .send(ServerInstruction::ReconfigureClient(
+                                        keybinds, style, opts, client_id,
+                                    ))
+                                    .with_context(err_context)?;
+                            }
+                        },
+                        ClientToServerMsg::ConfigReloadError(ref error) => {
+                            send_to_screen_or_retry_queue!(
+                                rlocked_sessions,
+                                ScreenInstruction::ConfigReloadError(error.clone(), client_id),
+                                instruction,
+                                retry_queue
+                            )
+                            .with_context(err_context)?;
+                        },
                     }
                     Ok(should_break)
                 };
@@ -1078,3 +1350,7 @@ pub(crate) fn route_thread_main(
     }
     Ok(())
 }
+
+#[path = "./unit/route_tests.rs"]
+#[cfg(test)]
+mod route_tests;
@@ -1,14 +1,15 @@
 //! Things related to [`Screen`]s.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use zellij_utils::data::{
-    Direction, PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionInfo,
+    ActionJournalEntry, Direction, FloatingPaneCoordinates, Key, KeyEventType, PaneManifest,
+    PluginPermission, Resize, ResizeStrategy, SessionInfo, TabIndexOrName,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -17,17 +18,21 @@ use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::{
     consts::{session_info_folder_for_session, ZELLIJ_SOCK_DIR},
     envs::set_session_name,
+    input::actions::{Action, CopyModeMotion, CopyModeSelection, PaneCondition},
     input::command::TerminalAction,
     input::layout::{
         FloatingPaneLayout, Layout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation,
         SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
     position::Position,
+    session_serialization::pane_snapshot_file_name,
 };
 
 use crate::background_jobs::BackgroundJob;
 use crate::os_input_output::ResizeCache;
+use crate::pane_waits::{PaneWait, PaneWaitResult};
 use crate::panes::alacritty_functions::xparse_color;
+use crate::panes::grid_snapshot::GridSnapshotDelta;
 use crate::panes::terminal_character::AnsiCode;
 use crate::session_layout_metadata::{PaneLayoutMetadata, SessionLayoutMetadata};
 
@@ -37,18 +42,18 @@ use crate::{
     panes::PaneId,
     plugins::{PluginInstruction, PluginRenderAsset},
     pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
-    tab::Tab,
+    tab::{Pane, Tab},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
-        overlay::{Overlay, OverlayWindow},
+        overlay::{notification::Notification, Overlay, OverlayType, OverlayWindow, Overlayable},
     },
     ClientId, ServerInstruction,
 };
 use zellij_utils::{
     data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo},
     errors::{ContextType, ScreenContext},
-    input::{get_mode_info, options::Options},
+    input::{get_mode_info, keybinds::Keybinds, options::Options},
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
 };
 
@@ -130,6 +135,11 @@ macro_rules! active_tab_and_connected_client_id {
     };
 }
 
+const MAX_ACTION_JOURNAL_LENGTH: usize = 1000;
+// every how many polls the cwds and commands of all terminals are queried, not only those of the
+// terminals that had output since the last poll
+const FULL_TERMINAL_CWDS_AND_COMMANDS_POLL_INTERVAL: usize = 10;
+
 type InitialTitle = String;
 type ShouldFloat = bool;
 type HoldForCommand = Option<RunCommand>;
@@ -146,6 +156,7 @@ pub enum ScreenInstruction {
         Option<ShouldFloat>,
         HoldForCommand,
         Option<Run>, // invoked with
+        Option<FloatingPaneCoordinates>,
         ClientTabIndexOrPaneId,
     ),
     OpenInPlaceEditor(PaneId, ClientId),
@@ -154,6 +165,7 @@ pub enum ScreenInstruction {
     HorizontalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     VerticalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     WriteCharacter(Vec<u8>, ClientId),
+    WriteKey(Key, KeyEventType, ClientId),
     Resize(ClientId, ResizeStrategy),
     SwitchFocus(ClientId),
     FocusNextPane(ClientId),
@@ -170,6 +182,18 @@ pub enum ScreenInstruction {
     MovePaneDown(ClientId),
     MovePaneRight(ClientId),
     MovePaneLeft(ClientId),
+    SwapPanes(PaneId, PaneId),
+    SwapPaneWith(Direction, ClientId),
+    ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
+    TogglePanePinned(ClientId),
+    SetPanePinned(PaneId, bool),
+    TogglePaneInGroup(ClientId),
+    ClearPaneGroup(ClientId),
+    TogglePaneGroupBroadcast(ClientId),
+    ClosePaneGroup(ClientId),
+    BreakPaneGroup(Box<Layout>, Option<TerminalAction>, ClientId),
+    TogglePaneGroupEmbedOrFloating(ClientId),
+    RenamePaneGroup(String, ClientId),
     Exit,
     ClearScreen(ClientId),
     DumpScreen(String, ClientId, bool),
@@ -182,6 +206,9 @@ pub enum ScreenInstruction {
     ScrollDownAt(Position, ClientId),
     ScrollToBottom(ClientId),
     ScrollToTop(ClientId),
+    ScrollToPreviousPrompt(ClientId),
+    ScrollToNextPrompt(ClientId),
+    CopyLastCommandOutput(ClientId),
     PageScrollUp(ClientId),
     PageScrollDown(ClientId),
     HalfPageScrollUp(ClientId),
@@ -221,6 +248,8 @@ pub enum ScreenInstruction {
     ),
     SwitchTabNext(ClientId),
     SwitchTabPrev(ClientId),
+    MoveTab(Direction, ClientId),
+    MoveTabToIndex(usize, ClientId),
     ToggleActiveSyncTab(ClientId),
     CloseTab(ClientId),
     GoToTab(u32, Option<ClientId>), // this Option is a hacky workaround, please do not copy this behaviour
@@ -255,6 +284,7 @@ pub enum ScreenInstruction {
         ClientId,
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
+        bool,                // is read only
     ),
     RemoveClient(ClientId),
     AddOverlay(Overlay, ClientId),
@@ -266,6 +296,9 @@ pub enum ScreenInstruction {
     SearchUp(ClientId),
     SearchToggleCaseSensitivity(ClientId),
     SearchToggleWholeWord(ClientId),
+    SearchToggleRegex(ClientId),
+    CopyModeMove(CopyModeMotion, ClientId),
+    CopyModeToggleSelection(CopyModeSelection, ClientId),
     SearchToggleWrap(ClientId),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
@@ -301,6 +334,7 @@ pub enum ScreenInstruction {
     FocusPaneWithId(PaneId, bool, ClientId),                             // bool is should_float
     RenamePane(PaneId, Vec<u8>),
     RenameTab(usize, Vec<u8>),
+    RenameTabWithPaneId(PaneId, Vec<u8>),
     RequestPluginPermissions(
         u32, // u32 - plugin_id
         PluginPermission,
@@ -308,6 +342,7 @@ pub enum ScreenInstruction {
     BreakPane(Box<Layout>, Option<TerminalAction>, ClientId),
     BreakPaneRight(ClientId),
     BreakPaneLeft(ClientId),
+    MovePaneToTab(PaneId, TabIndexOrName, bool, ClientId), // bool is should_float
     UpdateSessionInfos(
         BTreeMap<String, SessionInfo>, // String is the session name
         BTreeMap<String, Duration>,    // resurrectable sessions - <name, created>
@@ -321,6 +356,24 @@ pub enum ScreenInstruction {
     ),
     DumpLayoutToHd,
     RenameSession(String, ClientId), // String -> new name
+    // usize - scrollback lines to include, u32 - plugin_id of the requesting plugin
+    GetPaneScrollback(PaneId, usize, u32, ClientId),
+    ReportTerminalCwdsAndCommands,
+    UpdateTerminalCwdsAndCommands(
+        Vec<u32>,              // the terminal ids that were queried
+        HashMap<u32, PathBuf>, // terminal_id => cwd
+        HashMap<u32, String>,  // terminal_id => command running in its foreground
+    ),
+    UpdatePaneOutputLog(u32, Option<PathBuf>), // terminal_id, file its output is logged to
+    Reconfigure(Keybinds, Style, Box<Options>, ClientId),
+    ConfigReloadError(String, ClientId), // String -> the rendered parse error
+    RecordActionInJournal(Action, ClientId),
+    QueryActionJournal(ClientId),
+    GetActionJournal(u32, ClientId), // u32 - plugin_id of the requesting plugin
+    UndoLayoutChange(ClientId),
+    RefreshPluginVisibility(Vec<u32>), // plugin ids
+    // pipe_id, pane name or id, condition, should block until met
+    WaitForPane(String, String, PaneCondition, bool, ClientId),
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -338,6 +391,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::HorizontalSplit(..) => ScreenContext::HorizontalSplit,
             ScreenInstruction::VerticalSplit(..) => ScreenContext::VerticalSplit,
             ScreenInstruction::WriteCharacter(..) => ScreenContext::WriteCharacter,
+            ScreenInstruction::WriteKey(..) => ScreenContext::WriteKey,
             ScreenInstruction::Resize(.., strategy) => match strategy {
                 ResizeStrategy {
                     resize: Resize::Increase,
@@ -378,6 +432,24 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MovePane(..) => ScreenContext::MovePane,
             ScreenInstruction::MovePaneBackwards(..) => ScreenContext::MovePaneBackwards,
             ScreenInstruction::MovePaneDown(..) => ScreenContext::MovePaneDown,
+            ScreenInstruction::SwapPanes(..) => ScreenContext::SwapPanes,
+            ScreenInstruction::SwapPaneWith(..) => ScreenContext::SwapPaneWith,
+            ScreenInstruction::ChangeFloatingPaneCoordinates(..) => {
+                ScreenContext::ChangeFloatingPaneCoordinates
+            },
+            ScreenInstruction::TogglePanePinned(..) => ScreenContext::TogglePanePinned,
+            ScreenInstruction::SetPanePinned(..) => ScreenContext::SetPanePinned,
+            ScreenInstruction::TogglePaneInGroup(..) => ScreenContext::TogglePaneInGroup,
+            ScreenInstruction::ClearPaneGroup(..) => ScreenContext::ClearPaneGroup,
+            ScreenInstruction::TogglePaneGroupBroadcast(..) => {
+                ScreenContext::TogglePaneGroupBroadcast
+            },
+            ScreenInstruction::ClosePaneGroup(..) => ScreenContext::ClosePaneGroup,
+            ScreenInstruction::BreakPaneGroup(..) => ScreenContext::BreakPaneGroup,
+            ScreenInstruction::TogglePaneGroupEmbedOrFloating(..) => {
+                ScreenContext::TogglePaneGroupEmbedOrFloating
+            },
+            ScreenInstruction::RenamePaneGroup(..) => ScreenContext::RenamePaneGroup,
             ScreenInstruction::MovePaneUp(..) => ScreenContext::MovePaneUp,
             ScreenInstruction::MovePaneRight(..) => ScreenContext::MovePaneRight,
             ScreenInstruction::MovePaneLeft(..) => ScreenContext::MovePaneLeft,
@@ -390,6 +462,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
             ScreenInstruction::ScrollToTop(..) => ScreenContext::ScrollToTop,
+            ScreenInstruction::ScrollToPreviousPrompt(..) => ScreenContext::ScrollToPreviousPrompt,
+            ScreenInstruction::ScrollToNextPrompt(..) => ScreenContext::ScrollToNextPrompt,
+            ScreenInstruction::CopyLastCommandOutput(..) => ScreenContext::CopyLastCommandOutput,
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
@@ -409,6 +484,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ApplyLayout(..) => ScreenContext::ApplyLayout,
             ScreenInstruction::SwitchTabNext(..) => ScreenContext::SwitchTabNext,
             ScreenInstruction::SwitchTabPrev(..) => ScreenContext::SwitchTabPrev,
+            ScreenInstruction::MoveTab(..) => ScreenContext::MoveTab,
+            ScreenInstruction::MoveTabToIndex(..) => ScreenContext::MoveTabToIndex,
             ScreenInstruction::CloseTab(..) => ScreenContext::CloseTab,
             ScreenInstruction::GoToTab(..) => ScreenContext::GoToTab,
             ScreenInstruction::GoToTabName(..) => ScreenContext::GoToTabName,
@@ -456,6 +533,11 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::SearchToggleCaseSensitivity
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
+            ScreenInstruction::CopyModeMove(..) => ScreenContext::CopyModeMove,
+            ScreenInstruction::CopyModeToggleSelection(..) => {
+                ScreenContext::CopyModeToggleSelection
+            },
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
@@ -490,17 +572,37 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::FocusPaneWithId(..) => ScreenContext::FocusPaneWithId,
             ScreenInstruction::RenamePane(..) => ScreenContext::RenamePane,
             ScreenInstruction::RenameTab(..) => ScreenContext::RenameTab,
+            ScreenInstruction::RenameTabWithPaneId(..) => ScreenContext::RenameTabWithPaneId,
             ScreenInstruction::RequestPluginPermissions(..) => {
                 ScreenContext::RequestPluginPermissions
             },
             ScreenInstruction::BreakPane(..) => ScreenContext::BreakPane,
             ScreenInstruction::BreakPaneRight(..) => ScreenContext::BreakPaneRight,
             ScreenInstruction::BreakPaneLeft(..) => ScreenContext::BreakPaneLeft,
+            ScreenInstruction::MovePaneToTab(..) => ScreenContext::MovePaneToTab,
             ScreenInstruction::UpdateSessionInfos(..) => ScreenContext::UpdateSessionInfos,
             ScreenInstruction::ReplacePane(..) => ScreenContext::ReplacePane,
             ScreenInstruction::NewInPlacePluginPane(..) => ScreenContext::NewInPlacePluginPane,
             ScreenInstruction::DumpLayoutToHd => ScreenContext::DumpLayoutToHd,
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::GetPaneScrollback(..) => ScreenContext::GetPaneScrollback,
+            ScreenInstruction::ReportTerminalCwdsAndCommands => {
+                ScreenContext::ReportTerminalCwdsAndCommands
+            },
+            ScreenInstruction::UpdateTerminalCwdsAndCommands(..) => {
+                ScreenContext::UpdateTerminalCwdsAndCommands
+            },
+            ScreenInstruction::UpdatePaneOutputLog(..) => ScreenContext::UpdatePaneOutputLog,
+            ScreenInstruction::WaitForPane(..) => ScreenContext::WaitForPane,
+            ScreenInstruction::Reconfigure(..) => ScreenContext::Reconfigure,
+            ScreenInstruction::ConfigReloadError(..) => ScreenContext::ConfigReloadError,
+            ScreenInstruction::RecordActionInJournal(..) => ScreenContext::RecordActionInJournal,
+            ScreenInstruction::QueryActionJournal(..) => ScreenContext::QueryActionJournal,
+            ScreenInstruction::GetActionJournal(..) => ScreenContext::GetActionJournal,
+            ScreenInstruction::UndoLayoutChange(..) => ScreenContext::UndoLayoutChange,
+            ScreenInstruction::RefreshPluginVisibility(..) => {
+                ScreenContext::RefreshPluginVisibility
+            },
         }
     }
 }
@@ -551,6 +653,8 @@ pub(crate) struct Screen {
     sixel_image_store: Rc<RefCell<SixelImageStore>>,
     /// The overlay that is drawn on top of [`Pane`]'s', [`Tab`]'s and the [`Screen`]
     overlay: OverlayWindow,
+    /// Overlays drawn only for a specific client (eg. errors in its configuration)
+    client_overlays: BTreeMap<ClientId, Overlay>,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
@@ -573,6 +677,19 @@ pub(crate) struct Screen {
     // also be this session
     resurrectable_sessions: BTreeMap<String, Duration>, // String is the session name, duration is
     // its creation time
+    terminal_ids_to_cwds: HashMap<u32, PathBuf>,
+    terminal_ids_to_running_commands: HashMap<u32, String>,
+    // the terminals whose cwd and command might have changed since they were last queried
+    terminals_with_new_output: HashSet<u32>,
+    terminal_cwds_and_commands_polls: usize,
+    terminal_ids_to_output_logs: HashMap<u32, PathBuf>,
+    pane_waits: Vec<PaneWait>,
+    action_journal: VecDeque<ActionJournalEntry>,
+    pane_groups: HashMap<ClientId, Vec<PaneId>>, // in the order the panes were added
+    pane_group_broadcasts: HashSet<ClientId>, // clients whose input is written to their pane group
+    pane_groups_to_break_into_tabs: HashMap<usize, Vec<PaneId>>, // usize is the index of the tab
+    // the first pane of the group was broken into
+    read_only_clients: HashSet<ClientId>,
     default_layout: Box<Layout>,
     default_shell: Option<PathBuf>,
     styled_underlines: bool,
@@ -616,6 +733,7 @@ impl Screen {
             active_tab_indices: BTreeMap::new(),
             tabs: BTreeMap::new(),
             overlay: OverlayWindow::default(),
+            client_overlays: BTreeMap::new(),
             terminal_emulator_colors: Rc::new(RefCell::new(Palette::default())),
             terminal_emulator_color_codes: Rc::new(RefCell::new(HashMap::new())),
             tab_history: BTreeMap::new(),
@@ -628,6 +746,17 @@ impl Screen {
             debug,
             session_name,
             session_infos_on_machine,
+            terminal_ids_to_cwds: HashMap::new(),
+            terminal_ids_to_running_commands: HashMap::new(),
+            terminals_with_new_output: HashSet::new(),
+            terminal_cwds_and_commands_polls: 0,
+            terminal_ids_to_output_logs: HashMap::new(),
+            pane_waits: vec![],
+            action_journal: VecDeque::new(),
+            pane_groups: HashMap::new(),
+            pane_group_broadcasts: HashSet::new(),
+            pane_groups_to_break_into_tabs: HashMap::new(),
+            read_only_clients: HashSet::new(),
             default_layout,
             default_shell,
             session_serialization,
@@ -913,6 +1042,57 @@ impl Screen {
         Ok(())
     }
 
+    /// Moves the active [`Tab`] one position in `direction`, wrapping around at either end.
+    pub fn move_active_tab(&mut self, direction: Direction, client_id: ClientId) -> Result<()> {
+        let tab_count = self.tabs.len();
+        let active_tab_pos = self
+            .get_active_tab(client_id)
+            .with_context(|| format!("failed to move tab for client {client_id}"))?
+            .position;
+        let new_tab_pos = match direction {
+            Direction::Left | Direction::Up => (active_tab_pos + tab_count - 1) % tab_count,
+            Direction::Right | Direction::Down => (active_tab_pos + 1) % tab_count,
+        };
+        self.move_active_tab_to_position(new_tab_pos, client_id)
+    }
+
+    /// Moves the active [`Tab`] to `tab_index` (starting at 1), or to the end if there are fewer
+    /// tabs than that.
+    pub fn move_active_tab_to_index(
+        &mut self,
+        tab_index: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let new_tab_pos = tab_index.min(self.tabs.len()).saturating_sub(1);
+        self.move_active_tab_to_position(new_tab_pos, client_id)
+    }
+
+    fn move_active_tab_to_position(
+        &mut self,
+        new_tab_pos: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to move tab to position {new_tab_pos} for client {client_id}");
+        let active_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        let mut tabs_by_position: Vec<&mut Tab> = self.tabs.values_mut().collect();
+        tabs_by_position.sort_by_key(|t| t.position);
+        let active_tab_pos = tabs_by_position
+            .iter()
+            .position(|t| t.index == active_tab_index)
+            .with_context(err_context)?;
+        let active_tab = tabs_by_position.remove(active_tab_pos);
+        tabs_by_position.insert(new_tab_pos, active_tab);
+        for (position, tab) in tabs_by_position.into_iter().enumerate() {
+            tab.position = position;
+        }
+        self.log_and_report_session_state()
+            .with_context(err_context)
+    }
+
     pub fn go_to_tab(&mut self, tab_index: usize, client_id: ClientId) -> Result<()> {
         self.switch_active_tab(tab_index.saturating_sub(1), None, true, client_id)
     }
@@ -1057,6 +1237,10 @@ impl Screen {
         for tab_index in tabs_to_close {
             self.close_tab_at_index(tab_index).context(err_context)?;
         }
+        for (client_id, overlay) in &self.client_overlays {
+            let vte_output = overlay.generate_overlay(self.size).context(err_context)?;
+            output.add_post_vte_instruction_to_client(*client_id, &vte_output);
+        }
         if output.is_dirty() {
             let serialized_output = output.serialize().context(err_context)?;
             let _ = self
@@ -1297,13 +1481,18 @@ impl Screen {
             bail!("Can't find a valid tab to attach client to!");
         };
 
+        // read-only clients are known as such from the start rather than from their first mode
+        // switch, eg. so that they scroll on their own
+        let mut mode_info = self.default_mode_info.clone();
+        mode_info.is_read_only = self.read_only_clients.contains(&client_id);
+
         self.active_tab_indices.insert(client_id, tab_index);
         self.connected_clients.borrow_mut().insert(client_id);
         self.tab_history.insert(client_id, tab_history);
         self.tabs
             .get_mut(&tab_index)
             .with_context(|| err_context(tab_index))?
-            .add_client(client_id, None)
+            .add_client(client_id, Some(mode_info))
             .with_context(|| err_context(tab_index))
     }
 
@@ -1323,6 +1512,11 @@ impl Screen {
             self.tab_history.remove(&client_id);
         }
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.read_only_clients.remove(&client_id);
+        self.client_overlays.remove(&client_id);
+        self.pane_waits
+            .retain(|pane_wait| pane_wait.client_id != client_id);
+        self.remove_pane_group(client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
     }
@@ -1383,6 +1577,7 @@ impl Screen {
                 };
                 plugin_tab_updates.push(tab_info_for_plugins);
             }
+            plugin_tab_updates.sort_by_key(|t| t.position);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(plugin_tab_updates)));
         }
         self.bus
@@ -1392,10 +1587,7 @@ impl Screen {
         Ok(tab_infos_for_screen_state.values().cloned().collect())
     }
     fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
-        let mut pane_manifest = PaneManifest::default();
-        for tab in self.tabs.values() {
-            pane_manifest.panes.insert(tab.position, tab.pane_infos());
-        }
+        let pane_manifest = self.generate_pane_manifest();
         self.bus
             .senders
             .send_to_plugin(PluginInstruction::Update(vec![(
@@ -1407,10 +1599,28 @@ impl Screen {
 
         Ok(pane_manifest)
     }
+    fn generate_pane_manifest(&self) -> PaneManifest {
+        let mut pane_manifest = PaneManifest::default();
+        for tab in self.tabs.values() {
+            let mut pane_infos = tab.pane_infos();
+            for pane_info in pane_infos.iter_mut().filter(|p| !p.is_plugin) {
+                pane_info.cwd = self.terminal_ids_to_cwds.get(&pane_info.id).cloned();
+                pane_info.running_command = self
+                    .terminal_ids_to_running_commands
+                    .get(&pane_info.id)
+                    .cloned();
+                pane_info.output_log_path =
+                    self.terminal_ids_to_output_logs.get(&pane_info.id).cloned();
+            }
+            pane_manifest.panes.insert(tab.position, pane_infos);
+        }
+        pane_manifest
+    }
     fn log_and_report_session_state(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
         // generate own session info
         let pane_manifest = self.generate_and_report_pane_state()?;
+        self.check_pane_waits(&pane_manifest)?;
         let tab_infos = self.generate_and_report_tab_state()?;
         let session_info = SessionInfo {
             name: self.session_name.clone(),
@@ -1440,6 +1650,16 @@ impl Screen {
             .senders
             .send_to_plugin(PluginInstruction::LogLayoutToHd(session_layout_metadata))
             .with_context(err_context)?;
+        if self.serialize_pane_viewport {
+            let pane_snapshots = self.take_pane_snapshots();
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::ReportPaneSnapshots(
+                    pane_snapshots,
+                    self.scrollback_lines_to_serialize,
+                ))
+                .with_context(err_context)?;
+        }
 
         Ok(())
     }
@@ -1466,6 +1686,188 @@ impl Screen {
             .context("failed to update session info")?;
         Ok(())
     }
+    fn has_terminal(&self, terminal_id: u32) -> bool {
+        self.tabs
+            .values()
+            .any(|tab| tab.has_terminal_pid(terminal_id))
+    }
+
+    pub fn report_terminal_cwds_and_commands(&mut self) -> Result<()> {
+        // the cwd and foreground command of a terminal change as a result of activity in it (eg. a
+        // command starting or exiting, or `cd` printing a new prompt), so we only query the
+        // terminals that had output since the last poll - and every once in a while all of them,
+        // in case a change did not produce any output
+        self.terminal_cwds_and_commands_polls += 1;
+        let terminal_ids: Vec<u32> = if self.terminal_cwds_and_commands_polls
+            % FULL_TERMINAL_CWDS_AND_COMMANDS_POLL_INTERVAL
+            == 0
+        {
+            self.terminals_with_new_output.clear();
+            self.tabs
+                .values()
+                .flat_map(|tab| tab.get_all_pane_ids())
+                .filter_map(|pane_id| match pane_id {
+                    PaneId::Terminal(terminal_id) => Some(terminal_id),
+                    PaneId::Plugin(_) => None,
+                })
+                .collect()
+        } else {
+            self.terminals_with_new_output.drain().collect()
+        };
+        if terminal_ids.is_empty() {
+            return Ok(());
+        }
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::ReportTerminalCwdsAndCommands(terminal_ids))
+            .context("failed to report terminal cwds and commands")
+    }
+
+    pub fn update_terminal_cwds_and_commands(
+        &mut self,
+        queried_terminal_ids: Vec<u32>,
+        queried_terminal_ids_to_cwds: HashMap<u32, PathBuf>,
+        queried_terminal_ids_to_running_commands: HashMap<u32, String>,
+    ) -> Result<()> {
+        let mut terminal_ids_to_cwds = self.terminal_ids_to_cwds.clone();
+        let mut terminal_ids_to_running_commands = self.terminal_ids_to_running_commands.clone();
+        for terminal_id in &queried_terminal_ids {
+            terminal_ids_to_cwds.remove(terminal_id);
+            terminal_ids_to_running_commands.remove(terminal_id);
+        }
+        terminal_ids_to_cwds.extend(queried_terminal_ids_to_cwds);
+        terminal_ids_to_running_commands.extend(queried_terminal_ids_to_running_commands);
+        // forget about terminals that were closed in the meantime
+        terminal_ids_to_cwds.retain(|terminal_id, _| self.has_terminal(*terminal_id));
+        terminal_ids_to_running_commands.retain(|terminal_id, _| self.has_terminal(*terminal_id));
+        // we only report to plugins if something changed, since this is polled periodically
+        if terminal_ids_to_cwds != self.terminal_ids_to_cwds
+            || terminal_ids_to_running_commands != self.terminal_ids_to_running_commands
+        {
+            self.terminal_ids_to_cwds = terminal_ids_to_cwds;
+            self.terminal_ids_to_running_commands = terminal_ids_to_running_commands;
+            self.log_and_report_session_state()
+                .context("failed to update terminal cwds and commands")?;
+        }
+        Ok(())
+    }
+
+    // eg. after a plugin was reloaded, so that it knows whether it is visible without waiting for
+    // its tab to be switched to or from
+    pub fn refresh_plugin_visibility(&self, plugin_ids: Vec<u32>) -> Result<()> {
+        let mut plugin_updates = vec![];
+        for (tab_index, tab) in &self.tabs {
+            let visible = self.active_tab_indices.values().any(|i| i == tab_index);
+            for plugin_id in plugin_ids.iter().filter(|p| tab.has_plugin(**p)) {
+                plugin_updates.push((Some(*plugin_id), None, Event::Visible(visible)));
+            }
+        }
+        if plugin_updates.is_empty() {
+            return Ok(());
+        }
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(plugin_updates))
+            .context("failed to refresh plugin visibility")
+    }
+
+    pub fn update_pane_output_log(
+        &mut self,
+        terminal_id: u32,
+        output_log_path: Option<PathBuf>,
+    ) -> Result<()> {
+        match output_log_path {
+            Some(output_log_path) => {
+                self.terminal_ids_to_output_logs
+                    .insert(terminal_id, output_log_path);
+            },
+            None => {
+                self.terminal_ids_to_output_logs.remove(&terminal_id);
+            },
+        }
+        self.log_and_report_session_state()
+            .context("failed to update pane output log")
+    }
+
+    pub fn wait_for_pane(
+        &mut self,
+        pipe_id: String,
+        pane: String,
+        condition: PaneCondition,
+        block: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let mut pane_wait = match PaneWait::new(pipe_id.clone(), pane, condition, client_id) {
+            Ok(pane_wait) => pane_wait,
+            Err(e) => {
+                log::error!("{:?}", e);
+                return self.report_pane_wait(pipe_id, PaneWaitResult::Unmet);
+            },
+        };
+        let pane_manifest = self.generate_pane_manifest();
+        let result = pane_wait.check_session_state(&pane_manifest, |pane_id| {
+            self.tabs
+                .values()
+                .find_map(|tab| tab.get_pane_contents(pane_id, usize::MAX))
+        });
+        match result {
+            PaneWaitResult::Pending if block => {
+                self.pane_waits.push(pane_wait);
+                Ok(())
+            },
+            PaneWaitResult::Pending => self.report_pane_wait(pipe_id, PaneWaitResult::Unmet),
+            result => self.report_pane_wait(pipe_id, result),
+        }
+    }
+    fn check_pane_waits(&mut self, pane_manifest: &PaneManifest) -> Result<()> {
+        let mut pane_waits = std::mem::take(&mut self.pane_waits);
+        let mut results = vec![];
+        pane_waits.retain_mut(|pane_wait| {
+            let result = pane_wait.check_session_state(pane_manifest, |pane_id| {
+                self.tabs
+                    .values()
+                    .find_map(|tab| tab.get_pane_contents(pane_id, usize::MAX))
+            });
+            let is_pending = result == PaneWaitResult::Pending;
+            if !is_pending {
+                results.push((pane_wait.pipe_id.clone(), result));
+            }
+            is_pending
+        });
+        self.pane_waits = pane_waits;
+        for (pipe_id, result) in results {
+            self.report_pane_wait(pipe_id, result)?;
+        }
+        Ok(())
+    }
+    fn check_pane_waits_for_output(&mut self, terminal_id: u32, bytes: &[u8]) -> Result<()> {
+        let mut results = vec![];
+        self.pane_waits.retain_mut(|pane_wait| {
+            let result = pane_wait.check_output(terminal_id, bytes);
+            let is_pending = result == PaneWaitResult::Pending;
+            if !is_pending {
+                results.push((pane_wait.pipe_id.clone(), result));
+            }
+            is_pending
+        });
+        for (pipe_id, result) in results {
+            self.report_pane_wait(pipe_id, result)?;
+        }
+        Ok(())
+    }
+    fn report_pane_wait(&self, pipe_id: String, result: PaneWaitResult) -> Result<()> {
+        let err_context = || format!("failed to report on pane wait for pipe {}", pipe_id);
+        if let PaneWaitResult::Met(output) = result {
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::CliPipeOutput(pipe_id.clone(), output))
+                .with_context(err_context)?;
+        }
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_id.clone()))
+            .with_context(err_context)
+    }
 
     pub fn update_active_tab_name(&mut self, buf: Vec<u8>, client_id: ClientId) -> Result<()> {
         let err_context =
@@ -1511,6 +1913,20 @@ impl Screen {
             None => Ok(()),
         }
     }
+    pub fn rename_tab_with_pane_id(&mut self, pane_id: PaneId, new_name: Vec<u8>) {
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => {
+                tab.name = String::from_utf8_lossy(&new_name).to_string();
+            },
+            None => {
+                log::error!("Failed to find tab of pane: {:?}", pane_id);
+            },
+        }
+    }
     pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to undo active tab rename for client {}", client_id);
 
@@ -1541,6 +1957,8 @@ impl Screen {
         if mode_info.session_name.as_ref() != Some(&self.session_name) {
             mode_info.session_name = Some(self.session_name.clone());
         }
+        // eg. when changing the mode for all clients, the mode info is not specific to this one
+        mode_info.is_read_only = self.read_only_clients.contains(&client_id);
         let previous_mode = self
             .mode_info
             .get(&client_id)
@@ -1556,13 +1974,26 @@ impl Screen {
 
         // If we leave the Search-related modes, we need to clear all previous searches
         let search_related_modes = [InputMode::EnterSearch, InputMode::Search, InputMode::Scroll];
+        // (read-only clients cannot search, and the search of a pane is shared by all clients)
         if search_related_modes.contains(&previous_mode)
             && !search_related_modes.contains(&mode_info.mode)
+            && !mode_info.is_read_only
         {
             active_tab!(self, client_id, |tab: &mut Tab| tab.clear_search(client_id));
         }
 
-        if previous_mode == InputMode::Scroll
+        if previous_mode == InputMode::Copy && mode_info.mode != InputMode::Copy {
+            // not only the active tab, the client might have switched tabs in copy mode
+            for tab in self.tabs.values_mut() {
+                tab.exit_copy_mode(client_id);
+            }
+        }
+        if mode_info.mode == InputMode::Copy && previous_mode != InputMode::Copy {
+            active_tab!(self, client_id, |tab: &mut Tab| tab
+                .enter_copy_mode(client_id));
+        }
+
+        if (previous_mode == InputMode::Scroll || previous_mode == InputMode::Copy)
             && (mode_info.mode == InputMode::Normal || mode_info.mode == InputMode::Locked)
         {
             if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
@@ -1603,6 +2034,84 @@ impl Screen {
 
         Ok(())
     }
+    /// Applies a client's reloaded configuration to the running session, its keybindings are only
+    /// those of this client but the theme and options are shared by all clients of the session
+    pub fn reconfigure(
+        &mut self,
+        new_keybinds: Keybinds,
+        new_style: Style,
+        new_options: Options,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to reconfigure client {client_id}");
+
+        self.default_mode_info.style = new_style;
+
+        self.draw_pane_frames = new_options.pane_frames.unwrap_or(true);
+        self.auto_layout = new_options.auto_layout.unwrap_or(true);
+        self.session_serialization = new_options.session_serialization.unwrap_or(true);
+        self.serialize_pane_viewport = new_options.serialize_pane_viewport.unwrap_or(false);
+        self.scrollback_lines_to_serialize = new_options.scrollback_lines_to_serialize;
+        self.copy_options = CopyOptions::new(
+            new_options.copy_command,
+            new_options.copy_clipboard.unwrap_or_default(),
+            new_options.copy_on_select.unwrap_or(true),
+        );
+        for tab in self.tabs.values_mut() {
+            tab.update_style(new_style);
+            tab.set_pane_frames(self.draw_pane_frames);
+            tab.set_auto_layout(self.auto_layout);
+            tab.update_copy_options(&self.copy_options);
+        }
+
+        self.client_overlays.remove(&client_id);
+        let mut client_ids: Vec<ClientId> = self.active_tab_indices.keys().copied().collect();
+        if !client_ids.contains(&client_id) {
+            client_ids.push(client_id);
+        }
+        let mut plugin_updates = vec![];
+        for reconfigured_client_id in client_ids {
+            let mut mode_info = self
+                .mode_info
+                .get(&reconfigured_client_id)
+                .unwrap_or(&self.default_mode_info)
+                .clone();
+            if reconfigured_client_id == client_id {
+                mode_info.keybinds = new_keybinds.to_keybinds_vec();
+            }
+            mode_info.style = new_style;
+            self.change_mode(mode_info, reconfigured_client_id)
+                .with_context(err_context)?;
+            if let Some(mode_info) = self.mode_info.get(&reconfigured_client_id) {
+                plugin_updates.push((
+                    None,
+                    Some(reconfigured_client_id),
+                    Event::ModeUpdate(mode_info.clone()),
+                ));
+            }
+        }
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(plugin_updates))
+            .with_context(err_context)?;
+        self.render(None).with_context(err_context)
+    }
+    /// Shows an error in a client's reloaded configuration until it is fixed
+    pub fn show_config_reload_error(&mut self, error: String, client_id: ClientId) -> Result<()> {
+        let message = format!(
+            "{}\nThe previous configuration remains in effect until the file is fixed.",
+            error.trim_end()
+        );
+        self.client_overlays.insert(
+            client_id,
+            Overlay::new(OverlayType::Notification(Notification::new(message))),
+        );
+        for tab in self.tabs.values_mut() {
+            tab.set_force_render();
+        }
+        self.render(None)
+            .with_context(|| format!("failed to show config error for client {client_id}"))
+    }
     pub fn change_mode_for_all_clients(&mut self, mode_info: ModeInfo) -> Result<()> {
         let err_context = || {
             format!(
@@ -1737,6 +2246,7 @@ impl Screen {
                 new_active_tab.add_floating_pane(
                     plugin_pane_to_move_to_active_tab,
                     pane_id,
+                    None,
                     Some(client_id),
                 )?;
             } else {
@@ -1810,36 +2320,14 @@ impl Screen {
             let active_pane = active_tab
                 .close_pane(active_pane_id, false, Some(client_id))
                 .with_context(err_context)?;
-            let active_pane_run_instruction = active_pane.invoked_with().clone();
-            let tab_index = self.get_new_tab_index();
-            let swap_layouts = (
-                default_layout.swap_tiled_layouts.clone(),
-                default_layout.swap_floating_layouts.clone(),
-            );
-            self.new_tab(tab_index, swap_layouts, None, client_id)?;
-            let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
-            let (mut tiled_panes_layout, mut floating_panes_layout) = default_layout.new_tab();
-            if pane_to_break_is_floating {
-                tab.show_floating_panes();
-                tab.add_floating_pane(active_pane, active_pane_id, Some(client_id))?;
-                if let Some(already_running_layout) = floating_panes_layout
-                    .iter_mut()
-                    .find(|i| i.run == active_pane_run_instruction)
-                {
-                    already_running_layout.already_running = true;
-                }
-            } else {
-                tab.add_tiled_pane(active_pane, active_pane_id, Some(client_id))?;
-                tiled_panes_layout.ignore_run_instruction(active_pane_run_instruction.clone());
-            }
-            self.bus.senders.send_to_plugin(PluginInstruction::NewTab(
-                None,
+            self.break_pane_into_new_tab(
+                active_pane,
+                active_pane_id,
+                pane_to_break_is_floating,
                 default_shell,
-                Some(tiled_panes_layout),
-                floating_panes_layout,
-                tab_index,
+                default_layout,
                 client_id,
-            ))?;
+            )?;
         } else {
             let active_pane_id = active_tab
                 .get_active_pane_id(client_id)
@@ -1855,59 +2343,526 @@ impl Screen {
         }
         Ok(())
     }
+    pub fn break_pane_group(
+        &mut self,
+        default_shell: Option<TerminalAction>,
+        default_layout: Box<Layout>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || "failed to break pane group out of its tabs".to_string();
+        let Some(client_id) = self.connected_client_id(client_id) else {
+            return self.unblock_input();
+        };
+        let pane_ids_in_group = self.pane_group_by_tab(client_id);
+        let tab_left_with_only_floating_panes = self.tabs.iter().any(|(tab_index, tab)| {
+            let pane_ids_to_remove: Vec<PaneId> = pane_ids_in_group
+                .iter()
+                .filter(|(i, _)| i == tab_index)
+                .map(|(_, pane_id)| *pane_id)
+                .collect();
+            !pane_ids_to_remove.is_empty()
+                && tab.would_be_left_with_only_floating_panes(&pane_ids_to_remove)
+        });
+        if tab_left_with_only_floating_panes {
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                    pane_ids_in_group
+                        .iter()
+                        .map(|(_, pane_id)| *pane_id)
+                        .collect(),
+                    "Cannot break the last tiled pane out of a tab!".into(),
+                ))
+                .with_context(err_context)?;
+            return self.unblock_input();
+        }
+        let mut pane_ids_in_group = pane_ids_in_group.into_iter();
+        let Some((first_tab_index, first_pane_id)) = pane_ids_in_group.next() else {
+            return self.unblock_input();
+        };
+        let (first_pane, first_pane_is_floating) = {
+            let tab = self
+                .tabs
+                .get_mut(&first_tab_index)
+                .with_context(err_context)?;
+            let is_floating = tab.pane_is_floating(&first_pane_id);
+            let pane = tab
+                .extract_pane(first_pane_id, None)
+                .with_context(err_context)?;
+            (pane, is_floating)
+        };
+        let tab_index = self.break_pane_into_new_tab(
+            first_pane,
+            first_pane_id,
+            first_pane_is_floating,
+            default_shell,
+            default_layout,
+            client_id,
+        )?;
+        // the rest of the group is moved once the layout of the new tab is applied
+        self.pane_groups_to_break_into_tabs.insert(
+            tab_index,
+            pane_ids_in_group.map(|(_, pane_id)| pane_id).collect(),
+        );
+        Ok(())
+    }
+    fn break_pane_into_new_tab(
+        &mut self,
+        pane: Box<dyn Pane>,
+        pane_id: PaneId,
+        pane_is_floating: bool,
+        default_shell: Option<TerminalAction>,
+        default_layout: Box<Layout>,
+        client_id: ClientId,
+    ) -> Result<usize> {
+        let err_context = || format!("failed to break pane {pane_id:?} into a new tab");
+        let pane_run_instruction = pane.invoked_with().clone();
+        let tab_index = self.get_new_tab_index();
+        let swap_layouts = (
+            default_layout.swap_tiled_layouts.clone(),
+            default_layout.swap_floating_layouts.clone(),
+        );
+        self.new_tab(tab_index, swap_layouts, None, client_id)?;
+        let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+        let (mut tiled_panes_layout, mut floating_panes_layout) = default_layout.new_tab();
+        if pane_is_floating {
+            tab.show_floating_panes();
+            tab.add_floating_pane(pane, pane_id, None, Some(client_id))?;
+            if let Some(already_running_layout) = floating_panes_layout
+                .iter_mut()
+                .find(|i| i.run == pane_run_instruction)
+            {
+                already_running_layout.already_running = true;
+            }
+        } else {
+            tab.add_tiled_pane(pane, pane_id, Some(client_id))?;
+            tiled_panes_layout.ignore_run_instruction(pane_run_instruction.clone());
+        }
+        self.bus.senders.send_to_plugin(PluginInstruction::NewTab(
+            None,
+            default_shell,
+            Some(tiled_panes_layout),
+            floating_panes_layout,
+            tab_index,
+            client_id,
+        ))?;
+        Ok(tab_index)
+    }
+    fn move_pane_group_into_broken_out_tab(&mut self, tab_index: usize) -> Result<()> {
+        let err_context = || format!("failed to move pane group into tab {tab_index}");
+        let Some(pane_ids) = self.pane_groups_to_break_into_tabs.remove(&tab_index) else {
+            return Ok(());
+        };
+        for pane_id in pane_ids {
+            // the pane might have been closed or moved to this tab in the meantime
+            let Some(source_tab) = self
+                .tabs
+                .values_mut()
+                .find(|t| t.index != tab_index && t.has_non_suppressed_pane_with_pid(&pane_id))
+            else {
+                continue;
+            };
+            let pane_is_floating = source_tab.pane_is_floating(&pane_id);
+            let Some(pane) = source_tab.extract_pane(pane_id, None) else {
+                continue;
+            };
+            let destination_tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+            if pane_is_floating {
+                destination_tab.show_floating_panes();
+                destination_tab.add_floating_pane(pane, pane_id, None, None)?;
+            } else {
+                destination_tab.add_tiled_pane(pane, pane_id, None)?;
+            }
+        }
+        self.log_and_report_session_state()
+    }
+    pub fn record_action_in_journal(&mut self, action: &Action, client_id: ClientId) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let tab_client_id = self.connected_client_id(client_id);
+        let tab_position = tab_client_id
+            .and_then(|c| self.get_active_tab(c).ok())
+            .map(|t| t.position)
+            .unwrap_or(0);
+        if let Some((name, arguments)) = action.journal_name_and_arguments() {
+            self.action_journal.push_back(ActionJournalEntry {
+                action: name.to_owned(),
+                arguments,
+                tab_position,
+                client_id,
+                timestamp,
+            });
+            if self.action_journal.len() > MAX_ACTION_JOURNAL_LENGTH {
+                self.action_journal.pop_front();
+            }
+        }
+        if action != &Action::UndoLayoutChange {
+            if let Some(tab) = tab_client_id.and_then(|c| self.get_active_tab_mut(c).ok()) {
+                tab.record_layout();
+            }
+        }
+    }
+    // actions from the cli come with a client id that has no tab of its own, they act on behalf of
+    // the first connected client
+    fn connected_client_id(&self, client_id: ClientId) -> Option<ClientId> {
+        if self.active_tab_indices.contains_key(&client_id) {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        }
+    }
+    // the panes in the pane group of this client that are not suppressed, with the indices of
+    // their tabs
+    fn pane_group_by_tab(&mut self, client_id: ClientId) -> Vec<(usize, PaneId)> {
+        let tabs = &self.tabs;
+        let Some(pane_group) = self.pane_groups.get_mut(&client_id) else {
+            return vec![];
+        };
+        pane_group.retain(|pane_id| tabs.values().any(|t| t.has_pane_with_pid(pane_id)));
+        pane_group
+            .iter()
+            .filter_map(|pane_id| {
+                tabs.iter()
+                    .find(|(_, t)| t.has_non_suppressed_pane_with_pid(pane_id))
+                    .map(|(tab_index, _)| (*tab_index, *pane_id))
+            })
+            .collect()
+    }
+    // the pane group of this client if its input should be written to it rather than to the
+    // focused pane
+    fn broadcasting_pane_group(&mut self, client_id: ClientId) -> Option<Vec<PaneId>> {
+        let client_id = self.connected_client_id(client_id)?;
+        if !self.pane_group_broadcasts.contains(&client_id) {
+            return None;
+        }
+        let tabs = &self.tabs;
+        let pane_group = self.pane_groups.get_mut(&client_id)?;
+        pane_group.retain(|pane_id| tabs.values().any(|t| t.has_pane_with_pid(pane_id)));
+        if pane_group.is_empty() {
+            None
+        } else {
+            Some(pane_group.clone())
+        }
+    }
+    pub fn toggle_pane_in_group(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to toggle pane in group for client {client_id}");
+        let Some(client_id) = self.connected_client_id(client_id) else {
+            return Ok(());
+        };
+        let Some(pane_id) = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .get_active_pane_id(client_id)
+        else {
+            return Ok(());
+        };
+        let pane_group = self.pane_groups.entry(client_id).or_default();
+        let in_group = match pane_group.iter().position(|p| *p == pane_id) {
+            Some(index) => {
+                pane_group.remove(index);
+                false
+            },
+            None => {
+                pane_group.push(pane_id);
+                true
+            },
+        };
+        self.get_active_tab_mut(client_id)
+            .with_context(err_context)?
+            .set_pane_in_group(pane_id, client_id, in_group);
+        Ok(())
+    }
+    pub fn clear_pane_group(&mut self, client_id: ClientId) {
+        if let Some(client_id) = self.connected_client_id(client_id) {
+            self.remove_pane_group(client_id);
+        }
+    }
+    fn remove_pane_group(&mut self, client_id: ClientId) {
+        self.pane_group_broadcasts.remove(&client_id);
+        for pane_id in self.pane_groups.remove(&client_id).unwrap_or_default() {
+            for tab in self.tabs.values_mut() {
+                tab.set_pane_in_group(pane_id, client_id, false);
+            }
+        }
+    }
+    pub fn toggle_pane_group_broadcast(&mut self, client_id: ClientId) {
+        if let Some(client_id) = self.connected_client_id(client_id) {
+            if !self.pane_group_broadcasts.remove(&client_id) {
+                self.pane_group_broadcasts.insert(client_id);
+            }
+        }
+    }
+    pub fn close_pane_group(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to close pane group of client {client_id}");
+        let Some(client_id) = self.connected_client_id(client_id) else {
+            return Ok(());
+        };
+        for (tab_index, pane_id) in self.pane_group_by_tab(client_id) {
+            if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                tab.close_pane(pane_id, false, None);
+                self.bus
+                    .senders
+                    .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                    .with_context(err_context)?;
+            }
+        }
+        self.remove_pane_group(client_id);
+        Ok(())
+    }
+    pub fn toggle_pane_group_embed_or_floating(&mut self, client_id: ClientId) {
+        let Some(client_id) = self.connected_client_id(client_id) else {
+            return;
+        };
+        for (tab_index, pane_id) in self.pane_group_by_tab(client_id) {
+            if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                tab.toggle_pane_embed_or_floating_for_pane_id(pane_id)
+                    .non_fatal();
+            }
+        }
+    }
+    pub fn rename_pane_group(&mut self, name: String, client_id: ClientId) {
+        let Some(client_id) = self.connected_client_id(client_id) else {
+            return;
+        };
+        for (tab_index, pane_id) in self.pane_group_by_tab(client_id) {
+            if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                tab.rename_pane(name.as_bytes().to_vec(), pane_id)
+                    .non_fatal();
+            }
+        }
+    }
+    /// Writes to the pane group of this client if it broadcasts its input to it, returns None if
+    /// the input should be written to the focused pane instead
+    pub fn write_to_pane_group(
+        &mut self,
+        bytes: &[u8],
+        client_id: ClientId,
+    ) -> Result<Option<bool>> {
+        let Some(pane_group) = self.broadcasting_pane_group(client_id) else {
+            return Ok(None);
+        };
+        let mut state_changed = false;
+        for tab in self.tabs.values_mut() {
+            if tab.write_to_panes_in_group(bytes, &pane_group, client_id)? {
+                state_changed = true;
+            }
+        }
+        Ok(Some(state_changed))
+    }
+    pub fn write_key_to_pane_group(
+        &mut self,
+        key: &Key,
+        event_type: KeyEventType,
+        client_id: ClientId,
+    ) -> Result<Option<bool>> {
+        let Some(pane_group) = self.broadcasting_pane_group(client_id) else {
+            return Ok(None);
+        };
+        let mut state_changed = false;
+        for tab in self.tabs.values_mut() {
+            if tab.write_key_to_panes_in_group(key, event_type, &pane_group, client_id)? {
+                state_changed = true;
+            }
+        }
+        Ok(Some(state_changed))
+    }
     pub fn break_pane_to_new_tab(
         &mut self,
         direction: Direction,
         client_id: ClientId,
     ) -> Result<()> {
-        let err_context = || "failed break pane out of tab".to_string();
-        if self.tabs.len() > 1 {
-            let (active_pane_id, active_pane, pane_to_break_is_floating) = {
-                let active_tab = self.get_active_tab_mut(client_id)?;
-                let active_pane_id = active_tab
-                    .get_active_pane_id(client_id)
-                    .with_context(err_context)?;
-                let pane_to_break_is_floating = active_tab.are_floating_panes_visible();
-                let active_pane = active_tab
-                    .close_pane(active_pane_id, false, Some(client_id))
-                    .with_context(err_context)?;
-                (active_pane_id, active_pane, pane_to_break_is_floating)
-            };
-            let update_mode_infos = false;
-            match direction {
-                Direction::Right | Direction::Down => {
-                    self.switch_tab_next(None, update_mode_infos, client_id)?;
+        let err_context = || "failed break pane out of tab".to_string();
+        if self.tabs.len() > 1 {
+            let (active_pane_id, active_pane, pane_to_break_is_floating) = {
+                let active_tab = self.get_active_tab_mut(client_id)?;
+                let active_pane_id = active_tab
+                    .get_active_pane_id(client_id)
+                    .with_context(err_context)?;
+                let pane_to_break_is_floating = active_tab.are_floating_panes_visible();
+                let active_pane = active_tab
+                    .close_pane(active_pane_id, false, Some(client_id))
+                    .with_context(err_context)?;
+                (active_pane_id, active_pane, pane_to_break_is_floating)
+            };
+            let update_mode_infos = false;
+            match direction {
+                Direction::Right | Direction::Down => {
+                    self.switch_tab_next(None, update_mode_infos, client_id)?;
+                },
+                Direction::Left | Direction::Up => {
+                    self.switch_tab_prev(None, update_mode_infos, client_id)?;
+                },
+            };
+            let new_active_tab = self.get_active_tab_mut(client_id)?;
+
+            if pane_to_break_is_floating {
+                new_active_tab.show_floating_panes();
+                new_active_tab.add_floating_pane(
+                    active_pane,
+                    active_pane_id,
+                    None,
+                    Some(client_id),
+                )?;
+            } else {
+                new_active_tab.hide_floating_panes();
+                new_active_tab.add_tiled_pane(active_pane, active_pane_id, Some(client_id))?;
+            }
+
+            self.log_and_report_session_state()?;
+        } else {
+            let active_pane_id = {
+                let active_tab = self.get_active_tab_mut(client_id)?;
+                active_tab
+                    .get_active_pane_id(client_id)
+                    .with_context(err_context)?
+            };
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                    vec![active_pane_id],
+                    "No other tabs to add pane to!".into(),
+                ))
+                .with_context(err_context)?;
+        }
+        self.unblock_input()?;
+        self.render(None)?;
+        Ok(())
+    }
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) {
+        let tab_with_both_panes = self.tabs.values_mut().find(|t| {
+            let tiled_pane_ids = t.get_tiled_pane_ids();
+            tiled_pane_ids.contains(&first_pane_id) && tiled_pane_ids.contains(&second_pane_id)
+        });
+        match tab_with_both_panes {
+            Some(tab) => {
+                if !tab.swap_panes(first_pane_id, second_pane_id) {
+                    log::error!(
+                        "Failed to swap panes {:?} and {:?}",
+                        first_pane_id,
+                        second_pane_id
+                    );
+                }
+            },
+            None => {
+                log::error!(
+                    "Could not find tiled panes {:?} and {:?} in the same tab",
+                    first_pane_id,
+                    second_pane_id
+                );
+            },
+        }
+    }
+    pub fn change_floating_pane_coordinates(
+        &mut self,
+        pane_id: PaneId,
+        floating_pane_coordinates: FloatingPaneCoordinates,
+    ) -> Result<()> {
+        let err_context = || format!("failed to change coordinates of pane {pane_id:?}");
+        let tab_with_pane = self
+            .tabs
+            .values_mut()
+            .find(|t| t.has_non_suppressed_pane_with_pid(&pane_id))
+            .ok_or_else(|| anyhow!("pane {pane_id:?} not found"))
+            .with_context(err_context)?;
+        tab_with_pane.change_floating_pane_coordinates(&pane_id, floating_pane_coordinates)
+    }
+    pub fn set_pane_pinned(&mut self, pane_id: PaneId, should_be_pinned: bool) -> Result<()> {
+        let err_context = || format!("failed to set pinned state of pane {pane_id:?}");
+        let tab_with_pane = self
+            .tabs
+            .values_mut()
+            .find(|t| t.has_non_suppressed_pane_with_pid(&pane_id))
+            .ok_or_else(|| anyhow!("pane {pane_id:?} not found"))
+            .with_context(err_context)?;
+        tab_with_pane.set_pane_pinned(&pane_id, should_be_pinned)
+    }
+    pub fn move_pane_to_tab(
+        &mut self,
+        pane_id: PaneId,
+        tab_index_or_name: TabIndexOrName,
+        should_float: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to move pane {:?} to tab", pane_id);
+        let destination_tab_index = match &tab_index_or_name {
+            TabIndexOrName::Index(index) => self
+                .tabs
+                .values()
+                .find(|t| t.position + 1 == *index as usize)
+                .map(|t| t.index),
+            TabIndexOrName::Name(name) => self
+                .tabs
+                .values()
+                .find(|t| &t.name == name)
+                .map(|t| t.index),
+        };
+        let source_tab_index = self
+            .tabs
+            .values()
+            .find(|t| t.has_pane_with_pid(&pane_id))
+            .map(|t| t.index);
+        let (source_tab_index, destination_tab_index) =
+            match (source_tab_index, destination_tab_index) {
+                (Some(source_tab_index), Some(destination_tab_index)) => {
+                    (source_tab_index, destination_tab_index)
                 },
-                Direction::Left | Direction::Up => {
-                    self.switch_tab_prev(None, update_mode_infos, client_id)?;
+                (None, _) => {
+                    log::error!("Could not find pane with id: {:?}", pane_id);
+                    return self.unblock_input();
+                },
+                (_, None) => {
+                    log::error!("Could not find tab: {:?}", tab_index_or_name);
+                    return self.unblock_input();
                 },
             };
-            let new_active_tab = self.get_active_tab_mut(client_id)?;
-
-            if pane_to_break_is_floating {
-                new_active_tab.show_floating_panes();
-                new_active_tab.add_floating_pane(active_pane, active_pane_id, Some(client_id))?;
+        let error_message = {
+            let source_tab = self.tabs.get(&source_tab_index).with_context(err_context)?;
+            let is_last_tiled_pane = source_tab.get_selectable_tiled_panes_count() == 1
+                && source_tab.get_tiled_pane_ids().contains(&pane_id);
+            let has_other_panes = source_tab.get_static_and_floating_pane_ids().len() > 1;
+            if source_tab_index == destination_tab_index {
+                Some("Pane is already in this tab!")
+            } else if is_last_tiled_pane && has_other_panes {
+                Some("Cannot move the last tiled pane out of a tab!")
+            } else if !self
+                .tabs
+                .get_mut(&destination_tab_index)
+                .with_context(err_context)?
+                .has_room_for_new_pane(should_float)
+            {
+                Some("No room for pane in tab!")
             } else {
-                new_active_tab.hide_floating_panes();
-                new_active_tab.add_tiled_pane(active_pane, active_pane_id, Some(client_id))?;
+                None
             }
-
-            self.log_and_report_session_state()?;
-        } else {
-            let active_pane_id = {
-                let active_tab = self.get_active_tab_mut(client_id)?;
-                active_tab
-                    .get_active_pane_id(client_id)
-                    .with_context(err_context)?
-            };
+        };
+        if let Some(error_message) = error_message {
             self.bus
                 .senders
                 .send_to_background_jobs(BackgroundJob::DisplayPaneError(
-                    vec![active_pane_id],
-                    "No other tabs to add pane to!".into(),
+                    vec![pane_id],
+                    error_message.into(),
                 ))
                 .with_context(err_context)?;
+            return self.unblock_input();
+        }
+        let pane = self
+            .tabs
+            .get_mut(&source_tab_index)
+            .and_then(|tab| tab.extract_pane(pane_id, Some(client_id)))
+            .with_context(err_context)?;
+        let destination_tab = self
+            .tabs
+            .get_mut(&destination_tab_index)
+            .with_context(err_context)?;
+        if should_float {
+            destination_tab.show_floating_panes();
+            destination_tab.add_floating_pane(pane, pane_id, None, Some(client_id))?;
+        } else {
+            destination_tab.add_tiled_pane(pane, pane_id, Some(client_id))?;
         }
+        self.log_and_report_session_state()?;
         self.unblock_input()?;
         self.render(None)?;
         Ok(())
@@ -1984,8 +2939,12 @@ impl Screen {
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
 
-        for (tab_index, tab) in self.tabs.values().enumerate() {
-            let tab_is_focused = active_tab_index == Some(&tab_index);
+        // tabs are serialized in the order they appear in, which can differ from the order they
+        // were created in if they were moved
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|t| t.position);
+        for tab in tabs {
+            let tab_is_focused = active_tab_index == Some(&tab.index);
             let hide_floating_panes = !tab.are_floating_panes_visible();
             let mut suppressed_panes = HashMap::new();
             for (triggering_pane_id, p) in tab.get_suppressed_panes() {
@@ -2009,18 +2968,18 @@ impl Screen {
                     }
                 })
                 .map(|(pane_id, p)| {
+                    let (pane_contents, pane_snapshot_file) =
+                        self.serialized_pane_contents(pane_id, p.as_ref());
                     PaneLayoutMetadata::new(
                         pane_id,
                         p.position_and_size(),
                         p.borderless(),
+                        false, // tiled panes are never pinned
                         p.invoked_with().clone(),
                         p.custom_title(),
                         active_pane_id == Some(pane_id),
-                        if self.serialize_pane_viewport {
-                            p.serialize(self.scrollback_lines_to_serialize)
-                        } else {
-                            None
-                        },
+                        pane_contents,
+                        pane_snapshot_file,
                     )
                 })
                 .collect();
@@ -2040,18 +2999,18 @@ impl Screen {
                     }
                 })
                 .map(|(pane_id, p)| {
+                    let (pane_contents, pane_snapshot_file) =
+                        self.serialized_pane_contents(pane_id, p.as_ref());
                     PaneLayoutMetadata::new(
                         pane_id,
                         p.position_and_size(),
                         false, // floating panes are never borderless
+                        p.is_pinned(),
                         p.invoked_with().clone(),
                         p.custom_title(),
                         active_pane_id == Some(pane_id),
-                        if self.serialize_pane_viewport {
-                            p.serialize(self.scrollback_lines_to_serialize)
-                        } else {
-                            None
-                        },
+                        pane_contents,
+                        pane_snapshot_file,
                     )
                 })
                 .collect();
@@ -2065,6 +3024,34 @@ impl Screen {
         }
         session_layout_metadata
     }
+    // terminal panes are serialized as binary snapshots which are written separately (see
+    // take_pane_snapshots), so for them we only return the name of their snapshot file
+    fn serialized_pane_contents(
+        &self,
+        pane_id: PaneId,
+        pane: &dyn Pane,
+    ) -> (Option<String>, Option<String>) {
+        if !self.serialize_pane_viewport {
+            return (None, None);
+        }
+        match pane_id {
+            PaneId::Terminal(terminal_id) => (None, Some(pane_snapshot_file_name(terminal_id))),
+            PaneId::Plugin(_) => (pane.serialize(self.scrollback_lines_to_serialize), None),
+        }
+    }
+    /// Snapshots of the contents of all terminal panes by their file name, those that did not
+    /// change since the last time this was called are None
+    fn take_pane_snapshots(&mut self) -> BTreeMap<String, Option<GridSnapshotDelta>> {
+        let mut pane_snapshots = BTreeMap::new();
+        for tab in self.tabs.values_mut() {
+            for (pane_id, snapshot) in tab.take_pane_snapshots() {
+                if let PaneId::Terminal(terminal_id) = pane_id {
+                    pane_snapshots.insert(pane_snapshot_file_name(terminal_id), snapshot);
+                }
+            }
+        }
+        pane_snapshots
+    }
     fn update_plugin_loading_stage(
         &mut self,
         pid: u32,
@@ -2153,11 +3140,20 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                screen.terminals_with_new_output.insert(pid);
+                if !screen.pane_waits.is_empty() {
+                    screen.check_pane_waits_for_output(pid, &vte_bytes)?;
+                }
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
+                        let exit_code_before = tab.last_command_exit_code(pid);
                         tab.handle_pty_bytes(pid, vte_bytes)
                             .context("failed to process pty bytes")?;
+                        // a finished command (OSC 133;D) changes the pane's info
+                        if tab.last_command_exit_code(pid) != exit_code_before {
+                            screen.log_and_report_session_state()?;
+                        }
                         break;
                     }
                 }
@@ -2188,6 +3184,7 @@ pub(crate) fn screen_thread_main(
                 should_float,
                 hold_for_command,
                 invoked_with,
+                floating_pane_coordinates,
                 client_or_tab_index,
             ) => {
                 match client_or_tab_index {
@@ -2197,6 +3194,7 @@ pub(crate) fn screen_thread_main(
                                initial_pane_title,
                                should_float,
                                invoked_with,
+                               floating_pane_coordinates,
                                Some(client_id)
                            )
                         }, ?);
@@ -2221,6 +3219,7 @@ pub(crate) fn screen_thread_main(
                                 initial_pane_title,
                                 should_float,
                                 invoked_with,
+                                floating_pane_coordinates,
                                 None,
                             )?;
                             if let Some(hold_for_command) = hold_for_command {
@@ -2324,21 +3323,56 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::WriteCharacter(bytes, client_id) => {
                 let mut state_changed = false;
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| {
-                        let write_result = match tab.is_sync_panes_active() {
-                            true => tab.write_to_terminals_on_current_tab(bytes, client_id),
-                            false => tab.write_to_active_terminal(bytes, client_id),
-                        };
-                        if let Ok(true) = write_result {
-                            state_changed = true;
-                        }
-                        write_result
-                    },
-                    ?
-                );
+                if let Some(group_state_changed) = screen.write_to_pane_group(&bytes, client_id)? {
+                    state_changed = group_state_changed;
+                } else {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| {
+                            let write_result = match tab.is_sync_panes_active() {
+                                true => tab.write_to_terminals_on_current_tab(bytes, client_id),
+                                false => tab.write_to_active_terminal(bytes, client_id),
+                            };
+                            if let Ok(true) = write_result {
+                                state_changed = true;
+                            }
+                            write_result
+                        },
+                        ?
+                    );
+                }
+                if state_changed {
+                    screen.log_and_report_session_state()?;
+                }
+            },
+            ScreenInstruction::WriteKey(key, event_type, client_id) => {
+                let mut state_changed = false;
+                if let Some(group_state_changed) =
+                    screen.write_key_to_pane_group(&key, event_type, client_id)?
+                {
+                    state_changed = group_state_changed;
+                } else {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| {
+                            let write_result = match tab.is_sync_panes_active() {
+                                true => tab.write_key_to_terminals_on_current_tab(
+                                    &key, event_type, client_id,
+                                ),
+                                false => {
+                                    tab.write_key_to_active_terminal(&key, event_type, client_id)
+                                },
+                            };
+                            if let Ok(true) = write_result {
+                                state_changed = true;
+                            }
+                            write_result
+                        },
+                        ?
+                    );
+                }
                 if state_changed {
                     screen.log_and_report_session_state()?;
                 }
@@ -2556,6 +3590,84 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::SwapPanes(first_pane_id, second_pane_id) => {
+                screen.swap_panes(first_pane_id, second_pane_id);
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::SwapPaneWith(direction, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .swap_active_pane_with(direction, client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::ChangeFloatingPaneCoordinates(
+                pane_id,
+                floating_pane_coordinates,
+            ) => {
+                screen
+                    .change_floating_pane_coordinates(pane_id, floating_pane_coordinates)
+                    .non_fatal();
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::TogglePanePinned(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .toggle_pane_pinned(client_id), ?);
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::SetPanePinned(pane_id, should_be_pinned) => {
+                screen
+                    .set_pane_pinned(pane_id, should_be_pinned)
+                    .non_fatal();
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::TogglePaneInGroup(client_id) => {
+                screen.toggle_pane_in_group(client_id)?;
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::ClearPaneGroup(client_id) => {
+                screen.clear_pane_group(client_id);
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::TogglePaneGroupBroadcast(client_id) => {
+                screen.toggle_pane_group_broadcast(client_id);
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ClosePaneGroup(client_id) => {
+                screen.close_pane_group(client_id)?;
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::TogglePaneGroupEmbedOrFloating(client_id) => {
+                screen.toggle_pane_group_embed_or_floating(client_id);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render(None)?;
+            },
+            ScreenInstruction::RenamePaneGroup(name, client_id) => {
+                screen.rename_pane_group(name, client_id);
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::ScrollUpAt(point, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2605,6 +3717,36 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ScrollToPreviousPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_previous_prompt(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ScrollToNextPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_next_prompt(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CopyLastCommandOutput(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .copy_last_command_output(client_id), ?
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::PageScrollUp(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2780,6 +3922,16 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render(None)?;
             },
+            ScreenInstruction::MoveTab(direction, client_id) => {
+                screen.move_active_tab(direction, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::MoveTabToIndex(tab_index, client_id) => {
+                screen.move_active_tab_to_index(tab_index, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
             ScreenInstruction::CloseTab(client_id) => {
                 screen.close_tab(client_id)?;
                 screen.unblock_input()?;
@@ -2827,6 +3979,7 @@ pub(crate) fn screen_thread_main(
                     tab_index,
                     client_id,
                 )?;
+                screen.move_pane_group_into_broken_out_tab(tab_index)?;
                 pending_tab_ids.remove(&tab_index);
                 if pending_tab_ids.is_empty() {
                     for (tab_index, client_id) in pending_tab_switches.drain() {
@@ -3028,7 +4181,15 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render(None)?;
             },
-            ScreenInstruction::AddClient(client_id, tab_position_to_focus, pane_id_to_focus) => {
+            ScreenInstruction::AddClient(
+                client_id,
+                tab_position_to_focus,
+                pane_id_to_focus,
+                is_read_only,
+            ) => {
+                if is_read_only {
+                    screen.read_only_clients.insert(client_id);
+                }
                 screen.add_client(client_id)?;
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
                     if is_plugin {
@@ -3130,6 +4291,34 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SearchToggleRegex(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CopyModeMove(motion, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.copy_mode_move(motion, client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CopyModeToggleSelection(selection, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .copy_mode_toggle_selection(selection, client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -3344,6 +4533,7 @@ pub(crate) fn screen_thread_main(
                             should_float,
                             Some(run_plugin),
                             None,
+                            None,
                         )
                     }, ?);
                 } else if let Some(active_tab) =
@@ -3355,6 +4545,7 @@ pub(crate) fn screen_thread_main(
                         should_float,
                         Some(run_plugin),
                         None,
+                        None,
                     )?;
                 } else {
                     log::error!("Tab index not found: {:?}", tab_index);
@@ -3586,6 +4777,10 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::RenameTabWithPaneId(pane_id, new_name) => {
+                screen.rename_tab_with_pane_id(pane_id, new_name);
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::RequestPluginPermissions(plugin_id, plugin_permission) => {
                 let all_tabs = screen.get_tabs_mut();
                 let found = all_tabs.values_mut().any(|tab| {
@@ -3607,15 +4802,106 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::BreakPane(default_layout, default_shell, client_id) => {
                 screen.break_pane(default_shell, default_layout, client_id)?;
             },
+            ScreenInstruction::BreakPaneGroup(default_layout, default_shell, client_id) => {
+                screen.break_pane_group(default_shell, default_layout, client_id)?;
+            },
             ScreenInstruction::BreakPaneRight(client_id) => {
                 screen.break_pane_to_new_tab(Direction::Right, client_id)?;
             },
             ScreenInstruction::BreakPaneLeft(client_id) => {
                 screen.break_pane_to_new_tab(Direction::Left, client_id)?;
             },
+            ScreenInstruction::MovePaneToTab(
+                pane_id,
+                tab_index_or_name,
+                should_float,
+                client_id,
+            ) => {
+                screen.move_pane_to_tab(pane_id, tab_index_or_name, should_float, client_id)?;
+            },
             ScreenInstruction::UpdateSessionInfos(new_session_infos, resurrectable_sessions) => {
                 screen.update_session_infos(new_session_infos, resurrectable_sessions)?;
             },
+            ScreenInstruction::ReportTerminalCwdsAndCommands => {
+                screen.report_terminal_cwds_and_commands()?;
+            },
+            ScreenInstruction::UpdateTerminalCwdsAndCommands(
+                queried_terminal_ids,
+                terminal_ids_to_cwds,
+                terminal_ids_to_running_commands,
+            ) => {
+                screen.update_terminal_cwds_and_commands(
+                    queried_terminal_ids,
+                    terminal_ids_to_cwds,
+                    terminal_ids_to_running_commands,
+                )?;
+            },
+            ScreenInstruction::WaitForPane(pipe_id, pane, condition, block, client_id) => {
+                screen.wait_for_pane(pipe_id, pane, condition, block, client_id)?;
+            },
+            ScreenInstruction::Reconfigure(new_keybinds, new_style, new_options, client_id) => {
+                screen.reconfigure(new_keybinds, new_style, *new_options, client_id)?;
+            },
+            ScreenInstruction::ConfigReloadError(error, client_id) => {
+                screen.show_config_reload_error(error, client_id)?;
+            },
+            ScreenInstruction::RecordActionInJournal(action, client_id) => {
+                screen.record_action_in_journal(&action, client_id);
+            },
+            ScreenInstruction::QueryActionJournal(client_id) => {
+                let journal_lines = screen
+                    .action_journal
+                    .iter()
+                    .map(|entry| {
+                        let mut line = format!(
+                            "{} tab {} client {}: {}",
+                            entry.timestamp,
+                            entry.tab_position + 1,
+                            entry.client_id,
+                            entry.action
+                        );
+                        for argument in &entry.arguments {
+                            line.push_str(&format!(" {:?}", argument));
+                        }
+                        line
+                    })
+                    .collect::<Vec<String>>();
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(journal_lines, client_id))?;
+            },
+            ScreenInstruction::GetActionJournal(plugin_id, client_id) => {
+                screen
+                    .bus
+                    .senders
+                    .send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::ActionJournal(screen.action_journal.iter().cloned().collect()),
+                    )]))
+                    .context("failed to send action journal")?;
+            },
+            ScreenInstruction::UndoLayoutChange(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| {
+                        if !tab.undo_layout_change() {
+                            log::info!("No layout change to undo in tab {}", tab.name);
+                        }
+                    }
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::RefreshPluginVisibility(plugin_ids) => {
+                screen.refresh_plugin_visibility(plugin_ids)?;
+            },
+            ScreenInstruction::UpdatePaneOutputLog(terminal_id, output_log_path) => {
+                screen.update_pane_output_log(terminal_id, output_log_path)?;
+            },
             ScreenInstruction::ReplacePane(
                 new_pane_id,
                 hold_for_command,
@@ -3704,6 +4990,35 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.unblock_input()?;
             },
+            ScreenInstruction::GetPaneScrollback(
+                pane_id,
+                scrollback_lines,
+                plugin_id,
+                client_id,
+            ) => {
+                let pane_contents = screen
+                    .get_tabs_mut()
+                    .values()
+                    .find_map(|tab| tab.get_pane_contents(pane_id, scrollback_lines));
+                match pane_contents {
+                    Some(pane_contents) => {
+                        screen
+                            .bus
+                            .senders
+                            .send_to_plugin(PluginInstruction::Update(vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::PaneContents(pane_id.into(), pane_contents),
+                            )]))
+                            .with_context(|| {
+                                format!("failed to send contents of pane {:?}", pane_id)
+                            })?;
+                    },
+                    None => {
+                        log::error!("Failed to find pane with id: {:?}", pane_id);
+                    },
+                }
+            },
         }
     }
     Ok(())
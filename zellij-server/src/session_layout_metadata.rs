@@ -1,5 +1,5 @@
 use crate::panes::PaneId;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use zellij_utils::common_path::common_path_all;
 use zellij_utils::pane_size::PaneGeom;
@@ -174,6 +174,26 @@ impl SessionLayoutMetadata {
             }
         }
     }
+    pub fn update_terminal_envs(
+        &mut self,
+        mut terminal_ids_to_envs: HashMap<u32, BTreeMap<String, String>>,
+    ) {
+        let mut update_env_in_pane_metadata = |pane_layout_metadata: &mut PaneLayoutMetadata| {
+            if let PaneId::Terminal(id) = pane_layout_metadata.id {
+                if let Some(env) = terminal_ids_to_envs.remove(&id) {
+                    pane_layout_metadata.env = env;
+                }
+            }
+        };
+        for tab in self.tabs.iter_mut() {
+            for pane_layout_metadata in tab.tiled_panes.iter_mut() {
+                update_env_in_pane_metadata(pane_layout_metadata);
+            }
+            for pane_layout_metadata in tab.floating_panes.iter_mut() {
+                update_env_in_pane_metadata(pane_layout_metadata);
+            }
+        }
+    }
     pub fn update_plugin_cmds(&mut self, mut plugin_ids_to_run_plugins: HashMap<u32, RunPlugin>) {
         let mut update_cmd_in_pane_metadata = |pane_layout_metadata: &mut PaneLayoutMetadata| {
             if let PaneId::Plugin(id) = pane_layout_metadata.id {
@@ -226,9 +246,12 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             run: self.run,
             cwd: self.cwd,
             is_borderless: self.is_borderless,
+            is_pinned: self.is_pinned,
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
+            pane_snapshot_file: self.pane_snapshot_file,
+            env: self.env,
         }
     }
 }
@@ -249,20 +272,26 @@ pub struct PaneLayoutMetadata {
     run: Option<Run>,
     cwd: Option<PathBuf>,
     is_borderless: bool,
+    is_pinned: bool,
     title: Option<String>,
     is_focused: bool,
     pane_contents: Option<String>,
+    pane_snapshot_file: Option<String>,
+    env: BTreeMap<String, String>,
 }
 
 impl PaneLayoutMetadata {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: PaneId,
         geom: PaneGeom,
         is_borderless: bool,
+        is_pinned: bool,
         run: Option<Run>,
         title: Option<String>,
         is_focused: bool,
         pane_contents: Option<String>,
+        pane_snapshot_file: Option<String>,
     ) -> Self {
         PaneLayoutMetadata {
             id,
@@ -270,9 +299,12 @@ impl PaneLayoutMetadata {
             run,
             cwd: None,
             is_borderless,
+            is_pinned,
             title,
             is_focused,
             pane_contents,
+            pane_snapshot_file,
+            env: BTreeMap::new(),
         }
     }
 }
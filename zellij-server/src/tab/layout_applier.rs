@@ -303,6 +303,10 @@ impl<'a> LayoutApplier<'a> {
                                 self.arrow_fonts,
                                 self.styled_underlines,
                             );
+                            if let Some(snapshot_file) = &layout.pane_initial_snapshot_file {
+                                new_pane.restore_snapshot(snapshot_file);
+                                new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+                            }
                             if let Some(pane_initial_contents) = &layout.pane_initial_contents {
                                 new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
                                 new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -399,6 +403,7 @@ impl<'a> LayoutApplier<'a> {
                     new_pane.handle_pty_bytes("\n\r".as_bytes().into());
                 }
                 new_pane.set_borderless(false);
+                new_pane.set_pinned(floating_pane_layout.pinned.unwrap_or(false));
                 new_pane.set_content_offset(Offset::frame(1));
                 resize_pty!(
                     new_pane,
@@ -435,11 +440,16 @@ impl<'a> LayoutApplier<'a> {
                     self.arrow_fonts,
                     self.styled_underlines,
                 );
+                if let Some(snapshot_file) = &floating_pane_layout.pane_initial_snapshot_file {
+                    new_pane.restore_snapshot(snapshot_file);
+                    new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+                }
                 if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
                     new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
                     new_pane.handle_pty_bytes("\n\r".as_bytes().into());
                 }
                 new_pane.set_borderless(false);
+                new_pane.set_pinned(floating_pane_layout.pinned.unwrap_or(false));
                 new_pane.set_content_offset(Offset::frame(1));
                 if let Some(held_command) = hold_for_command {
                     new_pane.hold(None, true, held_command.clone());
@@ -656,6 +666,9 @@ impl<'a> LayoutApplier<'a> {
         if let Some(pane_title) = floating_pane_layout.and_then(|f| f.name.clone()) {
             pane.set_title(pane_title);
         }
+        if let Some(pinned) = floating_pane_layout.and_then(|f| f.pinned) {
+            pane.set_pinned(pinned);
+        }
         pane.set_content_offset(Offset::frame(1));
     }
     fn total_space_for_tiled_panes(&self) -> PaneGeom {
@@ -11,7 +11,8 @@ use std::env::temp_dir;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::{
-    Direction, PaneInfo, PermissionStatus, PermissionType, PluginPermission, ResizeStrategy,
+    Direction, FloatingPaneCoordinates, Key, KeyEventType, PaneContents, PaneInfo,
+    PermissionStatus, PermissionType, PluginPermission, ResizeStrategy,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -29,8 +30,9 @@ use self::clipboard::ClipboardProvider;
 use crate::{
     os_input_output::ServerOsApi,
     output::{CharacterChunk, Output, SixelImageChunk},
+    panes::grid_snapshot::GridSnapshotDelta,
     panes::sixel::SixelImageStore,
-    panes::{FloatingPanes, TiledPanes},
+    panes::{FloatingPanes, TiledPanes, TiledPanesGeometry},
     panes::{LinkHandler, PaneId, PluginPane, TerminalPane},
     plugins::PluginInstruction,
     pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
@@ -42,12 +44,13 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::time::Instant;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     str,
 };
 use zellij_utils::{
     data::{Event, InputMode, ModeInfo, Palette, PaletteColor, Style},
     input::{
+        actions::{CopyModeMotion, CopyModeSelection},
         command::TerminalAction,
         layout::{
             FloatingPaneLayout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation,
@@ -132,6 +135,7 @@ pub const MIN_TERMINAL_HEIGHT: usize = 5;
 pub const MIN_TERMINAL_WIDTH: usize = 5;
 
 const MAX_PENDING_VTE_EVENTS: usize = 7000;
+const MAX_LAYOUT_HISTORY_LENGTH: usize = 100;
 
 type HoldForCommand = Option<RunCommand>;
 
@@ -173,6 +177,7 @@ pub(crate) struct Tab {
     focus_pane_id: Option<PaneId>,
     copy_on_select: bool,
     last_mouse_hold_position: Option<Position>,
+    unselectable_pane_with_mouse_down: Option<PaneId>,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     pids_waiting_resize: HashSet<u32>, // u32 is the terminal_id
@@ -183,6 +188,9 @@ pub(crate) struct Tab {
     pending_instructions: Vec<BufferedTabInstruction>, // instructions that came while the tab was
     // pending and need to be re-applied
     swap_layouts: SwapLayouts,
+    copy_mode_panes: HashMap<ClientId, PaneId>, // the pane each client entered copy mode in
+    layout_history: VecDeque<TiledPanesGeometry>, // the geometry of the tiled panes before each
+    // structural action, used to undo layout changes
     default_shell: Option<PathBuf>,
     debug: bool,
     arrow_fonts: bool,
@@ -218,6 +226,14 @@ pub trait Pane {
     fn adjust_input_to_terminal(&mut self, _input_bytes: Vec<u8>) -> Option<AdjustedInput> {
         None
     }
+    fn serialize_key(&self, key: &Key, event_type: KeyEventType) -> Option<Vec<u8>> {
+        // the legacy encoding by default (only terminal-panes currently support the kitty
+        // keyboard protocol), which cannot represent releasing a key
+        match event_type {
+            KeyEventType::Release => None,
+            KeyEventType::Press | KeyEventType::Repeat => key.serialize_legacy(),
+        }
+    }
     fn position_and_size(&self) -> PaneGeom;
     fn current_geom(&self) -> PaneGeom;
     fn geom_override(&self) -> Option<PaneGeom>;
@@ -257,13 +273,29 @@ pub trait Pane {
     fn dump_screen(&mut self, _client_id: ClientId, _full: bool) -> String {
         "".to_owned()
     }
+    fn pane_contents(&self, _scrollback_lines: usize) -> PaneContents {
+        // empty by default (only terminal panes have text contents)
+        PaneContents::default()
+    }
     fn scroll_up(&mut self, count: usize, client_id: ClientId);
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
     fn is_scrolled(&self) -> bool;
+    // read-only clients scroll their own view of the pane rather than the pane itself, whose
+    // viewport is shared by all clients (only terminal panes have a scrollback)
+    fn scroll_up_for_viewer(&mut self, _count: usize, _client_id: ClientId) {}
+    fn scroll_down_for_viewer(&mut self, _count: usize, _client_id: ClientId) {}
+    fn clear_scroll_for_viewer(&mut self, _client_id: ClientId) {}
+    fn is_scrolled_for_viewer(&self, _client_id: ClientId) -> bool {
+        false
+    }
+    fn render_for_viewer(&self, _client_id: ClientId) -> Option<Vec<CharacterChunk>> {
+        None
+    }
     fn active_at(&self) -> Instant;
     fn set_active_at(&mut self, instant: Instant);
     fn set_frame(&mut self, frame: bool);
+    fn update_style(&mut self, _style: Style) {}
     fn set_content_offset(&mut self, offset: Offset);
     fn cursor_shape_csi(&self) -> String {
         "\u{1b}[0 q".to_string() // default to non blinking block
@@ -391,6 +423,11 @@ pub trait Pane {
     fn borderless(&self) -> bool;
     fn set_exclude_from_sync(&mut self, exclude_from_sync: bool);
     fn exclude_from_sync(&self) -> bool;
+    fn set_pinned(&mut self, should_be_pinned: bool);
+    fn is_pinned(&self) -> bool;
+    fn set_in_group(&mut self, client_id: ClientId, in_group: bool);
+    fn is_in_group(&self, client_id: ClientId) -> bool;
+    fn is_in_any_group(&self) -> bool;
 
     // TODO: this should probably be merged with the mouse_right_click
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
@@ -445,6 +482,21 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
+    fn enter_copy_mode(&mut self) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn exit_copy_mode(&mut self) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn copy_mode_move(&mut self, _motion: CopyModeMotion) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn copy_mode_toggle_selection(&mut self, _selection: CopyModeSelection) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -474,10 +526,24 @@ pub trait Pane {
     fn exit_status(&self) -> Option<i32> {
         None
     }
+    fn last_command_exit_code(&self) -> Option<i32> {
+        // only terminal panes with shell integration (OSC 133) report this
+        None
+    }
+    fn scroll_to_previous_prompt(&mut self) {}
+    fn scroll_to_next_prompt(&mut self) {}
+    fn select_last_command_output(&mut self) -> Option<String> {
+        None
+    }
     fn rename(&mut self, _buf: Vec<u8>) {}
     fn serialize(&self, _scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         None
     }
+    /// A snapshot of the contents of this pane to be applied to the previous one, if they
+    /// changed since it was taken
+    fn take_snapshot(&mut self) -> Option<GridSnapshotDelta> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -616,6 +682,7 @@ impl Tab {
             focus_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
             last_mouse_hold_position: None,
+            unselectable_pane_with_mouse_down: None,
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             pids_waiting_resize: HashSet::new(),
@@ -623,6 +690,8 @@ impl Tab {
             is_pending: true, // will be switched to false once the layout is applied
             pending_instructions: vec![],
             swap_layouts,
+            copy_mode_panes: HashMap::new(),
+            layout_history: VecDeque::new(),
             default_shell,
             debug,
             arrow_fonts,
@@ -932,6 +1001,19 @@ impl Tab {
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.focus_pane_id = None;
         self.connected_clients.borrow_mut().remove(&client_id);
+        // client ids are reused, and the next client with this one should not start scrolled
+        let tiled_pane_ids: Vec<PaneId> = self.tiled_panes.pane_ids().copied().collect();
+        for pane_id in tiled_pane_ids {
+            if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                pane.clear_scroll_for_viewer(client_id);
+            }
+        }
+        let floating_pane_ids: Vec<PaneId> = self.floating_panes.pane_ids().copied().collect();
+        for pane_id in floating_pane_ids {
+            if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+                pane.clear_scroll_for_viewer(client_id);
+            }
+        }
         self.set_force_render();
     }
     pub fn drain_connected_clients(
@@ -991,7 +1073,36 @@ impl Tab {
                 self.close_pane(focused_pane_id, true, Some(client_id))
             {
                 self.show_floating_panes();
-                self.add_floating_pane(embedded_pane_to_float, focused_pane_id, Some(client_id))?;
+                self.add_floating_pane(
+                    embedded_pane_to_float,
+                    focused_pane_id,
+                    None,
+                    Some(client_id),
+                )?;
+            }
+        }
+        Ok(())
+    }
+    pub fn toggle_pane_embed_or_floating_for_pane_id(&mut self, pane_id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to toggle embedded/floating pane {pane_id:?}");
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        if self.floating_panes.panes_contain(&pane_id) {
+            if self.tiled_panes.has_room_for_new_pane() {
+                let floating_pane_to_embed = self
+                    .close_pane(pane_id, true, None)
+                    .with_context(err_context)?;
+                self.add_tiled_pane(floating_pane_to_embed, pane_id, None)?;
+            }
+        } else if self.tiled_panes.panes_contain(&pane_id) {
+            if self.get_selectable_tiled_panes().count() <= 1 {
+                // don't close the only pane on screen...
+                return Ok(());
+            }
+            if let Some(embedded_pane_to_float) = self.close_pane(pane_id, true, None) {
+                self.show_floating_panes();
+                self.add_floating_pane(embedded_pane_to_float, pane_id, None, None)?;
             }
         }
         Ok(())
@@ -1030,6 +1141,7 @@ impl Tab {
                         default_shell,
                         Some(should_float),
                         name,
+                        None,
                         client_id_or_tab_index,
                     );
                     self.senders
@@ -1048,6 +1160,7 @@ impl Tab {
         initial_pane_title: Option<String>,
         should_float: Option<bool>,
         invoked_with: Option<Run>,
+        floating_pane_coordinates: Option<FloatingPaneCoordinates>,
         client_id: Option<ClientId>,
     ) -> Result<()> {
         let err_context = || format!("failed to create new pane with id {pid:?}");
@@ -1105,7 +1218,7 @@ impl Tab {
             },
         };
         if self.floating_panes.panes_are_visible() {
-            self.add_floating_pane(new_pane, pid, client_id)
+            self.add_floating_pane(new_pane, pid, floating_pane_coordinates, client_id)
         } else {
             self.add_tiled_pane(new_pane, pid, client_id)
         }
@@ -1471,6 +1584,19 @@ impl Tab {
                 .values()
                 .any(|s_p| s_p.1.pid() == PaneId::Terminal(pid))
     }
+    pub fn last_command_exit_code(&self, pid: u32) -> Option<i32> {
+        let pane_id = PaneId::Terminal(pid);
+        self.tiled_panes
+            .get_pane(pane_id)
+            .or_else(|| self.floating_panes.get_pane(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &s_p.1)
+            })
+            .and_then(|p| p.last_command_exit_code())
+    }
     pub fn has_plugin(&self, plugin_id: u32) -> bool {
         self.tiled_panes.panes_contain(&PaneId::Plugin(plugin_id))
             || self
@@ -1492,6 +1618,17 @@ impl Tab {
     pub fn has_non_suppressed_pane_with_pid(&self, pid: &PaneId) -> bool {
         self.tiled_panes.panes_contain(pid) || self.floating_panes.panes_contain(pid)
     }
+    pub fn get_pane_contents(
+        &self,
+        pane_id: PaneId,
+        scrollback_lines: usize,
+    ) -> Option<PaneContents> {
+        self.tiled_panes
+            .get_pane(pane_id)
+            .or_else(|| self.floating_panes.get_pane(pane_id))
+            .or_else(|| self.suppressed_panes.get(&pane_id).map(|s_p| &s_p.1))
+            .map(|pane| pane.pane_contents(scrollback_lines))
+    }
     pub fn handle_pty_bytes(&mut self, pid: u32, bytes: VteBytes) -> Result<()> {
         if self.is_pending {
             self.pending_instructions
@@ -1629,24 +1766,123 @@ impl Tab {
         };
 
         self.clear_search(client_id); // this is an inexpensive operation if empty, if we need more such cleanups we should consider moving this and the rest to some sort of cleanup method
-        let pane_id = if self.floating_panes.panes_are_visible() {
+        let pane_id = self
+            .get_active_pane_id_for_input(client_id)
+            .ok_or_else(|| {
+                anyhow!(format!(
+                    "failed to find active pane id for client {client_id}"
+                ))
+            })
+            .with_context(err_context)?;
+        // Can't use 'err_context' here since it borrows 'input_bytes'
+        self.write_to_pane_id(input_bytes, pane_id, Some(client_id))
+            .with_context(|| format!("failed to write to active terminal for client {client_id}"))
+    }
+
+    pub fn write_key_to_terminals_on_current_tab(
+        &mut self,
+        key: &Key,
+        event_type: KeyEventType,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let mut should_trigger_ui_change = false;
+        let pane_ids = self.get_static_and_floating_pane_ids();
+        for pane_id in pane_ids {
+            if let Some(input_bytes) = self.serialize_key_for_pane(key, event_type, pane_id) {
+                let ui_change_triggered = self
+                    .write_to_pane_id(input_bytes, pane_id, Some(client_id))
+                    .context("failed to write key to terminals on current tab")?;
+                if ui_change_triggered {
+                    should_trigger_ui_change = true;
+                }
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+
+    pub fn write_to_panes_in_group(
+        &mut self,
+        input_bytes: &[u8],
+        pane_group: &[PaneId],
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let mut should_trigger_ui_change = false;
+        for pane_id in pane_group {
+            if !self.has_non_suppressed_pane_with_pid(pane_id) {
+                continue;
+            }
+            let ui_change_triggered = self
+                .write_to_pane_id(input_bytes.to_vec(), *pane_id, Some(client_id))
+                .context("failed to write to panes in group")?;
+            if ui_change_triggered {
+                should_trigger_ui_change = true;
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+
+    pub fn write_key_to_panes_in_group(
+        &mut self,
+        key: &Key,
+        event_type: KeyEventType,
+        pane_group: &[PaneId],
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let mut should_trigger_ui_change = false;
+        for pane_id in pane_group {
+            if !self.has_non_suppressed_pane_with_pid(pane_id) {
+                continue;
+            }
+            if let Some(input_bytes) = self.serialize_key_for_pane(key, event_type, *pane_id) {
+                let ui_change_triggered = self
+                    .write_to_pane_id(input_bytes, *pane_id, Some(client_id))
+                    .context("failed to write key to panes in group")?;
+                if ui_change_triggered {
+                    should_trigger_ui_change = true;
+                }
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+
+    pub fn write_key_to_active_terminal(
+        &mut self,
+        key: &Key,
+        event_type: KeyEventType,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        // each pane gets the key in the encoding its application asked for, keys that can't be
+        // represented in that encoding are dropped
+        match self
+            .get_active_pane_id_for_input(client_id)
+            .and_then(|pane_id| self.serialize_key_for_pane(key, event_type, pane_id))
+        {
+            Some(input_bytes) => self.write_to_active_terminal(input_bytes, client_id),
+            None => Ok(false),
+        }
+    }
+
+    fn get_active_pane_id_for_input(&self, client_id: ClientId) -> Option<PaneId> {
+        if self.floating_panes.panes_are_visible() {
             self.floating_panes
                 .get_active_pane_id(client_id)
                 .or_else(|| self.tiled_panes.get_active_pane_id(client_id))
-                .ok_or_else(|| {
-                    anyhow!(format!(
-                        "failed to find active pane id for client {client_id}"
-                    ))
-                })
-                .with_context(err_context)?
         } else {
-            self.tiled_panes
-                .get_active_pane_id(client_id)
-                .with_context(err_context)?
-        };
-        // Can't use 'err_context' here since it borrows 'input_bytes'
-        self.write_to_pane_id(input_bytes, pane_id, Some(client_id))
-            .with_context(|| format!("failed to write to active terminal for client {client_id}"))
+            self.tiled_panes.get_active_pane_id(client_id)
+        }
+    }
+
+    fn serialize_key_for_pane(
+        &self,
+        key: &Key,
+        event_type: KeyEventType,
+        pane_id: PaneId,
+    ) -> Option<Vec<u8>> {
+        self.floating_panes
+            .get_pane(pane_id)
+            .or_else(|| self.tiled_panes.get_pane(pane_id))
+            .or_else(|| self.suppressed_panes.get(&pane_id).map(|p| &p.1))
+            .and_then(|pane| pane.serialize_key(key, event_type))
     }
 
     pub fn write_to_terminal_at(
@@ -1908,7 +2144,9 @@ impl Tab {
         self.tiled_panes
             .render(output, self.floating_panes.panes_are_visible())
             .with_context(err_context)?;
-        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
+        if (self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes())
+            || self.floating_panes.has_pinned_panes()
+        {
             self.floating_panes
                 .render(output)
                 .with_context(err_context)?;
@@ -1994,6 +2232,26 @@ impl Tab {
         // bool => is_scrollback_editor
         self.suppressed_panes.iter()
     }
+    pub(crate) fn take_pane_snapshots(&mut self) -> Vec<(PaneId, Option<GridSnapshotDelta>)> {
+        let mut pane_snapshots = vec![];
+        let tiled_pane_ids: Vec<PaneId> = self.tiled_panes.pane_ids().copied().collect();
+        for pane_id in tiled_pane_ids {
+            if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                pane_snapshots.push((pane_id, pane.take_snapshot()));
+            }
+        }
+        let floating_pane_ids: Vec<PaneId> = self.floating_panes.pane_ids().copied().collect();
+        for pane_id in floating_pane_ids {
+            if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+                pane_snapshots.push((pane_id, pane.take_snapshot()));
+            }
+        }
+        // panes suppressed by a scrollback editor are serialized in its place
+        for (pane_id, (_is_scrollback_editor, pane)) in self.suppressed_panes.iter_mut() {
+            pane_snapshots.push((*pane_id, pane.take_snapshot()));
+        }
+        pane_snapshots
+    }
     fn get_selectable_tiled_panes(&self) -> impl Iterator<Item = (&PaneId, &Box<dyn Pane>)> {
         self.get_tiled_panes().filter(|(_, p)| p.selectable())
     }
@@ -2037,6 +2295,13 @@ impl Tab {
             .filter(|(_, p)| p.selectable());
         selectable_tiled_panes.count() > 0 || selectable_floating_panes.count() > 0
     }
+    pub fn has_room_for_new_pane(&mut self, should_float: bool) -> bool {
+        if should_float {
+            self.floating_panes.find_room_for_new_pane().is_some()
+        } else {
+            self.tiled_panes.has_room_for_new_pane()
+        }
+    }
     pub fn has_selectable_tiled_panes(&self) -> bool {
         let selectable_tiled_panes = self.tiled_panes.get_panes().filter(|(_, p)| p.selectable());
         selectable_tiled_panes.count() > 0
@@ -2260,6 +2525,18 @@ impl Tab {
                 .move_active_pane(search_backwards, client_id);
         }
     }
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) -> bool {
+        if self.tiled_panes.fullscreen_is_active() {
+            return false;
+        }
+        self.tiled_panes.swap_panes(first_pane_id, second_pane_id)
+    }
+    pub fn swap_active_pane_with(&mut self, direction: Direction, client_id: ClientId) {
+        if self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes.swap_active_pane_with(direction, client_id);
+    }
     pub fn move_active_pane_down(&mut self, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.move_active_pane_down(client_id);
@@ -2617,8 +2894,20 @@ impl Tab {
             ))
             .with_context(err_context)
     }
+    fn client_is_read_only(&self, client_id: ClientId) -> bool {
+        self.mode_info
+            .borrow()
+            .get(&client_id)
+            .unwrap_or(&self.default_mode_info)
+            .is_read_only
+    }
     pub fn scroll_active_terminal_up(&mut self, client_id: ClientId) {
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if client_is_read_only {
+                active_pane.scroll_up_for_viewer(1, client_id);
+                return;
+            }
             active_pane.scroll_up(1, client_id);
         }
     }
@@ -2626,7 +2915,12 @@ impl Tab {
     pub fn scroll_active_terminal_down(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to scroll down active pane for client {client_id}");
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if client_is_read_only {
+                active_pane.scroll_down_for_viewer(1, client_id);
+                return Ok(());
+            }
             active_pane.scroll_down(1, client_id);
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
@@ -2639,9 +2933,14 @@ impl Tab {
     }
 
     pub fn scroll_active_terminal_up_page(&mut self, client_id: ClientId) {
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             // prevent overflow when row == 0
             let scroll_rows = active_pane.rows().max(1) - 1;
+            if client_is_read_only {
+                active_pane.scroll_up_for_viewer(scroll_rows, client_id);
+                return;
+            }
             active_pane.scroll_up(scroll_rows, client_id);
         }
     }
@@ -2650,8 +2949,13 @@ impl Tab {
         let err_context =
             || format!("failed to scroll down one page in active pane for client {client_id}");
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             let scroll_rows = active_pane.get_content_rows();
+            if client_is_read_only {
+                active_pane.scroll_down_for_viewer(scroll_rows, client_id);
+                return Ok(());
+            }
             active_pane.scroll_down(scroll_rows, client_id);
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
@@ -2664,9 +2968,14 @@ impl Tab {
     }
 
     pub fn scroll_active_terminal_up_half_page(&mut self, client_id: ClientId) {
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             // prevent overflow when row == 0
             let scroll_rows = (active_pane.rows().max(1) - 1) / 2;
+            if client_is_read_only {
+                active_pane.scroll_up_for_viewer(scroll_rows, client_id);
+                return;
+            }
             active_pane.scroll_up(scroll_rows, client_id);
         }
     }
@@ -2675,8 +2984,13 @@ impl Tab {
         let err_context =
             || format!("failed to scroll down half a page in active pane for client {client_id}");
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             let scroll_rows = (active_pane.rows().max(1) - 1) / 2;
+            if client_is_read_only {
+                active_pane.scroll_down_for_viewer(scroll_rows, client_id);
+                return Ok(());
+            }
             active_pane.scroll_down(scroll_rows, client_id);
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
@@ -2692,7 +3006,12 @@ impl Tab {
         let err_context =
             || format!("failed to scroll to bottom in active pane for client {client_id}");
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if client_is_read_only {
+                active_pane.clear_scroll_for_viewer(client_id);
+                return Ok(());
+            }
             active_pane.clear_scroll();
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
@@ -2705,7 +3024,12 @@ impl Tab {
     }
 
     pub fn scroll_active_terminal_to_top(&mut self, client_id: ClientId) -> Result<()> {
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if client_is_read_only {
+                active_pane.scroll_up_for_viewer(usize::MAX, client_id);
+                return Ok(());
+            }
             active_pane.clear_scroll();
             if let Some(size) = active_pane.get_line_number() {
                 active_pane.scroll_up(size, client_id);
@@ -2714,12 +3038,51 @@ impl Tab {
         Ok(())
     }
 
+    pub fn scroll_active_terminal_to_previous_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_previous_prompt();
+        }
+        Ok(())
+    }
+
+    pub fn scroll_active_terminal_to_next_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_next_prompt();
+            if !active_pane.is_scrolled() {
+                if let PaneId::Terminal(raw_fd) = active_pane.pid() {
+                    self.process_pending_vte_events(raw_fd).with_context(|| {
+                        format!("failed to scroll to next prompt for client {client_id}")
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn copy_last_command_output(&mut self, client_id: ClientId) -> Result<()> {
+        let output = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .and_then(|p| p.select_last_command_output());
+        if let Some(output) = output {
+            self.write_selection_to_clipboard(&output)
+                .with_context(|| {
+                    format!("failed to copy last command output for client {client_id}")
+                })?;
+        }
+        Ok(())
+    }
+
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) -> Result<()> {
         // TODO: is this a thing?
         let err_context =
             || format!("failed to clear scroll in active pane for client {client_id}");
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if client_is_read_only {
+                active_pane.clear_scroll_for_viewer(client_id);
+                return Ok(());
+            }
             active_pane.clear_scroll();
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
@@ -2741,9 +3104,13 @@ impl Tab {
             format!("failed to handle scrollwheel up at position {point:?} for client {client_id}")
         };
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(pane) = self.get_pane_at(point, false).with_context(err_context)? {
             let relative_position = pane.relative_position(point);
-            if let Some(mouse_event) = pane.mouse_scroll_up(&relative_position) {
+            if client_is_read_only {
+                // rather than being sent to the pane (eg. if it tracks the mouse)
+                pane.scroll_up_for_viewer(lines, client_id);
+            } else if let Some(mouse_event) = pane.mouse_scroll_up(&relative_position) {
                 self.write_to_terminal_at(mouse_event.into_bytes(), point, client_id)
                     .with_context(err_context)?;
             } else if pane.is_alternate_mode_active() {
@@ -2772,9 +3139,12 @@ impl Tab {
             )
         };
 
+        let client_is_read_only = self.client_is_read_only(client_id);
         if let Some(pane) = self.get_pane_at(point, false).with_context(err_context)? {
             let relative_position = pane.relative_position(point);
-            if let Some(mouse_event) = pane.mouse_scroll_down(&relative_position) {
+            if client_is_read_only {
+                pane.scroll_down_for_viewer(lines, client_id);
+            } else if let Some(mouse_event) = pane.mouse_scroll_down(&relative_position) {
                 self.write_to_terminal_at(mouse_event.into_bytes(), point, client_id)
                     .with_context(err_context)?;
             } else if pane.is_alternate_mode_active() {
@@ -2863,6 +3233,7 @@ impl Tab {
 
         self.focus_pane_at(position, client_id)
             .with_context(err_context)?;
+        self.unselectable_pane_with_mouse_down = None;
 
         let search_selectable = false;
         if self.floating_panes.panes_are_visible()
@@ -2889,6 +3260,10 @@ impl Tab {
                 pane.start_selection(&relative_position, client_id);
                 if let PaneId::Terminal(_) = pane.pid() {
                     self.selecting_with_mouse = true;
+                } else if !pane.selectable() {
+                    // unselectable plugins (eg. the tab-bar) never become the active pane, so we
+                    // keep track of them to send them the rest of this mouse gesture
+                    self.unselectable_pane_with_mouse_down = Some(pane.pid());
                 }
             }
         };
@@ -3057,6 +3432,14 @@ impl Tab {
             return Ok(());
         }
 
+        if let Some(pane_id) = self.unselectable_pane_with_mouse_down.take() {
+            if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                let relative_position = pane.relative_position(position);
+                pane.end_selection(&relative_position, client_id);
+            }
+            return Ok(());
+        }
+
         // read these here to avoid use of borrowed `*self`, since we are holding active_pane
         let selecting = self.selecting_with_mouse;
         let copy_on_release = self.copy_on_select;
@@ -3137,6 +3520,16 @@ impl Tab {
                                      // return;
         }
 
+        if let Some(pane_id) = self.unselectable_pane_with_mouse_down {
+            if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                if !is_repeated {
+                    let relative_position = pane.relative_position(position_on_screen);
+                    pane.update_selection(&relative_position, client_id);
+                }
+            }
+            return Ok(false);
+        }
+
         let selecting = self.selecting_with_mouse;
         let active_pane = self.get_active_pane_or_floating_pane_mut(client_id);
 
@@ -3416,6 +3809,25 @@ impl Tab {
             && column <= viewport.x + viewport.cols)
     }
 
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        self.tiled_panes.update_style(style);
+        self.floating_panes.update_style(style);
+        for (_, pane) in self.suppressed_panes.values_mut() {
+            pane.update_style(style);
+        }
+        self.set_force_render();
+    }
+    pub fn set_auto_layout(&mut self, auto_layout: bool) {
+        self.auto_layout = auto_layout;
+    }
+    pub fn update_copy_options(&mut self, copy_options: &CopyOptions) {
+        self.clipboard_provider = match &copy_options.command {
+            Some(command) => ClipboardProvider::Command(CopyCommand::new(command.clone())),
+            None => ClipboardProvider::Osc52(copy_options.clipboard),
+        };
+        self.copy_on_select = copy_options.copy_on_select;
+    }
     pub fn set_pane_frames(&mut self, should_set_pane_frames: bool) {
         self.tiled_panes.set_pane_frames(should_set_pane_frames);
         self.draw_pane_frames = should_set_pane_frames;
@@ -3472,6 +3884,52 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
+    pub fn enter_copy_mode(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.enter_copy_mode();
+            let pane_id = active_pane.pid();
+            self.copy_mode_panes.insert(client_id, pane_id);
+        }
+    }
+
+    pub fn exit_copy_mode(&mut self, client_id: ClientId) {
+        // the focus might have moved since we entered copy mode
+        if let Some(pane) = self.copy_mode_pane_mut(client_id) {
+            pane.exit_copy_mode();
+        }
+        self.copy_mode_panes.remove(&client_id);
+    }
+
+    pub fn copy_mode_move(&mut self, motion: CopyModeMotion, client_id: ClientId) {
+        if let Some(pane) = self.copy_mode_pane_mut(client_id) {
+            pane.copy_mode_move(motion);
+        }
+    }
+
+    pub fn copy_mode_toggle_selection(
+        &mut self,
+        selection: CopyModeSelection,
+        client_id: ClientId,
+    ) {
+        if let Some(pane) = self.copy_mode_pane_mut(client_id) {
+            pane.copy_mode_toggle_selection(selection);
+        }
+    }
+
+    fn copy_mode_pane_mut(&mut self, client_id: ClientId) -> Option<&mut Box<dyn Pane>> {
+        let pane_id = *self.copy_mode_panes.get(&client_id)?;
+        self.floating_panes
+            .get_mut(&pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| self.suppressed_panes.get_mut(&pane_id).map(|p| &mut p.1))
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
@@ -3615,7 +4073,7 @@ impl Tab {
                 Some(pane) => {
                     if should_float {
                         self.show_floating_panes();
-                        self.add_floating_pane(pane.1, pane_id, Some(client_id))
+                        self.add_floating_pane(pane.1, pane_id, None, Some(client_id))
                     } else {
                         self.hide_floating_panes();
                         self.add_tiled_pane(pane.1, pane_id, Some(client_id))
@@ -3655,10 +4113,17 @@ impl Tab {
         &mut self,
         mut pane: Box<dyn Pane>,
         pane_id: PaneId,
+        floating_pane_coordinates: Option<FloatingPaneCoordinates>,
         client_id: Option<ClientId>,
     ) -> Result<()> {
         let err_context = || format!("failed to add floating pane");
-        if let Some(new_pane_geom) = self.floating_panes.find_room_for_new_pane() {
+        let new_pane_geom = match &floating_pane_coordinates {
+            Some(floating_pane_coordinates) => self
+                .floating_panes
+                .find_room_for_new_pane_with_coordinates(floating_pane_coordinates),
+            None => self.floating_panes.find_room_for_new_pane(),
+        };
+        if let Some(new_pane_geom) = new_pane_geom {
             pane.set_active_at(Instant::now());
             pane.set_geom(new_pane_geom);
             pane.set_content_offset(Offset::frame(1)); // floating panes always have a frame
@@ -3667,7 +4132,10 @@ impl Tab {
             self.floating_panes.add_pane(pane_id, pane);
             self.floating_panes.focus_pane_for_all_clients(pane_id);
         }
-        if self.auto_layout && !self.swap_layouts.is_floating_damaged() {
+        if floating_pane_coordinates.is_some() {
+            // the pane was explicitly positioned, applying a swap layout would move it
+            self.swap_layouts.set_is_floating_damaged();
+        } else if self.auto_layout && !self.swap_layouts.is_floating_damaged() {
             // only do this if we're already in this layout, otherwise it might be
             // confusing and not what the user intends
             self.swap_layouts.set_is_floating_damaged(); // we do this so that we won't skip to the
@@ -3676,12 +4144,113 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn change_floating_pane_coordinates(
+        &mut self,
+        pane_id: &PaneId,
+        floating_pane_coordinates: FloatingPaneCoordinates,
+    ) -> Result<()> {
+        let err_context = || format!("failed to change coordinates of pane {pane_id:?}");
+        if !self.floating_panes.panes_contain(pane_id) {
+            return Err(anyhow!("pane {pane_id:?} is not a floating pane"))
+                .with_context(err_context);
+        }
+        self.floating_panes
+            .change_pane_coordinates(*pane_id, &floating_pane_coordinates)
+            .with_context(err_context)?;
+        self.swap_layouts.set_is_floating_damaged();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn toggle_pane_pinned(&mut self, client_id: ClientId) -> Result<()> {
+        if !self.floating_panes.panes_are_visible() {
+            // the focused pane is a tiled pane, which cannot be pinned
+            return Ok(());
+        }
+        if let Some(focused_floating_pane_id) = self.floating_panes.active_pane_id(client_id) {
+            let is_pinned = self
+                .floating_panes
+                .get_pane(focused_floating_pane_id)
+                .map(|p| p.is_pinned())
+                .unwrap_or(false);
+            self.set_pane_pinned(&focused_floating_pane_id, !is_pinned)
+                .with_context(|| format!("failed to toggle pinned pane for client {client_id}"))?;
+        }
+        Ok(())
+    }
+    pub fn set_pane_pinned(&mut self, pane_id: &PaneId, should_be_pinned: bool) -> Result<()> {
+        let err_context = || format!("failed to set pinned state of pane {pane_id:?}");
+        match self.floating_panes.get_pane_mut(*pane_id) {
+            Some(pane) => {
+                pane.set_pinned(should_be_pinned);
+                self.set_force_render();
+                Ok(())
+            },
+            None => {
+                Err(anyhow!("pane {pane_id:?} is not a floating pane")).with_context(err_context)
+            },
+        }
+    }
+    pub fn record_layout(&mut self) {
+        let geometry = self.tiled_panes.geometry();
+        if self.layout_history.back() != Some(&geometry) {
+            self.layout_history.push_back(geometry);
+            if self.layout_history.len() > MAX_LAYOUT_HISTORY_LENGTH {
+                self.layout_history.pop_front();
+            }
+        }
+    }
+    pub fn undo_layout_change(&mut self) -> bool {
+        // returns true if a previous layout was restored
+        let current_geometry = self.tiled_panes.geometry();
+        while let Some(geometry) = self.layout_history.pop_back() {
+            if geometry == current_geometry || !self.tiled_panes.can_restore_geometry(&geometry) {
+                // either nothing changed since this was recorded, or panes were opened or closed
+                // since (which we can't undo without killing their processes)
+                continue;
+            }
+            self.tiled_panes.restore_geometry(&geometry);
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_force_render();
+            return true;
+        }
+        false
+    }
+    pub fn set_pane_in_group(&mut self, pane_id: PaneId, client_id: ClientId, in_group: bool) {
+        if let Some(pane) = self
+            .floating_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .get_mut(&pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+        {
+            pane.set_in_group(client_id, in_group);
+            self.set_force_render();
+        }
+    }
+    pub fn pane_is_floating(&self, pane_id: &PaneId) -> bool {
+        self.floating_panes.panes_contain(pane_id)
+    }
+    pub fn would_be_left_with_only_floating_panes(&self, pane_ids_to_remove: &[PaneId]) -> bool {
+        let has_remaining_tiled_panes = self
+            .get_selectable_tiled_panes()
+            .any(|(pane_id, _)| !pane_ids_to_remove.contains(pane_id));
+        let has_remaining_floating_panes = self
+            .floating_panes
+            .get_panes()
+            .any(|(pane_id, _)| !pane_ids_to_remove.contains(pane_id));
+        !has_remaining_tiled_panes && has_remaining_floating_panes
+    }
     pub fn add_tiled_pane(
         &mut self,
         mut pane: Box<dyn Pane>,
         pane_id: PaneId,
         client_id: Option<ClientId>,
     ) -> Result<()> {
+        // only floating panes can be pinned
+        pane.set_pinned(false);
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
@@ -3741,7 +4310,10 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info.title = pane.current_title();
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
+    pane_info.last_command_exit_code = pane.last_command_exit_code();
     pane_info.is_held = pane.is_held();
+    pane_info.is_pinned = pane.is_pinned();
+    pane_info.is_in_group = pane.is_in_any_group();
 
     match pane_id {
         PaneId::Terminal(terminal_id) => {
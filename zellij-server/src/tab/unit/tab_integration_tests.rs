@@ -15,6 +15,7 @@ use std::sync::Mutex;
 
 use zellij_utils::channels::Receiver;
 use zellij_utils::data::Direction;
+use zellij_utils::data::FloatingPaneCoordinates;
 use zellij_utils::data::Resize;
 use zellij_utils::data::ResizeStrategy;
 use zellij_utils::envs::set_session_name;
@@ -760,7 +761,7 @@ fn dump_screen() {
         ..Default::default()
     });
     let new_pane_id = PaneId::Terminal(2);
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
         .unwrap();
@@ -788,7 +789,7 @@ fn clear_screen() {
         ..Default::default()
     });
     let new_pane_id = PaneId::Terminal(2);
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
         .unwrap();
@@ -814,7 +815,7 @@ fn new_floating_pane() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -831,6 +832,175 @@ fn new_floating_pane() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn new_floating_pane_with_coordinates() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    let mut output = Output::default();
+    let coordinates = FloatingPaneCoordinates::default()
+        .with_x_fixed(10)
+        .with_y_percent(20)
+        .with_width_fixed(40)
+        .with_height_percent(50);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(
+        new_pane_id,
+        None,
+        None,
+        None,
+        Some(coordinates),
+        Some(client_id),
+    )
+    .unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("\n\n\n                   I am scratch terminal".as_bytes()),
+    )
+    .unwrap();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn change_floating_pane_coordinates() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    let mut output = Output::default();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("\n\n\n                   I am scratch terminal".as_bytes()),
+    )
+    .unwrap();
+    let coordinates = FloatingPaneCoordinates::default()
+        .with_x_fixed(0)
+        .with_y_fixed(0)
+        .with_width_percent(50);
+    tab.change_floating_pane_coordinates(&new_pane_id, coordinates)
+        .unwrap();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn floating_pane_coordinates_are_at_least_one_cell() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
+        .unwrap();
+    let coordinates = FloatingPaneCoordinates::default()
+        .with_width_fixed(0)
+        .with_height_percent(1);
+    tab.change_floating_pane_coordinates(&new_pane_id, coordinates)
+        .unwrap();
+    let (_, pane) = tab
+        .get_floating_panes()
+        .find(|(pane_id, _)| **pane_id == new_pane_id)
+        .unwrap();
+    let geom = pane.position_and_size();
+    assert_eq!(geom.cols.as_usize(), 1, "width clamped to a single cell");
+    assert_eq!(geom.rows.as_usize(), 1, "height clamped to a single cell");
+}
+
+#[test]
+fn change_coordinates_of_tiled_pane_fails() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let coordinates = FloatingPaneCoordinates::default().with_x_fixed(0);
+    assert!(
+        tab.change_floating_pane_coordinates(&PaneId::Terminal(1), coordinates)
+            .is_err(),
+        "tiled panes cannot be given floating coordinates"
+    );
+}
+
+#[test]
+fn pinned_floating_pane_stays_visible_when_floating_panes_are_hidden() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    let mut output = Output::default();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("\n\n\n                   I am scratch terminal".as_bytes()),
+    )
+    .unwrap();
+    tab.toggle_pane_pinned(client_id).unwrap();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "pinned pane does not take focus when floating panes are hidden"
+    );
+    let pinned_pane_info = tab
+        .pane_infos()
+        .into_iter()
+        .find(|p| !p.is_plugin && p.id == 2)
+        .unwrap();
+    assert!(pinned_pane_info.is_pinned);
+}
+
+#[test]
+fn pinning_a_tiled_pane_fails() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    assert!(
+        tab.set_pane_pinned(&PaneId::Terminal(1), true).is_err(),
+        "only floating panes can be pinned"
+    );
+}
+
 #[test]
 fn floating_panes_persist_across_toggles() {
     let size = Size {
@@ -842,7 +1012,7 @@ fn floating_panes_persist_across_toggles() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     // here we send bytes to the pane when it's not visible to make sure they're still handled and
@@ -874,7 +1044,7 @@ fn toggle_floating_panes_off() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -903,7 +1073,7 @@ fn toggle_floating_panes_on() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -937,15 +1107,15 @@ fn five_new_floating_panes() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -981,7 +1151,7 @@ fn increase_floating_pane_size() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1011,7 +1181,7 @@ fn decrease_floating_pane_size() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1041,7 +1211,7 @@ fn resize_floating_pane_left() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1074,7 +1244,7 @@ fn resize_floating_pane_right() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1107,7 +1277,7 @@ fn resize_floating_pane_up() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1140,7 +1310,7 @@ fn resize_floating_pane_down() {
     let new_pane_id_1 = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1177,15 +1347,15 @@ fn move_floating_pane_focus_left() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1232,15 +1402,15 @@ fn move_floating_pane_focus_right() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1288,15 +1458,15 @@ fn move_floating_pane_focus_up() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1343,15 +1513,15 @@ fn move_floating_pane_focus_down() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1399,15 +1569,15 @@ fn move_floating_pane_focus_with_mouse() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1457,15 +1627,15 @@ fn move_pane_focus_with_mouse_to_non_floating_pane() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1515,15 +1685,15 @@ fn drag_pane_with_mouse() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1573,15 +1743,15 @@ fn mark_text_inside_floating_pane() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1639,15 +1809,15 @@ fn resize_tab_with_floating_panes() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1693,15 +1863,15 @@ fn shrink_whole_tab_with_floating_panes_horizontally_and_vertically() {
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1743,15 +1913,15 @@ fn shrink_whole_tab_with_floating_panes_horizontally_and_vertically_and_expand_b
     let new_pane_id_5 = PaneId::Terminal(6);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1794,7 +1964,7 @@ fn embed_floating_pane() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1822,7 +1992,7 @@ fn float_embedded_pane() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1852,7 +2022,7 @@ fn embed_floating_pane_without_pane_frames() {
     let mut output = Output::default();
     tab.set_pane_frames(false);
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1881,7 +2051,7 @@ fn float_embedded_pane_without_pane_frames() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.set_pane_frames(false);
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -1984,7 +2154,7 @@ fn rename_floating_pane() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         2,
@@ -2080,7 +2250,7 @@ fn move_floating_pane_with_sixel_image() {
     let mut output = Output::new(sixel_image_store.clone(), character_cell_size, true);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     let fixture = read_fixture("sixel-image-500px.six");
     tab.handle_pty_bytes(2, fixture).unwrap();
@@ -2118,7 +2288,7 @@ fn floating_pane_above_sixel_image() {
     let mut output = Output::new(sixel_image_store.clone(), character_cell_size, true);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     let fixture = read_fixture("sixel-image-500px.six");
     tab.handle_pty_bytes(1, fixture).unwrap();
@@ -2176,7 +2346,7 @@ fn suppress_floating_pane() {
     let mut output = Output::default();
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
         .unwrap();
@@ -2232,7 +2402,7 @@ fn close_suppressing_floating_pane() {
     let mut output = Output::default();
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
         .unwrap();
@@ -2292,7 +2462,7 @@ fn suppress_floating_pane_embed_it_and_close_it() {
     let mut output = Output::default();
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
         .unwrap();
@@ -2354,7 +2524,7 @@ fn resize_whole_tab_while_floting_pane_is_suppressed() {
     let mut output = Output::default();
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
     tab.replace_active_pane_with_editor_pane(editor_pane_id, client_id)
         .unwrap();
@@ -2375,6 +2545,90 @@ fn resize_whole_tab_while_floting_pane_is_suppressed() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn read_only_client_pane_frames_show_viewer_indication() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mode_info = ModeInfo {
+        is_read_only: true,
+        ..Default::default()
+    };
+    let mut tab = create_new_tab(size, mode_info);
+    let mut output = Output::default();
+    let pane_content = read_fixture("grid_copy");
+    tab.handle_pty_bytes(1, pane_content).unwrap();
+    tab.scroll_active_terminal_up(client_id);
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn read_only_client_scrolls_its_own_view_of_a_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let host_client_id = 1;
+    let viewer_client_id = 2;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.add_client(
+        viewer_client_id,
+        Some(ModeInfo {
+            is_read_only: true,
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+    let pane_content = read_fixture("grid_copy");
+    tab.handle_pty_bytes(1, pane_content).unwrap();
+    let render_snapshots = |tab: &mut Tab| {
+        let mut output = Output::default();
+        tab.set_force_render();
+        tab.render(&mut output).unwrap();
+        let serialized_output = output.serialize().unwrap();
+        let snapshot_of_client = |client_id| {
+            take_snapshot(
+                serialized_output.get(&client_id).unwrap(),
+                size.rows,
+                size.cols,
+                Palette::default(),
+            )
+        };
+        (
+            snapshot_of_client(host_client_id),
+            snapshot_of_client(viewer_client_id),
+        )
+    };
+    let (host_snapshot, viewer_snapshot) = render_snapshots(&mut tab);
+    tab.scroll_active_terminal_up_page(viewer_client_id);
+    let (host_snapshot_after_scroll, viewer_snapshot_after_scroll) = render_snapshots(&mut tab);
+    assert!(
+        !tab.get_active_pane(host_client_id).unwrap().is_scrolled(),
+        "pane itself is not scrolled"
+    );
+    assert_eq!(
+        host_snapshot_after_scroll, host_snapshot,
+        "host does not see the scroll of the viewer"
+    );
+    assert_snapshot!(viewer_snapshot_after_scroll);
+    tab.scroll_active_terminal_to_bottom(viewer_client_id)
+        .unwrap();
+    let (_, viewer_snapshot_after_scrolling_back) = render_snapshots(&mut tab);
+    assert_eq!(
+        viewer_snapshot_after_scrolling_back, viewer_snapshot,
+        "viewer is back at the shared view of the pane"
+    );
+}
+
 #[test]
 fn enter_search_pane() {
     let size = Size {
@@ -2456,7 +2710,7 @@ fn enter_search_floating_pane() {
     let new_pane_id = PaneId::Terminal(2);
     let mut output = Output::default();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id, None, None, None, None, Some(client_id))
         .unwrap();
 
     let pane_content = read_fixture("grid_copy");
@@ -2961,7 +3215,7 @@ fn move_pane_focus_sends_tty_csi_event() {
     });
     let mut tab = create_new_tab_with_os_api(size, ModeInfo::default(), &os_api);
     let new_pane_id_1 = PaneId::Terminal(2);
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         1,
@@ -2996,9 +3250,9 @@ fn move_floating_pane_focus_sends_tty_csi_event() {
     let new_pane_id_2 = PaneId::Terminal(3);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         1,
@@ -3039,9 +3293,9 @@ fn toggle_floating_panes_on_sends_tty_csi_event() {
     let new_pane_id_2 = PaneId::Terminal(3);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.handle_pty_bytes(
@@ -3083,9 +3337,9 @@ fn toggle_floating_panes_off_sends_tty_csi_event() {
     let new_pane_id_2 = PaneId::Terminal(3);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_pty_bytes(
         1,
@@ -3145,7 +3399,7 @@ fn can_swap_tiled_layout_at_runtime() {
     );
     let new_pane_id_1 = PaneId::Terminal(2);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.next_swap_layout(Some(client_id), false).unwrap();
     tab.render(&mut output).unwrap();
@@ -3200,9 +3454,9 @@ fn can_swap_floating_layout_at_runtime() {
     let new_pane_id_2 = PaneId::Terminal(3);
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
     tab.next_swap_layout(Some(client_id), false).unwrap();
     tab.render(&mut output).unwrap();
@@ -3253,7 +3507,7 @@ fn swapping_layouts_after_resize_snaps_to_current_layout() {
     );
     let new_pane_id_1 = PaneId::Terminal(2);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
     tab.next_swap_layout(Some(client_id), false).unwrap();
     tab.resize(client_id, ResizeStrategy::new(Resize::Increase, None))
@@ -3303,11 +3557,11 @@ fn swap_tiled_layout_with_stacked_children() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.render(&mut output).unwrap();
     let snapshot = take_snapshot(
@@ -3350,11 +3604,11 @@ fn swap_tiled_layout_with_only_stacked_children() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.render(&mut output).unwrap();
     let snapshot = take_snapshot(
@@ -3400,11 +3654,11 @@ fn swap_tiled_layout_with_stacked_children_and_no_pane_frames() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.render(&mut output).unwrap();
     let snapshot = take_snapshot(
@@ -3450,11 +3704,11 @@ fn move_focus_up_with_stacked_panes() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.move_focus_up(client_id);
@@ -3502,11 +3756,11 @@ fn move_focus_down_with_stacked_panes() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.move_focus_up(client_id);
@@ -3561,6 +3815,7 @@ fn move_focus_right_into_stacked_panes() {
             None,
             None,
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -3628,6 +3883,7 @@ fn move_focus_left_into_stacked_panes() {
             None,
             None,
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -3697,6 +3953,7 @@ fn move_focus_up_into_stacked_panes() {
             None,
             None,
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -3767,6 +4024,7 @@ fn move_focus_down_into_stacked_panes() {
             None,
             None,
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -3828,11 +4086,11 @@ fn close_main_stacked_pane() {
     let new_pane_id_2 = PaneId::Terminal(3);
     let new_pane_id_3 = PaneId::Terminal(4);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
     tab.close_pane(new_pane_id_2, false, None);
     tab.render(&mut output).unwrap();
@@ -3881,15 +4139,15 @@ fn close_main_stacked_pane_in_mid_stack() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.move_focus_up(client_id);
@@ -3941,15 +4199,15 @@ fn close_one_liner_stacked_pane_below_main_pane() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_left(client_id);
     tab.move_focus_right(client_id);
@@ -4002,15 +4260,15 @@ fn close_one_liner_stacked_pane_above_main_pane() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.move_focus_up(client_id);
@@ -4062,15 +4320,15 @@ fn can_increase_size_of_main_pane_in_stack_horizontally() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.resize(
@@ -4126,15 +4384,15 @@ fn can_increase_size_of_main_pane_in_stack_vertically() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.resize(
@@ -4190,15 +4448,15 @@ fn can_increase_size_of_main_pane_in_stack_non_directionally() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     let _ = tab.move_focus_up(client_id);
     let _ = tab.move_focus_right(client_id);
@@ -4250,15 +4508,15 @@ fn can_increase_size_into_pane_stack_horizontally() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.resize(
         client_id,
@@ -4313,15 +4571,15 @@ fn can_increase_size_into_pane_stack_vertically() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_right(client_id);
     tab.move_focus_down(client_id);
@@ -4378,15 +4636,15 @@ fn can_increase_size_into_pane_stack_non_directionally() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     let _ = tab.move_focus_up(client_id);
     tab.resize(client_id, ResizeStrategy::new(Resize::Increase, None))
@@ -4437,15 +4695,15 @@ fn decreasing_size_of_whole_tab_treats_stacked_panes_properly() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.resize_whole_tab(Size {
         cols: 100,
@@ -4497,15 +4755,15 @@ fn increasing_size_of_whole_tab_treats_stacked_panes_properly() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.resize_whole_tab(Size {
         cols: 100,
@@ -4562,15 +4820,15 @@ fn cannot_decrease_stack_size_beyond_minimum_height() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_down(client_id);
     for _ in 0..6 {
@@ -4627,15 +4885,15 @@ fn focus_stacked_pane_over_flexible_pane_with_the_mouse() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_left_click(&Position::new(1, 71), client_id)
         .unwrap();
@@ -4686,15 +4944,15 @@ fn focus_stacked_pane_under_flexible_pane_with_the_mouse() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_left_click(&Position::new(1, 71), client_id)
         .unwrap();
@@ -4747,15 +5005,15 @@ fn close_stacked_pane_with_previously_focused_other_pane() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.handle_left_click(&Position::new(2, 71), client_id)
         .unwrap();
@@ -4814,15 +5072,15 @@ fn close_pane_near_stacked_panes() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.close_pane(PaneId::Terminal(6), false, None);
     tab.render(&mut output).unwrap();
@@ -4878,15 +5136,15 @@ fn focus_next_pane_expands_stacked_panes() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_left(client_id);
     tab.focus_next_pane(client_id);
@@ -4938,15 +5196,15 @@ fn stacked_panes_can_become_fullscreen() {
     let new_pane_id_4 = PaneId::Terminal(5);
     let new_pane_id_5 = PaneId::Terminal(6);
 
-    tab.new_pane(new_pane_id_1, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_1, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_2, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_2, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_3, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_3, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_4, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_4, None, None, None, None, Some(client_id))
         .unwrap();
-    tab.new_pane(new_pane_id_5, None, None, None, Some(client_id))
+    tab.new_pane(new_pane_id_5, None, None, None, None, Some(client_id))
         .unwrap();
     tab.move_focus_up(client_id);
     tab.toggle_active_pane_fullscreen(client_id);
@@ -5608,6 +5866,7 @@ fn new_pane_in_auto_layout() {
             None,
             None,
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -6552,6 +6811,7 @@ fn new_floating_pane_in_auto_layout() {
             None,
             Some(should_float),
             None,
+            None,
             Some(client_id),
         )
         .unwrap();
@@ -10,6 +10,7 @@ use crate::{
 use std::path::PathBuf;
 use zellij_utils::data::{Direction, Resize, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::CopyModeSelection;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
@@ -356,6 +357,32 @@ fn write_to_suppressed_pane() {
         .unwrap();
 }
 
+#[test]
+fn exit_copy_mode_after_focus_moved_to_another_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.handle_pty_bytes(2, "some output".as_bytes().to_vec())
+        .unwrap();
+    tab.enter_copy_mode(client_id);
+    tab.copy_mode_toggle_selection(CopyModeSelection::Line, client_id);
+    tab.move_focus_left(client_id).unwrap();
+    tab.exit_copy_mode(client_id);
+    assert_eq!(
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(2))
+            .unwrap()
+            .get_selected_text(),
+        None,
+        "copy mode was left in the pane it was entered in"
+    );
+}
+
 #[test]
 fn split_panes_vertically() {
     let size = Size {
@@ -559,7 +586,7 @@ fn split_largest_pane() {
     let mut tab = create_new_tab(size);
     for i in 2..5 {
         let new_pane_id = PaneId::Terminal(i);
-        tab.new_pane(new_pane_id, None, None, None, Some(1))
+        tab.new_pane(new_pane_id, None, None, None, None, Some(1))
             .unwrap();
     }
     assert_eq!(tab.tiled_panes.panes.len(), 4, "The tab has four panes");
@@ -765,7 +792,7 @@ pub fn cannot_split_panes_horizontally_when_active_pane_is_too_small() {
 pub fn cannot_split_largest_pane_when_there_is_no_room() {
     let size = Size { cols: 8, rows: 4 };
     let mut tab = create_new_tab(size);
-    tab.new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+    tab.new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
         .unwrap();
     assert_eq!(
         tab.tiled_panes.panes.len(),
@@ -809,7 +836,7 @@ pub fn toggle_focused_pane_fullscreen() {
     let mut tab = create_new_tab(size);
     for i in 2..5 {
         let new_pane_id = PaneId::Terminal(i);
-        tab.new_pane(new_pane_id, None, None, None, Some(1))
+        tab.new_pane(new_pane_id, None, None, None, None, Some(1))
             .unwrap();
     }
     tab.toggle_active_pane_fullscreen(1);
@@ -884,16 +911,16 @@ fn switch_to_next_pane_fullscreen() {
     let mut active_tab = create_new_tab(size);
 
     active_tab
-        .new_pane(PaneId::Terminal(1), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(1), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(3), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(3), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(4), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(4), None, None, None, None, Some(1))
         .unwrap();
     active_tab.toggle_active_pane_fullscreen(1);
 
@@ -924,16 +951,16 @@ fn switch_to_prev_pane_fullscreen() {
     //testing four consecutive switches in fullscreen mode
 
     active_tab
-        .new_pane(PaneId::Terminal(1), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(1), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(3), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(3), None, None, None, None, Some(1))
         .unwrap();
     active_tab
-        .new_pane(PaneId::Terminal(4), None, None, None, Some(1))
+        .new_pane(PaneId::Terminal(4), None, None, None, None, Some(1))
         .unwrap();
     active_tab.toggle_active_pane_fullscreen(1);
     // order is now 1 2 3 4
@@ -14415,7 +14442,7 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 
-    tab.new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+    tab.new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
         .unwrap();
     tab.close_pane(PaneId::Terminal(2), true, None);
 
@@ -1,19 +1,37 @@
 use crate::{
     os_input_output::{AsyncReader, ServerOsApi},
+    plugins::PluginInstruction,
     screen::ScreenInstruction,
     thread_bus::ThreadSenders,
+    ServerInstruction,
 };
 use async_std::{future::timeout as async_timeout, task};
 use std::{
+    collections::{HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::Write,
     os::unix::io::RawFd,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
     time::{Duration, Instant},
 };
 use zellij_utils::{
     async_std,
+    channels::{unbounded, Receiver, Sender},
+    data::{Event, PaneId},
     errors::{get_current_ctx, prelude::*, ContextType},
+    input::triggers::{Trigger, Triggers},
     logging::debug_to_file,
+    regex::Regex,
+    shared::strip_ansi_bytes,
+    vte,
 };
 
+// a line longer than this without a newline is most likely not something a trigger is
+// interested in, we drop its beginning so that it does not grow forever
+const MAX_PENDING_LINE_LENGTH: usize = 4096;
+
 enum ReadResult {
     Ok(usize),
     Timeout,
@@ -29,6 +47,232 @@ impl From<std::io::Result<usize>> for ReadResult {
     }
 }
 
+/// Matches the output of a terminal pane line by line against the configured triggers
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TriggerMatcher {
+    triggers: Vec<(Regex, Trigger)>,
+    pending_line: Vec<u8>,
+}
+
+impl TriggerMatcher {
+    pub fn new(triggers: &Triggers) -> Self {
+        let triggers = triggers
+            .iter()
+            .filter_map(|trigger| match Regex::new(&trigger.pattern) {
+                Ok(regex) => Some((regex, trigger.clone())),
+                Err(e) => {
+                    log::error!(
+                        "Failed to compile trigger pattern {}: {}",
+                        trigger.pattern,
+                        e
+                    );
+                    None
+                },
+            })
+            .collect();
+        TriggerMatcher {
+            triggers,
+            pending_line: vec![],
+        }
+    }
+    /// Returns the triggers matched by the lines completed in `bytes` along with the line that
+    /// matched them, every trigger fires at most once per call
+    pub fn match_bytes(&mut self, bytes: &[u8]) -> Vec<(Trigger, String)> {
+        let mut matches: Vec<(Trigger, String)> = vec![];
+        if self.triggers.is_empty() {
+            return matches;
+        }
+        let mut segments = bytes.split(|b| *b == b'\n').peekable();
+        while let Some(segment) = segments.next() {
+            self.pending_line.extend_from_slice(segment);
+            if segments.peek().is_none() {
+                // the last segment does not end in a newline, keep it until the line is complete
+                break;
+            }
+            let line = strip_ansi_bytes(&self.pending_line);
+            let line = line.trim_end_matches('\r');
+            for (regex, trigger) in &self.triggers {
+                let already_matched = matches.iter().any(|(t, _)| t == trigger);
+                if !already_matched && regex.is_match(line) {
+                    matches.push((trigger.clone(), line.to_owned()));
+                }
+            }
+            self.pending_line.clear();
+        }
+        if self.pending_line.len() > MAX_PENDING_LINE_LENGTH {
+            let excess = self.pending_line.len() - MAX_PENDING_LINE_LENGTH;
+            self.pending_line.drain(..excess);
+        }
+        matches
+    }
+}
+
+enum PaneOutputLogInstruction {
+    Start(u32, Box<PaneOutputLog>), // u32 is the terminal id
+    Write(u32, Vec<u8>),
+    Stop(u32),
+}
+
+/// The files terminal panes are logging their output to, shared between the pty thread (which
+/// starts and stops logging) and the tasks reading from each pane's pty
+///
+/// The files are written to on a dedicated thread so that reading from a pty never waits for the
+/// disk, the tasks only check whether their terminal is being logged before sending it its output
+#[derive(Clone)]
+pub(crate) struct PaneOutputLogs {
+    logged_terminal_ids: Arc<Mutex<HashSet<u32>>>,
+    sender: Sender<PaneOutputLogInstruction>,
+}
+
+impl PaneOutputLogs {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded();
+        // the thread ends once all senders (the pty thread and the pty reading tasks) are dropped
+        if let Err(e) = thread::Builder::new()
+            .name("pane_output_logs".to_string())
+            .spawn(move || write_pane_output_logs(receiver))
+        {
+            log::error!("Failed to start thread for logging pane output: {:?}", e);
+        }
+        PaneOutputLogs {
+            logged_terminal_ids: Arc::new(Mutex::new(HashSet::new())),
+            sender,
+        }
+    }
+    pub fn start(&self, terminal_id: u32, output_log: PaneOutputLog) -> Result<()> {
+        let err_context = || format!("failed to start logging output of terminal {terminal_id}");
+        self.logged_terminal_ids
+            .lock()
+            .to_anyhow()
+            .with_context(err_context)?
+            .insert(terminal_id);
+        self.sender
+            .send(PaneOutputLogInstruction::Start(
+                terminal_id,
+                Box::new(output_log),
+            ))
+            .map_err(|_| anyhow!("pane output logging thread is not running"))
+            .with_context(err_context)
+    }
+    /// Returns whether the output of this terminal was being logged
+    pub fn stop(&self, terminal_id: u32) -> Result<bool> {
+        let err_context = || format!("failed to stop logging output of terminal {terminal_id}");
+        let was_logged = self
+            .logged_terminal_ids
+            .lock()
+            .to_anyhow()
+            .with_context(err_context)?
+            .remove(&terminal_id);
+        if was_logged {
+            self.sender
+                .send(PaneOutputLogInstruction::Stop(terminal_id))
+                .map_err(|_| anyhow!("pane output logging thread is not running"))
+                .with_context(err_context)?;
+        }
+        Ok(was_logged)
+    }
+    pub fn write(&self, terminal_id: u32, bytes: &[u8]) {
+        let is_logged = self
+            .logged_terminal_ids
+            .lock()
+            .map(|logged_terminal_ids| logged_terminal_ids.contains(&terminal_id))
+            .unwrap_or(false);
+        if is_logged {
+            let _ = self
+                .sender
+                .send(PaneOutputLogInstruction::Write(terminal_id, bytes.to_vec()));
+        }
+    }
+}
+
+fn write_pane_output_logs(receiver: Receiver<PaneOutputLogInstruction>) {
+    let mut output_logs: HashMap<u32, PaneOutputLog> = HashMap::new();
+    for instruction in receiver.iter() {
+        match instruction {
+            PaneOutputLogInstruction::Start(terminal_id, output_log) => {
+                output_logs.insert(terminal_id, *output_log);
+            },
+            PaneOutputLogInstruction::Write(terminal_id, bytes) => {
+                if let Some(output_log) = output_logs.get_mut(&terminal_id) {
+                    if let Err(e) = output_log.write(&bytes) {
+                        log::error!("{:?}", e);
+                    }
+                }
+            },
+            PaneOutputLogInstruction::Stop(terminal_id) => {
+                output_logs.remove(&terminal_id);
+            },
+        }
+    }
+}
+
+/// A file everything a terminal pane receives is appended to, see `Action::PipePaneToFile`
+pub(crate) struct PaneOutputLog {
+    path: PathBuf,
+    file: File,
+    // we keep one parser for the lifetime of the log so that escape sequences split between two
+    // reads from the pty are still stripped
+    ansi_stripper: Option<(vte::Parser, AnsiStripper)>,
+}
+
+impl PaneOutputLog {
+    pub fn new(path: PathBuf, strip_ansi: bool) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("failed to open {} for logging", path.display()))?;
+        let ansi_stripper = if strip_ansi {
+            Some((vte::Parser::new(), AnsiStripper::default()))
+        } else {
+            None
+        };
+        Ok(PaneOutputLog {
+            path,
+            file,
+            ansi_stripper,
+        })
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let err_context = || format!("failed to write to {}", self.path.display());
+        match self.ansi_stripper.as_mut() {
+            Some((parser, ansi_stripper)) => {
+                for byte in bytes {
+                    parser.advance(ansi_stripper, *byte);
+                }
+                self.file
+                    .write_all(&ansi_stripper.text)
+                    .with_context(err_context)?;
+                ansi_stripper.text.clear();
+            },
+            None => self.file.write_all(bytes).with_context(err_context)?,
+        }
+        Ok(())
+    }
+}
+
+/// Keeps only the printable text (and line breaks) of terminal output
+#[derive(Debug, Default)]
+pub(crate) struct AnsiStripper {
+    text: Vec<u8>,
+}
+
+impl vte::Perform for AnsiStripper {
+    fn print(&mut self, c: char) {
+        let mut buf = [0; 4];
+        self.text
+            .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    fn execute(&mut self, byte: u8) {
+        if matches!(byte, b'\n' | b'\r' | b'\t') {
+            self.text.push(byte);
+        }
+    }
+}
+
 pub(crate) struct TerminalBytes {
     pid: RawFd,
     terminal_id: u32,
@@ -40,6 +284,8 @@ pub(crate) struct TerminalBytes {
     minimum_render_send_time: Option<Duration>,
     buffering_pause: Duration,
     last_render: Instant,
+    trigger_matcher: TriggerMatcher,
+    pane_output_logs: PaneOutputLogs,
 }
 
 impl TerminalBytes {
@@ -49,6 +295,8 @@ impl TerminalBytes {
         os_input: Box<dyn ServerOsApi>,
         debug: bool,
         terminal_id: u32,
+        trigger_matcher: TriggerMatcher,
+        pane_output_logs: PaneOutputLogs,
     ) -> Self {
         TerminalBytes {
             pid,
@@ -61,6 +309,8 @@ impl TerminalBytes {
             minimum_render_send_time: None,
             buffering_pause: Duration::from_millis(30),
             last_render: Instant::now(),
+            trigger_matcher,
+            pane_output_logs,
         }
     }
     pub async fn listen(&mut self) -> Result<()> {
@@ -98,12 +348,14 @@ impl TerminalBytes {
                     if self.debug {
                         let _ = debug_to_file(bytes, self.pid);
                     }
+                    self.log_output(bytes);
                     self.async_send_to_screen(ScreenInstruction::PtyBytes(
                         self.terminal_id,
                         bytes.to_vec(),
                     ))
                     .await
                     .with_context(err_context)?;
+                    self.fire_triggers(bytes).await.with_context(err_context)?;
                     if !self.backed_up {
                         // we're not backed up, let's send an immediate render instruction
                         let time_to_send_render = self
@@ -151,6 +403,39 @@ impl TerminalBytes {
             .context("failed to async-send to screen")?;
         Ok(sent_at.elapsed())
     }
+    fn log_output(&self, bytes: &[u8]) {
+        self.pane_output_logs.write(self.terminal_id, bytes);
+    }
+    async fn fire_triggers(&mut self, bytes: &[u8]) -> Result<()> {
+        for (trigger, line) in self.trigger_matcher.match_bytes(bytes) {
+            let terminal_id = self.terminal_id;
+            let actions = trigger.actions_for_match(terminal_id, &line);
+            let senders = self.senders.clone();
+            task::spawn_blocking(move || -> Result<()> {
+                if let Some(tab_name) = trigger.rename_tab {
+                    senders.send_to_screen(ScreenInstruction::RenameTabWithPaneId(
+                        crate::panes::PaneId::Terminal(terminal_id),
+                        tab_name.into_bytes(),
+                    ))?;
+                }
+                senders.send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    None,
+                    Event::PaneOutputMatched(PaneId::Terminal(terminal_id), trigger.pattern, line),
+                )]))?;
+                if !actions.is_empty() {
+                    senders.send_to_server(ServerInstruction::RunTriggerActions(
+                        actions,
+                        terminal_id,
+                    ))?;
+                }
+                Ok(())
+            })
+            .await
+            .context("failed to fire triggers")?;
+        }
+        Ok(())
+    }
     fn update_render_send_time(&mut self, time_to_send_render: Duration) {
         match self.minimum_render_send_time.as_mut() {
             Some(minimum_render_time) => {
@@ -192,3 +477,7 @@ impl TerminalBytes {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "./unit/terminal_bytes_tests.rs"]
+mod terminal_bytes_tests;
@@ -0,0 +1,47 @@
+use zellij_utils::pane_size::Size;
+
+use super::Overlayable;
+use zellij_utils::errors::prelude::*;
+
+use std::fmt::Write;
+
+/// A message shown at the bottom of the screen until it is explicitly removed
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub message: String,
+}
+
+impl Notification {
+    pub fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl Overlayable for Notification {
+    fn generate_overlay(&self, size: Size) -> Result<String> {
+        // the cursor position is saved and restored so that the overlay can be drawn after the
+        // rest of the screen without moving the cursor away from the focused pane
+        let mut output = String::from("\u{1b}7");
+        let max_lines = std::cmp::max(size.rows / 2, 1);
+        let lines: Vec<&str> = self.message.lines().collect();
+        let lines = &lines[lines.len().saturating_sub(max_lines)..];
+        let first_row = size.rows.saturating_sub(lines.len()) + 1;
+        for (index, line) in lines.iter().enumerate() {
+            let line_width = std::cmp::min(line.chars().count(), size.cols);
+            let vte_output: String = line
+                .chars()
+                .take(line_width)
+                .chain(std::iter::repeat(' ').take(size.cols - line_width))
+                .collect();
+            write!(
+                &mut output,
+                "\u{1b}[{};1H\u{1b}[38;5;15;48;5;52m{}\u{1b}[m",
+                first_row + index,
+                vte_output,
+            )
+            .context("failed to generate VTE output from notification")?;
+        }
+        output.push_str("\u{1b}8");
+        Ok(output)
+    }
+}
@@ -60,6 +60,7 @@ pub struct FrameParams {
     pub pane_is_stacked_under: bool,
     pub pane_is_stacked_over: bool,
     pub should_draw_pane_frames: bool,
+    pub client_is_read_only: bool,
 }
 
 #[derive(Default, PartialEq)]
@@ -78,6 +79,7 @@ pub struct PaneFrame {
     pane_is_stacked_over: bool,
     pane_is_stacked_under: bool,
     should_draw_pane_frames: bool,
+    client_is_read_only: bool,
 }
 
 impl PaneFrame {
@@ -102,6 +104,7 @@ impl PaneFrame {
             pane_is_stacked_over: frame_params.pane_is_stacked_over,
             pane_is_stacked_under: frame_params.pane_is_stacked_under,
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
+            client_is_read_only: frame_params.client_is_read_only,
         }
     }
     pub fn add_exit_status(&mut self, exit_status: Option<i32>) {
@@ -149,6 +152,26 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
+        let viewer_indication = " VIEWER ";
+        let viewer_indication_len = viewer_indication.chars().count();
+        if self.client_is_read_only && viewer_indication_len <= max_length {
+            let mut right_side = foreground_color(viewer_indication, self.color);
+            let mut right_side_len = viewer_indication_len;
+            if let Some((mut scroll_indication, scroll_indication_len)) =
+                self.render_scroll_indication(max_length - viewer_indication_len)
+            {
+                right_side.append(&mut scroll_indication);
+                right_side_len += scroll_indication_len;
+            }
+            Some((right_side, right_side_len))
+        } else {
+            self.render_scroll_indication(max_length)
+        }
+    }
+    fn render_scroll_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
         if self.scroll_position.0 > 0 || self.scroll_position.1 > 0 {
             let prefix = " SCROLL: ";
             let full_indication =
@@ -6,7 +6,7 @@ use crate::ui::pane_boundaries_frame::FrameParams;
 use crate::ClientId;
 use std::collections::HashMap;
 use zellij_utils::data::{
-    client_id_to_colors, single_client_color, InputMode, PaletteColor, Style,
+    client_id_to_colors, pane_group_color, single_client_color, InputMode, PaletteColor, Style,
 };
 use zellij_utils::errors::prelude::*;
 pub struct PaneContentsAndUi<'a> {
@@ -57,10 +57,21 @@ impl<'a> PaneContentsAndUi<'a> {
     ) -> Result<()> {
         let err_context = "failed to render pane contents to multiple clients";
 
+        // read-only clients that scrolled the pane on their own get their own view of it
+        let (scrolled_viewers, clients): (Vec<ClientId>, Vec<ClientId>) =
+            clients.partition(|client_id| self.pane.is_scrolled_for_viewer(*client_id));
+        if self.pane.should_render() {
+            for client_id in scrolled_viewers {
+                if let Some(character_chunks) = self.pane.render_for_viewer(client_id) {
+                    self.output
+                        .add_character_chunks_to_client(client_id, character_chunks, self.z_index)
+                        .context(err_context)?;
+                }
+            }
+        }
         if let Some((character_chunks, raw_vte_output, sixel_image_chunks)) =
             self.pane.render(None).context(err_context)?
         {
-            let clients: Vec<ClientId> = clients.collect();
             self.output
                 .add_character_chunks_to_multiple_clients(
                     character_chunks,
@@ -174,6 +185,7 @@ impl<'a> PaneContentsAndUi<'a> {
         &mut self,
         client_id: ClientId,
         client_mode: InputMode,
+        client_is_read_only: bool,
         session_is_mirrored: bool,
     ) -> Result<()> {
         let err_context = || format!("failed to render pane frame for client {client_id}");
@@ -206,6 +218,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_stacked_over: self.pane_is_stacked_over,
                 pane_is_stacked_under: self.pane_is_stacked_under,
                 should_draw_pane_frames: self.should_draw_pane_frames,
+                client_is_read_only,
             }
         } else {
             FrameParams {
@@ -218,6 +231,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_stacked_over: self.pane_is_stacked_over,
                 pane_is_stacked_under: self.pane_is_stacked_under,
                 should_draw_pane_frames: self.should_draw_pane_frames,
+                client_is_read_only,
             }
         };
 
@@ -255,6 +269,8 @@ impl<'a> PaneContentsAndUi<'a> {
         let pane_focused_for_client_id = self.focused_clients.contains(&client_id);
         if let Some(override_color) = self.pane.frame_color_override() {
             Some(override_color)
+        } else if self.pane.is_in_group(client_id) {
+            Some(pane_group_color(self.style.colors))
         } else if pane_focused_for_client_id {
             match mode {
                 InputMode::Normal | InputMode::Locked => {
@@ -0,0 +1,146 @@
+use super::*;
+
+fn pane_manifest(pane_infos: Vec<PaneInfo>) -> PaneManifest {
+    let mut pane_manifest = PaneManifest::default();
+    pane_manifest.panes.insert(0, pane_infos);
+    pane_manifest
+}
+
+fn terminal_pane(id: u32, title: &str) -> PaneInfo {
+    PaneInfo {
+        id,
+        title: title.to_owned(),
+        ..Default::default()
+    }
+}
+
+fn no_contents(_pane_id: PaneId) -> Option<PaneContents> {
+    None
+}
+
+#[test]
+fn wait_for_exit_reports_exit_status() {
+    let mut pane_wait =
+        PaneWait::new("pipe".into(), "migrations".into(), PaneCondition::Exited, 1).unwrap();
+    let running = pane_manifest(vec![terminal_pane(1, "db"), terminal_pane(2, "migrations")]);
+    assert_eq!(
+        pane_wait.check_session_state(&running, no_contents),
+        PaneWaitResult::Pending
+    );
+    let mut exited_pane = terminal_pane(2, "migrations");
+    exited_pane.exited = true;
+    exited_pane.exit_status = Some(3);
+    let exited = pane_manifest(vec![terminal_pane(1, "db"), exited_pane]);
+    assert_eq!(
+        pane_wait.check_session_state(&exited, no_contents),
+        PaneWaitResult::Met("3".into())
+    );
+}
+
+#[test]
+fn wait_for_exit_of_pane_that_was_closed() {
+    let mut pane_wait = PaneWait::new("pipe".into(), "2".into(), PaneCondition::Exited, 1).unwrap();
+    let not_created_yet = pane_manifest(vec![terminal_pane(1, "db")]);
+    assert_eq!(
+        pane_wait.check_session_state(&not_created_yet, no_contents),
+        PaneWaitResult::Pending,
+        "Pane is looked up again until it is created"
+    );
+    let created = pane_manifest(vec![terminal_pane(1, "db"), terminal_pane(2, "cargo run")]);
+    assert_eq!(
+        pane_wait.check_session_state(&created, no_contents),
+        PaneWaitResult::Pending,
+        "Pane found by its terminal id"
+    );
+    let closed = pane_manifest(vec![terminal_pane(1, "db")]);
+    assert_eq!(
+        pane_wait.check_session_state(&closed, no_contents),
+        PaneWaitResult::Met("".into())
+    );
+}
+
+#[test]
+fn wait_for_output_printed_after_waiting() {
+    let mut pane_wait = PaneWait::new(
+        "pipe".into(),
+        "db".into(),
+        PaneCondition::OutputMatches("ready to accept \\w+".into()),
+        1,
+    )
+    .unwrap();
+    assert_eq!(
+        pane_wait.check_output(1, b"ready to accept connections\n"),
+        PaneWaitResult::Pending,
+        "Output is ignored until the pane is found"
+    );
+    let panes = pane_manifest(vec![terminal_pane(1, "db")]);
+    assert_eq!(
+        pane_wait.check_session_state(&panes, no_contents),
+        PaneWaitResult::Pending
+    );
+    assert_eq!(
+        pane_wait.check_output(2, b"ready to accept connections\n"),
+        PaneWaitResult::Pending,
+        "Output of other panes is ignored"
+    );
+    assert_eq!(
+        pane_wait.check_output(1, b"\x1b[1mready to accept conn"),
+        PaneWaitResult::Pending
+    );
+    assert_eq!(
+        pane_wait.check_output(1, b"ections\x1b[m\r\n"),
+        PaneWaitResult::Met("ready to accept connections".into())
+    );
+}
+
+#[test]
+fn wait_for_output_printed_before_waiting() {
+    let mut pane_wait = PaneWait::new(
+        "pipe".into(),
+        "db".into(),
+        PaneCondition::OutputMatches("^ready".into()),
+        1,
+    )
+    .unwrap();
+    let panes = pane_manifest(vec![terminal_pane(1, "db")]);
+    let pane_contents = |pane_id| {
+        assert_eq!(pane_id, PaneId::Terminal(1));
+        Some(PaneContents {
+            lines_above_viewport: vec!["starting".into(), "ready".into()],
+            viewport: vec!["listening".into()],
+            ..Default::default()
+        })
+    };
+    assert_eq!(
+        pane_wait.check_session_state(&panes, pane_contents),
+        PaneWaitResult::Met("ready".into())
+    );
+}
+
+#[test]
+fn wait_for_output_of_pane_that_was_closed() {
+    let mut pane_wait = PaneWait::new(
+        "pipe".into(),
+        "db".into(),
+        PaneCondition::OutputMatches("ready".into()),
+        1,
+    )
+    .unwrap();
+    let panes = pane_manifest(vec![terminal_pane(1, "db")]);
+    pane_wait.check_session_state(&panes, no_contents);
+    assert_eq!(
+        pane_wait.check_session_state(&pane_manifest(vec![]), no_contents),
+        PaneWaitResult::Unmet
+    );
+}
+
+#[test]
+fn invalid_pattern_is_an_error() {
+    assert!(PaneWait::new(
+        "pipe".into(),
+        "db".into(),
+        PaneCondition::OutputMatches("(".into()),
+        1
+    )
+    .is_err());
+}
@@ -0,0 +1,154 @@
+use super::{action_is_allowed_for_read_only_client, route_action};
+use crate::screen::ScreenInstruction;
+use crate::thread_bus::ThreadSenders;
+use crate::{SessionMetaData, SessionState};
+use std::collections::HashMap;
+use zellij_utils::channels::{self, ChannelWithContext, SenderWithContext};
+use zellij_utils::data::{InputMode, Key, ModeInfo, PluginCapabilities};
+use zellij_utils::input::actions::Action;
+use zellij_utils::input::keybinds::Keybinds;
+use zellij_utils::input::layout::Layout;
+use zellij_utils::ipc::ClientAttributes;
+use zellij_utils::pane_size::Size;
+
+fn keybinds_with(key: Key) -> Keybinds {
+    let mut normal_mode_keybinds = HashMap::new();
+    normal_mode_keybinds.insert(key, vec![Action::SwitchToMode(InputMode::Locked)]);
+    let mut keybinds = HashMap::new();
+    keybinds.insert(InputMode::Normal, normal_mode_keybinds);
+    Keybinds(keybinds)
+}
+
+#[test]
+fn read_only_clients_can_scroll_but_not_change_the_focused_pane() {
+    let actions_changing_shared_state = vec![
+        Action::ScrollToPreviousPrompt,
+        Action::EditScrollback,
+        Action::SwitchToMode(InputMode::Copy),
+        Action::SwitchToMode(InputMode::EnterSearch),
+        Action::SwitchToMode(InputMode::RenameTab),
+    ];
+    for action in actions_changing_shared_state {
+        assert!(
+            !action_is_allowed_for_read_only_client(&action),
+            "{:?} is not allowed",
+            action
+        );
+    }
+    let allowed_actions = vec![
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ScrollToTop,
+        Action::ScrollToBottom,
+        Action::PageScrollUp,
+        Action::HalfPageScrollDown,
+        Action::SwitchToMode(InputMode::Scroll),
+        Action::SwitchToMode(InputMode::Normal),
+        Action::SwitchToMode(InputMode::Tab),
+        Action::GoToNextTab,
+        Action::Detach,
+    ];
+    for action in allowed_actions {
+        assert!(
+            action_is_allowed_for_read_only_client(&action),
+            "{:?} is allowed",
+            action
+        );
+    }
+}
+
+#[test]
+fn read_only_clients_do_not_resize_the_session() {
+    let mut session_state = SessionState::new();
+    let host = session_state.new_client();
+    let viewer = session_state.new_client();
+    session_state.set_client_read_only(viewer);
+    session_state.set_client_size(
+        host,
+        Size {
+            rows: 50,
+            cols: 200,
+        },
+    );
+    session_state.set_client_size(viewer, Size { rows: 10, cols: 40 });
+    assert_eq!(
+        session_state.min_client_terminal_size(),
+        Some(Size {
+            rows: 50,
+            cols: 200,
+        }),
+        "the host's size is kept"
+    );
+    session_state.remove_client(host);
+    assert_eq!(
+        session_state.min_client_terminal_size(),
+        Some(Size { rows: 10, cols: 40 }),
+        "read-only clients get their size when they are on their own"
+    );
+}
+
+#[test]
+fn clients_switch_modes_with_their_own_keybinds_after_one_of_them_reloads_its_config() {
+    let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
+    let session_keybinds = keybinds_with(Key::Ctrl('g'));
+    let reloaded_keybinds = keybinds_with(Key::Ctrl('h'));
+    let mut session_metadata = SessionMetaData {
+        senders: ThreadSenders {
+            to_screen: Some(SenderWithContext::new(to_screen)),
+            to_pty: None,
+            to_plugin: None,
+            to_pty_writer: None,
+            to_background_jobs: None,
+            to_server: None,
+            should_silently_fail: true,
+        },
+        capabilities: PluginCapabilities::default(),
+        client_attributes: ClientAttributes {
+            keybinds: session_keybinds.clone(),
+            ..Default::default()
+        },
+        client_keybinds: HashMap::new(),
+        default_shell: None,
+        layout: Box::new(Layout::default()),
+        screen_thread: None,
+        pty_thread: None,
+        plugin_thread: None,
+        pty_writer_thread: None,
+        background_jobs_thread: None,
+    };
+    // what ServerInstruction::ReconfigureClient does when client 1 reloads its config
+    session_metadata
+        .client_keybinds
+        .insert(1, reloaded_keybinds.clone());
+    let switch_mode = |client_id| -> ModeInfo {
+        route_action(
+            Action::SwitchToMode(InputMode::Locked),
+            client_id,
+            None,
+            session_metadata.senders.clone(),
+            session_metadata.capabilities,
+            session_metadata.client_attributes_for(client_id),
+            None,
+            session_metadata.layout.clone(),
+            None,
+        )
+        .unwrap();
+        screen_receiver
+            .try_iter()
+            .find_map(|(instruction, _err_ctx)| match instruction {
+                ScreenInstruction::ChangeMode(mode_info, _client_id) => Some(mode_info),
+                _ => None,
+            })
+            .expect("mode was changed")
+    };
+    assert_eq!(
+        switch_mode(2).keybinds,
+        session_keybinds.to_keybinds_vec(),
+        "client 2 keeps the session keybinds"
+    );
+    assert_eq!(
+        switch_mode(1).keybinds,
+        reloaded_keybinds.to_keybinds_vec(),
+        "client 1 gets its reloaded keybinds"
+    );
+}
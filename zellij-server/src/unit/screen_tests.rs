@@ -10,16 +10,17 @@ use crate::{
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize, Style};
+use zellij_utils::data::{CharOrArrow, Event, Key, Resize, ResizeStrategy, Style, TabIndexOrName};
 use zellij_utils::errors::{prelude::*, ErrorContext};
-use zellij_utils::input::actions::Action;
+use zellij_utils::input::actions::{Action, PaneCondition};
 use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginLocation, SplitDirection, TiledPaneLayout,
 };
 use zellij_utils::input::options::Options;
 use zellij_utils::ipc::IpcReceiverWithContext;
-use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::pane_size::{PaneGeom, Size, SizeInPixels};
 
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
@@ -419,6 +420,7 @@ impl MockScreen {
             senders: self.session_metadata.senders.clone(),
             capabilities: self.session_metadata.capabilities.clone(),
             client_attributes: self.session_metadata.client_attributes.clone(),
+            client_keybinds: self.session_metadata.client_keybinds.clone(),
             default_shell: self.session_metadata.default_shell.clone(),
             screen_thread: None,
             pty_thread: None,
@@ -476,6 +478,7 @@ impl MockScreen {
             capabilities,
             default_shell: None,
             client_attributes: client_attributes.clone(),
+            client_keybinds: HashMap::new(),
             screen_thread: None,
             pty_thread: None,
             plugin_thread: None,
@@ -667,6 +670,93 @@ pub fn close_tab() {
     );
 }
 
+fn tab_positions(screen: &Screen) -> Vec<(usize, usize)> {
+    // (tab index, position) sorted by position
+    let mut tab_positions: Vec<(usize, usize)> = screen
+        .tabs
+        .values()
+        .map(|t| (t.index, t.position))
+        .collect();
+    tab_positions.sort_by_key(|(_, position)| *position);
+    tab_positions
+}
+
+#[test]
+pub fn move_active_tab_left_and_right() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(1, 0), (0, 1), (2, 2)],
+        "Active tab moved left"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().index,
+        1,
+        "Moved tab is still active"
+    );
+
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 0), (2, 1), (1, 2)],
+        "Active tab wrapped around to the end"
+    );
+
+    screen.move_active_tab(Direction::Right, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(1, 0), (0, 1), (2, 2)],
+        "Active tab wrapped around to the start"
+    );
+}
+
+#[test]
+pub fn move_active_tab_to_index() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    new_tab(&mut screen, 4, 3);
+
+    screen.move_active_tab_to_index(2, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 0), (3, 1), (1, 2), (2, 3)],
+        "Active tab moved to the second position"
+    );
+
+    screen.go_to_tab(3, 1).expect("TEST");
+    screen.move_active_tab_to_index(10, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 0), (3, 1), (2, 2), (1, 3)],
+        "Index larger than the number of tabs moves the tab to the end"
+    );
+
+    screen.switch_tab_next(None, true, 1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().index,
+        0,
+        "Switching tabs follows the new order"
+    );
+}
+
 #[test]
 pub fn close_the_middle_tab() {
     let size = Size {
@@ -894,19 +984,594 @@ pub fn toggle_to_previous_tab_delete() {
 
     screen.toggle_tab(1).expect("TEST");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        2,
-        "Active tab toggler to previous tab"
+        screen.get_active_tab(1).unwrap().position,
+        2,
+        "Active tab toggler to previous tab"
+    );
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 2],
+        "Tab history is invalid"
+    );
+}
+
+#[test]
+fn switch_to_tab_with_fullscreen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    {
+        let active_tab = screen.get_active_tab_mut(1).unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+            .unwrap();
+        active_tab.toggle_active_pane_fullscreen(1);
+    }
+    new_tab(&mut screen, 2, 2);
+
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to previous"
+    );
+    assert_eq!(
+        screen
+            .get_active_tab(1)
+            .unwrap()
+            .get_active_pane_id(1)
+            .unwrap(),
+        PaneId::Terminal(2),
+        "Active pane is still the fullscreen pane"
+    );
+}
+
+#[test]
+fn move_pane_to_existing_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    new_tab(&mut screen, 3, 2);
+    screen.tabs.get_mut(&2).unwrap().name = "logs".to_owned();
+
+    screen
+        .move_pane_to_tab(
+            PaneId::Terminal(2),
+            TabIndexOrName::Name("logs".to_owned()),
+            false,
+            1,
+        )
+        .expect("TEST");
+    assert_eq!(
+        screen.tabs.get(&1).unwrap().get_tiled_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Pane was removed from its original tab"
+    );
+    let mut destination_pane_ids = screen.tabs.get(&2).unwrap().get_tiled_pane_ids();
+    destination_pane_ids.sort();
+    assert_eq!(
+        destination_pane_ids,
+        vec![PaneId::Terminal(2), PaneId::Terminal(3)],
+        "Pane was added to the destination tab"
+    );
+
+    screen
+        .move_pane_to_tab(PaneId::Terminal(2), TabIndexOrName::Index(1), true, 1)
+        .expect("TEST");
+    let origin_tab = screen.tabs.get(&1).unwrap();
+    assert!(
+        origin_tab.get_tiled_pane_ids() == vec![PaneId::Terminal(1)]
+            && origin_tab.has_pane_with_pid(&PaneId::Terminal(2))
+            && origin_tab.are_floating_panes_visible(),
+        "Pane was moved back by tab index as a floating pane"
+    );
+}
+
+#[test]
+fn cannot_move_pane_to_nonexistent_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    new_tab(&mut screen, 3, 2);
+
+    screen
+        .move_pane_to_tab(PaneId::Terminal(2), TabIndexOrName::Index(3), false, 1)
+        .expect("TEST");
+    screen
+        .move_pane_to_tab(
+            PaneId::Terminal(2),
+            TabIndexOrName::Name("no such tab".to_owned()),
+            false,
+            1,
+        )
+        .expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "No tab was created");
+    assert!(
+        screen
+            .tabs
+            .get(&1)
+            .unwrap()
+            .has_pane_with_pid(&PaneId::Terminal(2)),
+        "Pane stayed in its original tab"
+    );
+}
+
+fn screen_with_pane_group(size: Size) -> Screen {
+    // tab 1 has panes 1 and 2, tab 2 has panes 3 and 4, panes 2 and 4 are in the group of client 1
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    screen.toggle_pane_in_group(1).unwrap();
+    new_tab(&mut screen, 3, 2);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(4), None, None, None, None, Some(1))
+        .unwrap();
+    screen.toggle_pane_in_group(1).unwrap();
+    screen
+}
+
+#[test]
+fn pane_group_spans_tabs_and_is_reported_to_plugins() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    assert_eq!(
+        screen.pane_group_by_tab(1),
+        vec![(1, PaneId::Terminal(2)), (2, PaneId::Terminal(4))],
+        "Panes from both tabs are in the group"
+    );
+    let grouped_pane_infos: Vec<u32> = screen
+        .tabs
+        .values()
+        .flat_map(|t| t.pane_infos())
+        .filter(|p| p.is_in_group)
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(
+        grouped_pane_infos,
+        vec![2, 4],
+        "PaneInfo reports group membership"
+    );
+
+    screen.toggle_pane_in_group(1).unwrap();
+    assert_eq!(
+        screen.pane_group_by_tab(1),
+        vec![(1, PaneId::Terminal(2))],
+        "Toggling a grouped pane removes it from the group"
+    );
+    screen.clear_pane_group(1);
+    assert!(screen.pane_group_by_tab(1).is_empty(), "Group was cleared");
+    assert!(
+        screen
+            .tabs
+            .values()
+            .flat_map(|t| t.pane_infos())
+            .all(|p| !p.is_in_group),
+        "Clearing the group unmarks its panes"
+    );
+}
+
+#[test]
+fn pane_groups_belong_to_their_client() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen.add_client(2).unwrap();
+    assert!(
+        screen.pane_group_by_tab(2).is_empty(),
+        "Other clients start with an empty group"
+    );
+    screen.close_pane_group(2).unwrap();
+    assert_eq!(
+        screen.pane_group_by_tab(1).len(),
+        2,
+        "Group actions of another client do not touch this group"
+    );
+    screen.remove_client(1).unwrap();
+    assert!(
+        screen.pane_group_by_tab(1).is_empty(),
+        "The group of a client is dropped when it disconnects"
+    );
+    assert!(
+        screen
+            .tabs
+            .values()
+            .flat_map(|t| t.pane_infos())
+            .all(|p| !p.is_in_group),
+        "The panes of a dropped group are unmarked"
+    );
+}
+
+#[test]
+fn input_is_written_to_pane_group_only_while_broadcasting() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    assert_eq!(
+        screen.write_to_pane_group(b"a", 1).unwrap(),
+        None,
+        "Input goes to the focused pane by default"
+    );
+    screen.toggle_pane_group_broadcast(1);
+    assert!(
+        screen.write_to_pane_group(b"a", 1).unwrap().is_some(),
+        "Input goes to the group while broadcasting"
+    );
+    screen.clear_pane_group(1);
+    assert_eq!(
+        screen.write_to_pane_group(b"a", 1).unwrap(),
+        None,
+        "Clearing the group stops broadcasting"
+    );
+}
+
+#[test]
+fn close_pane_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen.close_pane_group(1).unwrap();
+    assert_eq!(
+        screen.tabs.get(&1).unwrap().get_tiled_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Grouped pane was closed in the first tab"
+    );
+    assert_eq!(
+        screen.tabs.get(&2).unwrap().get_tiled_pane_ids(),
+        vec![PaneId::Terminal(3)],
+        "Grouped pane was closed in the second tab"
+    );
+    assert!(
+        screen.pane_group_by_tab(1).is_empty(),
+        "No panes are left in the group"
+    );
+}
+
+#[test]
+fn closing_focused_pane_ignores_pane_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .close_focused_pane(1)
+        .unwrap();
+    assert_eq!(
+        screen.pane_group_by_tab(1),
+        vec![(1, PaneId::Terminal(2))],
+        "Only the focused pane was closed"
+    );
+}
+
+#[test]
+fn toggle_pane_group_embed_or_floating() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen.toggle_pane_group_embed_or_floating(1);
+    for (tab_index, tiled_pane_id, floating_pane_id) in [
+        (1, PaneId::Terminal(1), PaneId::Terminal(2)),
+        (2, PaneId::Terminal(3), PaneId::Terminal(4)),
+    ] {
+        let tab = screen.tabs.get(&tab_index).unwrap();
+        assert_eq!(
+            tab.get_tiled_pane_ids(),
+            vec![tiled_pane_id],
+            "Grouped pane is no longer tiled"
+        );
+        assert!(
+            tab.pane_is_floating(&floating_pane_id),
+            "Grouped pane was floated"
+        );
+    }
+    assert_eq!(
+        screen.pane_group_by_tab(1).len(),
+        2,
+        "Floated panes stay in the group"
+    );
+}
+
+#[test]
+fn rename_pane_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen.rename_pane_group("grouped".to_owned(), 1);
+    let renamed_panes: Vec<u32> = screen
+        .tabs
+        .values()
+        .flat_map(|t| t.pane_infos())
+        .filter(|p| p.title == "grouped")
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(
+        renamed_panes,
+        vec![2, 4],
+        "Only the grouped panes were renamed"
+    );
+}
+
+#[test]
+fn break_pane_group_to_a_new_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = screen_with_pane_group(size);
+    screen
+        .break_pane_group(None, Default::default(), 1)
+        .unwrap();
+    let new_tab_index = 3;
+    // in prod, the layout is applied after the new tab instruction goes through the plugin thread
+    screen
+        .apply_layout(
+            TiledPaneLayout::default(),
+            vec![], // floating panes layout
+            vec![], // new terminal ids
+            vec![], // new floating terminal ids
+            HashMap::new(),
+            new_tab_index,
+            1,
+        )
+        .unwrap();
+    screen
+        .move_pane_group_into_broken_out_tab(new_tab_index)
+        .unwrap();
+    assert_eq!(
+        screen.tabs.get(&1).unwrap().get_tiled_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Grouped pane was removed from the first tab"
+    );
+    assert_eq!(
+        screen.tabs.get(&2).unwrap().get_tiled_pane_ids(),
+        vec![PaneId::Terminal(3)],
+        "Grouped pane was removed from the second tab"
+    );
+    let mut new_tab_pane_ids = screen
+        .tabs
+        .get(&new_tab_index)
+        .unwrap()
+        .get_tiled_pane_ids();
+    new_tab_pane_ids.sort();
+    assert_eq!(
+        new_tab_pane_ids,
+        vec![PaneId::Terminal(2), PaneId::Terminal(4)],
+        "Both grouped panes are in the new tab"
+    );
+    assert_eq!(
+        screen.pane_group_by_tab(1),
+        vec![
+            (new_tab_index, PaneId::Terminal(2)),
+            (new_tab_index, PaneId::Terminal(4))
+        ],
+        "Broken out panes stay in the group"
+    );
+}
+
+#[test]
+fn structural_actions_are_recorded_in_action_journal() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+
+    screen.record_action_in_journal(&Action::NewPane(None, None), 1);
+    screen.record_action_in_journal(&Action::PreviousSwapLayout, 1);
+    // actions from the cli are attributed to the tab of the first client
+    screen.record_action_in_journal(&Action::CloseFocus, 5);
+    screen.record_action_in_journal(&Action::Resize(Resize::Increase, Some(Direction::Left)), 1);
+    let journal: Vec<(String, Vec<String>, usize, u16)> = screen
+        .action_journal
+        .iter()
+        .map(|e| {
+            (
+                e.action.clone(),
+                e.arguments.clone(),
+                e.tab_position,
+                e.client_id,
+            )
+        })
+        .collect();
+    assert_eq!(
+        journal,
+        vec![
+            ("NewPane".to_owned(), vec![], 1, 1),
+            ("PreviousSwapLayout".to_owned(), vec![], 1, 1),
+            ("CloseFocus".to_owned(), vec![], 1, 5),
+            (
+                "Resize".to_owned(),
+                vec!["Increase".to_owned(), "Left".to_owned()],
+                1,
+                1
+            ),
+        ],
+        "Actions recorded with the tab they were performed in"
+    );
+}
+
+#[test]
+fn undo_layout_change_restores_tiled_pane_geometry() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    let geometry_before_resize = (
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(1)),
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(2)),
+    );
+
+    let resize = ResizeStrategy::new(Resize::Increase, Some(Direction::Left));
+    screen.record_action_in_journal(&Action::Resize(Resize::Increase, Some(Direction::Left)), 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .resize(1, resize)
+        .unwrap();
+    screen.record_action_in_journal(&Action::Resize(Resize::Increase, Some(Direction::Left)), 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .resize(1, resize)
+        .unwrap();
+    assert_ne!(
+        geometry_before_resize,
+        (
+            tiled_pane_geometry(&screen, 1, PaneId::Terminal(1)),
+            tiled_pane_geometry(&screen, 1, PaneId::Terminal(2)),
+        ),
+        "Panes were resized"
+    );
+
+    assert!(screen.get_active_tab_mut(1).unwrap().undo_layout_change());
+    assert!(screen.get_active_tab_mut(1).unwrap().undo_layout_change());
+    assert_eq!(
+        geometry_before_resize,
+        (
+            tiled_pane_geometry(&screen, 1, PaneId::Terminal(1)),
+            tiled_pane_geometry(&screen, 1, PaneId::Terminal(2)),
+        ),
+        "Both resizes were undone"
+    );
+    assert!(
+        !screen.get_active_tab_mut(1).unwrap().undo_layout_change(),
+        "Nothing left to undo"
+    );
+}
+
+#[test]
+fn undo_layout_change_does_not_undo_opening_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    screen.record_action_in_journal(&Action::NewPane(None, None), 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    assert!(
+        !screen.get_active_tab_mut(1).unwrap().undo_layout_change(),
+        "Layout from before the pane was opened is not restored"
+    );
+    let mut pane_ids = screen.get_active_tab(1).unwrap().get_tiled_pane_ids();
+    pane_ids.sort();
+    assert_eq!(
+        pane_ids,
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)],
+        "No pane was closed"
+    );
+}
+
+fn tiled_pane_geometry(screen: &Screen, tab_index: usize, pane_id: PaneId) -> PaneGeom {
+    screen
+        .tabs
+        .get(&tab_index)
+        .unwrap()
+        .get_tiled_panes()
+        .find(|(id, _)| **id == pane_id)
+        .map(|(_, pane)| pane.position_and_size())
+        .unwrap()
+}
+
+#[test]
+fn swap_panes_exchanges_geometry() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    {
+        let active_tab = screen.get_active_tab_mut(1).unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+            .unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(3), None, None, None, None, Some(1))
+            .unwrap();
+    }
+    let first_geometry = tiled_pane_geometry(&screen, 1, PaneId::Terminal(1));
+    let third_geometry = tiled_pane_geometry(&screen, 1, PaneId::Terminal(3));
+
+    screen.swap_panes(PaneId::Terminal(1), PaneId::Terminal(3));
+    assert_eq!(
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(1)),
+        third_geometry,
+        "First pane took the place of the third"
+    );
+    assert_eq!(
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(3)),
+        first_geometry,
+        "Third pane took the place of the first"
     );
     assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 2],
-        "Tab history is invalid"
+        screen.get_active_tab(1).unwrap().get_active_pane_id(1),
+        Some(PaneId::Terminal(3)),
+        "Focus stayed with the same pane"
     );
 }
 
 #[test]
-fn switch_to_tab_with_fullscreen() {
+fn swap_active_pane_with_pane_in_direction() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -914,30 +1579,33 @@ fn switch_to_tab_with_fullscreen() {
     let mut screen = create_new_screen(size);
 
     new_tab(&mut screen, 1, 1);
-    {
-        let active_tab = screen.get_active_tab_mut(1).unwrap();
-        active_tab
-            .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
-            .unwrap();
-        active_tab.toggle_active_pane_fullscreen(1);
-    }
-    new_tab(&mut screen, 2, 2);
-
-    screen.switch_tab_prev(None, true, 1).expect("TEST");
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    active_tab
+        .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
+        .unwrap();
+    let left_geometry = tiled_pane_geometry(&screen, 1, PaneId::Terminal(1));
+    let right_geometry = tiled_pane_geometry(&screen, 1, PaneId::Terminal(2));
 
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    active_tab.swap_active_pane_with(Direction::Left, 1);
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        0,
-        "Active tab switched to previous"
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(2)),
+        left_geometry,
+        "Focused pane was swapped to the left"
     );
     assert_eq!(
-        screen
-            .get_active_tab(1)
-            .unwrap()
-            .get_active_pane_id(1)
-            .unwrap(),
-        PaneId::Terminal(2),
-        "Active pane is still the fullscreen pane"
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(1)),
+        right_geometry,
+        "Pane on the left was swapped to the right"
+    );
+
+    // there is nothing further to the left, so this is a no-op
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    active_tab.swap_active_pane_with(Direction::Left, 1);
+    assert_eq!(
+        tiled_pane_geometry(&screen, 1, PaneId::Terminal(2)),
+        left_geometry,
+        "Focused pane stayed in place"
     );
 }
 
@@ -1032,7 +1700,7 @@ fn attach_after_first_tab_closed() {
     {
         let active_tab = screen.get_active_tab_mut(1).unwrap();
         active_tab
-            .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+            .new_pane(PaneId::Terminal(2), None, None, None, None, Some(1))
             .unwrap();
         active_tab.toggle_active_pane_fullscreen(1);
     }
@@ -1043,6 +1711,200 @@ fn attach_after_first_tab_closed() {
     screen.add_client(1).expect("TEST");
 }
 
+#[test]
+fn terminal_cwds_and_commands_are_added_to_pane_infos() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    let mut terminal_ids_to_cwds = HashMap::new();
+    terminal_ids_to_cwds.insert(1, PathBuf::from("/tmp/project"));
+    terminal_ids_to_cwds.insert(2, PathBuf::from("/tmp"));
+    let mut terminal_ids_to_running_commands = HashMap::new();
+    terminal_ids_to_running_commands.insert(1, "vim foo.rs".to_owned());
+    screen
+        .update_terminal_cwds_and_commands(
+            vec![1, 2],
+            terminal_ids_to_cwds,
+            terminal_ids_to_running_commands,
+        )
+        .expect("TEST");
+    let pane_manifest = screen.generate_and_report_pane_state().expect("TEST");
+    let first_pane = &pane_manifest.panes.get(&0).unwrap()[0];
+    let second_pane = &pane_manifest.panes.get(&1).unwrap()[0];
+    assert_eq!(first_pane.cwd, Some(PathBuf::from("/tmp/project")));
+    assert_eq!(first_pane.running_command, Some("vim foo.rs".to_owned()));
+    assert_eq!(second_pane.cwd, Some(PathBuf::from("/tmp")));
+    assert_eq!(
+        second_pane.running_command, None,
+        "no command running in the foreground of the second pane"
+    );
+}
+
+#[test]
+fn only_queried_terminal_cwds_and_commands_are_updated() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    let mut terminal_ids_to_cwds = HashMap::new();
+    terminal_ids_to_cwds.insert(1, PathBuf::from("/tmp/project"));
+    terminal_ids_to_cwds.insert(2, PathBuf::from("/tmp"));
+    let mut terminal_ids_to_running_commands = HashMap::new();
+    terminal_ids_to_running_commands.insert(1, "vim foo.rs".to_owned());
+    screen
+        .update_terminal_cwds_and_commands(
+            vec![1, 2],
+            terminal_ids_to_cwds,
+            terminal_ids_to_running_commands,
+        )
+        .expect("TEST");
+    let mut terminal_ids_to_cwds = HashMap::new();
+    terminal_ids_to_cwds.insert(2, PathBuf::from("/home"));
+    screen
+        .update_terminal_cwds_and_commands(vec![2], terminal_ids_to_cwds, HashMap::new())
+        .expect("TEST");
+    let pane_manifest = screen.generate_and_report_pane_state().expect("TEST");
+    let first_pane = &pane_manifest.panes.get(&0).unwrap()[0];
+    let second_pane = &pane_manifest.panes.get(&1).unwrap()[0];
+    assert_eq!(first_pane.cwd, Some(PathBuf::from("/tmp/project")));
+    assert_eq!(
+        first_pane.running_command,
+        Some("vim foo.rs".to_owned()),
+        "terminal that was not queried keeps its command"
+    );
+    assert_eq!(second_pane.cwd, Some(PathBuf::from("/home")));
+}
+
+#[test]
+fn terminal_cwds_and_commands_are_only_polled_after_output() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen.terminals_with_new_output.insert(2);
+    screen.report_terminal_cwds_and_commands().expect("TEST");
+    assert!(
+        screen.terminals_with_new_output.is_empty(),
+        "terminals with output were queried"
+    );
+}
+
+#[test]
+fn rename_tab_with_pane_id_renames_the_tab_of_the_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen.rename_tab_with_pane_id(PaneId::Terminal(1), "tests failed".as_bytes().to_vec());
+    assert_eq!(
+        screen.tabs.get(&0).unwrap().name,
+        "tests failed",
+        "tab of the pane was renamed"
+    );
+    assert_ne!(
+        screen.get_active_tab(1).unwrap().name,
+        "tests failed",
+        "focused tab was not renamed"
+    );
+}
+
+#[test]
+fn read_only_clients_keep_their_role_when_changing_modes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen.read_only_clients.insert(1);
+    let mode_info = ModeInfo {
+        mode: InputMode::Scroll,
+        ..Default::default()
+    };
+    screen.change_mode_for_all_clients(mode_info).expect("TEST");
+    assert!(
+        screen.mode_info.get(&1).unwrap().is_read_only,
+        "mode info of read-only client marks it as read-only"
+    );
+}
+
+#[test]
+fn reconfigure_applies_new_keybinds_style_and_options() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    let mut normal_mode_keybinds = HashMap::new();
+    normal_mode_keybinds.insert(Key::Alt(CharOrArrow::Char('z')), vec![Action::GoToNextTab]);
+    let mut keybinds = Keybinds::default();
+    keybinds.0.insert(InputMode::Normal, normal_mode_keybinds);
+    let style = Style {
+        rounded_corners: true,
+        ..Default::default()
+    };
+    let options = Options {
+        pane_frames: Some(true),
+        auto_layout: Some(false),
+        ..Default::default()
+    };
+    screen
+        .show_config_reload_error("failed to parse config".to_owned(), 1)
+        .expect("TEST");
+    assert!(
+        screen.client_overlays.contains_key(&1),
+        "config error is shown to the client"
+    );
+
+    screen.add_client(2).expect("TEST");
+    screen.change_mode(ModeInfo::default(), 2).expect("TEST");
+    let other_client_keybinds = screen.mode_info.get(&2).unwrap().keybinds.clone();
+    screen
+        .reconfigure(keybinds.clone(), style, options, 1)
+        .expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().keybinds,
+        keybinds.to_keybinds_vec(),
+        "client mode info has the new keybinds"
+    );
+    assert_eq!(
+        screen.mode_info.get(&2).unwrap().keybinds,
+        other_client_keybinds,
+        "other clients keep their own keybinds"
+    );
+    assert!(
+        screen.mode_info.get(&2).unwrap().style.rounded_corners,
+        "the style of the session is shared by all clients"
+    );
+    assert!(screen.style.rounded_corners, "screen has the new style");
+    assert!(screen.draw_pane_frames, "pane frames were turned on");
+    assert!(!screen.auto_layout, "auto layout was turned off");
+    assert!(
+        !screen.client_overlays.contains_key(&1),
+        "config error is cleared once the config is valid"
+    );
+}
+
 // Following are tests for sending CLI actions
 // these tests are only partially relevant to Screen
 // and are included here for two reasons:
@@ -1340,6 +2202,108 @@ pub fn send_cli_dump_screen_action() {
     ));
 }
 
+#[test]
+pub fn wait_for_pane_reports_through_cli_pipe() {
+    let size = Size { cols: 80, rows: 20 };
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::WaitForPane(
+        "output-pipe".into(),
+        "0".into(),
+        PaneCondition::OutputMatches("^server is \\w+".into()),
+        true,
+        1,
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::WaitForPane(
+        "missing-pane-pipe".into(),
+        "no-such-pane".into(),
+        PaneCondition::Exited,
+        false,
+        1,
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "starting\r\nserver is ready\r\n".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let pipe_messages: Vec<String> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::CliPipeOutput(pipe_id, output) => {
+                Some(format!("{}: {}", pipe_id, output))
+            },
+            ServerInstruction::UnblockCliPipeInput(pipe_id) => {
+                Some(format!("{}: unblocked", pipe_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        pipe_messages,
+        vec![
+            "missing-pane-pipe: unblocked".to_owned(),
+            "output-pipe: server is ready".to_owned(),
+            "output-pipe: unblocked".to_owned(),
+        ],
+        "Unmet condition is reported right away without output, met condition with the matching line"
+    );
+}
+
+#[test]
+pub fn pane_wait_is_dropped_when_its_client_disconnects() {
+    let size = Size { cols: 80, rows: 20 };
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_client_id = 10;
+    let _ = mock_screen.to_screen.send(ScreenInstruction::WaitForPane(
+        "output-pipe".into(),
+        "0".into(),
+        PaneCondition::OutputMatches("^server is \\w+".into()),
+        true,
+        cli_client_id,
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RemoveClient(cli_client_id));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "starting\r\nserver is ready\r\n".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let reported_to_pipe = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|instruction| {
+            matches!(
+                instruction,
+                ServerInstruction::CliPipeOutput(..) | ServerInstruction::UnblockCliPipeInput(..)
+            )
+        });
+    assert!(
+        !reported_to_pipe,
+        "nothing is reported to the pipe of a client that disconnected"
+    );
+}
+
 #[test]
 pub fn send_cli_edit_scrollback_action() {
     let size = Size { cols: 80, rows: 20 };
@@ -1892,8 +2856,15 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
+        watch: false,
+        clear_data: false,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1931,8 +2902,15 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
+        watch: false,
+        clear_data: false,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1940,6 +2918,53 @@ pub fn send_cli_new_pane_action_with_split_direction() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn send_cli_pipe_pane_to_file_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_pipe_pane_to_file_action = CliAction::PipePaneToFile {
+        pane_id: 1,
+        path: PathBuf::from("/tmp/pane-1.log"),
+        strip_ansi: true,
+    };
+    send_cli_action_to_server(&session_metadata, cli_pipe_pane_to_file_action, client_id);
+    let cli_stop_pipe_pane_to_file_action = CliAction::StopPipePaneToFile { pane_id: 1 };
+    send_cli_action_to_server(
+        &session_metadata,
+        cli_stop_pipe_pane_to_file_action,
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    assert!(
+        received_pty_instructions.iter().any(|instruction| matches!(
+            instruction,
+            PtyInstruction::PipePaneToFile(1, path, true, _) if path == &PathBuf::from("/tmp/pane-1.log")
+        )),
+        "pipe-pane-to-file sent to pty"
+    );
+    assert!(
+        received_pty_instructions
+            .iter()
+            .any(|instruction| matches!(instruction, PtyInstruction::StopPipePaneToFile(1, _))),
+        "stop-pipe-pane-to-file sent to pty"
+    );
+}
+
 #[test]
 pub fn send_cli_new_pane_action_with_command_and_cwd() {
     let size = Size {
@@ -1970,8 +2995,15 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        env: vec![],
         configuration: None,
         skip_plugin_cache: false,
+        watch: false,
+        clear_data: false,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2006,6 +3038,10 @@ pub fn send_cli_edit_action_with_default_parameters() {
         floating: false,
         in_place: false,
         cwd: None,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_edit_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2040,6 +3076,10 @@ pub fn send_cli_edit_action_with_line_number() {
         floating: false,
         in_place: false,
         cwd: None,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_edit_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2074,6 +3114,10 @@ pub fn send_cli_edit_action_with_split_direction() {
         floating: false,
         in_place: false,
         cwd: None,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
     };
     send_cli_action_to_server(&session_metadata, cli_edit_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2635,6 +3679,8 @@ pub fn send_cli_launch_or_focus_plugin_action_when_plugin_is_already_loaded() {
     let existing_plugin_pane = TiledPaneLayout {
         run: Some(Run::Plugin(RunPlugin {
             _allow_exec_host_cmd: false,
+            _watch: false,
+            _watch_clear_data: false,
             location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
             configuration: Default::default(),
         })),
@@ -2902,6 +3948,8 @@ pub fn screen_can_break_plugin_pane_to_a_new_tab() {
     pane_to_break_free.name = Some("plugin_pane_to_break_free".to_owned());
     pane_to_break_free.run = Some(Run::Plugin(RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
         configuration: Default::default(),
     }));
@@ -2972,6 +4020,8 @@ pub fn screen_can_break_floating_plugin_pane_to_a_new_tab() {
     floating_pane.name = Some("floating_plugin_pane_to_eject".to_owned());
     floating_pane.run = Some(Run::Plugin(RunPlugin {
         _allow_exec_host_cmd: false,
+        _watch: false,
+        _watch_clear_data: false,
         location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
         configuration: Default::default(),
     }));
@@ -3129,3 +4179,50 @@ pub fn screen_can_move_pane_to_a_new_tab_left() {
     }
     assert_snapshot!(format!("{}", snapshot_count));
 }
+
+#[test]
+pub fn refreshing_plugin_visibility_sends_visible_event() {
+    // eg. after a watched plugin is reloaded
+    let size = Size { cols: 80, rows: 20 };
+    let mut initial_layout = TiledPaneLayout::default();
+    let plugin_pane = TiledPaneLayout {
+        run: Some(Run::Plugin(RunPlugin {
+            _allow_exec_host_cmd: false,
+            _watch: true,
+            _watch_clear_data: false,
+            location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
+            configuration: Default::default(),
+        })),
+        ..Default::default()
+    };
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![plugin_pane, TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let instruction_count_before_refresh = received_plugin_instructions.lock().unwrap().len();
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RefreshPluginVisibility(vec![1]));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let visible_event_sent = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .skip(instruction_count_before_refresh)
+        .any(|instruction| match instruction {
+            PluginInstruction::Update(updates) => {
+                updates.contains(&(Some(1), None, Event::Visible(true)))
+            },
+            _ => false,
+        });
+    assert!(visible_event_sent);
+}
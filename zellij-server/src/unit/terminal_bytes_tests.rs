@@ -0,0 +1,129 @@
+use super::*;
+use tempfile::tempdir;
+
+fn trigger(pattern: &str) -> Trigger {
+    Trigger {
+        pattern: pattern.to_owned(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn trigger_matches_complete_lines_without_ansi_codes() {
+    let mut trigger_matcher = TriggerMatcher::new(&Triggers(vec![trigger("^test result: FAILED")]));
+    let matches = trigger_matcher
+        .match_bytes(b"running 3 tests\r\n\x1b[31mtest result: FAILED\x1b[0m. 1 passed\r\n");
+    assert_eq!(
+        matches,
+        vec![(
+            trigger("^test result: FAILED"),
+            "test result: FAILED. 1 passed".to_owned()
+        )],
+        "Trigger matched line stripped of ANSI codes"
+    );
+}
+
+#[test]
+fn trigger_matches_lines_split_across_reads() {
+    let mut trigger_matcher = TriggerMatcher::new(&Triggers(vec![trigger("listening on \\d+")]));
+    assert!(
+        trigger_matcher.match_bytes(b"server liste").is_empty(),
+        "Incomplete line does not match"
+    );
+    let matches = trigger_matcher.match_bytes(b"ning on 8080\n");
+    assert_eq!(
+        matches,
+        vec![(
+            trigger("listening on \\d+"),
+            "server listening on 8080".to_owned()
+        )],
+        "Line completed in a later read matched"
+    );
+}
+
+#[test]
+fn trigger_fires_once_per_read() {
+    let mut trigger_matcher = TriggerMatcher::new(&Triggers(vec![trigger("FAILED")]));
+    let matches = trigger_matcher.match_bytes(b"test a FAILED\ntest b FAILED\n");
+    assert_eq!(matches.len(), 1, "Trigger fired once for both lines");
+}
+
+#[test]
+fn trigger_matcher_drops_the_beginning_of_very_long_lines() {
+    let mut trigger_matcher = TriggerMatcher::new(&Triggers(vec![trigger("^x")]));
+    trigger_matcher.match_bytes(b"x");
+    trigger_matcher.match_bytes(&[b'a'; MAX_PENDING_LINE_LENGTH]);
+    trigger_matcher.match_bytes(b"bbbb");
+    assert_eq!(
+        trigger_matcher.pending_line.len(),
+        MAX_PENDING_LINE_LENGTH,
+        "Pending line is capped"
+    );
+    assert!(
+        trigger_matcher.match_bytes(b"\n").is_empty(),
+        "Dropped beginning of line is not matched"
+    );
+}
+
+#[test]
+fn pane_output_log_appends_raw_bytes() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("pane.log");
+    std::fs::write(&path, "existing\n").unwrap();
+    let mut output_log = PaneOutputLog::new(path.clone(), false).unwrap();
+    output_log.write(b"\x1b[31mred\x1b[0m\r\n").unwrap();
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        b"existing\n\x1b[31mred\x1b[0m\r\n".to_vec(),
+        "Output appended to the log as is"
+    );
+}
+
+#[test]
+fn pane_output_log_strips_ansi_codes_split_across_reads() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("pane.log");
+    let mut output_log = PaneOutputLog::new(path.clone(), true).unwrap();
+    output_log.write(b"\x1b[3").unwrap();
+    output_log.write(b"1mred\x1b[0m\r\n\tdone\x07").unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "red\r\n\tdone",
+        "Escape sequence split between reads stripped from the log"
+    );
+}
+
+#[test]
+fn pane_output_log_to_unwritable_path_is_an_error() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("no-such-dir").join("pane.log");
+    assert!(PaneOutputLog::new(path, false).is_err());
+}
+
+#[test]
+fn pane_output_logs_write_only_the_output_of_logged_terminals() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("pane.log");
+    let pane_output_logs = PaneOutputLogs::new();
+    pane_output_logs
+        .start(1, PaneOutputLog::new(path.clone(), false).unwrap())
+        .unwrap();
+    pane_output_logs.write(1, b"logged\n");
+    pane_output_logs.write(2, b"other terminal\n");
+    assert!(pane_output_logs.stop(1).unwrap(), "Terminal was logged");
+    pane_output_logs.write(1, b"after stop\n");
+    assert!(
+        !pane_output_logs.stop(1).unwrap(),
+        "Terminal no longer logged"
+    );
+    // the file is written to on another thread
+    let mut contents = String::new();
+    for _ in 0..100 {
+        contents = std::fs::read_to_string(&path).unwrap();
+        if !contents.is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(contents, "logged\n", "Only output logged while started");
+}
@@ -85,7 +85,16 @@ pub fn open_file(file_to_open: FileToOpen) {
 
 /// Open a file in the user's default `$EDITOR` in a new floating pane
 pub fn open_file_floating(file_to_open: FileToOpen) {
-    let plugin_command = PluginCommand::OpenFileFloating(file_to_open);
+    open_file_floating_with_coordinates(file_to_open, None)
+}
+
+/// Open a file in the user's default `$EDITOR` in a new floating pane, optionally at the
+/// specified coordinates
+pub fn open_file_floating_with_coordinates(
+    file_to_open: FileToOpen,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
+    let plugin_command = PluginCommand::OpenFileFloating(file_to_open, coordinates);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -110,8 +119,17 @@ pub fn open_terminal<P: AsRef<Path>>(path: P) {
 
 /// Open a new floating terminal pane to the specified location on the host filesystem
 pub fn open_terminal_floating<P: AsRef<Path>>(path: P) {
+    open_terminal_floating_with_coordinates(path, None)
+}
+
+/// Open a new floating terminal pane to the specified location on the host filesystem, optionally
+/// at the specified coordinates
+pub fn open_terminal_floating_with_coordinates<P: AsRef<Path>>(
+    path: P,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
     let file_to_open = FileToOpen::new(path.as_ref().to_path_buf());
-    let plugin_command = PluginCommand::OpenTerminalFloating(file_to_open);
+    let plugin_command = PluginCommand::OpenTerminalFloating(file_to_open, coordinates);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -137,7 +155,15 @@ pub fn open_command_pane(command_to_run: CommandToRun) {
 
 /// Open a new floating command pane with the specified command and args (this sort of pane allows the user to control the command, re-run it and see its exit status through the Zellij UI).
 pub fn open_command_pane_floating(command_to_run: CommandToRun) {
-    let plugin_command = PluginCommand::OpenCommandPaneFloating(command_to_run);
+    open_command_pane_floating_with_coordinates(command_to_run, None)
+}
+
+/// Open a new floating command pane with the specified command and args, optionally at the specified coordinates (this sort of pane allows the user to control the command, re-run it and see its exit status through the Zellij UI).
+pub fn open_command_pane_floating_with_coordinates(
+    command_to_run: CommandToRun,
+    coordinates: Option<FloatingPaneCoordinates>,
+) {
+    let plugin_command = PluginCommand::OpenCommandPaneFloating(command_to_run, coordinates);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -570,6 +596,22 @@ pub fn go_to_tab(tab_index: u32) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Move the focused tab one position in the specified direction, wrapping around at either end
+pub fn move_tab(direction: Direction) {
+    let plugin_command = PluginCommand::MoveTab(direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Move the focused tab to the specified position (starting at 1)
+pub fn move_tab_to_index(tab_index: u32) {
+    let plugin_command = PluginCommand::MoveTabToIndex(tab_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn start_or_reload_plugin(url: &str) {
     let plugin_command = PluginCommand::StartOrReloadPlugin(url.to_owned());
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -726,6 +768,157 @@ pub fn pipe_message_to_plugin(message_to_plugin: MessageToPlugin) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Send a request to the plugin with the `plugin_url` of the request's message (it will be
+/// launched if it is not already running). The answer, or a timeout if none arrives in time, will
+/// be sent to this plugin as an `Event::PluginResponse` with the same `request_id` (requires the
+/// `MessageAndLaunchOtherPlugins` permission)
+pub fn send_plugin_request(plugin_request: PluginRequest) {
+    let plugin_command = PluginCommand::SendPluginRequest(plugin_request);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Set a key in the plugin store, notifying the plugins watching it with an
+/// `Event::StoreChanged`. Plugin and global keys are persisted across sessions. Using the session
+/// and global scopes requires the `ShareDataWithOtherPlugins` permission.
+pub fn store_set(scope: StoreScope, key: impl Into<String>, value: impl Into<String>) {
+    let plugin_command = PluginCommand::StoreSet(scope, key.into(), Some(value.into()));
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Remove a key from the plugin store, notifying the plugins watching it with an
+/// `Event::StoreChanged`
+pub fn store_remove(scope: StoreScope, key: impl Into<String>) {
+    let plugin_command = PluginCommand::StoreSet(scope, key.into(), None);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Request the value of a key in the plugin store. The result will be sent to the plugin as an
+/// `Event::StoreValue`
+pub fn store_get(scope: StoreScope, key: impl Into<String>) {
+    let plugin_command = PluginCommand::StoreGet(scope, key.into());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Be notified with an `Event::StoreChanged` whenever a key in the plugin store is set or removed
+/// in this session
+pub fn store_watch(scope: StoreScope, key: impl Into<String>) {
+    let plugin_command = PluginCommand::StoreWatch(scope, key.into());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Answer a `PipeMessage` with a `request_id` that was sent by the plugin with this id (its
+/// `PipeSource::Plugin`)
+pub fn reply_to_plugin_request(requester_plugin_id: u32, response: PluginResponse) {
+    let plugin_command = PluginCommand::ReplyToPluginRequest(requester_plugin_id, response);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Request the contents of a pane, including up to `scrollback_lines` lines of its scrollback
+/// buffer. The result will be sent to the plugin as an `Event::PaneContents` (requires the
+/// `ReadPaneContents` permission)
+pub fn get_pane_scrollback(pane_id: PaneId, scrollback_lines: usize) {
+    let plugin_command = PluginCommand::GetPaneScrollback(pane_id, scrollback_lines);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Request the structural actions (eg. opening, closing, moving and resizing panes, swap layout and
+/// tab changes) performed in this session. The result will be sent to the plugin as an
+/// `Event::ActionJournal` (requires the `ReadApplicationState` permission)
+pub fn get_action_journal() {
+    let plugin_command = PluginCommand::GetActionJournal;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Log everything the terminal pane with this id receives to the file at `path` (appending to
+/// it), optionally stripping ANSI escape codes, until stopped with `stop_pipe_pane_to_file`
+/// (requires the `ReadPaneContents` and `WriteToFiles` permissions)
+pub fn pipe_pane_to_file<P: AsRef<Path>>(terminal_pane_id: u32, path: P, strip_ansi: bool) {
+    let plugin_command =
+        PluginCommand::PipePaneToFile(terminal_pane_id, path.as_ref().to_path_buf(), strip_ansi);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stop logging the output of the terminal pane with this id to a file
+pub fn stop_pipe_pane_to_file(terminal_pane_id: u32) {
+    let plugin_command = PluginCommand::StopPipePaneToFile(terminal_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Swap the positions of two tiled panes in the same tab, keeping their contents and focus
+pub fn swap_panes(first_pane_id: PaneId, second_pane_id: PaneId) {
+    let plugin_command = PluginCommand::SwapPanes(first_pane_id, second_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Swap the positions of the focused pane and the pane next to it in the specified direction
+pub fn swap_pane_with(direction: Direction) {
+    let plugin_command = PluginCommand::SwapPaneWith(direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Move and/or resize the floating pane with this id, leaving the coordinates that are not
+/// specified unchanged
+pub fn change_floating_pane_coordinates(pane_id: PaneId, coordinates: FloatingPaneCoordinates) {
+    let plugin_command = PluginCommand::ChangeFloatingPaneCoordinates(pane_id, coordinates);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Pin or unpin the floating pane with this id, pinned panes are always drawn on top of the tiled
+/// panes, even when the floating panes are hidden
+pub fn set_pane_pinned(pane_id: PaneId, should_be_pinned: bool) {
+    let plugin_command = PluginCommand::SetPanePinned(pane_id, should_be_pinned);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Bind a key in the specified input mode to this plugin, which will then receive it as an
+/// `Event::Key` (if subscribed to it) whether or not it is focused (requires the `InterceptKeys`
+/// permission). If `intercept` is true the key is not handled any further (eg. by the configured
+/// keybinds or the focused pane). The binding is removed when the plugin is unloaded
+pub fn register_keybind(mode: InputMode, key: Key, intercept: bool) {
+    let plugin_command = PluginCommand::RegisterKeybind(mode, key, intercept);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Move the pane with this id to an existing tab, either by its position (starting at 1) or its
+/// name, keeping its process and scrollback. The pane becomes floating in the new tab if
+/// `should_float` is true
+pub fn move_pane_to_tab(pane_id: PaneId, tab: TabIndexOrName, should_float: bool) {
+    let plugin_command = PluginCommand::MovePaneToTab(pane_id, tab, should_float);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
 #[allow(unused)]
@@ -5,7 +5,7 @@ pub struct Action {
     pub name: i32,
     #[prost(
         oneof = "action::OptionalPayload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59"
     )]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
@@ -106,6 +106,30 @@ pub mod action {
         LaunchPluginPayload(super::LaunchOrFocusPluginPayload),
         #[prost(message, tag = "47")]
         MessagePayload(super::CliPipePayload),
+        #[prost(enumeration = "super::CopyModeMotion", tag = "48")]
+        CopyModeMovePayload(i32),
+        #[prost(enumeration = "super::CopyModeSelection", tag = "49")]
+        CopyModeToggleSelectionPayload(i32),
+        #[prost(message, tag = "50")]
+        PipePaneToFilePayload(super::PipePaneToFilePayload),
+        #[prost(uint32, tag = "51")]
+        StopPipePaneToFilePayload(u32),
+        #[prost(message, tag = "52")]
+        MovePaneToTabPayload(super::MovePaneToTabPayload),
+        #[prost(enumeration = "super::super::resize::ResizeDirection", tag = "53")]
+        MoveTabPayload(i32),
+        #[prost(uint32, tag = "54")]
+        MoveTabToIndexPayload(u32),
+        #[prost(message, tag = "55")]
+        SwapPanesPayload(super::SwapPanesPayload),
+        #[prost(enumeration = "super::super::resize::ResizeDirection", tag = "56")]
+        SwapPaneWithPayload(i32),
+        #[prost(message, tag = "57")]
+        ChangeFloatingPaneCoordinatesPayload(super::ChangeFloatingPaneCoordinatesPayload),
+        #[prost(message, tag = "58")]
+        SetPanePinnedPayload(super::SetPanePinnedPayload),
+        #[prost(string, tag = "59")]
+        RenamePaneGroupPayload(::prost::alloc::string::String),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -138,6 +162,82 @@ pub struct PaneIdAndShouldFloat {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipePaneToFilePayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub strip_ansi: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MovePaneToTabPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(uint32, optional, tag = "3")]
+    pub tab_index: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "4")]
+    pub tab_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "5")]
+    pub should_float: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapPanesPayload {
+    #[prost(uint32, tag = "1")]
+    pub first_pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub first_pane_is_plugin: bool,
+    #[prost(uint32, tag = "3")]
+    pub second_pane_id: u32,
+    #[prost(bool, tag = "4")]
+    pub second_pane_is_plugin: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PercentOrFixed {
+    #[prost(bool, tag = "1")]
+    pub is_percent: bool,
+    #[prost(uint32, tag = "2")]
+    pub value: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatingPaneCoordinates {
+    #[prost(message, optional, tag = "1")]
+    pub x: ::core::option::Option<PercentOrFixed>,
+    #[prost(message, optional, tag = "2")]
+    pub y: ::core::option::Option<PercentOrFixed>,
+    #[prost(message, optional, tag = "3")]
+    pub width: ::core::option::Option<PercentOrFixed>,
+    #[prost(message, optional, tag = "4")]
+    pub height: ::core::option::Option<PercentOrFixed>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeFloatingPaneCoordinatesPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(message, optional, tag = "3")]
+    pub coordinates: ::core::option::Option<FloatingPaneCoordinates>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPanePinnedPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(bool, tag = "3")]
+    pub should_be_pinned: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewPluginPanePayload {
     #[prost(string, tag = "1")]
     pub plugin_url: ::prost::alloc::string::String,
@@ -175,6 +275,8 @@ pub struct GoToTabNamePayload {
 pub struct NewFloatingPanePayload {
     #[prost(message, optional, tag = "1")]
     pub command: ::core::option::Option<RunCommandAction>,
+    #[prost(message, optional, tag = "2")]
+    pub coordinates: ::core::option::Option<FloatingPaneCoordinates>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -203,6 +305,8 @@ pub struct EditFilePayload {
     pub direction: ::core::option::Option<i32>,
     #[prost(bool, tag = "5")]
     pub should_float: bool,
+    #[prost(message, optional, tag = "6")]
+    pub coordinates: ::core::option::Option<FloatingPaneCoordinates>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -269,6 +373,16 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag = "7")]
     pub hold_on_start: bool,
+    #[prost(message, repeated, tag = "8")]
+    pub env_variables: ::prost::alloc::vec::Vec<EnvVariable>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnvVariable {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub value: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -316,6 +430,7 @@ pub enum SearchOption {
     CaseSensitivity = 0,
     WholeWord = 1,
     Wrap = 2,
+    Regex = 3,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -327,6 +442,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "CaseSensitivity",
             SearchOption::WholeWord => "WholeWord",
             SearchOption::Wrap => "Wrap",
+            SearchOption::Regex => "Regex",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -335,6 +451,89 @@ impl SearchOption {
             "CaseSensitivity" => Some(Self::CaseSensitivity),
             "WholeWord" => Some(Self::WholeWord),
             "Wrap" => Some(Self::Wrap),
+            "Regex" => Some(Self::Regex),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeMotion {
+    CursorLeft = 0,
+    CursorRight = 1,
+    CursorUp = 2,
+    CursorDown = 3,
+    NextWord = 4,
+    PreviousWord = 5,
+    EndOfWord = 6,
+    LineStart = 7,
+    LineEnd = 8,
+    Top = 9,
+    Bottom = 10,
+    GoPrefix = 11,
+}
+impl CopyModeMotion {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeMotion::CursorLeft => "CursorLeft",
+            CopyModeMotion::CursorRight => "CursorRight",
+            CopyModeMotion::CursorUp => "CursorUp",
+            CopyModeMotion::CursorDown => "CursorDown",
+            CopyModeMotion::NextWord => "NextWord",
+            CopyModeMotion::PreviousWord => "PreviousWord",
+            CopyModeMotion::EndOfWord => "EndOfWord",
+            CopyModeMotion::LineStart => "LineStart",
+            CopyModeMotion::LineEnd => "LineEnd",
+            CopyModeMotion::Top => "Top",
+            CopyModeMotion::Bottom => "Bottom",
+            CopyModeMotion::GoPrefix => "GoPrefix",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CursorLeft" => Some(Self::CursorLeft),
+            "CursorRight" => Some(Self::CursorRight),
+            "CursorUp" => Some(Self::CursorUp),
+            "CursorDown" => Some(Self::CursorDown),
+            "NextWord" => Some(Self::NextWord),
+            "PreviousWord" => Some(Self::PreviousWord),
+            "EndOfWord" => Some(Self::EndOfWord),
+            "LineStart" => Some(Self::LineStart),
+            "LineEnd" => Some(Self::LineEnd),
+            "Top" => Some(Self::Top),
+            "Bottom" => Some(Self::Bottom),
+            "GoPrefix" => Some(Self::GoPrefix),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeSelection {
+    Character = 0,
+    Line = 1,
+}
+impl CopyModeSelection {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeSelection::Character => "Character",
+            CopyModeSelection::Line => "Line",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Character" => Some(Self::Character),
+            "Line" => Some(Self::Line),
             _ => None,
         }
     }
@@ -425,6 +624,30 @@ pub enum ActionName {
     RenameSession = 80,
     LaunchPlugin = 81,
     CliPipe = 82,
+    CopyModeMove = 83,
+    CopyModeToggleSelection = 84,
+    ScrollToPreviousPrompt = 85,
+    ScrollToNextPrompt = 86,
+    CopyLastCommandOutput = 87,
+    PipePaneToFile = 88,
+    StopPipePaneToFile = 89,
+    MovePaneToTab = 90,
+    MoveTab = 91,
+    MoveTabToIndex = 92,
+    SwapPanes = 93,
+    SwapPaneWith = 94,
+    ChangeFloatingPaneCoordinates = 95,
+    TogglePanePinned = 96,
+    SetPanePinned = 97,
+    TogglePaneInGroup = 98,
+    ClearPaneGroup = 99,
+    QueryActionJournal = 100,
+    UndoLayoutChange = 101,
+    TogglePaneGroupBroadcast = 102,
+    ClosePaneGroup = 103,
+    BreakPaneGroup = 104,
+    TogglePaneGroupEmbedOrFloating = 105,
+    RenamePaneGroup = 106,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -516,6 +739,30 @@ impl ActionName {
             ActionName::RenameSession => "RenameSession",
             ActionName::LaunchPlugin => "LaunchPlugin",
             ActionName::CliPipe => "CliPipe",
+            ActionName::CopyModeMove => "CopyModeMove",
+            ActionName::CopyModeToggleSelection => "CopyModeToggleSelection",
+            ActionName::ScrollToPreviousPrompt => "ScrollToPreviousPrompt",
+            ActionName::ScrollToNextPrompt => "ScrollToNextPrompt",
+            ActionName::CopyLastCommandOutput => "CopyLastCommandOutput",
+            ActionName::PipePaneToFile => "PipePaneToFile",
+            ActionName::StopPipePaneToFile => "StopPipePaneToFile",
+            ActionName::MovePaneToTab => "MovePaneToTab",
+            ActionName::MoveTab => "MoveTab",
+            ActionName::MoveTabToIndex => "MoveTabToIndex",
+            ActionName::SwapPanes => "SwapPanes",
+            ActionName::SwapPaneWith => "SwapPaneWith",
+            ActionName::ChangeFloatingPaneCoordinates => "ChangeFloatingPaneCoordinates",
+            ActionName::TogglePanePinned => "TogglePanePinned",
+            ActionName::SetPanePinned => "SetPanePinned",
+            ActionName::TogglePaneInGroup => "TogglePaneInGroup",
+            ActionName::ClearPaneGroup => "ClearPaneGroup",
+            ActionName::QueryActionJournal => "QueryActionJournal",
+            ActionName::UndoLayoutChange => "UndoLayoutChange",
+            ActionName::TogglePaneGroupBroadcast => "TogglePaneGroupBroadcast",
+            ActionName::ClosePaneGroup => "ClosePaneGroup",
+            ActionName::BreakPaneGroup => "BreakPaneGroup",
+            ActionName::TogglePaneGroupEmbedOrFloating => "TogglePaneGroupEmbedOrFloating",
+            ActionName::RenamePaneGroup => "RenamePaneGroup",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -604,6 +851,30 @@ impl ActionName {
             "RenameSession" => Some(Self::RenameSession),
             "LaunchPlugin" => Some(Self::LaunchPlugin),
             "CliPipe" => Some(Self::CliPipe),
+            "CopyModeMove" => Some(Self::CopyModeMove),
+            "CopyModeToggleSelection" => Some(Self::CopyModeToggleSelection),
+            "ScrollToPreviousPrompt" => Some(Self::ScrollToPreviousPrompt),
+            "ScrollToNextPrompt" => Some(Self::ScrollToNextPrompt),
+            "CopyLastCommandOutput" => Some(Self::CopyLastCommandOutput),
+            "PipePaneToFile" => Some(Self::PipePaneToFile),
+            "StopPipePaneToFile" => Some(Self::StopPipePaneToFile),
+            "MovePaneToTab" => Some(Self::MovePaneToTab),
+            "MoveTab" => Some(Self::MoveTab),
+            "MoveTabToIndex" => Some(Self::MoveTabToIndex),
+            "SwapPanes" => Some(Self::SwapPanes),
+            "SwapPaneWith" => Some(Self::SwapPaneWith),
+            "ChangeFloatingPaneCoordinates" => Some(Self::ChangeFloatingPaneCoordinates),
+            "TogglePanePinned" => Some(Self::TogglePanePinned),
+            "SetPanePinned" => Some(Self::SetPanePinned),
+            "TogglePaneInGroup" => Some(Self::TogglePaneInGroup),
+            "ClearPaneGroup" => Some(Self::ClearPaneGroup),
+            "QueryActionJournal" => Some(Self::QueryActionJournal),
+            "UndoLayoutChange" => Some(Self::UndoLayoutChange),
+            "TogglePaneGroupBroadcast" => Some(Self::TogglePaneGroupBroadcast),
+            "ClosePaneGroup" => Some(Self::ClosePaneGroup),
+            "BreakPaneGroup" => Some(Self::BreakPaneGroup),
+            "TogglePaneGroupEmbedOrFloating" => Some(Self::TogglePaneGroupEmbedOrFloating),
+            "RenamePaneGroup" => Some(Self::RenamePaneGroup),
             _ => None,
         }
     }
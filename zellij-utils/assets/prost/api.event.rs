@@ -11,7 +11,7 @@ pub struct Event {
     pub name: i32,
     #[prost(
         oneof = "event::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21"
     )]
     pub payload: ::core::option::Option<event::Payload>,
 }
@@ -48,10 +48,88 @@ pub mod event {
         RunCommandResultPayload(super::RunCommandResultPayload),
         #[prost(message, tag = "15")]
         WebRequestResultPayload(super::WebRequestResultPayload),
+        #[prost(message, tag = "16")]
+        PaneContentsPayload(super::PaneContentsPayload),
+        #[prost(message, tag = "17")]
+        PaneOutputMatchedPayload(super::PaneOutputMatchedPayload),
+        #[prost(message, tag = "18")]
+        ActionJournalPayload(super::ActionJournalPayload),
+        #[prost(message, tag = "19")]
+        PluginResponsePayload(super::PluginResponsePayload),
+        #[prost(message, tag = "20")]
+        StoreValuePayload(super::StoreEntryPayload),
+        #[prost(message, tag = "21")]
+        StoreChangedPayload(super::StoreEntryPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneContentsPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(string, repeated, tag = "3")]
+    pub viewport: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "4")]
+    pub lines_above_viewport: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "5")]
+    pub lines_below_viewport: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneOutputMatchedPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub is_plugin: bool,
+    #[prost(string, tag = "3")]
+    pub pattern: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub line: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActionJournalPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<ActionJournalEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActionJournalEntry {
+    #[prost(string, tag = "1")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub tab_position: u32,
+    #[prost(uint32, tag = "3")]
+    pub client_id: u32,
+    #[prost(uint64, tag = "4")]
+    pub timestamp: u64,
+    #[prost(string, repeated, tag = "5")]
+    pub arguments: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginResponsePayload {
+    #[prost(string, tag = "1")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "PluginResponseStatus", tag = "2")]
+    pub status: i32,
+    #[prost(string, optional, tag = "3")]
+    pub payload: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreEntryPayload {
+    #[prost(enumeration = "StoreScope", tag = "1")]
+    pub scope: i32,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub value: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SessionUpdatePayload {
     #[prost(message, repeated, tag = "1")]
     pub session_manifests: ::prost::alloc::vec::Vec<SessionManifest>,
@@ -228,6 +306,18 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(string, optional, tag = "23")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "24")]
+    pub running_command: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int32, optional, tag = "25")]
+    pub last_command_exit_code: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "26")]
+    pub output_log_path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "27")]
+    pub is_pinned: bool,
+    #[prost(bool, tag = "28")]
+    pub is_in_group: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -266,6 +356,8 @@ pub struct ModeUpdatePayload {
     pub arrow_fonts_support: bool,
     #[prost(string, optional, tag = "5")]
     pub session_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "6")]
+    pub is_read_only: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -320,6 +412,18 @@ pub enum EventType {
     SessionUpdate = 16,
     RunCommandResult = 17,
     WebRequestResult = 18,
+    /// / The contents of a pane, requested by the plugin
+    PaneContents = 19,
+    /// / The output of a terminal pane matched one of the configured triggers
+    PaneOutputMatched = 20,
+    /// / The action journal of the session, requested by the plugin
+    ActionJournal = 21,
+    /// / The response to a request this plugin sent to another plugin
+    PluginResponse = 22,
+    /// / The value of a key in the plugin store, requested by the plugin
+    StoreValue = 23,
+    /// / A key in the plugin store watched by the plugin changed
+    StoreChanged = 24,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -347,6 +451,12 @@ impl EventType {
             EventType::SessionUpdate => "SessionUpdate",
             EventType::RunCommandResult => "RunCommandResult",
             EventType::WebRequestResult => "WebRequestResult",
+            EventType::PaneContents => "PaneContents",
+            EventType::PaneOutputMatched => "PaneOutputMatched",
+            EventType::ActionJournal => "ActionJournal",
+            EventType::PluginResponse => "PluginResponse",
+            EventType::StoreValue => "StoreValue",
+            EventType::StoreChanged => "StoreChanged",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -371,6 +481,73 @@ impl EventType {
             "SessionUpdate" => Some(Self::SessionUpdate),
             "RunCommandResult" => Some(Self::RunCommandResult),
             "WebRequestResult" => Some(Self::WebRequestResult),
+            "PaneContents" => Some(Self::PaneContents),
+            "PaneOutputMatched" => Some(Self::PaneOutputMatched),
+            "ActionJournal" => Some(Self::ActionJournal),
+            "PluginResponse" => Some(Self::PluginResponse),
+            "StoreValue" => Some(Self::StoreValue),
+            "StoreChanged" => Some(Self::StoreChanged),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PluginResponseStatus {
+    Ok = 0,
+    Error = 1,
+    TimedOut = 2,
+    Unavailable = 3,
+}
+impl PluginResponseStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PluginResponseStatus::Ok => "Ok",
+            PluginResponseStatus::Error => "Error",
+            PluginResponseStatus::TimedOut => "TimedOut",
+            PluginResponseStatus::Unavailable => "Unavailable",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Ok" => Some(Self::Ok),
+            "Error" => Some(Self::Error),
+            "TimedOut" => Some(Self::TimedOut),
+            "Unavailable" => Some(Self::Unavailable),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum StoreScope {
+    Plugin = 0,
+    Session = 1,
+    Global = 2,
+}
+impl StoreScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            StoreScope::Plugin => "Plugin",
+            StoreScope::Session => "Session",
+            StoreScope::Global => "Global",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Plugin" => Some(Self::Plugin),
+            "Session" => Some(Self::Session),
+            "Global" => Some(Self::Global),
             _ => None,
         }
     }
@@ -3,6 +3,9 @@
 pub struct Key {
     #[prost(enumeration = "key::KeyModifier", optional, tag = "1")]
     pub modifier: ::core::option::Option<i32>,
+    /// used instead of modifier for keys with any combination of modifiers
+    #[prost(enumeration = "key::KeyModifier", repeated, tag = "4")]
+    pub modifiers: ::prost::alloc::vec::Vec<i32>,
     #[prost(oneof = "key::MainKey", tags = "2, 3")]
     pub main_key: ::core::option::Option<key::MainKey>,
 }
@@ -23,6 +26,8 @@ pub mod key {
     pub enum KeyModifier {
         Ctrl = 0,
         Alt = 1,
+        Shift = 2,
+        Super = 3,
     }
     impl KeyModifier {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -33,6 +38,8 @@ pub mod key {
             match self {
                 KeyModifier::Ctrl => "CTRL",
                 KeyModifier::Alt => "ALT",
+                KeyModifier::Shift => "SHIFT",
+                KeyModifier::Super => "SUPER",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -40,6 +47,8 @@ pub mod key {
             match value {
                 "CTRL" => Some(Self::Ctrl),
                 "ALT" => Some(Self::Alt),
+                "SHIFT" => Some(Self::Shift),
+                "SUPER" => Some(Self::Super),
                 _ => None,
             }
         }
@@ -5,7 +5,7 @@ pub struct PluginCommand {
     pub name: i32,
     #[prost(
         oneof = "plugin_command::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66"
     )]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
@@ -112,10 +112,52 @@ pub mod plugin_command {
         CliPipeOutputPayload(super::CliPipeOutputPayload),
         #[prost(message, tag = "50")]
         MessageToPluginPayload(super::MessageToPluginPayload),
+        #[prost(message, tag = "51")]
+        GetPaneScrollbackPayload(super::GetPaneScrollbackPayload),
+        #[prost(message, tag = "52")]
+        PipePaneToFilePayload(super::super::action::PipePaneToFilePayload),
+        #[prost(uint32, tag = "53")]
+        StopPipePaneToFilePayload(u32),
+        #[prost(message, tag = "54")]
+        MovePaneToTabPayload(super::super::action::MovePaneToTabPayload),
+        #[prost(message, tag = "55")]
+        MoveTabPayload(super::MovePayload),
+        #[prost(uint32, tag = "56")]
+        MoveTabToIndexPayload(u32),
+        #[prost(message, tag = "57")]
+        SwapPanesPayload(super::super::action::SwapPanesPayload),
+        #[prost(message, tag = "58")]
+        SwapPaneWithPayload(super::MovePayload),
+        #[prost(message, tag = "59")]
+        RegisterKeybindPayload(super::RegisterKeybindPayload),
+        #[prost(message, tag = "60")]
+        ChangeFloatingPaneCoordinatesPayload(
+            super::super::action::ChangeFloatingPaneCoordinatesPayload,
+        ),
+        #[prost(message, tag = "61")]
+        SetPanePinnedPayload(super::super::action::SetPanePinnedPayload),
+        #[prost(message, tag = "62")]
+        SendPluginRequestPayload(super::SendPluginRequestPayload),
+        #[prost(message, tag = "63")]
+        ReplyToPluginRequestPayload(super::ReplyToPluginRequestPayload),
+        #[prost(message, tag = "64")]
+        StoreSetPayload(super::super::event::StoreEntryPayload),
+        #[prost(message, tag = "65")]
+        StoreGetPayload(super::StoreKeyPayload),
+        #[prost(message, tag = "66")]
+        StoreWatchPayload(super::StoreKeyPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPaneScrollbackPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub scrollback_lines: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CliPipeOutputPayload {
     #[prost(string, tag = "1")]
     pub pipe_name: ::prost::alloc::string::String,
@@ -140,6 +182,32 @@ pub struct MessageToPluginPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPluginRequestPayload {
+    #[prost(string, tag = "1")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub timeout_ms: u64,
+    #[prost(message, optional, tag = "3")]
+    pub message: ::core::option::Option<MessageToPluginPayload>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplyToPluginRequestPayload {
+    #[prost(uint32, tag = "1")]
+    pub requester_plugin_id: u32,
+    #[prost(message, optional, tag = "2")]
+    pub response: ::core::option::Option<super::event::PluginResponsePayload>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreKeyPayload {
+    #[prost(enumeration = "super::event::StoreScope", tag = "1")]
+    pub scope: i32,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewPluginArgs {
     #[prost(bool, optional, tag = "1")]
     pub should_float: ::core::option::Option<bool>,
@@ -199,12 +267,20 @@ pub struct UnsubscribePayload {
 pub struct OpenFilePayload {
     #[prost(message, optional, tag = "1")]
     pub file_to_open: ::core::option::Option<super::file::File>,
+    #[prost(message, optional, tag = "2")]
+    pub floating_pane_coordinates: ::core::option::Option<
+        super::action::FloatingPaneCoordinates,
+    >,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OpenCommandPanePayload {
     #[prost(message, optional, tag = "1")]
     pub command_to_run: ::core::option::Option<super::command::Command>,
+    #[prost(message, optional, tag = "2")]
+    pub floating_pane_coordinates: ::core::option::Option<
+        super::action::FloatingPaneCoordinates,
+    >,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -293,6 +369,16 @@ pub struct IdAndNewName {
     #[prost(string, tag = "2")]
     pub new_name: ::prost::alloc::string::String,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterKeybindPayload {
+    #[prost(enumeration = "super::input_mode::InputMode", tag = "1")]
+    pub input_mode: i32,
+    #[prost(message, optional, tag = "2")]
+    pub key: ::core::option::Option<super::key::Key>,
+    #[prost(bool, tag = "3")]
+    pub intercept: bool,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum CommandName {
@@ -376,6 +462,23 @@ pub enum CommandName {
     BlockCliPipeInput = 77,
     CliPipeOutput = 78,
     MessageToPlugin = 79,
+    GetPaneScrollback = 80,
+    PipePaneToFile = 81,
+    StopPipePaneToFile = 82,
+    MovePaneToTab = 83,
+    MoveTab = 84,
+    MoveTabToIndex = 85,
+    SwapPanes = 86,
+    SwapPaneWith = 87,
+    RegisterKeybind = 88,
+    ChangeFloatingPaneCoordinates = 89,
+    SetPanePinned = 90,
+    GetActionJournal = 91,
+    SendPluginRequest = 92,
+    ReplyToPluginRequest = 93,
+    StoreSet = 94,
+    StoreGet = 95,
+    StoreWatch = 96,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -464,6 +567,23 @@ impl CommandName {
             CommandName::BlockCliPipeInput => "BlockCliPipeInput",
             CommandName::CliPipeOutput => "CliPipeOutput",
             CommandName::MessageToPlugin => "MessageToPlugin",
+            CommandName::GetPaneScrollback => "GetPaneScrollback",
+            CommandName::PipePaneToFile => "PipePaneToFile",
+            CommandName::StopPipePaneToFile => "StopPipePaneToFile",
+            CommandName::MovePaneToTab => "MovePaneToTab",
+            CommandName::MoveTab => "MoveTab",
+            CommandName::MoveTabToIndex => "MoveTabToIndex",
+            CommandName::SwapPanes => "SwapPanes",
+            CommandName::SwapPaneWith => "SwapPaneWith",
+            CommandName::RegisterKeybind => "RegisterKeybind",
+            CommandName::ChangeFloatingPaneCoordinates => "ChangeFloatingPaneCoordinates",
+            CommandName::SetPanePinned => "SetPanePinned",
+            CommandName::GetActionJournal => "GetActionJournal",
+            CommandName::SendPluginRequest => "SendPluginRequest",
+            CommandName::ReplyToPluginRequest => "ReplyToPluginRequest",
+            CommandName::StoreSet => "StoreSet",
+            CommandName::StoreGet => "StoreGet",
+            CommandName::StoreWatch => "StoreWatch",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -549,6 +669,23 @@ impl CommandName {
             "BlockCliPipeInput" => Some(Self::BlockCliPipeInput),
             "CliPipeOutput" => Some(Self::CliPipeOutput),
             "MessageToPlugin" => Some(Self::MessageToPlugin),
+            "GetPaneScrollback" => Some(Self::GetPaneScrollback),
+            "PipePaneToFile" => Some(Self::PipePaneToFile),
+            "StopPipePaneToFile" => Some(Self::StopPipePaneToFile),
+            "MovePaneToTab" => Some(Self::MovePaneToTab),
+            "MoveTab" => Some(Self::MoveTab),
+            "MoveTabToIndex" => Some(Self::MoveTabToIndex),
+            "SwapPanes" => Some(Self::SwapPanes),
+            "SwapPaneWith" => Some(Self::SwapPaneWith),
+            "RegisterKeybind" => Some(Self::RegisterKeybind),
+            "ChangeFloatingPaneCoordinates" => Some(Self::ChangeFloatingPaneCoordinates),
+            "SetPanePinned" => Some(Self::SetPanePinned),
+            "GetActionJournal" => Some(Self::GetActionJournal),
+            "SendPluginRequest" => Some(Self::SendPluginRequest),
+            "ReplyToPluginRequest" => Some(Self::ReplyToPluginRequest),
+            "StoreSet" => Some(Self::StoreSet),
+            "StoreGet" => Some(Self::StoreGet),
+            "StoreWatch" => Some(Self::StoreWatch),
             _ => None,
         }
     }
@@ -10,6 +10,10 @@ pub enum PermissionType {
     WebAccess = 6,
     ReadCliPipes = 7,
     MessageAndLaunchOtherPlugins = 8,
+    ReadPaneContents = 9,
+    InterceptKeys = 10,
+    WriteToFiles = 11,
+    ShareDataWithOtherPlugins = 12,
 }
 impl PermissionType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -29,6 +33,10 @@ impl PermissionType {
             PermissionType::MessageAndLaunchOtherPlugins => {
                 "MessageAndLaunchOtherPlugins"
             }
+            PermissionType::ReadPaneContents => "ReadPaneContents",
+            PermissionType::InterceptKeys => "InterceptKeys",
+            PermissionType::WriteToFiles => "WriteToFiles",
+            PermissionType::ShareDataWithOtherPlugins => "ShareDataWithOtherPlugins",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -43,6 +51,10 @@ impl PermissionType {
             "WebAccess" => Some(Self::WebAccess),
             "ReadCliPipes" => Some(Self::ReadCliPipes),
             "MessageAndLaunchOtherPlugins" => Some(Self::MessageAndLaunchOtherPlugins),
+            "ReadPaneContents" => Some(Self::ReadPaneContents),
+            "InterceptKeys" => Some(Self::InterceptKeys),
+            "WriteToFiles" => Some(Self::WriteToFiles),
+            "ShareDataWithOtherPlugins" => Some(Self::ShareDataWithOtherPlugins),
             _ => None,
         }
     }
@@ -2,13 +2,37 @@ use crate::data::{Direction, InputMode, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
-    input::{layout::PluginUserConfiguration, options::CliOptions},
+    input::{
+        layout::{PercentOrFixed, PluginUserConfiguration},
+        options::CliOptions,
+    },
 };
 use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use url::Url;
 
+fn parse_env_variable(env_variable: &str) -> Result<(String, String), String> {
+    match env_variable.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "invalid environment variable '{}', expected NAME=VALUE",
+            env_variable
+        )),
+    }
+}
+
+fn parse_percent_or_fixed(value: &str) -> Result<PercentOrFixed, String> {
+    PercentOrFixed::from_str(value).map_err(|e| {
+        format!(
+            "invalid value '{}' ({}), expected a bare integer (eg. 1) or percent (eg. 10%)",
+            value, e
+        )
+    })
+}
+
 fn validate_session(name: &str) -> Result<String, String> {
     #[cfg(unix)]
     {
@@ -130,6 +154,10 @@ pub enum Sessions {
         /// If resurrecting a dead session, immediately run all its commands on startup
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         force_run_commands: bool,
+
+        /// Attach as a viewer that can only scroll, switch tabs and detach
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        read_only: bool,
     },
 
     /// Kill a specific session
@@ -216,6 +244,22 @@ pub enum Sessions {
         /// Start the command suspended, only running after you first presses ENTER
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         start_suspended: bool,
+
+        /// Set an environment variable for the command (NAME=VALUE), can be repeated
+        #[clap(long, value_parser = parse_env_variable, multiple_occurrences(true))]
+        env: Vec<(String, String)>,
+        /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        x: Option<PercentOrFixed>,
+        /// The y coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        y: Option<PercentOrFixed>,
+        /// The width if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        width: Option<PercentOrFixed>,
+        /// The height if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        height: Option<PercentOrFixed>,
     },
     /// Load a plugin
     #[clap(visible_alias = "r")]
@@ -246,6 +290,22 @@ pub enum Sessions {
         /// Skip the memory and HD cache and force recompile of the plugin (good for development)
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         skip_plugin_cache: bool,
+
+        /// Reload the plugin whenever its wasm file changes (only for file: plugins, good for
+        /// development). The plugin keeps its /data folder across reloads unless --clear-data is
+        /// given
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        watch: bool,
+
+        /// Empty the /data folder of the plugin before each reload
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("watch")
+        )]
+        clear_data: bool,
     },
     /// Edit file with default $EDITOR / $VISUAL
     #[clap(visible_alias = "e")]
@@ -279,6 +339,58 @@ pub enum Sessions {
         /// Change the working directory of the editor
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
+        /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        x: Option<PercentOrFixed>,
+        /// The y coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        y: Option<PercentOrFixed>,
+        /// The width if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        width: Option<PercentOrFixed>,
+        /// The height if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        height: Option<PercentOrFixed>,
+    },
+    /// Serve a web page that can attach to running sessions from a browser
+    Web {
+        /// The address to listen on
+        #[clap(long, value_parser, default_value("127.0.0.1"))]
+        ip: IpAddr,
+
+        /// The port to listen on
+        #[clap(short, long, value_parser, default_value("8082"))]
+        port: u16,
+
+        /// Read the token the browser must present from this file rather than from the
+        /// ZELLIJ_WEB_TOKEN environment variable, it is generated on startup if neither is set
+        #[clap(long, value_parser)]
+        token_file: Option<PathBuf>,
+    },
+    /// Run a script of actions against a session, waiting for panes to print something or exit
+    /// in between
+    #[clap(override_usage(
+        r#"
+zellij script <FILE>
+
+* The script is a KDL file, eg:
+
+action "new-pane" "--name" "db" "--" "postgres" "-D" "/tmp/db"
+wait_for_output "db" "ready to accept connections" timeout=30
+action "new-pane" "--name" "migrations" "--" "cargo" "run" "--bin" "migrate"
+wait_for_exit "migrations" timeout=120
+if "migrations" exit_status=0 {
+    action "new-pane" "--name" "server" "--" "cargo" "run"
+}
+else {
+    action "write-chars" "migrations failed"
+}
+"#
+    ))]
+    Script {
+        /// The script file
+        #[clap(value_parser)]
+        file: PathBuf,
     },
     ConvertConfig {
         old_config_file: PathBuf,
@@ -328,6 +440,14 @@ tail -f /tmp/my-live-logfile | zellij pipe --name logs --plugin https://example.
     },
 }
 
+/// The arguments of an `action` step in a script, parsed as they would be by `zellij action`
+#[derive(Debug, Parser, Clone)]
+#[clap(no_binary_name(true))]
+pub struct ScriptAction {
+    #[clap(subcommand)]
+    pub action: CliAction,
+}
+
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
 pub enum CliAction {
     /// Write bytes to the terminal.
@@ -363,6 +483,77 @@ pub enum CliAction {
     },
     /// Rotate the location of the previous pane backwards
     MovePaneBackwards,
+    /// Move a pane to an existing tab, keeping its process, scrollback and title
+    MovePaneToTab {
+        /// The id of the pane to move
+        #[clap(short, long, value_parser)]
+        pane_id: u32,
+
+        /// The pane id refers to a plugin pane rather than a terminal pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        plugin: bool,
+
+        /// The index of the tab to move the pane to (as in go-to-tab)
+        #[clap(
+            short('i'),
+            long,
+            value_parser,
+            conflicts_with("tab_name"),
+            required_unless_present("tab_name")
+        )]
+        tab_index: Option<u32>,
+
+        /// The name of the tab to move the pane to
+        #[clap(short('n'), long, value_parser)]
+        tab_name: Option<String>,
+
+        /// Make the pane floating in its new tab
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        floating: bool,
+    },
+    /// Swap the positions of two tiled panes in the same tab, keeping their contents and focus
+    SwapPanes {
+        /// The id of the first pane
+        first_pane_id: u32,
+
+        /// The id of the second pane
+        second_pane_id: u32,
+
+        /// The first pane id refers to a plugin pane rather than a terminal pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        first_is_plugin: bool,
+
+        /// The second pane id refers to a plugin pane rather than a terminal pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        second_is_plugin: bool,
+    },
+    /// Swap the positions of the focused pane and the pane next to it in the specified direction
+    /// [right|left|up|down]
+    SwapPaneWith {
+        direction: Direction,
+    },
+    /// Move and/or resize a floating pane, coordinates that are left out keep their current value
+    ChangeFloatingPaneCoordinates {
+        /// The id of the floating pane
+        pane_id: u32,
+
+        /// The pane id refers to a plugin pane rather than a terminal pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        is_plugin: bool,
+
+        /// The x coordinates as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed)]
+        x: Option<PercentOrFixed>,
+        /// The y coordinates as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed)]
+        y: Option<PercentOrFixed>,
+        /// The width as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed)]
+        width: Option<PercentOrFixed>,
+        /// The height as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed)]
+        height: Option<PercentOrFixed>,
+    },
     /// Clear all buffers for a focused pane
     Clear,
     /// Dump the focused pane to a file
@@ -373,6 +564,25 @@ pub enum CliAction {
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         full: bool,
     },
+    /// Log everything a terminal pane receives to a file, until stopped with
+    /// stop-pipe-pane-to-file
+    PipePaneToFile {
+        /// The id of the terminal pane whose output should be logged
+        #[clap(short, long, value_parser)]
+        pane_id: u32,
+
+        path: PathBuf,
+
+        /// Strip ANSI escape codes from the logged output
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        strip_ansi: bool,
+    },
+    /// Stop logging the output of a terminal pane to a file
+    StopPipePaneToFile {
+        /// The id of the terminal pane whose output is being logged
+        #[clap(short, long, value_parser)]
+        pane_id: u32,
+    },
     /// Dump current layout to stdout
     DumpLayout,
     /// Open the pane scrollback in your default editor
@@ -385,6 +595,12 @@ pub enum CliAction {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt in focus pane (requires shell integration).
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt in focus pane (requires shell integration).
+    ScrollToNextPrompt,
+    /// Copy the output of the last command in focus pane (requires shell integration).
+    CopyLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -456,10 +672,49 @@ pub enum CliAction {
             requires("command")
         )]
         start_suspended: bool,
+        /// Set an environment variable for the command (NAME=VALUE), can be repeated
+        #[clap(
+            long,
+            value_parser = parse_env_variable,
+            multiple_occurrences(true),
+            requires("command")
+        )]
+        env: Vec<(String, String)>,
         #[clap(long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
         #[clap(short, long, value_parser)]
         skip_plugin_cache: bool,
+        /// Reload the plugin whenever its wasm file changes (only for file: plugins). The plugin
+        /// keeps its /data folder across reloads unless --clear-data is given
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("plugin")
+        )]
+        watch: bool,
+        /// Empty the /data folder of the plugin before each reload
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("watch")
+        )]
+        clear_data: bool,
+        /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        x: Option<PercentOrFixed>,
+        /// The y coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        y: Option<PercentOrFixed>,
+        /// The width if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        width: Option<PercentOrFixed>,
+        /// The height if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        height: Option<PercentOrFixed>,
     },
     /// Open the specified file in a new zellij pane with your default EDITOR
     Edit {
@@ -492,6 +747,18 @@ pub enum CliAction {
         /// Change the working directory of the editor
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
+        /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        x: Option<PercentOrFixed>,
+        /// The y coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(short, long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        y: Option<PercentOrFixed>,
+        /// The width if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        width: Option<PercentOrFixed>,
+        /// The height if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
+        #[clap(long, value_parser = parse_percent_or_fixed, requires("floating"))]
+        height: Option<PercentOrFixed>,
     },
     /// Switch input mode of all connected clients [locked|pane|tab|resize|move|search|session]
     SwitchMode {
@@ -501,6 +768,27 @@ pub enum CliAction {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes in the current Tab, open one if none exist
     ToggleFloatingPanes,
+    /// Pin the focused floating pane if unpinned or unpin it if pinned, pinned panes stay on top
+    /// of the tiled panes even when the floating panes are hidden
+    TogglePanePinned,
+    /// Add the focused pane to the pane group or remove it if it is already in it, each client
+    /// has its own pane group
+    TogglePaneInGroup,
+    /// Remove all panes from the pane group
+    ClearPaneGroup,
+    /// Write input to all the panes in the pane group instead of to the focused pane, or stop
+    /// doing so
+    TogglePaneGroupBroadcast,
+    /// Close all the panes in the pane group
+    ClosePaneGroup,
+    /// Break all the panes in the pane group out into a new tab
+    BreakPaneGroup,
+    /// Embed the floating panes in the pane group and float the embedded ones
+    TogglePaneGroupEmbedOrFloating,
+    /// Rename all the panes in the pane group
+    RenamePaneGroup {
+        name: String,
+    },
     /// Close the focused pane.
     ClosePane,
     /// Renames the focused pane
@@ -526,6 +814,15 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         create: bool,
     },
+    /// Move the focused tab in the specified direction, wrapping around at either end
+    /// [right|left]
+    MoveTab {
+        direction: Direction,
+    },
+    /// Move the focused tab to position [index]
+    MoveTabToIndex {
+        index: usize,
+    },
     /// Renames the focused pane
     RenameTab {
         name: String,
@@ -554,6 +851,12 @@ pub enum CliAction {
     NextSwapLayout,
     /// Query all tab names
     QueryTabNames,
+    /// Query the structural actions (opening, closing, moving and resizing panes, swap layout and
+    /// tab changes) performed in this session
+    QueryActionJournal,
+    /// Restore the geometry the tiled panes of the focused tab had before the last layout change,
+    /// if the same panes are still open in it
+    UndoLayoutChange,
     StartOrReloadPlugin {
         url: String,
         #[clap(short, long, value_parser)]
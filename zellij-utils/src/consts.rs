@@ -10,6 +10,7 @@ use uuid::Uuid;
 pub const ZELLIJ_CONFIG_FILE_ENV: &str = "ZELLIJ_CONFIG_FILE";
 pub const ZELLIJ_CONFIG_DIR_ENV: &str = "ZELLIJ_CONFIG_DIR";
 pub const ZELLIJ_LAYOUT_DIR_ENV: &str = "ZELLIJ_LAYOUT_DIR";
+pub const ZELLIJ_WEB_TOKEN_ENV: &str = "ZELLIJ_WEB_TOKEN";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_SCROLL_BUFFER_SIZE: usize = 10_000;
 pub static SCROLL_BUFFER_SIZE: OnceCell<usize> = OnceCell::new();
@@ -55,6 +56,8 @@ lazy_static! {
     pub static ref ZELLIJ_STDIN_CACHE_FILE: PathBuf =
         ZELLIJ_CACHE_DIR.join(VERSION).join("stdin_cache");
     pub static ref ZELLIJ_PLUGIN_ARTIFACT_DIR: PathBuf = ZELLIJ_CACHE_DIR.join(VERSION);
+    pub static ref ZELLIJ_PLUGIN_STORE_DIR: PathBuf =
+        ZELLIJ_PROJ_DIR.data_dir().join("plugin_store");
 }
 
 pub const FEATURES: &[&str] = &[
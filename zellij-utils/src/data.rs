@@ -1,5 +1,6 @@
 use crate::input::actions::Action;
 use crate::input::config::ConversionError;
+use crate::input::layout::PercentOrFixed;
 use clap::ArgEnum;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -35,6 +36,12 @@ pub fn single_client_color(colors: Palette) -> (PaletteColor, PaletteColor) {
     (colors.green, colors.black)
 }
 
+// the frame color of panes in the pane group, distinct from the colors of the focused pane in
+// the different input modes of a single client
+pub fn pane_group_color(colors: Palette) -> PaletteColor {
+    colors.cyan
+}
+
 // TODO: Add a shortened string representation (beyond `Display::fmt` below) that can be used when
 // screen space is scarce. Useful for e.g. "ENTER", "SPACE", "TAB" to display as Unicode
 // representations instead.
@@ -60,97 +67,504 @@ pub enum Key {
     BackTab,
     Null,
     Esc,
+    /// A key with a combination of modifiers that can't be represented by the variants above,
+    /// eg. Ctrl+Shift+a or Super+Left (see [`Key::with_modifiers`])
+    WithModifiers(BareKey, KeyModifiers),
+}
+
+impl Key {
+    /// Creates the [`Key`] for the given key pressed with the given modifiers, preferring the
+    /// simpler variants whenever they can represent this combination so that keys reported through
+    /// the kitty keyboard protocol match the ones reported through the legacy encoding (and the ones
+    /// bound in the configuration)
+    pub fn with_modifiers(bare_key: BareKey, modifiers: KeyModifiers) -> Self {
+        let KeyModifiers {
+            shift,
+            alt,
+            ctrl,
+            super_key,
+        } = modifiers;
+        match (bare_key, shift, alt, ctrl, super_key) {
+            (bare_key, false, false, false, false) => bare_key.into(),
+            (BareKey::Char(c), true, false, false, false) => Key::Char(to_uppercase(c)),
+            (BareKey::Char(c), false, false, true, false) => Key::Ctrl(c),
+            (BareKey::Char(c), shift, true, false, false) => {
+                let c = if shift { to_uppercase(c) } else { c };
+                Key::Alt(CharOrArrow::Char(c))
+            },
+            (BareKey::Left, false, true, false, false) => {
+                Key::Alt(CharOrArrow::Direction(Direction::Left))
+            },
+            (BareKey::Right, false, true, false, false) => {
+                Key::Alt(CharOrArrow::Direction(Direction::Right))
+            },
+            (BareKey::Up, false, true, false, false) => {
+                Key::Alt(CharOrArrow::Direction(Direction::Up))
+            },
+            (BareKey::Down, false, true, false, false) => {
+                Key::Alt(CharOrArrow::Direction(Direction::Down))
+            },
+            (bare_key, ..) => Key::WithModifiers(bare_key, modifiers),
+        }
+    }
+
+    /// The bytes a terminal sends for this key when it does not use the kitty keyboard protocol,
+    /// `None` if this key cannot be represented that way (eg. Super+a)
+    pub fn serialize_legacy(&self) -> Option<Vec<u8>> {
+        match self {
+            Key::Char('\n') => Some(vec![b'\r']),
+            Key::Char(c) => Some(c.to_string().into_bytes()),
+            Key::Ctrl(c) => ctrl_byte(*c).map(|byte| vec![byte]),
+            Key::Alt(CharOrArrow::Char(c)) => {
+                let mut bytes = vec![0x1b];
+                bytes.append(&mut Key::Char(*c).serialize_legacy()?);
+                Some(bytes)
+            },
+            Key::Alt(CharOrArrow::Direction(direction)) => BareKey::from(*direction)
+                .serialize_with_modifier_parameter(KeyModifiers {
+                    alt: true,
+                    ..Default::default()
+                }),
+            Key::BackTab => Some(vec![b'\t']),
+            Key::Esc => Some(vec![0x1b]),
+            Key::Null => None,
+            Key::WithModifiers(BareKey::Char(c), modifiers) if modifiers.ctrl => {
+                let mut bytes = if modifiers.alt { vec![0x1b] } else { vec![] };
+                bytes.push(ctrl_byte(*c)?);
+                Some(bytes)
+            },
+            Key::WithModifiers(BareKey::Char(_), _) => None,
+            Key::WithModifiers(bare_key, modifiers) => {
+                bare_key.serialize_with_modifier_parameter(*modifiers)
+            },
+            key => {
+                let bare_key = BareKey::try_from(*key).ok()?;
+                bare_key.serialize_with_modifier_parameter(KeyModifiers::default())
+            },
+        }
+    }
+
+    /// The bytes a terminal sends for this key when the application requested the "disambiguate
+    /// escape codes" enhancement of the kitty keyboard protocol
+    pub fn serialize_kitty(&self) -> Option<Vec<u8>> {
+        match self {
+            Key::Ctrl(_) | Key::Alt(_) | Key::Esc | Key::WithModifiers(..) => {},
+            // keys without modifiers (other than Esc) are sent the same way as in the legacy
+            // encoding
+            _ => return self.serialize_legacy(),
+        }
+        let (bare_key, modifiers) = self.kitty_key_and_modifiers()?;
+        let code = match bare_key.kitty_key_code() {
+            Some(code) => code,
+            None => return bare_key.serialize_with_modifier_parameter(modifiers),
+        };
+        if modifiers == KeyModifiers::default() {
+            Some(format!("\u{1b}[{}u", code).into_bytes())
+        } else {
+            Some(format!("\u{1b}[{};{}u", code, modifiers.encode()).into_bytes())
+        }
+    }
+
+    /// Like [`Key::serialize_kitty`], for applications that also asked for the type of the key
+    /// event to be reported (the "report event types" enhancement), so that they know when a key
+    /// is held down or released
+    pub fn serialize_kitty_event(&self, event_type: KeyEventType) -> Option<Vec<u8>> {
+        let event_type_parameter = match event_type {
+            KeyEventType::Press => return self.serialize_kitty(),
+            KeyEventType::Repeat => 2,
+            KeyEventType::Release => 3,
+        };
+        let (bare_key, modifiers) = self.kitty_key_and_modifiers()?;
+        let is_text = matches!(bare_key, BareKey::Char(c) if c != '\n')
+            && KeyModifiers {
+                shift: false,
+                ..modifiers
+            } == KeyModifiers::default();
+        let is_unmodified_enter_tab_or_backspace = matches!(
+            bare_key,
+            BareKey::Char('\n') | BareKey::Enter | BareKey::Tab | BareKey::Backspace
+        ) && modifiers == KeyModifiers::default();
+        match event_type {
+            // repeated text is sent as text, like pressing the key again
+            KeyEventType::Repeat if is_text || is_unmodified_enter_tab_or_backspace => {
+                return self.serialize_kitty()
+            },
+            // these are only reported when released with the "report all keys as escape codes"
+            // enhancement, which we do not support
+            KeyEventType::Release if is_unmodified_enter_tab_or_backspace => return None,
+            _ => {},
+        }
+        match bare_key.kitty_key_code() {
+            Some(code) => Some(
+                format!(
+                    "\u{1b}[{};{}:{}u",
+                    code,
+                    modifiers.encode(),
+                    event_type_parameter
+                )
+                .into_bytes(),
+            ),
+            None => bare_key.serialize_with_parameters(modifiers, Some(event_type_parameter)),
+        }
+    }
+
+    // The key and its modifiers as the kitty keyboard protocol reports them
+    fn kitty_key_and_modifiers(&self) -> Option<(BareKey, KeyModifiers)> {
+        let (bare_key, modifiers) = match *self {
+            Key::Ctrl(c) => (
+                BareKey::Char(c),
+                KeyModifiers {
+                    ctrl: true,
+                    ..Default::default()
+                },
+            ),
+            Key::Alt(CharOrArrow::Char(c)) => (
+                BareKey::Char(c),
+                KeyModifiers {
+                    alt: true,
+                    ..Default::default()
+                },
+            ),
+            Key::Alt(CharOrArrow::Direction(direction)) => (
+                direction.into(),
+                KeyModifiers {
+                    alt: true,
+                    ..Default::default()
+                },
+            ),
+            Key::WithModifiers(bare_key, modifiers) => (bare_key, modifiers),
+            key => (BareKey::try_from(key).ok()?, KeyModifiers::default()),
+        };
+        let (bare_key, modifiers) = match bare_key {
+            // the protocol always reports the un-shifted key, with the shift modifier
+            BareKey::Char(c) if c.is_uppercase() => (
+                BareKey::Char(c.to_lowercase().next().unwrap_or(c)),
+                KeyModifiers {
+                    shift: true,
+                    ..modifiers
+                },
+            ),
+            bare_key => (bare_key, modifiers),
+        };
+        Some((bare_key, modifiers))
+    }
 }
 
-impl FromStr for Key {
+/// Whether a key was pressed, is being held down or was released, as reported by the kitty
+/// keyboard protocol
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyEventType {
+    Press,
+    Repeat,
+    Release,
+}
+
+fn to_uppercase(c: char) -> char {
+    c.to_uppercase().next().unwrap_or(c)
+}
+
+fn ctrl_byte(c: char) -> Option<u8> {
+    match c {
+        ' ' | '@' | '2' => Some(0),
+        'a'..='z' => Some(c as u8 - b'a' + 1),
+        'A'..='Z' => Some(c as u8 - b'A' + 1),
+        '[' | '3' => Some(0x1b),
+        '\\' | '4' => Some(0x1c),
+        ']' | '5' => Some(0x1d),
+        '^' | '6' => Some(0x1e),
+        '_' | '7' | '/' => Some(0x1f),
+        '?' | '8' => Some(0x7f),
+        _ => None,
+    }
+}
+
+/// A key regardless of the modifiers it was pressed with, see [`Key::WithModifiers`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum BareKey {
+    PageDown,
+    PageUp,
+    Left,
+    Down,
+    Up,
+    Right,
+    Home,
+    End,
+    Backspace,
+    Delete,
+    Insert,
+    F(u8),
+    Char(char),
+    Tab,
+    Enter,
+    Esc,
+}
+
+impl BareKey {
+    // The key code of the keys the kitty keyboard protocol sends as <ESC>[{code}u, the others are
+    // sent like their xterm style sequences
+    fn kitty_key_code(&self) -> Option<u32> {
+        match self {
+            BareKey::Char('\n') | BareKey::Enter => Some(13),
+            BareKey::Char(c) => Some(*c as u32),
+            BareKey::Tab => Some(9),
+            BareKey::Backspace => Some(127),
+            BareKey::Esc => Some(27),
+            _ => None,
+        }
+    }
+    // The xterm style sequences for keys that are not characters, eg. <ESC>[1;6D for Ctrl+Shift+Left
+    fn serialize_with_modifier_parameter(&self, modifiers: KeyModifiers) -> Option<Vec<u8>> {
+        self.serialize_with_parameters(modifiers, None)
+    }
+    // Like `serialize_with_modifier_parameter`, with the event type parameter of the kitty keyboard
+    // protocol, eg. <ESC>[1;1:3D for releasing Left
+    fn serialize_with_parameters(
+        &self,
+        modifiers: KeyModifiers,
+        event_type_parameter: Option<u8>,
+    ) -> Option<Vec<u8>> {
+        let (number, terminator) = match self {
+            BareKey::Up => (1, 'A'),
+            BareKey::Down => (1, 'B'),
+            BareKey::Right => (1, 'C'),
+            BareKey::Left => (1, 'D'),
+            BareKey::End => (1, 'F'),
+            BareKey::Home => (1, 'H'),
+            BareKey::F(1) => (1, 'P'),
+            BareKey::F(2) => (1, 'Q'),
+            BareKey::F(3) => (13, '~'),
+            BareKey::F(4) => (1, 'S'),
+            BareKey::F(5) => (15, '~'),
+            BareKey::F(6) => (17, '~'),
+            BareKey::F(7) => (18, '~'),
+            BareKey::F(8) => (19, '~'),
+            BareKey::F(9) => (20, '~'),
+            BareKey::F(10) => (21, '~'),
+            BareKey::F(11) => (23, '~'),
+            BareKey::F(12) => (24, '~'),
+            BareKey::Insert => (2, '~'),
+            BareKey::Delete => (3, '~'),
+            BareKey::PageUp => (5, '~'),
+            BareKey::PageDown => (6, '~'),
+            BareKey::Enter if modifiers == KeyModifiers::default() => return Some(vec![b'\r']),
+            BareKey::Tab if modifiers == KeyModifiers::default() => return Some(vec![b'\t']),
+            BareKey::Backspace if modifiers == KeyModifiers::default() => return Some(vec![0x7f]),
+            BareKey::Esc if modifiers == KeyModifiers::default() => return Some(vec![0x1b]),
+            _ => return None,
+        };
+        let without_parameters =
+            modifiers == KeyModifiers::default() && event_type_parameter.is_none();
+        let sequence = match (without_parameters, number, terminator) {
+            (true, 1, 'P' | 'Q' | 'S') => format!("\u{1b}O{}", terminator),
+            (true, 1, _) => format!("\u{1b}[{}", terminator),
+            (true, _, _) if number == 13 => String::from("\u{1b}OR"),
+            (true, _, _) => format!("\u{1b}[{}{}", number, terminator),
+            (false, _, _) => match event_type_parameter {
+                Some(event_type_parameter) => format!(
+                    "\u{1b}[{};{}:{}{}",
+                    number,
+                    modifiers.encode(),
+                    event_type_parameter,
+                    terminator
+                ),
+                None => format!("\u{1b}[{};{}{}", number, modifiers.encode(), terminator),
+            },
+        };
+        Some(sequence.into_bytes())
+    }
+}
+
+impl From<BareKey> for Key {
+    fn from(bare_key: BareKey) -> Self {
+        match bare_key {
+            BareKey::PageDown => Key::PageDown,
+            BareKey::PageUp => Key::PageUp,
+            BareKey::Left => Key::Left,
+            BareKey::Down => Key::Down,
+            BareKey::Up => Key::Up,
+            BareKey::Right => Key::Right,
+            BareKey::Home => Key::Home,
+            BareKey::End => Key::End,
+            BareKey::Backspace => Key::Backspace,
+            BareKey::Delete => Key::Delete,
+            BareKey::Insert => Key::Insert,
+            BareKey::F(index) => Key::F(index),
+            BareKey::Char(c) => Key::Char(c),
+            BareKey::Tab => Key::BackTab, // this is how the Tab key is represented in Key
+            BareKey::Enter => Key::Char('\n'),
+            BareKey::Esc => Key::Esc,
+        }
+    }
+}
+
+impl From<Direction> for BareKey {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Left => BareKey::Left,
+            Direction::Right => BareKey::Right,
+            Direction::Up => BareKey::Up,
+            Direction::Down => BareKey::Down,
+        }
+    }
+}
+
+impl TryFrom<Key> for BareKey {
+    type Error = &'static str;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        match key {
+            Key::PageDown => Ok(BareKey::PageDown),
+            Key::PageUp => Ok(BareKey::PageUp),
+            Key::Left => Ok(BareKey::Left),
+            Key::Down => Ok(BareKey::Down),
+            Key::Up => Ok(BareKey::Up),
+            Key::Right => Ok(BareKey::Right),
+            Key::Home => Ok(BareKey::Home),
+            Key::End => Ok(BareKey::End),
+            Key::Backspace => Ok(BareKey::Backspace),
+            Key::Delete => Ok(BareKey::Delete),
+            Key::Insert => Ok(BareKey::Insert),
+            Key::F(index) => Ok(BareKey::F(index)),
+            Key::Char('\n') => Ok(BareKey::Enter),
+            Key::Char(c) => Ok(BareKey::Char(c)),
+            Key::BackTab => Ok(BareKey::Tab),
+            Key::Esc => Ok(BareKey::Esc),
+            Key::WithModifiers(bare_key, _) => Ok(bare_key),
+            _ => Err("Key has modifiers"),
+        }
+    }
+}
+
+impl FromStr for BareKey {
     type Err = Box<dyn std::error::Error>;
     fn from_str(key_str: &str) -> Result<Self, Self::Err> {
-        let mut modifier: Option<&str> = None;
-        let mut main_key: Option<&str> = None;
-        for (index, part) in key_str.split_ascii_whitespace().enumerate() {
-            if index == 0 && (part == "Ctrl" || part == "Alt") {
-                modifier = Some(part);
-            } else if main_key.is_none() {
-                main_key = Some(part)
-            }
-        }
-        match (modifier, main_key) {
-            (Some("Ctrl"), Some(main_key)) => {
-                let mut key_chars = main_key.chars();
-                let key_count = main_key.chars().count();
+        match key_str {
+            "Backspace" => Ok(BareKey::Backspace),
+            "Left" => Ok(BareKey::Left),
+            "Right" => Ok(BareKey::Right),
+            "Up" => Ok(BareKey::Up),
+            "Down" => Ok(BareKey::Down),
+            "Home" => Ok(BareKey::Home),
+            "End" => Ok(BareKey::End),
+            "PageUp" => Ok(BareKey::PageUp),
+            "PageDown" => Ok(BareKey::PageDown),
+            "Tab" => Ok(BareKey::Tab),
+            "Delete" => Ok(BareKey::Delete),
+            "Insert" => Ok(BareKey::Insert),
+            "Space" => Ok(BareKey::Char(' ')),
+            "Enter" => Ok(BareKey::Enter),
+            "Esc" => Ok(BareKey::Esc),
+            _ => {
+                let mut key_chars = key_str.chars();
+                let key_count = key_str.chars().count();
                 if key_count == 1 {
                     let key_char = key_chars.next().unwrap();
-                    Ok(Key::Ctrl(key_char))
-                } else {
-                    Err(format!("Failed to parse key: {}", key_str).into())
-                }
-            },
-            (Some("Alt"), Some(main_key)) => {
-                match main_key {
-                    // why crate::data::Direction and not just Direction?
-                    // Because it's a different type that we export in this wasm mandated soup - we
-                    // don't like it either! This will be solved as we chip away at our tech-debt
-                    "Left" => Ok(Key::Alt(CharOrArrow::Direction(Direction::Left))),
-                    "Right" => Ok(Key::Alt(CharOrArrow::Direction(Direction::Right))),
-                    "Up" => Ok(Key::Alt(CharOrArrow::Direction(Direction::Up))),
-                    "Down" => Ok(Key::Alt(CharOrArrow::Direction(Direction::Down))),
-                    _ => {
-                        let mut key_chars = main_key.chars();
-                        let key_count = main_key.chars().count();
-                        if key_count == 1 {
-                            let key_char = key_chars.next().unwrap();
-                            Ok(Key::Alt(CharOrArrow::Char(key_char)))
-                        } else {
-                            Err(format!("Failed to parse key: {}", key_str).into())
-                        }
-                    },
-                }
-            },
-            (None, Some(main_key)) => match main_key {
-                "Backspace" => Ok(Key::Backspace),
-                "Left" => Ok(Key::Left),
-                "Right" => Ok(Key::Right),
-                "Up" => Ok(Key::Up),
-                "Down" => Ok(Key::Down),
-                "Home" => Ok(Key::Home),
-                "End" => Ok(Key::End),
-                "PageUp" => Ok(Key::PageUp),
-                "PageDown" => Ok(Key::PageDown),
-                "Tab" => Ok(Key::BackTab),
-                "Delete" => Ok(Key::Delete),
-                "Insert" => Ok(Key::Insert),
-                "Space" => Ok(Key::Char(' ')),
-                "Enter" => Ok(Key::Char('\n')),
-                "Esc" => Ok(Key::Esc),
-                _ => {
-                    let mut key_chars = main_key.chars();
-                    let key_count = main_key.chars().count();
-                    if key_count == 1 {
-                        let key_char = key_chars.next().unwrap();
-                        Ok(Key::Char(key_char))
-                    } else if key_count > 1 {
-                        if let Some(first_char) = key_chars.next() {
-                            if first_char == 'F' {
-                                let f_index: String = key_chars.collect();
-                                let f_index: u8 = f_index
-                                    .parse()
-                                    .map_err(|e| format!("Failed to parse F index: {}", e))?;
-                                if f_index >= 1 && f_index <= 12 {
-                                    return Ok(Key::F(f_index));
-                                }
+                    Ok(BareKey::Char(key_char))
+                } else if key_count > 1 {
+                    if let Some(first_char) = key_chars.next() {
+                        if first_char == 'F' {
+                            let f_index: String = key_chars.collect();
+                            let f_index: u8 = f_index
+                                .parse()
+                                .map_err(|e| format!("Failed to parse F index: {}", e))?;
+                            if (1..=12).contains(&f_index) {
+                                return Ok(BareKey::F(f_index));
                             }
                         }
-                        Err(format!("Failed to parse key: {}", key_str).into())
-                    } else {
-                        Err(format!("Failed to parse key: {}", key_str).into())
                     }
-                },
+                    Err(format!("Failed to parse key: {}", key_str).into())
+                } else {
+                    Err(format!("Failed to parse key: {}", key_str).into())
+                }
             },
-            _ => Err(format!("Failed to parse key: {}", key_str).into()),
         }
     }
 }
 
+impl fmt::Display for BareKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BareKey::Tab => write!(f, "TAB"),
+            BareKey::Enter => write!(f, "ENTER"),
+            bare_key => write!(f, "{}", Key::from(*bare_key)),
+        }
+    }
+}
+
+/// The modifiers a key was pressed with, see [`Key::WithModifiers`]
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+    pub super_key: bool,
+}
+
+impl KeyModifiers {
+    /// Decodes the modifiers parameter of the kitty keyboard protocol (and of the xterm modified
+    /// key sequences), which is 1 + a bit field of the modifiers
+    pub fn decode(modifier_parameter: u8) -> Self {
+        let bits = modifier_parameter.saturating_sub(1);
+        KeyModifiers {
+            shift: bits & 0b1 != 0,
+            alt: bits & 0b10 != 0,
+            ctrl: bits & 0b100 != 0,
+            super_key: bits & 0b1000 != 0,
+        }
+    }
+    /// The reverse of [`KeyModifiers::decode`]
+    pub fn encode(&self) -> u8 {
+        1 + (self.shift as u8)
+            + ((self.alt as u8) << 1)
+            + ((self.ctrl as u8) << 2)
+            + ((self.super_key as u8) << 3)
+    }
+}
+
+impl fmt::Display for KeyModifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut modifiers = vec![];
+        if self.ctrl {
+            modifiers.push("Ctrl");
+        }
+        if self.alt {
+            modifiers.push("Alt");
+        }
+        if self.shift {
+            modifiers.push("Shift");
+        }
+        if self.super_key {
+            modifiers.push("Super");
+        }
+        write!(f, "{}", modifiers.join("+"))
+    }
+}
+
+impl FromStr for Key {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(key_str: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::default();
+        let mut main_key: Option<&str> = None;
+        for part in key_str.split_ascii_whitespace() {
+            if main_key.is_some() {
+                break;
+            }
+            match part {
+                "Ctrl" => modifiers.ctrl = true,
+                "Alt" => modifiers.alt = true,
+                "Shift" => modifiers.shift = true,
+                "Super" => modifiers.super_key = true,
+                _ => main_key = Some(part),
+            }
+        }
+        let main_key = main_key.ok_or_else(|| format!("Failed to parse key: {}", key_str))?;
+        let bare_key =
+            BareKey::from_str(main_key).map_err(|_| format!("Failed to parse key: {}", key_str))?;
+        Ok(Key::with_modifiers(bare_key, modifiers))
+    }
+}
+
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -177,6 +591,7 @@ impl fmt::Display for Key {
             Key::Ctrl(c) => write!(f, "Ctrl+{}", Key::Char(*c)),
             Key::Null => write!(f, "NULL"),
             Key::Esc => write!(f, "ESC"),
+            Key::WithModifiers(bare_key, modifiers) => write!(f, "{}+{}", modifiers, bare_key),
         }
     }
 }
@@ -503,14 +918,27 @@ pub enum Event {
     RunCommandResult(Option<i32>, Vec<u8>, Vec<u8>, BTreeMap<String, String>), // exit_code, STDOUT, STDERR,
     // context
     WebRequestResult(
-        u16,
-        BTreeMap<String, String>,
-        Vec<u8>,
-        BTreeMap<String, String>,
-    ), // status,
-       // headers,
-       // body,
-       // context
+        u16,                      // status
+        BTreeMap<String, String>, // headers
+        Vec<u8>,                  // body
+        BTreeMap<String, String>, // context
+    ),
+    /// The contents of a pane, requested with the `get_pane_scrollback` method exported by
+    /// `zellij-tile`
+    PaneContents(PaneId, PaneContents),
+    /// A line of output in a terminal pane matched one of the `triggers` in the configuration
+    PaneOutputMatched(PaneId, String, String), // pane_id, pattern, matched line
+    /// The structural actions performed in this session, requested with the `get_action_journal`
+    /// method exported by `zellij-tile`
+    ActionJournal(Vec<ActionJournalEntry>),
+    /// The response to a request sent to another plugin with the `send_plugin_request` method
+    /// exported by `zellij-tile`
+    PluginResponse(PluginResponse),
+    /// The value of a key in the plugin store, requested with the `store_get` method exported by
+    /// `zellij-tile`
+    StoreValue(StoreScope, String, Option<String>), // scope, key, value (None if not set)
+    /// A key watched with the `store_watch` method exported by `zellij-tile` was set or removed
+    StoreChanged(StoreScope, String, Option<String>), // scope, key, new value (None if removed)
 }
 
 #[derive(
@@ -540,6 +968,10 @@ pub enum Permission {
     WebAccess,
     ReadCliPipes,
     MessageAndLaunchOtherPlugins,
+    ReadPaneContents,
+    InterceptKeys,
+    WriteToFiles,
+    ShareDataWithOtherPlugins,
 }
 
 impl PermissionType {
@@ -560,6 +992,16 @@ impl PermissionType {
             PermissionType::MessageAndLaunchOtherPlugins => {
                 "Send messages to and launch other plugins".to_owned()
             },
+            PermissionType::ReadPaneContents => {
+                "Read the contents and scrollback of panes".to_owned()
+            },
+            PermissionType::InterceptKeys => {
+                "Bind keys to the plugin and intercept them in any focus state".to_owned()
+            },
+            PermissionType::WriteToFiles => "Write to files on the host machine".to_owned(),
+            PermissionType::ShareDataWithOtherPlugins => {
+                "Read and write the session and global plugin stores".to_owned()
+            },
         }
     }
 }
@@ -636,6 +1078,9 @@ pub enum InputMode {
     /// `Tmux` mode allows for basic tmux keybindings functionality
     #[serde(alias = "tmux")]
     Tmux,
+    /// `Copy` mode allows moving a cursor in the scroll buffer of a pane and selecting text with the keyboard
+    #[serde(alias = "copy")]
+    Copy,
 }
 
 impl Default for InputMode {
@@ -684,6 +1129,7 @@ impl FromStr for InputMode {
             "move" | "Move" => Ok(InputMode::Move),
             "prompt" | "Prompt" => Ok(InputMode::Prompt),
             "tmux" | "Tmux" => Ok(InputMode::Tmux),
+            "copy" | "Copy" => Ok(InputMode::Copy),
             "entersearch" | "Entersearch" | "EnterSearch" => Ok(InputMode::EnterSearch),
             e => Err(ConversionError::UnknownInputMode(e.into())),
         }
@@ -741,6 +1187,9 @@ pub struct ModeInfo {
     pub style: Style,
     pub capabilities: PluginCapabilities,
     pub session_name: Option<String>,
+    /// Whether this client attached to the session as a read-only viewer (eg. with `zellij
+    /// attach --read-only`), in which case it cannot change the session
+    pub is_read_only: bool,
 }
 
 impl ModeInfo {
@@ -846,6 +1295,12 @@ pub struct PaneInfo {
     pub is_fullscreen: bool,
     /// Whether a pane is floating or tiled (embedded)
     pub is_floating: bool,
+    /// Whether a floating pane is pinned - pinned panes are always drawn on top of the tiled
+    /// panes, even when the floating panes are hidden
+    pub is_pinned: bool,
+    /// Whether the pane is in the pane group of any client, which the pane group actions (eg.
+    /// closing, breaking, floating/embedding, renaming and broadcasting input) apply to
+    pub is_in_group: bool,
     /// Whether a pane is suppressed - suppressed panes are not visible to the user, but still run
     /// in the background
     pub is_suppressed: bool,
@@ -879,6 +1334,114 @@ pub struct PaneInfo {
     /// Unselectable panes are often used for UI elements that do not have direct user interaction
     /// (eg. the default `status-bar` or `tab-bar`).
     pub is_selectable: bool,
+    /// The current working directory of the process running in this terminal pane (eg. the
+    /// shell), refreshed periodically
+    pub cwd: Option<PathBuf>,
+    /// The command currently running in the foreground of this terminal pane (eg. `vim foo.rs`
+    /// when it was started from the shell), refreshed periodically
+    pub running_command: Option<String>,
+    /// The exit code of the last command run from the shell in this terminal pane, if the shell
+    /// reports it with semantic prompt marks (OSC 133)
+    pub last_command_exit_code: Option<i32>,
+    /// The file everything this terminal pane receives is being logged to (see
+    /// `pipe_pane_to_file`), if any
+    pub output_log_path: Option<PathBuf>,
+}
+
+/// The text contents of a terminal pane, as they appear in its scrollback buffer
+///
+/// Lines that were wrapped because they were longer than the pane is wide appear as a single
+/// line, and trailing whitespace is removed.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PaneContents {
+    /// The lines currently displayed in the pane
+    pub viewport: Vec<String>,
+    /// The lines in the scrollback buffer above the viewport, up to the number of lines requested
+    pub lines_above_viewport: Vec<String>,
+    /// The lines below the viewport (only relevant if the pane is scrolled up)
+    pub lines_below_viewport: Vec<String>,
+}
+
+/// A structural action (eg. opening, closing, moving or resizing panes, changing the swap layout or
+/// opening, closing and moving tabs) performed in the session
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ActionJournalEntry {
+    /// The name of the action that was performed, as it is written in the configuration
+    pub action: String,
+    /// The arguments of the action, optional ones as `key=value`
+    pub arguments: Vec<String>,
+    /// The position of the tab that was focused by the client when the action was performed
+    pub tab_position: usize,
+    /// The client that performed the action
+    pub client_id: u16,
+    /// When the action was performed, in seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+/// Which plugins share the keys of the plugin store, and how long they are kept
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum StoreScope {
+    /// Shared by all instances of this plugin (identified by its location), across sessions
+    Plugin,
+    /// Shared by all plugins in this session, removed when the session ends (requires the
+    /// `ShareDataWithOtherPlugins` permission)
+    Session,
+    /// Shared by all plugins, across sessions (requires the `ShareDataWithOtherPlugins`
+    /// permission)
+    Global,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PluginResponseStatus {
+    /// The plugin answered the request
+    Ok,
+    /// The plugin answered the request with an error
+    Error,
+    /// No plugin answered the request before its timeout elapsed
+    TimedOut,
+    /// The request could not be delivered or the plugin it was sent to was unloaded before
+    /// answering it
+    Unavailable,
+}
+
+/// The response to a request one plugin sent to another, correlated by the id the requesting
+/// plugin chose for the request
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PluginResponse {
+    pub request_id: String,
+    pub status: PluginResponseStatus,
+    pub payload: Option<String>,
+}
+
+impl PluginResponse {
+    pub fn ok(request_id: impl Into<String>, payload: Option<String>) -> Self {
+        PluginResponse {
+            request_id: request_id.into(),
+            status: PluginResponseStatus::Ok,
+            payload,
+        }
+    }
+    pub fn error(request_id: impl Into<String>, error: impl Into<String>) -> Self {
+        PluginResponse {
+            request_id: request_id.into(),
+            status: PluginResponseStatus::Error,
+            payload: Some(error.into()),
+        }
+    }
+    pub fn timed_out(request_id: impl Into<String>) -> Self {
+        PluginResponse {
+            request_id: request_id.into(),
+            status: PluginResponseStatus::TimedOut,
+            payload: None,
+        }
+    }
+    pub fn unavailable(request_id: impl Into<String>) -> Self {
+        PluginResponse {
+            request_id: request_id.into(),
+            status: PluginResponseStatus::Unavailable,
+            payload: None,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -963,6 +1526,7 @@ pub struct CommandToRun {
     pub path: PathBuf,
     pub args: Vec<String>,
     pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl CommandToRun {
@@ -1002,12 +1566,83 @@ pub struct NewPluginArgs {
     pub skip_cache: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum PaneId {
     Terminal(u32),
     Plugin(u32),
 }
 
+/// The position and size of a floating pane, each either a number of columns/rows or a
+/// percentage of the area floating panes are placed in. Missing values are filled in the same
+/// way as for a new floating pane without coordinates.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FloatingPaneCoordinates {
+    pub x: Option<PercentOrFixed>,
+    pub y: Option<PercentOrFixed>,
+    pub width: Option<PercentOrFixed>,
+    pub height: Option<PercentOrFixed>,
+}
+
+impl FloatingPaneCoordinates {
+    /// Returns `None` if none of the coordinates were specified
+    pub fn new(
+        x: Option<PercentOrFixed>,
+        y: Option<PercentOrFixed>,
+        width: Option<PercentOrFixed>,
+        height: Option<PercentOrFixed>,
+    ) -> Option<Self> {
+        if x.is_none() && y.is_none() && width.is_none() && height.is_none() {
+            None
+        } else {
+            Some(FloatingPaneCoordinates {
+                x,
+                y,
+                width,
+                height,
+            })
+        }
+    }
+    pub fn with_x_fixed(mut self, x: usize) -> Self {
+        self.x = Some(PercentOrFixed::Fixed(x));
+        self
+    }
+    pub fn with_x_percent(mut self, x: usize) -> Self {
+        self.x = Some(PercentOrFixed::Percent(x));
+        self
+    }
+    pub fn with_y_fixed(mut self, y: usize) -> Self {
+        self.y = Some(PercentOrFixed::Fixed(y));
+        self
+    }
+    pub fn with_y_percent(mut self, y: usize) -> Self {
+        self.y = Some(PercentOrFixed::Percent(y));
+        self
+    }
+    pub fn with_width_fixed(mut self, width: usize) -> Self {
+        self.width = Some(PercentOrFixed::Fixed(width));
+        self
+    }
+    pub fn with_width_percent(mut self, width: usize) -> Self {
+        self.width = Some(PercentOrFixed::Percent(width));
+        self
+    }
+    pub fn with_height_fixed(mut self, height: usize) -> Self {
+        self.height = Some(PercentOrFixed::Fixed(height));
+        self
+    }
+    pub fn with_height_percent(mut self, height: usize) -> Self {
+        self.height = Some(PercentOrFixed::Percent(height));
+        self
+    }
+}
+
+/// Identifies an existing tab either by its index (starting at 1, as in `GoToTab`) or its name
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TabIndexOrName {
+    Index(u32),
+    Name(String),
+}
+
 impl MessageToPlugin {
     pub fn new(message_name: impl Into<String>) -> Self {
         MessageToPlugin {
@@ -1061,6 +1696,28 @@ impl MessageToPlugin {
     }
 }
 
+/// A message sent to a specific plugin (the `plugin_url` of the message must be set) that expects
+/// an answer. The plugin receives it in its `pipe` method with the `request_id` of the
+/// `PipeMessage` set, and the answer is sent back to the requesting plugin as an
+/// `Event::PluginResponse`
+#[derive(Debug, Default, Clone)]
+pub struct PluginRequest {
+    pub request_id: String,
+    /// How long to wait for an answer before responding with `PluginResponseStatus::TimedOut`
+    pub timeout: Duration,
+    pub message: MessageToPlugin,
+}
+
+impl PluginRequest {
+    pub fn new(request_id: impl Into<String>, message: MessageToPlugin, timeout: Duration) -> Self {
+        PluginRequest {
+            request_id: request_id.into(),
+            timeout,
+            message,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ConnectToSession {
     pub name: Option<String>,
@@ -1113,6 +1770,9 @@ pub struct PipeMessage {
     pub payload: Option<String>,
     pub args: BTreeMap<String, String>,
     pub is_private: bool,
+    /// Set if this message is a request from another plugin (the `PipeSource`) that expects to be
+    /// answered with the `reply_to_plugin_request` method exported by `zellij-tile`
+    pub request_id: Option<String>,
 }
 
 impl PipeMessage {
@@ -1129,8 +1789,13 @@ impl PipeMessage {
             payload: payload.clone(),
             args: args.clone().unwrap_or_else(|| Default::default()),
             is_private,
+            request_id: None,
         }
     }
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, EnumDiscriminants, ToString)]
@@ -1143,11 +1808,11 @@ pub enum PluginCommand {
     GetPluginIds,
     GetZellijVersion,
     OpenFile(FileToOpen),
-    OpenFileFloating(FileToOpen),
-    OpenTerminal(FileToOpen),         // only used for the path as cwd
-    OpenTerminalFloating(FileToOpen), // only used for the path as cwd
+    OpenFileFloating(FileToOpen, Option<FloatingPaneCoordinates>),
+    OpenTerminal(FileToOpen), // only used for the path as cwd
+    OpenTerminalFloating(FileToOpen, Option<FloatingPaneCoordinates>), // only used for the path as cwd
     OpenCommandPane(CommandToRun),
-    OpenCommandPaneFloating(CommandToRun),
+    OpenCommandPaneFloating(CommandToRun, Option<FloatingPaneCoordinates>),
     SwitchTabTo(u32), // tab index
     SetTimeout(f64),  // seconds
     ExecCmd(Vec<String>),
@@ -1228,4 +1893,24 @@ pub enum PluginCommand {
     BlockCliPipeInput(String),     // String => pipe name
     CliPipeOutput(String, String), // String => pipe name, String => output
     MessageToPlugin(MessageToPlugin),
+    GetPaneScrollback(
+        PaneId,
+        usize, // number of scrollback lines to include
+    ),
+    PipePaneToFile(u32, PathBuf, bool), // u32 => terminal pane id, bool => strip_ansi
+    StopPipePaneToFile(u32),            // u32 => terminal pane id
+    MovePaneToTab(PaneId, TabIndexOrName, bool), // bool => should_float
+    MoveTab(Direction),
+    MoveTabToIndex(u32), // tab index
+    SwapPanes(PaneId, PaneId),
+    SwapPaneWith(Direction),
+    RegisterKeybind(InputMode, Key, bool), // bool => intercept
+    ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
+    SetPanePinned(PaneId, bool), // bool => should_be_pinned
+    GetActionJournal,
+    SendPluginRequest(PluginRequest),
+    ReplyToPluginRequest(u32, PluginResponse), // requesting plugin id, response
+    StoreSet(StoreScope, String, Option<String>), // scope, key, value (None to remove the key)
+    StoreGet(StoreScope, String),              // scope, key
+    StoreWatch(StoreScope, String),            // scope, key
 }
@@ -222,6 +222,7 @@ pub enum ScreenContext {
     HorizontalSplit,
     VerticalSplit,
     WriteCharacter,
+    WriteKey,
     ResizeIncreaseAll,
     ResizeIncreaseLeft,
     ResizeIncreaseDown,
@@ -251,6 +252,18 @@ pub enum ScreenContext {
     MovePane,
     MovePaneBackwards,
     MovePaneDown,
+    SwapPanes,
+    SwapPaneWith,
+    ChangeFloatingPaneCoordinates,
+    TogglePanePinned,
+    SetPanePinned,
+    TogglePaneInGroup,
+    ClearPaneGroup,
+    TogglePaneGroupBroadcast,
+    ClosePaneGroup,
+    BreakPaneGroup,
+    TogglePaneGroupEmbedOrFloating,
+    RenamePaneGroup,
     MovePaneUp,
     MovePaneRight,
     MovePaneLeft,
@@ -265,6 +278,9 @@ pub enum ScreenContext {
     ScrollDownAt,
     ScrollToBottom,
     ScrollToTop,
+    ScrollToPreviousPrompt,
+    ScrollToNextPrompt,
+    CopyLastCommandOutput,
     PageScrollUp,
     PageScrollDown,
     HalfPageScrollUp,
@@ -286,6 +302,8 @@ pub enum ScreenContext {
     ApplyLayout,
     SwitchTabNext,
     SwitchTabPrev,
+    MoveTab,
+    MoveTabToIndex,
     CloseTab,
     GoToTab,
     GoToTabName,
@@ -320,6 +338,9 @@ pub enum ScreenContext {
     SearchUp,
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
+    SearchToggleRegex,
+    CopyModeMove,
+    CopyModeToggleSelection,
     SearchToggleWrap,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
@@ -340,15 +361,29 @@ pub enum ScreenContext {
     FocusPaneWithId,
     RenamePane,
     RenameTab,
+    RenameTabWithPaneId,
     RequestPluginPermissions,
     BreakPane,
     BreakPaneRight,
     BreakPaneLeft,
+    MovePaneToTab,
     UpdateSessionInfos,
     ReplacePane,
     NewInPlacePluginPane,
     DumpLayoutToHd,
     RenameSession,
+    GetPaneScrollback,
+    ReportTerminalCwdsAndCommands,
+    UpdateTerminalCwdsAndCommands,
+    UpdatePaneOutputLog,
+    WaitForPane,
+    Reconfigure,
+    ConfigReloadError,
+    RecordActionInJournal,
+    QueryActionJournal,
+    GetActionJournal,
+    UndoLayoutChange,
+    RefreshPluginVisibility,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -369,6 +404,9 @@ pub enum PtyContext {
     DumpLayout,
     LogLayoutToHd,
     FillPluginCwd,
+    ReportTerminalCwdsAndCommands,
+    PipePaneToFile,
+    StopPipePaneToFile,
     Exit,
 }
 
@@ -398,6 +436,14 @@ pub enum PluginContext {
     CachePluginEvents,
     MessageFromPlugin,
     UnblockCliPipes,
+    RegisterKeybind,
+    PluginRequest,
+    PluginResponse,
+    PluginRequestTimedOut,
+    StoreSet,
+    StoreGet,
+    StoreWatch,
+    WatchedPluginFilesChanged,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -420,6 +466,7 @@ pub enum ClientContext {
     SetSynchronisedOutput,
     UnblockCliPipeInput,
     CliPipeOutput,
+    PluginKeybinds,
 }
 
 /// Stack call representations corresponding to the different types of [`ServerInstruction`]s.
@@ -442,6 +489,9 @@ pub enum ServerContext {
     UnblockCliPipeInput,
     CliPipeOutput,
     AssociatePipeWithClient,
+    RunTriggerActions,
+    PluginKeybinds,
+    ReconfigureClient,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -461,6 +511,7 @@ pub enum BackgroundJobContext {
     ReadAllSessionInfosOnMachine,
     ReportSessionInfo,
     ReportLayoutInfo,
+    ReportPaneSnapshots,
     RunCommand,
     WebRequest,
     Exit,
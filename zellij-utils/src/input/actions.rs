@@ -2,12 +2,14 @@
 
 use super::command::RunCommandAction;
 use super::layout::{
-    FloatingPaneLayout, Layout, RunPlugin, RunPluginLocation, SwapFloatingLayout, SwapTiledLayout,
-    TiledPaneLayout,
+    FloatingPaneLayout, Layout, PercentOrFixed, RunPlugin, RunPluginLocation, SwapFloatingLayout,
+    SwapTiledLayout, TiledPaneLayout,
 };
 use crate::cli::CliAction;
 use crate::data::InputMode;
-use crate::data::{Direction, Resize};
+use crate::data::{
+    Direction, FloatingPaneCoordinates, Key, KeyEventType, PaneId, Resize, TabIndexOrName,
+};
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::options::OnForceClose;
@@ -73,6 +75,7 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
 }
 
 impl FromStr for SearchOption {
@@ -84,6 +87,7 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" => Ok(SearchOption::Regex),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -92,6 +96,76 @@ impl FromStr for SearchOption {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CopyModeMotion {
+    Left,
+    Right,
+    Up,
+    Down,
+    NextWord,
+    PreviousWord,
+    EndOfWord,
+    LineStart,
+    LineEnd,
+    Top,
+    Bottom,
+    /// vi's `g` prefix, moves to the top when used twice in a row (`gg`)
+    GoPrefix,
+}
+
+impl FromStr for CopyModeMotion {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Left" | "left" => Ok(CopyModeMotion::Left),
+            "Right" | "right" => Ok(CopyModeMotion::Right),
+            "Up" | "up" => Ok(CopyModeMotion::Up),
+            "Down" | "down" => Ok(CopyModeMotion::Down),
+            "NextWord" | "nextword" | "Nextword" => Ok(CopyModeMotion::NextWord),
+            "PreviousWord" | "previousword" | "Previousword" => Ok(CopyModeMotion::PreviousWord),
+            "EndOfWord" | "endofword" | "Endofword" => Ok(CopyModeMotion::EndOfWord),
+            "LineStart" | "linestart" | "Linestart" => Ok(CopyModeMotion::LineStart),
+            "LineEnd" | "lineend" | "Lineend" => Ok(CopyModeMotion::LineEnd),
+            "Top" | "top" => Ok(CopyModeMotion::Top),
+            "Bottom" | "bottom" => Ok(CopyModeMotion::Bottom),
+            "GoPrefix" | "goprefix" | "Goprefix" => Ok(CopyModeMotion::GoPrefix),
+            _ => Err(format!(
+                "Failed to parse CopyModeMotion. Unknown CopyModeMotion: {}",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CopyModeSelection {
+    Character,
+    Line,
+}
+
+impl FromStr for CopyModeSelection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Character" | "character" => Ok(CopyModeSelection::Character),
+            "Line" | "line" => Ok(CopyModeSelection::Line),
+            _ => Err(format!(
+                "Failed to parse CopyModeSelection. Unknown CopyModeSelection: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// The state of a pane `Action::WaitForPane` waits for
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PaneCondition {
+    /// The command running in the pane exited, or the pane was closed
+    Exited,
+    /// A line matching this regular expression was printed to the pane
+    OutputMatches(String),
+}
+
 // As these actions are bound to the default config, please
 // do take care when refactoring - or renaming.
 // They might need to be adjusted in the default config
@@ -105,6 +179,9 @@ pub enum Action {
     Write(Vec<u8>),
     /// Write Characters to the terminal.
     WriteChars(String),
+    /// Write a key to the terminal, encoded in the way the application running inside it asked
+    /// for (eg. through the kitty keyboard protocol).
+    WriteKey(Key, KeyEventType),
     /// Switch to the specified input mode.
     SwitchToMode(InputMode),
     /// Switch all connected clients to the specified input mode.
@@ -122,10 +199,20 @@ pub enum Action {
     MoveFocusOrTab(Direction),
     MovePane(Option<Direction>),
     MovePaneBackwards,
+    /// Exchange the positions of two tiled panes in the same tab
+    SwapPanes(PaneId, PaneId),
+    /// Exchange the positions of the focused pane and its neighbour in the specified direction
+    SwapPaneWith(Direction),
+    /// Move and/or resize a floating pane, leaving out coordinates keeps their current value
+    ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
     /// Clear all buffers of a current screen
     ClearScreen,
     /// Dumps the screen to a file
     DumpScreen(String, bool),
+    /// Logs everything the terminal pane with this id receives to a file, until stopped
+    PipePaneToFile(u32, PathBuf, bool), // bool is strip_ansi
+    /// Stops logging the output of the terminal pane with this id
+    StopPipePaneToFile(u32),
     /// Dumps
     DumpLayout,
     /// Scroll up in focus pane.
@@ -141,6 +228,12 @@ pub enum Action {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt (requires shell integration, OSC 133)
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt (requires shell integration, OSC 133)
+    ScrollToNextPrompt,
+    /// Select and copy the output of the last command (requires shell integration, OSC 133)
+    CopyLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -166,9 +259,14 @@ pub enum Action {
         Option<Direction>,
         bool,
         bool,
+        Option<FloatingPaneCoordinates>,
     ), // usize is an optional line number, Option<PathBuf> is an optional cwd, bool is floating true/false, second bool is in_place
     /// Open a new floating pane
-    NewFloatingPane(Option<RunCommandAction>, Option<String>), // String is an optional pane name
+    NewFloatingPane(
+        Option<RunCommandAction>,
+        Option<String>,
+        Option<FloatingPaneCoordinates>,
+    ), // String is an optional pane name
     /// Open a new tiled (embedded, non-floating) pane
     NewTiledPane(Option<Direction>, Option<RunCommandAction>, Option<String>), // String is an
     /// Open a new pane in place of the focused one, suppressing it instead
@@ -179,6 +277,26 @@ pub enum Action {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes (if any) in the current Tab
     ToggleFloatingPanes,
+    /// Pin the focused floating pane if unpinned or unpin it if pinned
+    TogglePanePinned,
+    /// Pin or unpin a floating pane, pinned panes stay on top even when floating panes are hidden
+    SetPanePinned(PaneId, bool),
+    /// Add the focused pane to the pane group of this client if it is not in it, remove it
+    /// otherwise
+    TogglePaneInGroup,
+    /// Remove all panes from the pane group of this client
+    ClearPaneGroup,
+    /// Write the input of this client to all the panes in its pane group instead of to the
+    /// focused pane, or stop doing so
+    TogglePaneGroupBroadcast,
+    /// Close all the panes in the pane group
+    ClosePaneGroup,
+    /// Break all the panes in the pane group out into a new tab
+    BreakPaneGroup,
+    /// Embed the floating panes in the pane group and float the embedded ones
+    TogglePaneGroupEmbedOrFloating,
+    /// Rename all the panes in the pane group
+    RenamePaneGroup(String),
     /// Close the focus pane.
     CloseFocus,
     PaneNameInput(Vec<u8>),
@@ -201,6 +319,10 @@ pub enum Action {
     CloseTab,
     GoToTab(u32),
     GoToTabName(String, bool),
+    /// Move the active tab one position in the given direction, wrapping around at either end
+    MoveTab(Direction),
+    /// Move the active tab to the given position (starting at 1)
+    MoveTabToIndex(usize),
     ToggleTab,
     TabNameInput(Vec<u8>),
     UndoRenameTab,
@@ -234,11 +356,20 @@ pub enum Action {
     Search(SearchDirection),
     /// Toggle case sensitivity of search
     SearchToggleOption(SearchOption),
+    /// Move the cursor of copy mode
+    CopyModeMove(CopyModeMotion),
+    /// Start or stop selecting from the cursor of copy mode
+    CopyModeToggleSelection(CopyModeSelection),
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
     /// Query all tab names
     QueryTabNames,
+    /// Query the structural actions performed in this session
+    QueryActionJournal,
+    /// Restore the geometry the tiled panes of the focused tab had before the last layout change,
+    /// if the same panes are still open in it
+    UndoLayoutChange,
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPlugin, Option<String>, bool), // String is an optional name, bool is
     // skip_cache
@@ -257,6 +388,9 @@ pub enum Action {
     BreakPane,
     BreakPaneRight,
     BreakPaneLeft,
+    /// Move a pane (and its running process) to an existing tab, the index is the same as in
+    /// `GoToTab`
+    MovePaneToTab(PaneId, TabIndexOrName, bool), // bool is should_float
     RenameSession(String),
     CliPipe {
         pipe_id: String,
@@ -272,6 +406,19 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
+    /// Report on a pane (identified by its name or id) through the cli pipe with this id, once
+    /// it meets the condition if `block` is true or right away otherwise. Nothing is reported if
+    /// the condition is not met, for `PaneCondition::Exited` the exit status of the command is
+    /// reported (empty if it is unknown) and for `PaneCondition::OutputMatches` the matching
+    /// line.
+    WaitForPane {
+        pipe_id: String,
+        pane: String,
+        condition: PaneCondition,
+        block: bool,
+    },
+    /// Send a key to the plugin that bound it through `PluginCommand::RegisterKeybind`
+    SendKeyToPlugin(u32, Key), // u32 => plugin id
 }
 
 impl Action {
@@ -285,6 +432,186 @@ impl Action {
         }
     }
 
+    /// Whether this action changes the structure of the session (eg. opening, closing, moving or
+    /// resizing panes, changing the swap layout or opening, closing and moving tabs) and should
+    /// be recorded in its action journal
+    pub fn is_structural(&self) -> bool {
+        matches!(
+            self,
+            Action::Resize(..)
+                | Action::MovePane(..)
+                | Action::MovePaneBackwards
+                | Action::SwapPanes(..)
+                | Action::SwapPaneWith(..)
+                | Action::ChangeFloatingPaneCoordinates(..)
+                | Action::ToggleFocusFullscreen
+                | Action::NewPane(..)
+                | Action::EditFile(..)
+                | Action::NewFloatingPane(..)
+                | Action::NewTiledPane(..)
+                | Action::NewInPlacePane(..)
+                | Action::TogglePaneEmbedOrFloating
+                | Action::TogglePaneGroupEmbedOrFloating
+                | Action::CloseFocus
+                | Action::ClosePaneGroup
+                | Action::NewTab(..)
+                | Action::CloseTab
+                | Action::MoveTab(..)
+                | Action::MoveTabToIndex(..)
+                | Action::Run(..)
+                | Action::PreviousSwapLayout
+                | Action::NextSwapLayout
+                | Action::UndoLayoutChange
+                | Action::NewTiledPluginPane(..)
+                | Action::NewFloatingPluginPane(..)
+                | Action::NewInPlacePluginPane(..)
+                | Action::CloseTerminalPane(..)
+                | Action::ClosePluginPane(..)
+                | Action::BreakPane
+                | Action::BreakPaneRight
+                | Action::BreakPaneLeft
+                | Action::BreakPaneGroup
+                | Action::MovePaneToTab(..)
+        )
+    }
+
+    /// The name of a structural action as it is written in the configuration and its arguments
+    /// (optional ones as `key=value`), a representation that unlike the debug one of the action
+    /// stays stable across versions
+    pub fn journal_name_and_arguments(&self) -> Option<(&'static str, Vec<String>)> {
+        let mut arguments = vec![];
+        let name = match self {
+            Action::Resize(resize, direction) => {
+                arguments.push(resize_name(resize).to_owned());
+                arguments.extend(direction.as_ref().map(|d| direction_name(d).to_owned()));
+                "Resize"
+            },
+            Action::MovePane(direction) => {
+                arguments.extend(direction.as_ref().map(|d| direction_name(d).to_owned()));
+                "MovePane"
+            },
+            Action::MovePaneBackwards => "MovePaneBackwards",
+            Action::SwapPanes(first_pane_id, second_pane_id) => {
+                arguments.push(pane_id_name(first_pane_id));
+                arguments.push(pane_id_name(second_pane_id));
+                "SwapPanes"
+            },
+            Action::SwapPaneWith(direction) => {
+                arguments.push(direction_name(direction).to_owned());
+                "SwapPaneWith"
+            },
+            Action::ChangeFloatingPaneCoordinates(pane_id, coordinates) => {
+                arguments.push(pane_id_name(pane_id));
+                push_coordinates(&mut arguments, Some(coordinates));
+                "ChangeFloatingPaneCoordinates"
+            },
+            Action::ToggleFocusFullscreen => "ToggleFocusFullscreen",
+            Action::NewPane(direction, name) => {
+                arguments.extend(direction.as_ref().map(|d| direction_name(d).to_owned()));
+                push_named(&mut arguments, "name", name);
+                "NewPane"
+            },
+            Action::EditFile(
+                path,
+                line_number,
+                _cwd,
+                direction,
+                floating,
+                in_place,
+                coordinates,
+            ) => {
+                arguments.push(path.display().to_string());
+                arguments.extend(direction.as_ref().map(|d| direction_name(d).to_owned()));
+                push_named(&mut arguments, "line_number", line_number);
+                push_flag(&mut arguments, "floating", *floating);
+                push_flag(&mut arguments, "in_place", *in_place);
+                push_coordinates(&mut arguments, coordinates.as_ref());
+                "EditFile"
+            },
+            Action::NewFloatingPane(run_command, name, coordinates) => {
+                push_command(&mut arguments, run_command.as_ref());
+                push_named(&mut arguments, "name", name);
+                push_coordinates(&mut arguments, coordinates.as_ref());
+                "NewFloatingPane"
+            },
+            Action::NewTiledPane(direction, run_command, name) => {
+                arguments.extend(direction.as_ref().map(|d| direction_name(d).to_owned()));
+                push_command(&mut arguments, run_command.as_ref());
+                push_named(&mut arguments, "name", name);
+                "NewTiledPane"
+            },
+            Action::NewInPlacePane(run_command, name) => {
+                push_command(&mut arguments, run_command.as_ref());
+                push_named(&mut arguments, "name", name);
+                "NewInPlacePane"
+            },
+            Action::TogglePaneEmbedOrFloating => "TogglePaneEmbedOrFloating",
+            Action::TogglePaneGroupEmbedOrFloating => "TogglePaneGroupEmbedOrFloating",
+            Action::CloseFocus => "CloseFocus",
+            Action::ClosePaneGroup => "ClosePaneGroup",
+            Action::NewTab(_, _, _, _, name) => {
+                push_named(&mut arguments, "name", name);
+                "NewTab"
+            },
+            Action::CloseTab => "CloseTab",
+            Action::MoveTab(direction) => {
+                arguments.push(direction_name(direction).to_owned());
+                "MoveTab"
+            },
+            Action::MoveTabToIndex(index) => {
+                arguments.push(index.to_string());
+                "MoveTabToIndex"
+            },
+            Action::Run(run_command) => {
+                arguments.extend(
+                    run_command
+                        .direction
+                        .as_ref()
+                        .map(|d| direction_name(d).to_owned()),
+                );
+                push_command(&mut arguments, Some(run_command));
+                "Run"
+            },
+            Action::PreviousSwapLayout => "PreviousSwapLayout",
+            Action::NextSwapLayout => "NextSwapLayout",
+            Action::UndoLayoutChange => "UndoLayoutChange",
+            Action::NewTiledPluginPane(run_plugin, name, _)
+            | Action::NewFloatingPluginPane(run_plugin, name, _)
+            | Action::NewInPlacePluginPane(run_plugin, name, _) => {
+                arguments.push(run_plugin.location.to_string());
+                push_named(&mut arguments, "name", name);
+                match self {
+                    Action::NewTiledPluginPane(..) => "NewTiledPluginPane",
+                    Action::NewFloatingPluginPane(..) => "NewFloatingPluginPane",
+                    _ => "NewInPlacePluginPane",
+                }
+            },
+            Action::CloseTerminalPane(id) => {
+                arguments.push(id.to_string());
+                "CloseTerminalPane"
+            },
+            Action::ClosePluginPane(id) => {
+                arguments.push(id.to_string());
+                "ClosePluginPane"
+            },
+            Action::BreakPane => "BreakPane",
+            Action::BreakPaneRight => "BreakPaneRight",
+            Action::BreakPaneLeft => "BreakPaneLeft",
+            Action::BreakPaneGroup => "BreakPaneGroup",
+            Action::MovePaneToTab(pane_id, tab, should_float) => {
+                arguments.push(pane_id_name(pane_id));
+                arguments.push(match tab {
+                    TabIndexOrName::Index(index) => index.to_string(),
+                    TabIndexOrName::Name(name) => name.clone(),
+                });
+                push_flag(&mut arguments, "floating", *should_float);
+                "MovePaneToTab"
+            },
+            _ => return None,
+        };
+        Some((name, arguments))
+    }
+
     pub fn actions_from_cli(
         cli_action: CliAction,
         get_current_dir: Box<dyn Fn() -> PathBuf>,
@@ -300,17 +627,95 @@ impl Action {
             CliAction::MoveFocusOrTab { direction } => Ok(vec![Action::MoveFocusOrTab(direction)]),
             CliAction::MovePane { direction } => Ok(vec![Action::MovePane(direction)]),
             CliAction::MovePaneBackwards => Ok(vec![Action::MovePaneBackwards]),
+            CliAction::MovePaneToTab {
+                pane_id,
+                plugin,
+                tab_index,
+                tab_name,
+                floating,
+            } => {
+                let pane_id = if plugin {
+                    PaneId::Plugin(pane_id)
+                } else {
+                    PaneId::Terminal(pane_id)
+                };
+                let tab = match (tab_index, tab_name) {
+                    (Some(tab_index), _) => TabIndexOrName::Index(tab_index),
+                    (None, Some(tab_name)) => TabIndexOrName::Name(tab_name),
+                    (None, None) => {
+                        return Err("Either a tab index or a tab name is required".to_owned())
+                    },
+                };
+                Ok(vec![Action::MovePaneToTab(pane_id, tab, floating)])
+            },
+            CliAction::SwapPanes {
+                first_pane_id,
+                second_pane_id,
+                first_is_plugin,
+                second_is_plugin,
+            } => {
+                let to_pane_id = |pane_id, is_plugin| {
+                    if is_plugin {
+                        PaneId::Plugin(pane_id)
+                    } else {
+                        PaneId::Terminal(pane_id)
+                    }
+                };
+                Ok(vec![Action::SwapPanes(
+                    to_pane_id(first_pane_id, first_is_plugin),
+                    to_pane_id(second_pane_id, second_is_plugin),
+                )])
+            },
+            CliAction::SwapPaneWith { direction } => Ok(vec![Action::SwapPaneWith(direction)]),
+            CliAction::ChangeFloatingPaneCoordinates {
+                pane_id,
+                is_plugin,
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let pane_id = if is_plugin {
+                    PaneId::Plugin(pane_id)
+                } else {
+                    PaneId::Terminal(pane_id)
+                };
+                let coordinates = FloatingPaneCoordinates::new(x, y, width, height)
+                    .ok_or("At least one of x, y, width or height must be specified")?;
+                Ok(vec![Action::ChangeFloatingPaneCoordinates(
+                    pane_id,
+                    coordinates,
+                )])
+            },
             CliAction::Clear => Ok(vec![Action::ClearScreen]),
             CliAction::DumpScreen { path, full } => Ok(vec![Action::DumpScreen(
                 path.as_os_str().to_string_lossy().into(),
                 full,
             )]),
+            CliAction::PipePaneToFile {
+                pane_id,
+                path,
+                strip_ansi,
+            } => {
+                let path = if path.is_relative() {
+                    get_current_dir().join(path)
+                } else {
+                    path
+                };
+                Ok(vec![Action::PipePaneToFile(pane_id, path, strip_ansi)])
+            },
+            CliAction::StopPipePaneToFile { pane_id } => {
+                Ok(vec![Action::StopPipePaneToFile(pane_id)])
+            },
             CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
             CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottom]),
             CliAction::ScrollToTop => Ok(vec![Action::ScrollToTop]),
+            CliAction::ScrollToPreviousPrompt => Ok(vec![Action::ScrollToPreviousPrompt]),
+            CliAction::ScrollToNextPrompt => Ok(vec![Action::ScrollToNextPrompt]),
+            CliAction::CopyLastCommandOutput => Ok(vec![Action::CopyLastCommandOutput]),
             CliAction::PageScrollUp => Ok(vec![Action::PageScrollUp]),
             CliAction::PageScrollDown => Ok(vec![Action::PageScrollDown]),
             CliAction::HalfPageScrollUp => Ok(vec![Action::HalfPageScrollUp]),
@@ -328,8 +733,15 @@ impl Action {
                 name,
                 close_on_exit,
                 start_suspended,
+                env,
                 configuration,
                 skip_plugin_cache,
+                watch,
+                clear_data,
+                x,
+                y,
+                width,
+                height,
             } => {
                 let current_dir = get_current_dir();
                 let cwd = cwd
@@ -341,6 +753,8 @@ impl Action {
                         .map_err(|e| format!("Failed to parse plugin loction {plugin}: {}", e))?;
                     let plugin = RunPlugin {
                         _allow_exec_host_cmd: false,
+                        _watch: watch,
+                        _watch_clear_data: clear_data,
                         location,
                         configuration: user_configuration,
                     };
@@ -383,11 +797,13 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        env: env.into_iter().collect(),
                     };
                     if floating {
                         Ok(vec![Action::NewFloatingPane(
                             Some(run_command_action),
                             name,
+                            FloatingPaneCoordinates::new(x, y, width, height),
                         )])
                     } else if in_place {
                         Ok(vec![Action::NewInPlacePane(Some(run_command_action), name)])
@@ -400,7 +816,11 @@ impl Action {
                     }
                 } else {
                     if floating {
-                        Ok(vec![Action::NewFloatingPane(None, name)])
+                        Ok(vec![Action::NewFloatingPane(
+                            None,
+                            name,
+                            FloatingPaneCoordinates::new(x, y, width, height),
+                        )])
                     } else if in_place {
                         Ok(vec![Action::NewInPlacePane(None, name)])
                     } else {
@@ -415,6 +835,10 @@ impl Action {
                 floating,
                 in_place,
                 cwd,
+                x,
+                y,
+                width,
+                height,
             } => {
                 let mut file = file;
                 let current_dir = get_current_dir();
@@ -433,12 +857,23 @@ impl Action {
                     direction,
                     floating,
                     in_place,
+                    FloatingPaneCoordinates::new(x, y, width, height),
                 )])
             },
             CliAction::SwitchMode { input_mode } => {
                 Ok(vec![Action::SwitchModeForAllClients(input_mode)])
             },
             CliAction::TogglePaneEmbedOrFloating => Ok(vec![Action::TogglePaneEmbedOrFloating]),
+            CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
+            CliAction::TogglePaneInGroup => Ok(vec![Action::TogglePaneInGroup]),
+            CliAction::ClearPaneGroup => Ok(vec![Action::ClearPaneGroup]),
+            CliAction::TogglePaneGroupBroadcast => Ok(vec![Action::TogglePaneGroupBroadcast]),
+            CliAction::ClosePaneGroup => Ok(vec![Action::ClosePaneGroup]),
+            CliAction::BreakPaneGroup => Ok(vec![Action::BreakPaneGroup]),
+            CliAction::TogglePaneGroupEmbedOrFloating => {
+                Ok(vec![Action::TogglePaneGroupEmbedOrFloating])
+            },
+            CliAction::RenamePaneGroup { name } => Ok(vec![Action::RenamePaneGroup(name)]),
             CliAction::ToggleFloatingPanes => Ok(vec![Action::ToggleFloatingPanes]),
             CliAction::ClosePane => Ok(vec![Action::CloseFocus]),
             CliAction::RenamePane { name } => Ok(vec![
@@ -451,6 +886,8 @@ impl Action {
             CliAction::CloseTab => Ok(vec![Action::CloseTab]),
             CliAction::GoToTab { index } => Ok(vec![Action::GoToTab(index)]),
             CliAction::GoToTabName { name, create } => Ok(vec![Action::GoToTabName(name, create)]),
+            CliAction::MoveTab { direction } => Ok(vec![Action::MoveTab(direction)]),
+            CliAction::MoveTabToIndex { index } => Ok(vec![Action::MoveTabToIndex(index)]),
             CliAction::RenameTab { name } => Ok(vec![
                 Action::TabNameInput(vec![0]),
                 Action::TabNameInput(name.as_bytes().to_vec()),
@@ -540,6 +977,8 @@ impl Action {
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
             CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
+            CliAction::QueryActionJournal => Ok(vec![Action::QueryActionJournal]),
+            CliAction::UndoLayoutChange => Ok(vec![Action::UndoLayoutChange]),
             CliAction::StartOrReloadPlugin { url, configuration } => {
                 let current_dir = get_current_dir();
                 let run_plugin_location = RunPluginLocation::parse(&url, Some(current_dir))
@@ -547,6 +986,8 @@ impl Action {
                 let run_plugin = RunPlugin {
                     location: run_plugin_location,
                     _allow_exec_host_cmd: false,
+                    _watch: false,
+                    _watch_clear_data: false,
                     configuration: configuration.unwrap_or_default(),
                 };
                 Ok(vec![Action::StartOrReloadPlugin(run_plugin)])
@@ -565,6 +1006,8 @@ impl Action {
                 let run_plugin = RunPlugin {
                     location: run_plugin_location,
                     _allow_exec_host_cmd: false,
+                    _watch: false,
+                    _watch_clear_data: false,
                     configuration: configuration.unwrap_or_default(),
                 };
                 Ok(vec![Action::LaunchOrFocusPlugin(
@@ -588,6 +1031,8 @@ impl Action {
                 let run_plugin = RunPlugin {
                     location: run_plugin_location,
                     _allow_exec_host_cmd: false,
+                    _watch: false,
+                    _watch_clear_data: false,
                     configuration: configuration.unwrap_or_default(),
                 };
                 Ok(vec![Action::LaunchPlugin(
@@ -645,3 +1090,63 @@ impl From<OnForceClose> for Action {
         }
     }
 }
+
+fn direction_name(direction: &Direction) -> &'static str {
+    match direction {
+        Direction::Left => "Left",
+        Direction::Right => "Right",
+        Direction::Up => "Up",
+        Direction::Down => "Down",
+    }
+}
+
+fn resize_name(resize: &Resize) -> &'static str {
+    match resize {
+        Resize::Increase => "Increase",
+        Resize::Decrease => "Decrease",
+    }
+}
+
+fn pane_id_name(pane_id: &PaneId) -> String {
+    match pane_id {
+        PaneId::Terminal(id) => format!("terminal_{}", id),
+        PaneId::Plugin(id) => format!("plugin_{}", id),
+    }
+}
+
+fn push_named<T: ToString>(arguments: &mut Vec<String>, key: &str, value: &Option<T>) {
+    if let Some(value) = value {
+        arguments.push(format!("{}={}", key, value.to_string()));
+    }
+}
+
+fn push_flag(arguments: &mut Vec<String>, key: &str, value: bool) {
+    if value {
+        arguments.push(format!("{}=true", key));
+    }
+}
+
+fn push_command(arguments: &mut Vec<String>, run_command: Option<&RunCommandAction>) {
+    if let Some(run_command) = run_command {
+        arguments.push(run_command.command.display().to_string());
+        arguments.extend(run_command.args.iter().cloned());
+    }
+}
+
+fn push_coordinates(arguments: &mut Vec<String>, coordinates: Option<&FloatingPaneCoordinates>) {
+    let Some(coordinates) = coordinates else {
+        return;
+    };
+    for (key, value) in [
+        ("x", &coordinates.x),
+        ("y", &coordinates.y),
+        ("width", &coordinates.width),
+        ("height", &coordinates.height),
+    ] {
+        let value = value.as_ref().map(|value| match value {
+            PercentOrFixed::Percent(percent) => format!("{}%", percent),
+            PercentOrFixed::Fixed(fixed) => fixed.to_string(),
+        });
+        push_named(arguments, key, &value);
+    }
+}
@@ -11,6 +11,7 @@ use super::keybinds::Keybinds;
 use super::options::Options;
 use super::plugins::{PluginsConfig, PluginsConfigError};
 use super::theme::{Themes, UiConfig};
+use super::triggers::Triggers;
 use crate::cli::{CliArgs, Command};
 use crate::envs::EnvironmentVariables;
 use crate::{home, setup};
@@ -28,6 +29,7 @@ pub struct Config {
     pub plugins: PluginsConfig,
     pub ui: UiConfig,
     pub env: EnvironmentVariables,
+    pub triggers: Triggers,
 }
 
 #[derive(Error, Debug)]
@@ -160,6 +162,22 @@ impl TryFrom<&CliArgs> for Config {
 }
 
 impl Config {
+    /// The path of the configuration file that `Config::try_from` would read for these
+    /// arguments, if any (whether or not it exists yet)
+    pub fn config_file_path(opts: &CliArgs) -> Option<PathBuf> {
+        if let Some(ref path) = opts.config {
+            return Some(path.clone());
+        }
+        if let Some(Command::Setup(ref setup)) = opts.command {
+            if setup.clean {
+                return None;
+            }
+        }
+        opts.config_dir
+            .clone()
+            .or_else(home::find_default_config_dir)
+            .map(|config_dir| config_dir.join(DEFAULT_CONFIG_FILE_NAME))
+    }
     pub fn theme_config(&self, opts: &Options) -> Option<Palette> {
         match &opts.theme {
             Some(theme_name) => self.themes.get_theme(theme_name).map(|theme| theme.palette),
@@ -678,4 +696,54 @@ mod config_test {
             "Env variables defined in config"
         );
     }
+
+    #[test]
+    fn can_define_triggers_in_config_file() {
+        use crate::input::actions::Action;
+        use crate::input::triggers::{Trigger, Triggers};
+        let config_contents = r#"
+            triggers {
+                trigger "test result: FAILED" {
+                    focus_pane true
+                    rename_tab "tests failed"
+                }
+                trigger "listening on \\d+" {
+                    pipe "server_ready"
+                    GoToTab 2
+                }
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let expected_triggers = Triggers(vec![
+            Trigger {
+                pattern: "test result: FAILED".into(),
+                focus_pane: true,
+                rename_tab: Some("tests failed".into()),
+                ..Default::default()
+            },
+            Trigger {
+                pattern: "listening on \\d+".into(),
+                pipe: Some("server_ready".into()),
+                actions: vec![Action::GoToTab(2)],
+                ..Default::default()
+            },
+        ]);
+        assert_eq!(
+            config.triggers, expected_triggers,
+            "Triggers defined in config"
+        );
+    }
+
+    #[test]
+    fn invalid_trigger_pattern_is_a_config_error() {
+        let config_contents = r#"
+            triggers {
+                trigger "FAILED(" {
+                    focus_pane true
+                }
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None);
+        assert!(config.is_err(), "Invalid regex in trigger is rejected");
+    }
 }
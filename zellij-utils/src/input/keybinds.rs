@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
 use super::actions::Action;
-use crate::data::{InputMode, Key, KeybindsVec};
+use crate::data::{InputMode, Key, KeyEventType, KeybindsVec};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -10,6 +10,16 @@ use std::fmt;
 #[derive(Clone, PartialEq, Deserialize, Serialize, Default)]
 pub struct Keybinds(pub HashMap<InputMode, HashMap<Key, Vec<Action>>>);
 
+/// A key bound to a plugin while it is running (see `PluginCommand::RegisterKeybind`), it is sent
+/// to the plugin whatever is focused and unless `intercept` is true is then handled as usual
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PluginKeybind {
+    pub plugin_id: u32,
+    pub mode: InputMode,
+    pub key: Key,
+    pub intercept: bool,
+}
+
 impl fmt::Debug for Keybinds {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut stable_sorted = BTreeMap::new();
@@ -35,12 +45,79 @@ impl Keybinds {
         mode: &InputMode,
         key: &Key,
         raw_bytes: Vec<u8>,
+        is_kitty_keyboard_protocol: bool,
     ) -> Vec<Action> {
         self.0
             .get(mode)
             .and_then(|normal_mode_keybindings| normal_mode_keybindings.get(key))
             .cloned()
-            .unwrap_or_else(|| vec![self.default_action_for_mode(mode, raw_bytes)])
+            .unwrap_or_else(|| {
+                if is_kitty_keyboard_protocol {
+                    vec![self.default_action_for_mode_and_key(mode, key)]
+                } else {
+                    vec![self.default_action_for_mode(mode, raw_bytes)]
+                }
+            })
+    }
+    /// Like `get_actions_for_key_in_mode_or_default_action`, but the key is first sent to the
+    /// plugins that bound it
+    pub fn get_actions_for_key_with_plugin_keybinds(
+        &self,
+        plugin_keybinds: &[PluginKeybind],
+        mode: &InputMode,
+        key: &Key,
+        raw_bytes: Vec<u8>,
+        is_kitty_keyboard_protocol: bool,
+    ) -> Vec<Action> {
+        let mut intercepted = false;
+        let mut actions: Vec<Action> = plugin_keybinds
+            .iter()
+            .filter(|plugin_keybind| &plugin_keybind.mode == mode && &plugin_keybind.key == key)
+            .map(|plugin_keybind| {
+                intercepted |= plugin_keybind.intercept;
+                Action::SendKeyToPlugin(plugin_keybind.plugin_id, *key)
+            })
+            .collect();
+        if !intercepted {
+            actions.append(&mut self.get_actions_for_key_in_mode_or_default_action(
+                mode,
+                key,
+                raw_bytes,
+                is_kitty_keyboard_protocol,
+            ));
+        }
+        actions
+    }
+    /// Like `get_actions_for_key_with_plugin_keybinds`, for keys reported through the kitty
+    /// keyboard protocol along with their event type. Holding down a key repeats its actions,
+    /// releasing it only reaches the application the key is written to and does not trigger
+    /// any bindings.
+    pub fn get_actions_for_key_event_with_plugin_keybinds(
+        &self,
+        plugin_keybinds: &[PluginKeybind],
+        mode: &InputMode,
+        key: &Key,
+        event_type: KeyEventType,
+    ) -> Vec<Action> {
+        let actions =
+            self.get_actions_for_key_with_plugin_keybinds(plugin_keybinds, mode, key, vec![], true);
+        match event_type {
+            KeyEventType::Press => actions,
+            KeyEventType::Repeat => actions
+                .into_iter()
+                .map(|action| match action {
+                    Action::WriteKey(key, _) => Action::WriteKey(key, KeyEventType::Repeat),
+                    action => action,
+                })
+                .collect(),
+            KeyEventType::Release => actions
+                .into_iter()
+                .filter_map(|action| match action {
+                    Action::WriteKey(key, _) => Some(Action::WriteKey(key, KeyEventType::Release)),
+                    _ => None,
+                })
+                .collect(),
+        }
     }
     pub fn get_input_mode_mut(&mut self, input_mode: &InputMode) -> &mut HashMap<Key, Vec<Action>> {
         self.0.entry(*input_mode).or_insert_with(HashMap::new)
@@ -54,6 +131,17 @@ impl Keybinds {
             _ => Action::NoOp,
         }
     }
+    /// Like `default_action_for_mode`, but for keys that did not arrive in the legacy encoding
+    /// (eg. through the kitty keyboard protocol) and so cannot be passed on as they are
+    pub fn default_action_for_mode_and_key(&self, mode: &InputMode, key: &Key) -> Action {
+        match *mode {
+            InputMode::Normal | InputMode::Locked => Action::WriteKey(*key, KeyEventType::Press),
+            _ => match key.serialize_legacy() {
+                Some(raw_bytes) => self.default_action_for_mode(mode, raw_bytes),
+                None => Action::NoOp,
+            },
+        }
+    }
     pub fn to_keybinds_vec(&self) -> KeybindsVec {
         let mut ret = vec![];
         for (mode, mode_binds) in &self.0 {
@@ -222,6 +222,12 @@ impl Run {
 pub struct RunPlugin {
     #[serde(default)]
     pub _allow_exec_host_cmd: bool,
+    /// Reload the plugin whenever its wasm file changes on disk (only for `file:` locations)
+    #[serde(default)]
+    pub _watch: bool,
+    /// Empty the `/data` folder of a watched plugin before reloading it, instead of keeping it
+    #[serde(default)]
+    pub _watch_clear_data: bool,
     pub location: RunPluginLocation,
     pub configuration: PluginUserConfiguration,
 }
@@ -478,10 +484,13 @@ pub struct FloatingPaneLayout {
     pub width: Option<PercentOrFixed>,
     pub x: Option<PercentOrFixed>,
     pub y: Option<PercentOrFixed>,
+    pub pinned: Option<bool>,
     pub run: Option<Run>,
     pub focus: Option<bool>,
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
+    pub pane_initial_snapshot_file: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl FloatingPaneLayout {
@@ -493,6 +502,14 @@ impl FloatingPaneLayout {
             },
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        // variables set on the pane itself take precedence
+        for (name, value) in env {
+            self.env
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
     pub fn add_start_suspended(&mut self, start_suspended: Option<bool>) {
         if let Some(run) = self.run.as_mut() {
             run.add_start_suspended(start_suspended);
@@ -506,6 +523,7 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            env: pane_layout.env.clone(),
             ..Default::default()
         }
     }
@@ -527,6 +545,8 @@ pub struct TiledPaneLayout {
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub pane_initial_snapshot_file: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl TiledPaneLayout {
@@ -638,21 +658,29 @@ impl TiledPaneLayout {
         Ok(layouts)
     }
     pub fn extract_run_instructions(&self) -> Vec<Option<Run>> {
+        self.extract_run_instructions_with_env()
+            .into_iter()
+            .map(|(run_instruction, _env)| run_instruction)
+            .collect()
+    }
+    pub fn extract_run_instructions_with_env(
+        &self,
+    ) -> Vec<(Option<Run>, BTreeMap<String, String>)> {
         // the order of these run instructions is significant and needs to be the same
         // as the order of the "flattened" layout panes received from eg. position_panes_in_space
         let mut run_instructions = vec![];
         if self.children.is_empty() {
-            run_instructions.push(self.run.clone());
+            run_instructions.push((self.run.clone(), self.env.clone()));
         }
         for child in &self.children {
-            let mut child_run_instructions = child.extract_run_instructions();
+            let mut child_run_instructions = child.extract_run_instructions_with_env();
             run_instructions.append(&mut child_run_instructions);
         }
         let mut successfully_ignored = 0;
         for instruction_to_ignore in &self.run_instructions_to_ignore {
             if let Some(position) = run_instructions
                 .iter()
-                .position(|i| i == instruction_to_ignore)
+                .position(|(i, _env)| i == instruction_to_ignore)
             {
                 run_instructions.remove(position);
                 successfully_ignored += 1;
@@ -668,7 +696,7 @@ impl TiledPaneLayout {
                 .len()
                 .saturating_sub(successfully_ignored)
             {
-                if let Some(position) = run_instructions.iter().position(|i| i == &None) {
+                if let Some(position) = run_instructions.iter().position(|(i, _env)| i.is_none()) {
                     run_instructions.remove(position);
                 }
             }
@@ -694,6 +722,17 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        // variables set on the pane itself take precedence
+        for (name, value) in env {
+            self.env
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        for child in self.children.iter_mut() {
+            child.add_env_to_layout(env);
+        }
+    }
     pub fn deepest_depth(&self) -> usize {
         let mut deepest_child_depth = 0;
         for child in self.children.iter() {
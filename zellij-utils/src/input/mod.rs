@@ -6,7 +6,9 @@ pub mod layout;
 pub mod options;
 pub mod permission;
 pub mod plugins;
+pub mod script;
 pub mod theme;
+pub mod triggers;
 
 // Can't use this in wasm due to dependency on the `termwiz` crate.
 #[cfg(not(target_family = "wasm"))]
@@ -40,6 +42,7 @@ mod not_wasm {
             style: attributes.style,
             capabilities,
             session_name,
+            is_read_only: attributes.is_read_only,
         }
     }
 
@@ -0,0 +1,168 @@
+//! Scripts run against a session by `zellij script`: a sequence of cli actions, with steps that
+//! wait for panes to print something or exit and steps that run conditionally on those.
+use super::actions::{Action, PaneCondition};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Script(pub Vec<ScriptStep>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptStep {
+    /// The actions of a single `zellij action` invocation
+    Actions(Vec<Action>),
+    /// Block until the pane (identified by its name or id) meets the condition, failing the
+    /// script if it does not before the timeout
+    WaitForPane {
+        pane: String,
+        condition: PaneCondition,
+        timeout: Option<Duration>,
+    },
+    Sleep(Duration),
+    /// Run `then` if the pane currently meets the condition and `otherwise` if it does not
+    If {
+        pane: String,
+        condition: ScriptCondition,
+        then: Vec<ScriptStep>,
+        otherwise: Vec<ScriptStep>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptCondition {
+    /// The command of the pane exited with this status
+    ExitStatus(i32),
+    /// A line matching this regular expression was printed to the pane
+    OutputMatches(String),
+}
+
+impl ScriptCondition {
+    pub fn pane_condition(&self) -> PaneCondition {
+        match self {
+            ScriptCondition::ExitStatus(_) => PaneCondition::Exited,
+            ScriptCondition::OutputMatches(pattern) => {
+                PaneCondition::OutputMatches(pattern.clone())
+            },
+        }
+    }
+    /// Whether what the server reported for `Action::WaitForPane` (if anything) meets this
+    /// condition
+    pub fn is_met(&self, report: Option<&str>) -> bool {
+        match (self, report) {
+            (ScriptCondition::ExitStatus(exit_status), Some(report)) => {
+                report.trim().parse::<i32>().ok() == Some(*exit_status)
+            },
+            (ScriptCondition::OutputMatches(_), Some(_)) => true,
+            (_, None) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod script_test {
+    use super::*;
+    use crate::input::config::Config;
+
+    fn parse(raw_script: &str) -> Script {
+        Script::from_kdl(raw_script, "script.kdl".into(), Some(Config::default())).unwrap()
+    }
+
+    #[test]
+    fn actions_are_parsed_as_cli_actions() {
+        let script = parse(
+            r#"
+            action "go-to-tab-name" "dev"
+            action "write-chars" "cargo run"
+        "#,
+        );
+        assert_eq!(
+            script,
+            Script(vec![
+                ScriptStep::Actions(vec![Action::GoToTabName("dev".into(), false)]),
+                ScriptStep::Actions(vec![Action::WriteChars("cargo run".into())]),
+            ])
+        );
+    }
+
+    #[test]
+    fn waits_and_conditionals() {
+        let script = parse(
+            r#"
+            wait_for_output "db" "ready to accept connections" timeout=30
+            wait_for_exit "migrations"
+            sleep 0.5
+            if "migrations" exit_status=0 {
+                action "go-to-tab-name" "server"
+            }
+            else {
+                action "go-to-tab-name" "logs"
+            }
+            if "db" output="ERROR" {
+                sleep 1
+            }
+        "#,
+        );
+        assert_eq!(
+            script,
+            Script(vec![
+                ScriptStep::WaitForPane {
+                    pane: "db".into(),
+                    condition: PaneCondition::OutputMatches("ready to accept connections".into()),
+                    timeout: Some(Duration::from_secs(30)),
+                },
+                ScriptStep::WaitForPane {
+                    pane: "migrations".into(),
+                    condition: PaneCondition::Exited,
+                    timeout: None,
+                },
+                ScriptStep::Sleep(Duration::from_millis(500)),
+                ScriptStep::If {
+                    pane: "migrations".into(),
+                    condition: ScriptCondition::ExitStatus(0),
+                    then: vec![ScriptStep::Actions(vec![Action::GoToTabName(
+                        "server".into(),
+                        false
+                    )])],
+                    otherwise: vec![ScriptStep::Actions(vec![Action::GoToTabName(
+                        "logs".into(),
+                        false
+                    )])],
+                },
+                ScriptStep::If {
+                    pane: "db".into(),
+                    condition: ScriptCondition::OutputMatches("ERROR".into()),
+                    then: vec![ScriptStep::Sleep(Duration::from_secs(1))],
+                    otherwise: vec![],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_scripts_are_config_errors() {
+        let invalid_scripts = [
+            r#"action "not-an-action""#,
+            r#"wait_for_output "db" "(unclosed""#,
+            r#"wait_for_exit"#,
+            r#"else { sleep 1; }"#,
+            r#"if "db" { sleep 1; }"#,
+            r#"frobnicate "db""#,
+        ];
+        for raw_script in invalid_scripts {
+            assert!(
+                Script::from_kdl(raw_script, "script.kdl".into(), None).is_err(),
+                "{} should not parse",
+                raw_script
+            );
+        }
+    }
+
+    #[test]
+    fn conditions_are_met_by_server_reports() {
+        assert!(ScriptCondition::ExitStatus(0).is_met(Some("0")));
+        assert!(!ScriptCondition::ExitStatus(0).is_met(Some("1")));
+        assert!(!ScriptCondition::ExitStatus(0).is_met(Some("")));
+        assert!(!ScriptCondition::ExitStatus(0).is_met(None));
+        assert!(ScriptCondition::OutputMatches("x".into()).is_met(Some("x marks the spot")));
+        assert!(!ScriptCondition::OutputMatches("x".into()).is_met(None));
+    }
+}
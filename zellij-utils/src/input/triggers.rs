@@ -0,0 +1,71 @@
+//! Triggers run actions when the output of a terminal pane matches a pattern.
+use super::actions::Action;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The `triggers` block of the configuration
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Triggers(pub Vec<Trigger>);
+
+impl Triggers {
+    /// Merges two structs, triggers from `other` are added after those of `self`
+    pub fn merge(&self, other: Self) -> Self {
+        let mut triggers = self.0.clone();
+        triggers.extend(other.0);
+        Triggers(triggers)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &Trigger> {
+        self.0.iter()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    /// A regular expression matched against each line of terminal output (stripped of ANSI
+    /// escape codes)
+    pub pattern: String,
+    /// Focus the pane whose output matched, switching to its tab if needed
+    pub focus_pane: bool,
+    /// Rename the tab of the pane whose output matched
+    pub rename_tab: Option<String>,
+    /// Send a pipe message with this name to all plugins, with the matched line as its payload
+    pub pipe: Option<String>,
+    /// Any other actions to run, as they would be in a keybinding
+    pub actions: Vec<Action>,
+}
+
+impl Trigger {
+    /// The actions to perform when `line` was printed to the terminal pane with `terminal_id`,
+    /// `rename_tab` is not one of them since it applies to the tab of that pane rather than to the
+    /// focused one
+    pub fn actions_for_match(&self, terminal_id: u32, line: &str) -> Vec<Action> {
+        let mut actions = vec![];
+        if self.focus_pane {
+            actions.push(Action::FocusTerminalPaneWithId(terminal_id, true));
+        }
+        if let Some(pipe_name) = &self.pipe {
+            let mut args = BTreeMap::new();
+            args.insert("pane_id".to_owned(), terminal_id.to_string());
+            args.insert("pattern".to_owned(), self.pattern.clone());
+            actions.push(Action::CliPipe {
+                pipe_id: format!("trigger-{}-{}", pipe_name, terminal_id),
+                name: Some(pipe_name.clone()),
+                payload: Some(line.to_owned()),
+                args: Some(args),
+                plugin: None,
+                configuration: None,
+                launch_new: false,
+                skip_cache: false,
+                floating: None,
+                in_place: None,
+                cwd: None,
+                pane_title: None,
+            });
+        }
+        actions.extend(self.actions.iter().cloned());
+        actions
+    }
+}
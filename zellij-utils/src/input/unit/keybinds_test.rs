@@ -1,6 +1,6 @@
 use super::super::actions::*;
 use super::super::keybinds::*;
-use crate::data::{self, CharOrArrow, Direction, Key};
+use crate::data::{self, CharOrArrow, Direction, Key, KeyEventType};
 use crate::input::config::Config;
 use insta::assert_snapshot;
 use strum::IntoEnumIterator;
@@ -54,6 +54,33 @@ fn can_define_multiple_keybinds_for_same_action() {
     );
 }
 
+#[test]
+fn can_define_tab_reordering_keybindings() {
+    let config_contents = r#"
+        keybinds {
+            tab {
+                bind "H" { MoveTab "Left"; }
+                bind "1" { MoveTabToIndex 1; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Tab, &Key::Char('H')),
+        Some(&vec![Action::MoveTab(Direction::Left)]),
+        "MoveTab keybinding defined in config"
+    );
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Tab, &Key::Char('1')),
+        Some(&vec![Action::MoveTabToIndex(1)]),
+        "MoveTabToIndex keybinding defined in config"
+    );
+}
+
 #[test]
 fn can_define_series_of_actions_for_same_keybinding() {
     let config_contents = r#"
@@ -130,6 +157,171 @@ fn uppercase_and_lowercase_keybindings_are_distinct() {
     );
 }
 
+#[test]
+fn can_bind_keys_with_any_combination_of_modifiers() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl Shift a" { SwitchToMode "Pane"; }
+                bind "Ctrl Alt h" { SwitchToMode "Resize"; }
+                bind "Super x" { SwitchToMode "Tab"; }
+                bind "Ctrl Shift Left" { SwitchToMode "Move"; }
+                bind "Alt Shift b" { SwitchToMode "Scroll"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let action_for_key = |key: Key| {
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &key)
+            .cloned()
+    };
+    assert_eq!(
+        action_for_key(Key::WithModifiers(
+            data::BareKey::Char('a'),
+            data::KeyModifiers {
+                ctrl: true,
+                shift: true,
+                ..Default::default()
+            }
+        )),
+        Some(vec![Action::SwitchToMode(InputMode::Pane)]),
+        "Ctrl Shift a bound"
+    );
+    assert_eq!(
+        action_for_key(Key::WithModifiers(
+            data::BareKey::Char('h'),
+            data::KeyModifiers {
+                ctrl: true,
+                alt: true,
+                ..Default::default()
+            }
+        )),
+        Some(vec![Action::SwitchToMode(InputMode::Resize)]),
+        "Ctrl Alt h bound"
+    );
+    assert_eq!(
+        action_for_key(Key::WithModifiers(
+            data::BareKey::Char('x'),
+            data::KeyModifiers {
+                super_key: true,
+                ..Default::default()
+            }
+        )),
+        Some(vec![Action::SwitchToMode(InputMode::Tab)]),
+        "Super x bound"
+    );
+    assert_eq!(
+        action_for_key(Key::WithModifiers(
+            data::BareKey::Left,
+            data::KeyModifiers {
+                ctrl: true,
+                shift: true,
+                ..Default::default()
+            }
+        )),
+        Some(vec![Action::SwitchToMode(InputMode::Move)]),
+        "Ctrl Shift Left bound"
+    );
+    assert_eq!(
+        action_for_key(Key::Alt(CharOrArrow::Char('B'))),
+        Some(vec![Action::SwitchToMode(InputMode::Scroll)]),
+        "Alt Shift b is bound as its legacy representation"
+    );
+    assert_eq!(
+        action_for_key(Key::Ctrl('a')),
+        None,
+        "Ctrl a is distinct from Ctrl Shift a"
+    );
+}
+
+#[test]
+fn default_action_for_keys_with_modifiers() {
+    let keybinds = Keybinds::default();
+    let ctrl_a = Key::Ctrl('a');
+    let super_a = Key::WithModifiers(
+        data::BareKey::Char('a'),
+        data::KeyModifiers {
+            super_key: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode_or_default_action(
+            &InputMode::Normal,
+            &ctrl_a,
+            vec![],
+            true
+        ),
+        vec![Action::WriteKey(ctrl_a, KeyEventType::Press)],
+        "Keys are written as keys in normal mode"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode_or_default_action(
+            &InputMode::RenameTab,
+            &ctrl_a,
+            vec![],
+            true
+        ),
+        vec![Action::TabNameInput(vec![1])],
+        "Keys are sent in their legacy encoding when renaming"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode_or_default_action(
+            &InputMode::RenameTab,
+            &super_a,
+            vec![],
+            true
+        ),
+        vec![Action::NoOp],
+        "Keys without a legacy encoding are dropped when renaming"
+    );
+}
+
+#[test]
+fn key_events_repeat_bindings_and_only_release_written_keys() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl g" { SwitchToMode "Locked"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let ctrl_g = Key::Ctrl('g');
+    let ctrl_a = Key::Ctrl('a');
+    let actions_for_key_event = |key: &Key, event_type: KeyEventType| {
+        config
+            .keybinds
+            .get_actions_for_key_event_with_plugin_keybinds(
+                &[],
+                &InputMode::Normal,
+                key,
+                event_type,
+            )
+    };
+    assert_eq!(
+        actions_for_key_event(&ctrl_g, KeyEventType::Repeat),
+        vec![Action::SwitchToMode(InputMode::Locked)],
+        "holding down a bound key repeats its actions"
+    );
+    assert_eq!(
+        actions_for_key_event(&ctrl_g, KeyEventType::Release),
+        vec![],
+        "releasing a bound key does not trigger its actions"
+    );
+    assert_eq!(
+        actions_for_key_event(&ctrl_a, KeyEventType::Repeat),
+        vec![Action::WriteKey(ctrl_a, KeyEventType::Repeat)],
+    );
+    assert_eq!(
+        actions_for_key_event(&ctrl_a, KeyEventType::Release),
+        vec![Action::WriteKey(ctrl_a, KeyEventType::Release)],
+        "releasing a written key is written as well"
+    );
+}
+
 #[test]
 fn can_override_keybindings() {
     let default_config_contents = r#"
@@ -542,3 +734,69 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn plugin_keybinds_are_sent_to_plugins_before_configured_keybinds() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Alt h" { MoveFocusOrTab "Left"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let alt_h = Key::Alt(CharOrArrow::Char('h'));
+    let plugin_keybinds = vec![
+        PluginKeybind {
+            plugin_id: 1,
+            mode: InputMode::Normal,
+            key: alt_h,
+            intercept: false,
+        },
+        PluginKeybind {
+            plugin_id: 2,
+            mode: InputMode::Locked,
+            key: alt_h,
+            intercept: true,
+        },
+    ];
+    let actions_in_normal_mode = config.keybinds.get_actions_for_key_with_plugin_keybinds(
+        &plugin_keybinds,
+        &InputMode::Normal,
+        &alt_h,
+        vec![27, 104],
+        false,
+    );
+    assert_eq!(
+        actions_in_normal_mode,
+        vec![
+            Action::SendKeyToPlugin(1, alt_h),
+            Action::MoveFocusOrTab(Direction::Left)
+        ],
+        "Key sent to the plugin and then handled as usual"
+    );
+    let actions_in_locked_mode = config.keybinds.get_actions_for_key_with_plugin_keybinds(
+        &plugin_keybinds,
+        &InputMode::Locked,
+        &alt_h,
+        vec![27, 104],
+        false,
+    );
+    assert_eq!(
+        actions_in_locked_mode,
+        vec![Action::SendKeyToPlugin(2, alt_h)],
+        "Key intercepted by the plugin"
+    );
+    let actions_in_pane_mode = config.keybinds.get_actions_for_key_with_plugin_keybinds(
+        &plugin_keybinds,
+        &InputMode::Pane,
+        &alt_h,
+        vec![27, 104],
+        false,
+    );
+    assert_eq!(
+        actions_in_pane_mode,
+        vec![Action::NoOp],
+        "Key not bound by plugins in this mode"
+    );
+}
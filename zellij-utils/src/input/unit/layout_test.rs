@@ -203,6 +203,48 @@ fn layout_with_hidden_floating_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_pinned_floating_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="pinned_template" pinned=true
+            pane
+            floating_panes {
+                pane pinned=true
+                pane {
+                    pinned false
+                }
+                pinned_template
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![TiledPaneLayout::default()],
+                ..Default::default()
+            },
+            vec![
+                FloatingPaneLayout {
+                    pinned: Some(true),
+                    ..Default::default()
+                },
+                FloatingPaneLayout {
+                    pinned: Some(false),
+                    ..Default::default()
+                },
+                FloatingPaneLayout {
+                    pinned: Some(true),
+                    ..Default::default()
+                },
+            ],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
 #[test]
 fn layout_with_floating_panes_template() {
     let kdl_layout = r#"
@@ -554,6 +596,8 @@ fn layout_with_plugin_panes() {
                         run: Some(Run::Plugin(RunPlugin {
                             location: RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             configuration: Default::default(),
                         })),
                         ..Default::default()
@@ -564,6 +608,8 @@ fn layout_with_plugin_panes() {
                                 "/path/to/my/plugin.wasm",
                             )),
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             configuration: Default::default(),
                         })),
                         ..Default::default()
@@ -572,6 +618,8 @@ fn layout_with_plugin_panes() {
                         run: Some(Run::Plugin(RunPlugin {
                             location: RunPluginLocation::Zellij(PluginTag::new("status-bar")),
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             configuration: PluginUserConfiguration(expected_plugin_configuration),
                         })),
                         ..Default::default()
@@ -586,6 +634,66 @@ fn layout_with_plugin_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_watched_plugin_pane() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" {
+                    _watch true
+                    config_key_1 "config_value_1"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let run_plugin = match &layout.template.unwrap().0.children[0].run {
+        Some(Run::Plugin(run_plugin)) => run_plugin.clone(),
+        run => panic!("expected a plugin, got: {:?}", run),
+    };
+    let mut expected_plugin_configuration = BTreeMap::new();
+    expected_plugin_configuration.insert("config_key_1".to_owned(), "config_value_1".to_owned());
+    assert!(run_plugin._watch, "plugin is watched");
+    assert!(
+        !run_plugin._watch_clear_data,
+        "plugin keeps its /data folder across reloads by default"
+    );
+    assert_eq!(
+        run_plugin.configuration,
+        PluginUserConfiguration(expected_plugin_configuration),
+        "_watch is not passed to the plugin as configuration"
+    );
+}
+
+#[test]
+fn layout_with_watched_plugin_pane_clearing_its_data() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" {
+                    _watch true
+                    _watch_clear_data true
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let run_plugin = match &layout.template.unwrap().0.children[0].run {
+        Some(Run::Plugin(run_plugin)) => run_plugin.clone(),
+        run => panic!("expected a plugin, got: {:?}", run),
+    };
+    assert!(run_plugin._watch, "plugin is watched");
+    assert!(
+        run_plugin._watch_clear_data,
+        "plugin /data folder is cleared on reload"
+    );
+    assert_eq!(
+        run_plugin.configuration,
+        PluginUserConfiguration::default(),
+        "_watch_clear_data is not passed to the plugin as configuration"
+    );
+}
+
 #[test]
 fn layout_with_borderless_panes() {
     let kdl_layout = r#"
@@ -1829,6 +1937,101 @@ fn tab_cwd_prepended_to_panes_with_cwd() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn env_variables_given_to_panes_and_command_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+            pane command="tail" {
+                args "-f" "/tmp/foo"
+                env {
+                    PORT 8080
+                    VERBOSE true
+                }
+            }
+            floating_panes {
+                pane {
+                    env {
+                        RUST_LOG "info"
+                    }
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn tab_env_variables_given_to_panes_without_overriding_their_own() {
+    let kdl_layout = r#"
+        layout {
+            tab {
+                env {
+                    RUST_LOG "debug"
+                    PORT "8080"
+                }
+                pane
+                pane command="tail" {
+                    env {
+                        PORT "9090"
+                    }
+                }
+                floating_panes {
+                    pane
+                }
+            }
+            // the tail pane should have PORT 9090, all panes should have RUST_LOG debug
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn env_variables_in_pane_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="service" {
+                command "cargo"
+                env {
+                    RUST_LOG "debug"
+                    PORT "8080"
+                }
+            }
+            service {
+                args "run"
+                env {
+                    PORT "9090"
+                }
+            }
+            // should have both RUST_LOG debug and PORT 9090
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn env_variables_must_be_inside_the_env_block() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env "RUST_LOG" "debug"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for env variables outside the env block"
+    );
+}
+
 #[test]
 fn global_cwd_and_tab_cwd_prepended_to_panes_with_and_without_cwd() {
     let kdl_layout = r#"
@@ -2089,6 +2292,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
                             configuration: Default::default(),
                         })),
@@ -2097,6 +2302,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::File(PathBuf::from(
                                 "/path/to/my/plugin.wasm",
                             )),
@@ -2107,6 +2314,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::File(PathBuf::from("plugin.wasm")),
                             configuration: Default::default(),
                         })),
@@ -2115,6 +2324,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::File(PathBuf::from(
                                 "relative/with space/plugin.wasm",
                             )),
@@ -2125,6 +2336,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::File(PathBuf::from(
                                 "/absolute/with space/plugin.wasm",
                             )),
@@ -2135,6 +2348,8 @@ fn run_plugin_location_parsing() {
                     TiledPaneLayout {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: RunPluginLocation::File(PathBuf::from(
                                 "c:/absolute/windows/plugin.wasm",
                             )),
@@ -3,8 +3,11 @@ use crate::{
     cli::CliArgs,
     data::{ClientId, ConnectToSession, InputMode, Style},
     errors::{get_current_ctx, prelude::*, ErrorContext},
-    input::keybinds::Keybinds,
-    input::{actions::Action, layout::Layout, options::Options, plugins::PluginsConfig},
+    input::keybinds::{Keybinds, PluginKeybind},
+    input::{
+        actions::Action, layout::Layout, options::Options, plugins::PluginsConfig,
+        triggers::Triggers,
+    },
     pane_size::{Size, SizeInPixels},
 };
 use interprocess::local_socket::LocalSocketStream;
@@ -42,6 +45,7 @@ pub struct ClientAttributes {
     pub size: Size,
     pub style: Style,
     pub keybinds: Keybinds,
+    pub is_read_only: bool, // read-only clients can only look around the session, not change it
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -77,6 +81,7 @@ pub enum ClientToServerMsg {
         Box<Options>,
         Box<Layout>,
         Option<PluginsConfig>,
+        Triggers,
     ),
     AttachClient(
         ClientAttributes,
@@ -89,6 +94,8 @@ pub enum ClientToServerMsg {
     KillSession,
     ConnStatus,
     ListClients,
+    ReconfigureClient(Keybinds, Style, Box<Options>), // sent when the config file changes
+    ConfigReloadError(String),                        // String -> the rendered parse error
 }
 
 // Types of messages sent from the server to the client
@@ -105,6 +112,7 @@ pub enum ServerToClientMsg {
     SwitchSession(ConnectToSession),
     UnblockCliPipeInput(String),   // String -> pipe name
     CliPipeOutput(String, String), // String -> pipe name, String -> Output
+    PluginKeybinds(Vec<PluginKeybind>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
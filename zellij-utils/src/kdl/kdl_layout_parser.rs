@@ -82,6 +82,7 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "snapshot_file"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -102,6 +103,8 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "snapshot_file"
+            || property_name == "env"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -118,7 +121,10 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "y"
             || property_name == "width"
             || property_name == "height"
+            || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "snapshot_file"
+            || property_name == "env"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -135,6 +141,8 @@ impl<'a> KdlLayoutParser<'a> {
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
             || property_name == "_allow_exec_host_cmd"
+            || property_name == "_watch"
+            || property_name == "_watch_clear_data"
             || property_name == "path"
     }
     fn assert_legal_node_name(&self, name: &str, kdl_node: &KdlNode) -> Result<(), ConfigError> {
@@ -144,7 +152,8 @@ impl<'a> KdlLayoutParser<'a> {
                 kdl_node.span().offset(),
                 kdl_node.span().len(),
             ))
-        } else if self.is_a_reserved_word(&name) {
+        } else if self.is_a_reserved_word(&name) || name == "env" {
+            // env is not a reserved word because it is also a config node at the root of a layout
             Err(ConfigError::new_layout_kdl_error(
                 format!("Node name '{}' is a reserved word.", name),
                 kdl_node.span().offset(),
@@ -298,6 +307,11 @@ impl<'a> KdlLayoutParser<'a> {
         let _allow_exec_host_cmd =
             kdl_get_bool_property_or_child_value_with_error!(plugin_block, "_allow_exec_host_cmd")
                 .unwrap_or(false);
+        let _watch = kdl_get_bool_property_or_child_value_with_error!(plugin_block, "_watch")
+            .unwrap_or(false);
+        let _watch_clear_data =
+            kdl_get_bool_property_or_child_value_with_error!(plugin_block, "_watch_clear_data")
+                .unwrap_or(false);
         let string_url =
             kdl_get_string_property_or_child_value_with_error!(plugin_block, "location").ok_or(
                 ConfigError::new_layout_kdl_error(
@@ -324,6 +338,8 @@ impl<'a> KdlLayoutParser<'a> {
         let configuration = KdlLayoutParser::parse_plugin_user_configuration(&plugin_block)?;
         Ok(Some(Run::Plugin(RunPlugin {
             _allow_exec_host_cmd,
+            _watch,
+            _watch_clear_data,
             location,
             configuration,
         })))
@@ -393,6 +409,41 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(&self, kdl_node: &KdlNode) -> Result<BTreeMap<String, String>, ConfigError> {
+        let mut env = BTreeMap::new();
+        if let Some(kdl_env) = kdl_get_child!(kdl_node, "env") {
+            if !kdl_env.entries().is_empty() {
+                return Err(kdl_parsing_error!(
+                    format!("env variables should be placed inside the env block (eg. env {{ RUST_LOG \"debug\"; }})"),
+                    kdl_env
+                ));
+            }
+            for env_variable in kdl_children_nodes!(kdl_env).unwrap_or(&[]) {
+                let name = kdl_name!(env_variable);
+                let value = kdl_first_entry_as_string!(env_variable)
+                    .map(|s| s.to_string())
+                    .or_else(|| kdl_first_entry_as_i64!(env_variable).map(|i| i.to_string()))
+                    .or_else(|| kdl_first_entry_as_bool!(env_variable).map(|b| b.to_string()))
+                    .ok_or(kdl_parsing_error!(
+                        format!("env variable {} must have a value", name),
+                        env_variable
+                    ))?;
+                env.insert(name.to_owned(), value);
+            }
+        }
+        Ok(env)
+    }
+    fn parse_snapshot_file(&self, kdl_node: &KdlNode) -> Result<Option<PathBuf>, ConfigError> {
+        // snapshot files are relative to the layout, they're read by the server when the pane is
+        // created rather than here because they can be quite large
+        let snapshot_file =
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "snapshot_file");
+        Ok(snapshot_file.and_then(|snapshot_file| {
+            self.file_name
+                .parent()
+                .map(|parent_folder| parent_folder.join(snapshot_file))
+        }))
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -446,6 +497,7 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                env: BTreeMap::new(),
             }))),
             (None, Some(edit), Some(cwd)) => {
                 Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
@@ -558,7 +610,9 @@ impl<'a> KdlLayoutParser<'a> {
                 std::fs::read_to_string(parent_folder.join(contents_file)).ok()
             })
         });
-        Ok(TiledPaneLayout {
+        let pane_initial_snapshot_file = self.parse_snapshot_file(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
+        let mut pane_layout = TiledPaneLayout {
             borderless: borderless.unwrap_or_default(),
             focus,
             name,
@@ -571,8 +625,11 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            pane_initial_snapshot_file,
             ..Default::default()
-        })
+        };
+        pane_layout.add_env_to_layout(&env);
+        Ok(pane_layout)
     }
     fn parse_floating_pane_node(
         &self,
@@ -583,6 +640,7 @@ impl<'a> KdlLayoutParser<'a> {
         let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
@@ -595,15 +653,20 @@ impl<'a> KdlLayoutParser<'a> {
                 std::fs::read_to_string(parent_folder.join(contents_file)).ok()
             })
         });
+        let pane_initial_snapshot_file = self.parse_snapshot_file(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         Ok(FloatingPaneLayout {
             name,
             height,
             width,
             x,
             y,
+            pinned,
             run,
             focus,
             pane_initial_contents,
+            pane_initial_snapshot_file,
+            env,
             ..Default::default()
         })
     }
@@ -742,6 +805,11 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
+                // variables set on the pane take precedence over those of the template, and
+                // are added to any panes nested inside it
+                let env = self.parse_env(kdl_node)?;
+                pane_template.env.extend(env.clone());
+                pane_template.add_env_to_layout(&env);
                 if let Some(index_of_children) = pane_template.external_children_index {
                     pane_template.children.insert(
                         index_of_children,
@@ -825,10 +893,12 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                pane_template.env.extend(self.parse_env(kdl_node)?);
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+                let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
                 // let mut floating_pane = FloatingPaneLayout::from(&pane_template);
                 if let Some(height) = height {
                     pane_template.height = Some(height);
@@ -842,6 +912,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(x) = x {
                     pane_template.x = Some(x);
                 }
+                if let Some(pinned) = pinned {
+                    pane_template.pinned = Some(pinned);
+                }
                 Ok(pane_template)
             },
             PaneOrFloatingPane::Either(mut pane_template) => {
@@ -876,10 +949,12 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                pane_template.env.extend(self.parse_env(kdl_node)?);
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+                let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
                 let mut floating_pane = FloatingPaneLayout::from(&pane_template);
                 if let Some(height) = height {
                     floating_pane.height = Some(height);
@@ -893,6 +968,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(x) = x {
                     floating_pane.x = Some(x);
                 }
+                if let Some(pinned) = pinned {
+                    floating_pane.pinned = Some(pinned);
+                }
                 Ok(floating_pane)
             },
         }
@@ -932,6 +1010,7 @@ impl<'a> KdlLayoutParser<'a> {
         let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
@@ -940,7 +1019,7 @@ impl<'a> KdlLayoutParser<'a> {
             || is_expanded_in_stack.is_some()
             || has_children_nodes;
         let has_floating_pane_properties =
-            height.is_some() || width.is_some() || x.is_some() || y.is_some();
+            height.is_some() || width.is_some() || x.is_some() || y.is_some() || pinned.is_some();
         if has_pane_properties || has_floating_pane_properties {
             Ok(false)
         } else {
@@ -969,6 +1048,7 @@ impl<'a> KdlLayoutParser<'a> {
         let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
@@ -977,7 +1057,7 @@ impl<'a> KdlLayoutParser<'a> {
             || is_expanded_in_stack.is_some()
             || has_children_nodes;
         let has_floating_pane_properties =
-            height.is_some() || width.is_some() || x.is_some() || y.is_some();
+            height.is_some() || width.is_some() || x.is_some() || y.is_some() || pinned.is_some();
 
         if has_pane_properties && has_floating_pane_properties {
             let mut pane_properties = vec![];
@@ -1012,6 +1092,9 @@ impl<'a> KdlLayoutParser<'a> {
             if y.is_some() {
                 floating_pane_properties.push("y");
             }
+            if pinned.is_some() {
+                floating_pane_properties.push("pinned");
+            }
             Err(ConfigError::new_layout_kdl_error(
                 format!(
                     "A pane_template cannot have both pane ({}) and floating pane ({}) properties",
@@ -1039,6 +1122,7 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
         let can_be_either_floating_or_tiled =
@@ -1051,6 +1135,7 @@ impl<'a> KdlLayoutParser<'a> {
                     PaneOrFloatingPane::Either(TiledPaneLayout {
                         focus,
                         run,
+                        env,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1063,6 +1148,7 @@ impl<'a> KdlLayoutParser<'a> {
             let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
             let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
             let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
+            let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
             self.pane_templates.insert(
                 template_name,
                 (
@@ -1073,6 +1159,8 @@ impl<'a> KdlLayoutParser<'a> {
                         width,
                         x,
                         y,
+                        pinned,
+                        env,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1098,23 +1186,22 @@ impl<'a> KdlLayoutParser<'a> {
                 None => (None, vec![]),
             };
             self.assert_no_mixed_children_and_properties(kdl_node)?;
+            let mut pane_template = TiledPaneLayout {
+                borderless: borderless.unwrap_or_default(),
+                focus,
+                split_size,
+                run,
+                children_split_direction,
+                external_children_index,
+                children: pane_parts,
+                children_are_stacked,
+                is_expanded_in_stack,
+                ..Default::default()
+            };
+            pane_template.add_env_to_layout(&env);
             self.pane_templates.insert(
                 template_name,
-                (
-                    PaneOrFloatingPane::Pane(TiledPaneLayout {
-                        borderless: borderless.unwrap_or_default(),
-                        focus,
-                        split_size,
-                        run,
-                        children_split_direction,
-                        external_children_index,
-                        children: pane_parts,
-                        children_are_stacked,
-                        is_expanded_in_stack,
-                        ..Default::default()
-                    }),
-                    kdl_node.clone(),
-                ),
+                (PaneOrFloatingPane::Pane(pane_template), kdl_node.clone()),
             );
         }
 
@@ -1162,6 +1249,11 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
             pane_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        let tab_env = self.parse_env(kdl_node)?;
+        pane_layout.add_env_to_layout(&tab_env);
+        for floating_pane in child_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
     fn parse_child_pane_nodes_for_tab(
@@ -1186,6 +1278,8 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" {
+                // parsed separately and applied to all the panes in the tab
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1568,6 +1662,11 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = self.cwd_prefix(tab_cwd.as_ref())? {
             tab_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        let tab_env = self.parse_env(kdl_node)?;
+        tab_layout.add_env_to_layout(&tab_env);
+        for floating_pane in tab_template_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1747,7 +1846,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let child_name = kdl_name!(child);
                 if child_name == "pane" {
                     self.get_pane_template_dependencies(child, all_dependencies)?;
-                } else if !self.is_a_reserved_word(child_name) {
+                } else if child_name != "env" && !self.is_a_reserved_word(child_name) {
                     all_dependencies.insert(child_name);
                     self.get_pane_template_dependencies(child, all_dependencies)?;
                 }
@@ -1,17 +1,23 @@
 mod kdl_layout_parser;
+use crate::cli::ScriptAction;
 use crate::data::{
-    Direction, InputMode, Key, Palette, PaletteColor, PaneInfo, PaneManifest, PermissionType,
-    Resize, SessionInfo, TabInfo,
+    Direction, FloatingPaneCoordinates, InputMode, Key, Palette, PaletteColor, PaneInfo,
+    PaneManifest, PermissionType, Resize, SessionInfo, TabInfo,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::keybinds::Keybinds;
-use crate::input::layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation};
+use crate::input::layout::{
+    Layout, PercentOrFixed, PluginUserConfiguration, RunPlugin, RunPluginLocation,
+};
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::{PluginConfig, PluginTag, PluginType, PluginsConfig};
+use crate::input::script::{Script, ScriptCondition, ScriptStep};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
+use crate::input::triggers::{Trigger, Triggers};
+use clap::Parser;
 use kdl_layout_parser::KdlLayoutParser;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use strum::IntoEnumIterator;
@@ -22,8 +28,11 @@ use kdl::{KdlDocument, KdlEntry, KdlNode};
 
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::input::actions::{Action, SearchDirection, SearchOption};
+use crate::input::actions::{
+    Action, CopyModeMotion, CopyModeSelection, PaneCondition, SearchDirection, SearchOption,
+};
 use crate::input::command::RunCommandAction;
 
 #[macro_export]
@@ -46,6 +55,9 @@ macro_rules! parse_kdl_action_arguments {
                 "ScrollDown" => Ok(Action::ScrollDown),
                 "ScrollToBottom" => Ok(Action::ScrollToBottom),
                 "ScrollToTop" => Ok(Action::ScrollToTop),
+                "ScrollToPreviousPrompt" => Ok(Action::ScrollToPreviousPrompt),
+                "ScrollToNextPrompt" => Ok(Action::ScrollToNextPrompt),
+                "CopyLastCommandOutput" => Ok(Action::CopyLastCommandOutput),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
@@ -55,8 +67,15 @@ macro_rules! parse_kdl_action_arguments {
                 "ToggleActiveSyncTab" => Ok(Action::ToggleActiveSyncTab),
                 "TogglePaneEmbedOrFloating" => Ok(Action::TogglePaneEmbedOrFloating),
                 "ToggleFloatingPanes" => Ok(Action::ToggleFloatingPanes),
+                "TogglePanePinned" => Ok(Action::TogglePanePinned),
+                "TogglePaneInGroup" => Ok(Action::TogglePaneInGroup),
+                "ClearPaneGroup" => Ok(Action::ClearPaneGroup),
+                "TogglePaneGroupBroadcast" => Ok(Action::TogglePaneGroupBroadcast),
+                "ClosePaneGroup" => Ok(Action::ClosePaneGroup),
+                "TogglePaneGroupEmbedOrFloating" => Ok(Action::TogglePaneGroupEmbedOrFloating),
                 "CloseFocus" => Ok(Action::CloseFocus),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
+                "UndoLayoutChange" => Ok(Action::UndoLayoutChange),
                 "NoOp" => Ok(Action::NoOp),
                 "GoToNextTab" => Ok(Action::GoToNextTab),
                 "GoToPreviousTab" => Ok(Action::GoToPreviousTab),
@@ -354,6 +373,21 @@ pub fn kdl_child_string_value_for_entry<'a>(
         .and_then(|cwd_value| cwd_value.value().as_string())
 }
 
+pub fn kdl_child_percent_or_fixed_value_for_entry<'a>(
+    command_metadata: &'a KdlDocument,
+    entry_name: &'a str,
+) -> Option<PercentOrFixed> {
+    let value = command_metadata
+        .get(entry_name)
+        .and_then(|node| node.entries().iter().next())
+        .map(|entry| entry.value())?;
+    match (value.as_string(), value.as_i64()) {
+        (Some(string_value), _) => PercentOrFixed::from_str(string_value).ok(),
+        (_, Some(int_value)) if int_value >= 0 => Some(PercentOrFixed::Fixed(int_value as usize)),
+        _ => None,
+    }
+}
+
 pub fn kdl_child_bool_value_for_entry<'a>(
     command_metadata: &'a KdlDocument,
     entry_name: &'a str,
@@ -385,6 +419,16 @@ impl Action {
                 })? as u32;
                 Ok(Action::GoToTab(tab_index))
             },
+            "MoveTabToIndex" => {
+                let tab_index = *bytes.first().ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        "Missing tab index".into(),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as usize;
+                Ok(Action::MoveTabToIndex(tab_index))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 "Failed to parse action".into(),
                 action_node.span().offset(),
@@ -443,6 +487,26 @@ impl Action {
                 })?;
                 Ok(Action::MoveFocus(direction))
             },
+            "MoveTab" => {
+                let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid direction: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::MoveTab(direction))
+            },
+            "SwapPaneWith" => {
+                let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid direction: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::SwapPaneWith(direction))
+            },
             "MoveFocusOrTab" => {
                 let direction = Direction::from_str(string.as_str()).map_err(|_| {
                     ConfigError::new_kdl_error(
@@ -494,6 +558,26 @@ impl Action {
                 })?;
                 Ok(Action::SearchToggleOption(toggle_option))
             },
+            "CopyModeMove" => {
+                let motion = CopyModeMotion::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid copy mode motion: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::CopyModeMove(motion))
+            },
+            "CopyModeToggleSelection" => {
+                let selection = CopyModeSelection::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid copy mode selection: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::CopyModeToggleSelection(selection))
+            },
             "Search" => {
                 let search_direction =
                     SearchDirection::from_str(string.as_str()).map_err(|_| {
@@ -506,6 +590,7 @@ impl Action {
                 Ok(Action::Search(search_direction))
             },
             "RenameSession" => Ok(Action::RenameSession(string)),
+            "RenamePaneGroup" => Ok(Action::RenamePaneGroup(string)),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -658,6 +743,15 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ScrollToTop" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ScrollToPreviousPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollToNextPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "CopyLastCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "PageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -685,10 +779,31 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ToggleFloatingPanes" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "TogglePanePinned" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneInGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClearPaneGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneGroupBroadcast" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClosePaneGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneGroupEmbedOrFloating" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "UndoLayoutChange" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "NoOp" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "GoToNextTab" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "GoToPreviousTab" => {
@@ -743,6 +858,16 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "MoveTab" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SwapPaneWith" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MovePane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -851,6 +976,9 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 }
             },
             "GoToTab" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
+            "MoveTabToIndex" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TabNameInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -862,6 +990,16 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "CopyModeMove" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "CopyModeToggleSelection" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "Run" => {
                 let arguments = action_arguments.iter().copied();
                 let mut args = kdl_arguments_that_are_strings(arguments)?;
@@ -903,9 +1041,24 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    env: BTreeMap::new(),
                 };
                 if floating {
-                    Ok(Action::NewFloatingPane(Some(run_command_action), name))
+                    let coordinate = |entry_name| {
+                        command_metadata.and_then(|c_m| {
+                            kdl_child_percent_or_fixed_value_for_entry(c_m, entry_name)
+                        })
+                    };
+                    Ok(Action::NewFloatingPane(
+                        Some(run_command_action),
+                        name,
+                        FloatingPaneCoordinates::new(
+                            coordinate("x"),
+                            coordinate("y"),
+                            coordinate("width"),
+                            coordinate("height"),
+                        ),
+                    ))
                 } else if in_place {
                     Ok(Action::NewInPlacePane(Some(run_command_action), name))
                 } else {
@@ -947,6 +1100,8 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let run_plugin = RunPlugin {
                     location,
                     _allow_exec_host_cmd: false,
+                    _watch: false,
+                    _watch_clear_data: false,
                     configuration,
                 };
                 Ok(Action::LaunchOrFocusPlugin(
@@ -985,6 +1140,8 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let run_plugin = RunPlugin {
                     location,
                     _allow_exec_host_cmd: false,
+                    _watch: false,
+                    _watch_clear_data: false,
                     configuration,
                 };
                 Ok(Action::LaunchPlugin(
@@ -999,11 +1156,17 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "BreakPane" => Ok(Action::BreakPane),
             "BreakPaneRight" => Ok(Action::BreakPaneRight),
             "BreakPaneLeft" => Ok(Action::BreakPaneLeft),
+            "BreakPaneGroup" => Ok(Action::BreakPaneGroup),
             "RenameSession" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
                 kdl_action
             ),
+            "RenamePaneGroup" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name).into(),
                 kdl_action.span().offset(),
@@ -1781,6 +1944,10 @@ impl Config {
             let config_env = EnvironmentVariables::from_kdl(&env_config)?;
             config.env = config.env.merge(config_env);
         }
+        if let Some(kdl_triggers) = kdl_config.get("triggers") {
+            let config_triggers = Triggers::from_kdl(kdl_triggers, &config.options)?;
+            config.triggers = config.triggers.merge(config_triggers);
+        }
         Ok(config)
     }
 }
@@ -1836,6 +2003,248 @@ impl UiConfig {
     }
 }
 
+impl Triggers {
+    pub fn from_kdl(kdl_triggers: &KdlNode, config_options: &Options) -> Result<Self, ConfigError> {
+        let mut triggers = vec![];
+        for trigger_block in kdl_children_nodes_or_error!(kdl_triggers, "no triggers found") {
+            if kdl_name!(trigger_block) != "trigger" {
+                return Err(ConfigError::new_kdl_error(
+                    format!(
+                        "Unknown trigger instruction: '{}'",
+                        kdl_name!(trigger_block)
+                    ),
+                    trigger_block.span().offset(),
+                    trigger_block.span().len(),
+                ));
+            }
+            let pattern =
+                kdl_first_entry_as_string!(trigger_block).ok_or(ConfigError::new_kdl_error(
+                    "A trigger must have a pattern to match, eg. trigger \"FAILED\"".into(),
+                    trigger_block.span().offset(),
+                    trigger_block.span().len(),
+                ))?;
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(ConfigError::new_kdl_error(
+                    format!("Invalid trigger pattern: {}", e),
+                    trigger_block.span().offset(),
+                    trigger_block.span().len(),
+                ));
+            }
+            let mut trigger = Trigger {
+                pattern: pattern.to_owned(),
+                ..Default::default()
+            };
+            for kdl_action in
+                kdl_children_nodes_or_error!(trigger_block, "no actions found for trigger")
+            {
+                match kdl_name!(kdl_action) {
+                    "focus_pane" => {
+                        trigger.focus_pane = kdl_first_entry_as_bool!(kdl_action).unwrap_or(true);
+                    },
+                    "rename_tab" => {
+                        trigger.rename_tab =
+                            kdl_first_entry_as_string!(kdl_action).map(|s| s.to_owned());
+                    },
+                    "pipe" => {
+                        trigger.pipe = kdl_first_entry_as_string!(kdl_action).map(|s| s.to_owned());
+                    },
+                    _ => trigger
+                        .actions
+                        .push(Action::try_from((kdl_action, config_options))?),
+                }
+            }
+            triggers.push(trigger);
+        }
+        Ok(Triggers(triggers))
+    }
+}
+
+impl Script {
+    pub fn from_kdl(
+        raw_script: &str,
+        file_name: String,
+        config: Option<Config>,
+    ) -> Result<Self, ConfigError> {
+        let parse_script = || -> Result<Self, ConfigError> {
+            let kdl_script: KdlDocument = raw_script.parse()?;
+            Ok(Script(script_steps_from_kdl(kdl_script.nodes(), &config)?))
+        };
+        parse_script().map_err(|e| match e {
+            ConfigError::KdlError(kdl_error) => {
+                ConfigError::KdlError(kdl_error.add_src(file_name, String::from(raw_script)))
+            },
+            ConfigError::KdlDeserializationError(kdl_error) => {
+                kdl_layout_error(kdl_error, file_name, raw_script)
+            },
+            e => e,
+        })
+    }
+}
+
+fn script_steps_from_kdl(
+    kdl_steps: &[KdlNode],
+    config: &Option<Config>,
+) -> Result<Vec<ScriptStep>, ConfigError> {
+    let mut steps = vec![];
+    let mut kdl_steps = kdl_steps.iter().peekable();
+    while let Some(kdl_step) = kdl_steps.next() {
+        let arguments = script_step_arguments(kdl_step)?;
+        let step = match (kdl_name!(kdl_step), arguments.as_slice()) {
+            ("action", [_, ..]) => {
+                let cli_action = ScriptAction::try_parse_from(&arguments)
+                    .map_err(|e| kdl_parsing_error!(format!("Invalid action: {}", e), kdl_step))?
+                    .action;
+                let get_current_dir =
+                    || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+                let actions =
+                    Action::actions_from_cli(cli_action, Box::new(get_current_dir), config.clone())
+                        .map_err(|e| kdl_parsing_error!(e, kdl_step))?;
+                ScriptStep::Actions(actions)
+            },
+            ("wait_for_output", [pane, pattern]) => ScriptStep::WaitForPane {
+                pane: pane.to_owned(),
+                condition: PaneCondition::OutputMatches(script_pattern(pattern, kdl_step)?),
+                timeout: script_timeout(kdl_step)?,
+            },
+            ("wait_for_exit", [pane]) => ScriptStep::WaitForPane {
+                pane: pane.to_owned(),
+                condition: PaneCondition::Exited,
+                timeout: script_timeout(kdl_step)?,
+            },
+            ("sleep", [duration]) => match script_duration(duration) {
+                Some(duration) => ScriptStep::Sleep(duration),
+                None => {
+                    return Err(kdl_parsing_error!(
+                        format!("Invalid duration: {}", duration),
+                        kdl_step
+                    ))
+                },
+            },
+            ("if", [pane]) => {
+                let condition = match (kdl_step.get("exit_status"), kdl_step.get("output")) {
+                    (Some(exit_status), None) => exit_status
+                        .value()
+                        .as_i64()
+                        .map(|exit_status| ScriptCondition::ExitStatus(exit_status as i32))
+                        .ok_or(kdl_parsing_error!(
+                            "exit_status must be a number".into(),
+                            exit_status
+                        ))?,
+                    (None, Some(output)) => match output.value().as_string() {
+                        Some(pattern) => {
+                            ScriptCondition::OutputMatches(script_pattern(pattern, kdl_step)?)
+                        },
+                        None => {
+                            return Err(kdl_parsing_error!(
+                                "output must be a string".into(),
+                                output
+                            ))
+                        },
+                    },
+                    _ => {
+                        return Err(kdl_parsing_error!(
+                            "An if must have either an exit_status or an output to check, eg. if \"db\" exit_status=0 { .. }".into(),
+                            kdl_step
+                        ))
+                    },
+                };
+                let then =
+                    script_steps_from_kdl(kdl_children_nodes!(kdl_step).unwrap_or(&[]), config)?;
+                let otherwise = match kdl_steps.next_if(|s| kdl_name!(s) == "else") {
+                    Some(kdl_else) => {
+                        script_steps_from_kdl(kdl_children_nodes!(kdl_else).unwrap_or(&[]), config)?
+                    },
+                    None => vec![],
+                };
+                ScriptStep::If {
+                    pane: pane.to_owned(),
+                    condition,
+                    then,
+                    otherwise,
+                }
+            },
+            ("else", _) => {
+                return Err(kdl_parsing_error!(
+                    "An else must come right after an if".into(),
+                    kdl_step
+                ))
+            },
+            (step_name, _) => {
+                return Err(kdl_parsing_error!(
+                    format!(
+                        "Unknown or malformed script step: '{}', expected one of: action, wait_for_output, wait_for_exit, sleep, if",
+                        step_name
+                    ),
+                    kdl_step
+                ))
+            },
+        };
+        steps.push(step);
+    }
+    Ok(steps)
+}
+
+// the positional arguments of a script step, numbers are taken as their string representation so
+// that eg. `action "move-tab-to-index" 2` works as it would on the command line
+fn script_step_arguments(kdl_step: &KdlNode) -> Result<Vec<String>, ConfigError> {
+    kdl_step
+        .entries()
+        .iter()
+        .filter(|e| e.name().is_none())
+        .map(|e| match e.value() {
+            kdl::KdlValue::String(s) | kdl::KdlValue::RawString(s) => Ok(s.to_owned()),
+            kdl::KdlValue::Base10(i) => Ok(i.to_string()),
+            kdl::KdlValue::Base10Float(f) => Ok(f.to_string()),
+            _ => Err(kdl_parsing_error!(
+                "Script arguments must be strings or numbers".into(),
+                e
+            )),
+        })
+        .collect()
+}
+
+fn script_pattern(pattern: &str, kdl_step: &KdlNode) -> Result<String, ConfigError> {
+    match regex::Regex::new(pattern) {
+        Ok(_) => Ok(pattern.to_owned()),
+        Err(e) => Err(kdl_parsing_error!(
+            format!("Invalid pattern: {}", e),
+            kdl_step
+        )),
+    }
+}
+
+fn script_duration(seconds: &str) -> Option<Duration> {
+    seconds.parse::<f64>().ok().and_then(seconds_to_duration)
+}
+
+fn seconds_to_duration(seconds: f64) -> Option<Duration> {
+    if seconds.is_finite() && seconds >= 0.0 {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+fn script_timeout(kdl_step: &KdlNode) -> Result<Option<Duration>, ConfigError> {
+    match kdl_step.get("timeout") {
+        Some(timeout) => {
+            let seconds = match timeout.value() {
+                kdl::KdlValue::Base10(i) => Some(*i as f64),
+                kdl::KdlValue::Base10Float(f) => Some(*f),
+                _ => None,
+            };
+            seconds
+                .and_then(seconds_to_duration)
+                .map(Some)
+                .ok_or(kdl_parsing_error!(
+                    "timeout must be a number of seconds".into(),
+                    timeout
+                ))
+        },
+        None => Ok(None),
+    }
+}
+
 impl Themes {
     pub fn from_kdl(themes_from_kdl: &KdlNode) -> Result<Self, ConfigError> {
         let mut themes: HashMap<String, Theme> = HashMap::new();
@@ -2246,6 +2655,17 @@ impl PaneInfo {
         let is_focused = bool_node!("is_focused");
         let is_fullscreen = bool_node!("is_fullscreen");
         let is_floating = bool_node!("is_floating");
+        // not present in session info serialized by older versions
+        let is_pinned = kdl_document
+            .get("is_pinned")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_bool())
+            .unwrap_or(false);
+        let is_in_group = kdl_document
+            .get("is_in_group")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_bool())
+            .unwrap_or(false);
         let is_suppressed = bool_node!("is_suppressed");
         let title = string_node!("title");
         let exited = bool_node!("exited");
@@ -2276,6 +2696,10 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let cwd = optional_string_node!("cwd").map(PathBuf::from);
+        let running_command = optional_string_node!("running_command");
+        let last_command_exit_code = optional_int_node!("last_command_exit_code", i32);
+        let output_log_path = optional_string_node!("output_log_path").map(PathBuf::from);
 
         let pane_info = PaneInfo {
             id,
@@ -2283,6 +2707,8 @@ impl PaneInfo {
             is_focused,
             is_fullscreen,
             is_floating,
+            is_pinned,
+            is_in_group,
             is_suppressed,
             title,
             exited,
@@ -2300,6 +2726,10 @@ impl PaneInfo {
             terminal_command,
             plugin_url,
             is_selectable,
+            cwd,
+            running_command,
+            last_command_exit_code,
+            output_log_path,
         };
         Ok((tab_position, pane_info))
     }
@@ -2332,6 +2762,8 @@ impl PaneInfo {
         bool_node!("is_focused", self.is_focused);
         bool_node!("is_fullscreen", self.is_fullscreen);
         bool_node!("is_floating", self.is_floating);
+        bool_node!("is_pinned", self.is_pinned);
+        bool_node!("is_in_group", self.is_in_group);
         bool_node!("is_suppressed", self.is_suppressed);
         string_node!("title", self.title.to_string());
         bool_node!("exited", self.exited);
@@ -2360,6 +2792,18 @@ impl PaneInfo {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if let Some(cwd) = &self.cwd {
+            string_node!("cwd", cwd.display().to_string());
+        }
+        if let Some(running_command) = &self.running_command {
+            string_node!("running_command", running_command.to_string());
+        }
+        if let Some(last_command_exit_code) = self.last_command_exit_code {
+            int_node!("last_command_exit_code", last_command_exit_code);
+        }
+        if let Some(output_log_path) = &self.output_log_path {
+            string_node!("output_log_path", output_log_path.display().to_string());
+        }
         kdl_doucment
     }
 }
@@ -2430,6 +2874,8 @@ fn serialize_and_deserialize_session_info_with_data() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
+            is_in_group: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
@@ -2447,6 +2893,10 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            cwd: Some(PathBuf::from("/home/aram/code")),
+            running_command: Some("vim foo.rs".to_owned()),
+            last_command_exit_code: Some(1),
+            output_log_path: Some(PathBuf::from("/tmp/pane-1.log")),
         },
         PaneInfo {
             id: 1,
@@ -2454,6 +2904,8 @@ fn serialize_and_deserialize_session_info_with_data() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
+            is_in_group: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
@@ -2471,6 +2923,10 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            cwd: None,
+            running_command: None,
+            last_command_exit_code: None,
+            output_log_path: None,
         },
     ];
     let mut panes = HashMap::new();
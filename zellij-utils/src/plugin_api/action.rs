@@ -1,23 +1,31 @@
 pub use super::generated_api::api::{
     action::{
         action::OptionalPayload, Action as ProtobufAction, ActionName as ProtobufActionName,
-        DumpScreenPayload, EditFilePayload, GoToTabNamePayload, IdAndName,
-        LaunchOrFocusPluginPayload, MovePanePayload, NameAndValue as ProtobufNameAndValue,
-        NewFloatingPanePayload, NewPanePayload, NewPluginPanePayload, NewTiledPanePayload,
-        PaneIdAndShouldFloat, PluginConfiguration as ProtobufPluginConfiguration,
-        Position as ProtobufPosition, RunCommandAction as ProtobufRunCommandAction,
-        ScrollAtPayload, SearchDirection as ProtobufSearchDirection,
-        SearchOption as ProtobufSearchOption, SwitchToModePayload, WriteCharsPayload, WritePayload,
+        ChangeFloatingPaneCoordinatesPayload, CopyModeMotion as ProtobufCopyModeMotion,
+        CopyModeSelection as ProtobufCopyModeSelection, DumpScreenPayload, EditFilePayload,
+        EnvVariable as ProtobufEnvVariable,
+        FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, GoToTabNamePayload, IdAndName,
+        LaunchOrFocusPluginPayload, MovePanePayload, MovePaneToTabPayload,
+        NameAndValue as ProtobufNameAndValue, NewFloatingPanePayload, NewPanePayload,
+        NewPluginPanePayload, NewTiledPanePayload, PaneIdAndShouldFloat,
+        PercentOrFixed as ProtobufPercentOrFixed, PipePaneToFilePayload,
+        PluginConfiguration as ProtobufPluginConfiguration, Position as ProtobufPosition,
+        RunCommandAction as ProtobufRunCommandAction, ScrollAtPayload,
+        SearchDirection as ProtobufSearchDirection, SearchOption as ProtobufSearchOption,
+        SetPanePinnedPayload, SwapPanesPayload, SwitchToModePayload, WriteCharsPayload,
+        WritePayload,
     },
     input_mode::InputMode as ProtobufInputMode,
     resize::{Resize as ProtobufResize, ResizeDirection as ProtobufResizeDirection},
 };
-use crate::data::{Direction, InputMode, ResizeStrategy};
+use crate::data::{
+    Direction, FloatingPaneCoordinates, InputMode, PaneId, ResizeStrategy, TabIndexOrName,
+};
 use crate::errors::prelude::*;
 use crate::input::actions::Action;
-use crate::input::actions::{SearchDirection, SearchOption};
+use crate::input::actions::{CopyModeMotion, CopyModeSelection, SearchDirection, SearchOption};
 use crate::input::command::RunCommandAction;
-use crate::input::layout::{PluginUserConfiguration, RunPlugin, RunPluginLocation};
+use crate::input::layout::{PercentOrFixed, PluginUserConfiguration, RunPlugin, RunPluginLocation};
 use crate::position::Position;
 use url::Url;
 
@@ -25,6 +33,169 @@ use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
 
+pub(crate) fn swap_panes_payload(
+    first_pane_id: PaneId,
+    second_pane_id: PaneId,
+) -> SwapPanesPayload {
+    let to_parts = |pane_id| match pane_id {
+        PaneId::Terminal(id) => (id, false),
+        PaneId::Plugin(id) => (id, true),
+    };
+    let (first_pane_id, first_pane_is_plugin) = to_parts(first_pane_id);
+    let (second_pane_id, second_pane_is_plugin) = to_parts(second_pane_id);
+    SwapPanesPayload {
+        first_pane_id,
+        first_pane_is_plugin,
+        second_pane_id,
+        second_pane_is_plugin,
+    }
+}
+
+pub(crate) fn swap_panes_from_payload(payload: SwapPanesPayload) -> (PaneId, PaneId) {
+    let to_pane_id = |id, is_plugin| {
+        if is_plugin {
+            PaneId::Plugin(id)
+        } else {
+            PaneId::Terminal(id)
+        }
+    };
+    (
+        to_pane_id(payload.first_pane_id, payload.first_pane_is_plugin),
+        to_pane_id(payload.second_pane_id, payload.second_pane_is_plugin),
+    )
+}
+
+pub(crate) fn floating_pane_coordinates_to_protobuf(
+    coordinates: FloatingPaneCoordinates,
+) -> ProtobufFloatingPaneCoordinates {
+    let to_protobuf = |value: PercentOrFixed| match value {
+        PercentOrFixed::Percent(percent) => ProtobufPercentOrFixed {
+            is_percent: true,
+            value: percent as u32,
+        },
+        PercentOrFixed::Fixed(fixed) => ProtobufPercentOrFixed {
+            is_percent: false,
+            value: fixed as u32,
+        },
+    };
+    ProtobufFloatingPaneCoordinates {
+        x: coordinates.x.map(to_protobuf),
+        y: coordinates.y.map(to_protobuf),
+        width: coordinates.width.map(to_protobuf),
+        height: coordinates.height.map(to_protobuf),
+    }
+}
+
+pub(crate) fn floating_pane_coordinates_from_protobuf(
+    coordinates: ProtobufFloatingPaneCoordinates,
+) -> FloatingPaneCoordinates {
+    let from_protobuf = |value: ProtobufPercentOrFixed| {
+        if value.is_percent {
+            PercentOrFixed::Percent(value.value as usize)
+        } else {
+            PercentOrFixed::Fixed(value.value as usize)
+        }
+    };
+    FloatingPaneCoordinates {
+        x: coordinates.x.map(from_protobuf),
+        y: coordinates.y.map(from_protobuf),
+        width: coordinates.width.map(from_protobuf),
+        height: coordinates.height.map(from_protobuf),
+    }
+}
+
+pub(crate) fn change_floating_pane_coordinates_payload(
+    pane_id: PaneId,
+    coordinates: FloatingPaneCoordinates,
+) -> ChangeFloatingPaneCoordinatesPayload {
+    let (pane_id, is_plugin) = match pane_id {
+        PaneId::Terminal(id) => (id, false),
+        PaneId::Plugin(id) => (id, true),
+    };
+    ChangeFloatingPaneCoordinatesPayload {
+        pane_id,
+        is_plugin,
+        coordinates: Some(floating_pane_coordinates_to_protobuf(coordinates)),
+    }
+}
+
+pub(crate) fn change_floating_pane_coordinates_from_payload(
+    payload: ChangeFloatingPaneCoordinatesPayload,
+) -> Result<(PaneId, FloatingPaneCoordinates), &'static str> {
+    let pane_id = if payload.is_plugin {
+        PaneId::Plugin(payload.pane_id)
+    } else {
+        PaneId::Terminal(payload.pane_id)
+    };
+    let coordinates = payload
+        .coordinates
+        .map(floating_pane_coordinates_from_protobuf)
+        .ok_or("Missing coordinates for floating pane")?;
+    Ok((pane_id, coordinates))
+}
+
+pub(crate) fn set_pane_pinned_payload(
+    pane_id: PaneId,
+    should_be_pinned: bool,
+) -> SetPanePinnedPayload {
+    let (pane_id, is_plugin) = match pane_id {
+        PaneId::Terminal(id) => (id, false),
+        PaneId::Plugin(id) => (id, true),
+    };
+    SetPanePinnedPayload {
+        pane_id,
+        is_plugin,
+        should_be_pinned,
+    }
+}
+
+pub(crate) fn set_pane_pinned_from_payload(payload: SetPanePinnedPayload) -> (PaneId, bool) {
+    let pane_id = if payload.is_plugin {
+        PaneId::Plugin(payload.pane_id)
+    } else {
+        PaneId::Terminal(payload.pane_id)
+    };
+    (pane_id, payload.should_be_pinned)
+}
+
+pub(crate) fn move_pane_to_tab_payload(
+    pane_id: PaneId,
+    tab: TabIndexOrName,
+    should_float: bool,
+) -> MovePaneToTabPayload {
+    let (pane_id, is_plugin) = match pane_id {
+        PaneId::Terminal(id) => (id, false),
+        PaneId::Plugin(id) => (id, true),
+    };
+    let (tab_index, tab_name) = match tab {
+        TabIndexOrName::Index(index) => (Some(index), None),
+        TabIndexOrName::Name(name) => (None, Some(name)),
+    };
+    MovePaneToTabPayload {
+        pane_id,
+        is_plugin,
+        tab_index,
+        tab_name,
+        should_float,
+    }
+}
+
+pub(crate) fn move_pane_to_tab_from_payload(
+    payload: MovePaneToTabPayload,
+) -> Result<(PaneId, TabIndexOrName, bool), &'static str> {
+    let pane_id = if payload.is_plugin {
+        PaneId::Plugin(payload.pane_id)
+    } else {
+        PaneId::Terminal(payload.pane_id)
+    };
+    let tab = match (payload.tab_index, payload.tab_name) {
+        (Some(index), None) => TabIndexOrName::Index(index),
+        (None, Some(name)) => TabIndexOrName::Name(name),
+        _ => return Err("MovePaneToTab requires exactly one of tab_index or tab_name"),
+    };
+    Ok((pane_id, tab, payload.should_float))
+}
+
 impl TryFrom<ProtobufAction> for Action {
     type Error = &'static str;
     fn try_from(protobuf_action: ProtobufAction) -> Result<Self, &'static str> {
@@ -177,6 +348,24 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("ScrollToTop should not have a payload"),
                 None => Ok(Action::ScrollToTop),
             },
+            Some(ProtobufActionName::ScrollToPreviousPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToPreviousPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToPreviousPrompt),
+                }
+            },
+            Some(ProtobufActionName::ScrollToNextPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToNextPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToNextPrompt),
+                }
+            },
+            Some(ProtobufActionName::CopyLastCommandOutput) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("CopyLastCommandOutput should not have a payload"),
+                    None => Ok(Action::CopyLastCommandOutput),
+                }
+            },
             Some(ProtobufActionName::PageScrollUp) => match protobuf_action.optional_payload {
                 Some(_) => Err("PageScrollUp should not have a payload"),
                 None => Ok(Action::PageScrollUp),
@@ -233,6 +422,9 @@ impl TryFrom<ProtobufAction> for Action {
                         .and_then(|d| d.try_into().ok());
                     let should_float = payload.should_float;
                     let should_be_in_place = false;
+                    let coordinates = payload
+                        .coordinates
+                        .map(floating_pane_coordinates_from_protobuf);
                     Ok(Action::EditFile(
                         file_to_edit,
                         line_number,
@@ -240,18 +432,26 @@ impl TryFrom<ProtobufAction> for Action {
                         direction,
                         should_float,
                         should_be_in_place,
+                        coordinates,
                     ))
                 },
                 _ => Err("Wrong payload for Action::NewPane"),
             },
             Some(ProtobufActionName::NewFloatingPane) => match protobuf_action.optional_payload {
                 Some(OptionalPayload::NewFloatingPanePayload(payload)) => {
+                    let coordinates = payload
+                        .coordinates
+                        .map(floating_pane_coordinates_from_protobuf);
                     if let Some(payload) = payload.command {
                         let pane_name = payload.pane_name.clone();
                         let run_command_action: RunCommandAction = payload.try_into()?;
-                        Ok(Action::NewFloatingPane(Some(run_command_action), pane_name))
+                        Ok(Action::NewFloatingPane(
+                            Some(run_command_action),
+                            pane_name,
+                            coordinates,
+                        ))
                     } else {
-                        Ok(Action::NewFloatingPane(None, None))
+                        Ok(Action::NewFloatingPane(None, None, coordinates))
                     }
                 },
                 _ => Err("Wrong payload for Action::NewFloatingPane"),
@@ -288,6 +488,61 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::ToggleFloatingPanes),
                 }
             },
+            Some(ProtobufActionName::TogglePanePinned) => match protobuf_action.optional_payload {
+                Some(_) => Err("TogglePanePinned should not have a payload"),
+                None => Ok(Action::TogglePanePinned),
+            },
+            Some(ProtobufActionName::SetPanePinned) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SetPanePinnedPayload(payload)) => {
+                    let (pane_id, should_be_pinned) = set_pane_pinned_from_payload(payload);
+                    Ok(Action::SetPanePinned(pane_id, should_be_pinned))
+                },
+                _ => Err("Wrong payload for Action::SetPanePinned"),
+            },
+            Some(ProtobufActionName::TogglePaneInGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("TogglePaneInGroup should not have a payload"),
+                None => Ok(Action::TogglePaneInGroup),
+            },
+            Some(ProtobufActionName::ClearPaneGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("ClearPaneGroup should not have a payload"),
+                None => Ok(Action::ClearPaneGroup),
+            },
+            Some(ProtobufActionName::TogglePaneGroupBroadcast) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("TogglePaneGroupBroadcast should not have a payload"),
+                    None => Ok(Action::TogglePaneGroupBroadcast),
+                }
+            },
+            Some(ProtobufActionName::ClosePaneGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("ClosePaneGroup should not have a payload"),
+                None => Ok(Action::ClosePaneGroup),
+            },
+            Some(ProtobufActionName::BreakPaneGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("BreakPaneGroup should not have a payload"),
+                None => Ok(Action::BreakPaneGroup),
+            },
+            Some(ProtobufActionName::TogglePaneGroupEmbedOrFloating) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("TogglePaneGroupEmbedOrFloating should not have a payload"),
+                    None => Ok(Action::TogglePaneGroupEmbedOrFloating),
+                }
+            },
+            Some(ProtobufActionName::RenamePaneGroup) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::RenamePaneGroupPayload(name)) => {
+                    Ok(Action::RenamePaneGroup(name))
+                },
+                _ => Err("Wrong payload for Action::RenamePaneGroup"),
+            },
+            Some(ProtobufActionName::QueryActionJournal) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("QueryActionJournal should not have a payload"),
+                    None => Ok(Action::QueryActionJournal),
+                }
+            },
+            Some(ProtobufActionName::UndoLayoutChange) => match protobuf_action.optional_payload {
+                Some(_) => Err("UndoLayoutChange should not have a payload"),
+                None => Ok(Action::UndoLayoutChange),
+            },
             Some(ProtobufActionName::CloseFocus) => match protobuf_action.optional_payload {
                 Some(_) => Err("CloseFocus should not have a payload"),
                 None => Ok(Action::CloseFocus),
@@ -397,6 +652,8 @@ impl TryFrom<ProtobufAction> for Action {
                             .unwrap_or_default();
                         let run_plugin = RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: run_plugin_location,
                             configuration,
                         };
@@ -426,6 +683,8 @@ impl TryFrom<ProtobufAction> for Action {
                         .unwrap_or_default();
                     let run_plugin = RunPlugin {
                         _allow_exec_host_cmd: false,
+                        _watch: false,
+                        _watch_clear_data: false,
                         location: run_plugin_location,
                         configuration,
                     };
@@ -513,6 +772,26 @@ impl TryFrom<ProtobufAction> for Action {
                     _ => Err("Wrong payload for Action::SearchToggleOption"),
                 }
             },
+            Some(ProtobufActionName::CopyModeMove) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::CopyModeMovePayload(motion)) => Ok(Action::CopyModeMove(
+                    ProtobufCopyModeMotion::from_i32(motion)
+                        .ok_or("Malformed payload for Action::CopyModeMove")?
+                        .try_into()?,
+                )),
+                _ => Err("Wrong payload for Action::CopyModeMove"),
+            },
+            Some(ProtobufActionName::CopyModeToggleSelection) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::CopyModeToggleSelectionPayload(selection)) => {
+                        Ok(Action::CopyModeToggleSelection(
+                            ProtobufCopyModeSelection::from_i32(selection)
+                                .ok_or("Malformed payload for Action::CopyModeToggleSelection")?
+                                .try_into()?,
+                        ))
+                    },
+                    _ => Err("Wrong payload for Action::CopyModeToggleSelection"),
+                }
+            },
             Some(ProtobufActionName::ToggleMouseMode) => match protobuf_action.optional_payload {
                 Some(_) => Err("ToggleMouseMode should not have a payload"),
                 None => Ok(Action::ToggleMouseMode),
@@ -540,6 +819,8 @@ impl TryFrom<ProtobufAction> for Action {
                         let run_plugin = RunPlugin {
                             location: run_plugin_location,
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             configuration: PluginUserConfiguration::default(),
                         };
                         let pane_name = payload.pane_name;
@@ -562,6 +843,8 @@ impl TryFrom<ProtobufAction> for Action {
                         let run_plugin = RunPlugin {
                             location: run_plugin_location,
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             configuration: PluginUserConfiguration::default(),
                         };
                         let pane_name = payload.pane_name;
@@ -582,6 +865,8 @@ impl TryFrom<ProtobufAction> for Action {
                             .map_err(|_| "Malformed StartOrReloadPluginPayload payload")?;
                         let run_plugin = RunPlugin {
                             _allow_exec_host_cmd: false,
+                            _watch: false,
+                            _watch_clear_data: false,
                             location: run_plugin_location,
                             configuration: PluginUserConfiguration::default(),
                         };
@@ -596,6 +881,73 @@ impl TryFrom<ProtobufAction> for Action {
                 },
                 _ => Err("Wrong payload for Action::CloseTerminalPane"),
             },
+            Some(ProtobufActionName::PipePaneToFile) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::PipePaneToFilePayload(payload)) => {
+                    Ok(Action::PipePaneToFile(
+                        payload.pane_id,
+                        payload.path.into(),
+                        payload.strip_ansi,
+                    ))
+                },
+                _ => Err("Wrong payload for Action::PipePaneToFile"),
+            },
+            Some(ProtobufActionName::StopPipePaneToFile) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::StopPipePaneToFilePayload(payload)) => {
+                        Ok(Action::StopPipePaneToFile(payload))
+                    },
+                    _ => Err("Wrong payload for Action::StopPipePaneToFile"),
+                }
+            },
+            Some(ProtobufActionName::MoveTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MoveTabPayload(move_tab_payload)) => {
+                    let direction: Direction = ProtobufResizeDirection::from_i32(move_tab_payload)
+                        .ok_or("Malformed resize direction for Action::MoveTab")?
+                        .try_into()?;
+                    Ok(Action::MoveTab(direction))
+                },
+                _ => Err("Wrong payload for Action::MoveTab"),
+            },
+            Some(ProtobufActionName::MoveTabToIndex) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MoveTabToIndexPayload(index)) => {
+                    Ok(Action::MoveTabToIndex(index as usize))
+                },
+                _ => Err("Wrong payload for Action::MoveTabToIndex"),
+            },
+            Some(ProtobufActionName::SwapPanes) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SwapPanesPayload(payload)) => {
+                    let (first_pane_id, second_pane_id) = swap_panes_from_payload(payload);
+                    Ok(Action::SwapPanes(first_pane_id, second_pane_id))
+                },
+                _ => Err("Wrong payload for Action::SwapPanes"),
+            },
+            Some(ProtobufActionName::SwapPaneWith) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SwapPaneWithPayload(swap_pane_with_payload)) => {
+                    let direction: Direction =
+                        ProtobufResizeDirection::from_i32(swap_pane_with_payload)
+                            .ok_or("Malformed resize direction for Action::SwapPaneWith")?
+                            .try_into()?;
+                    Ok(Action::SwapPaneWith(direction))
+                },
+                _ => Err("Wrong payload for Action::SwapPaneWith"),
+            },
+            Some(ProtobufActionName::ChangeFloatingPaneCoordinates) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::ChangeFloatingPaneCoordinatesPayload(payload)) => {
+                        let (pane_id, coordinates) =
+                            change_floating_pane_coordinates_from_payload(payload)?;
+                        Ok(Action::ChangeFloatingPaneCoordinates(pane_id, coordinates))
+                    },
+                    _ => Err("Wrong payload for Action::ChangeFloatingPaneCoordinates"),
+                }
+            },
+            Some(ProtobufActionName::MovePaneToTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MovePaneToTabPayload(payload)) => {
+                    let (pane_id, tab, should_float) = move_pane_to_tab_from_payload(payload)?;
+                    Ok(Action::MovePaneToTab(pane_id, tab, should_float))
+                },
+                _ => Err("Wrong payload for Action::MovePaneToTab"),
+            },
             Some(ProtobufActionName::ClosePluginPane) => match protobuf_action.optional_payload {
                 Some(OptionalPayload::ClosePluginPanePayload(payload)) => {
                     Ok(Action::ClosePluginPane(payload))
@@ -823,6 +1175,18 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ScrollToTop as i32,
                 optional_payload: None,
             }),
+            Action::ScrollToPreviousPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToPreviousPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::ScrollToNextPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToNextPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::CopyLastCommandOutput => Ok(ProtobufAction {
+                name: ProtobufActionName::CopyLastCommandOutput as i32,
+                optional_payload: None,
+            }),
             Action::PageScrollUp => Ok(ProtobufAction {
                 name: ProtobufActionName::PageScrollUp as i32,
                 optional_payload: None,
@@ -871,6 +1235,7 @@ impl TryFrom<Action> for ProtobufAction {
                 direction,
                 should_float,
                 _should_be_in_place,
+                coordinates,
             ) => {
                 let file_to_edit = path_to_file.display().to_string();
                 let cwd = cwd.map(|cwd| cwd.display().to_string());
@@ -886,10 +1251,11 @@ impl TryFrom<Action> for ProtobufAction {
                         should_float,
                         direction,
                         cwd,
+                        coordinates: coordinates.map(floating_pane_coordinates_to_protobuf),
                     })),
                 })
             },
-            Action::NewFloatingPane(run_command_action, pane_name) => {
+            Action::NewFloatingPane(run_command_action, pane_name, coordinates) => {
                 let command = run_command_action.and_then(|r| {
                     let mut protobuf_run_command_action: ProtobufRunCommandAction =
                         r.try_into().ok()?;
@@ -899,7 +1265,10 @@ impl TryFrom<Action> for ProtobufAction {
                 Ok(ProtobufAction {
                     name: ProtobufActionName::NewFloatingPane as i32,
                     optional_payload: Some(OptionalPayload::NewFloatingPanePayload(
-                        NewFloatingPanePayload { command },
+                        NewFloatingPanePayload {
+                            command,
+                            coordinates: coordinates.map(floating_pane_coordinates_to_protobuf),
+                        },
                     )),
                 })
             },
@@ -930,6 +1299,52 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ToggleFloatingPanes as i32,
                 optional_payload: None,
             }),
+            Action::TogglePanePinned => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePanePinned as i32,
+                optional_payload: None,
+            }),
+            Action::SetPanePinned(pane_id, should_be_pinned) => Ok(ProtobufAction {
+                name: ProtobufActionName::SetPanePinned as i32,
+                optional_payload: Some(OptionalPayload::SetPanePinnedPayload(
+                    set_pane_pinned_payload(pane_id, should_be_pinned),
+                )),
+            }),
+            Action::TogglePaneInGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePaneInGroup as i32,
+                optional_payload: None,
+            }),
+            Action::ClearPaneGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::ClearPaneGroup as i32,
+                optional_payload: None,
+            }),
+            Action::TogglePaneGroupBroadcast => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePaneGroupBroadcast as i32,
+                optional_payload: None,
+            }),
+            Action::ClosePaneGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::ClosePaneGroup as i32,
+                optional_payload: None,
+            }),
+            Action::BreakPaneGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::BreakPaneGroup as i32,
+                optional_payload: None,
+            }),
+            Action::TogglePaneGroupEmbedOrFloating => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePaneGroupEmbedOrFloating as i32,
+                optional_payload: None,
+            }),
+            Action::RenamePaneGroup(name) => Ok(ProtobufAction {
+                name: ProtobufActionName::RenamePaneGroup as i32,
+                optional_payload: Some(OptionalPayload::RenamePaneGroupPayload(name)),
+            }),
+            Action::QueryActionJournal => Ok(ProtobufAction {
+                name: ProtobufActionName::QueryActionJournal as i32,
+                optional_payload: None,
+            }),
+            Action::UndoLayoutChange => Ok(ProtobufAction {
+                name: ProtobufActionName::UndoLayoutChange as i32,
+                optional_payload: None,
+            }),
             Action::CloseFocus => Ok(ProtobufAction {
                 name: ProtobufActionName::CloseFocus as i32,
                 optional_payload: None,
@@ -1121,6 +1536,22 @@ impl TryFrom<Action> for ProtobufAction {
                     )),
                 })
             },
+            Action::CopyModeMove(motion) => {
+                let motion: ProtobufCopyModeMotion = motion.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::CopyModeMove as i32,
+                    optional_payload: Some(OptionalPayload::CopyModeMovePayload(motion as i32)),
+                })
+            },
+            Action::CopyModeToggleSelection(selection) => {
+                let selection: ProtobufCopyModeSelection = selection.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::CopyModeToggleSelection as i32,
+                    optional_payload: Some(OptionalPayload::CopyModeToggleSelectionPayload(
+                        selection as i32,
+                    )),
+                })
+            },
             Action::ToggleMouseMode => Ok(ProtobufAction {
                 name: ProtobufActionName::ToggleMouseMode as i32,
                 optional_payload: None,
@@ -1176,6 +1607,22 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::CloseTerminalPane as i32,
                 optional_payload: Some(OptionalPayload::CloseTerminalPanePayload(terminal_pane_id)),
             }),
+            Action::PipePaneToFile(terminal_pane_id, path, strip_ansi) => Ok(ProtobufAction {
+                name: ProtobufActionName::PipePaneToFile as i32,
+                optional_payload: Some(OptionalPayload::PipePaneToFilePayload(
+                    PipePaneToFilePayload {
+                        pane_id: terminal_pane_id,
+                        path: path.display().to_string(),
+                        strip_ansi,
+                    },
+                )),
+            }),
+            Action::StopPipePaneToFile(terminal_pane_id) => Ok(ProtobufAction {
+                name: ProtobufActionName::StopPipePaneToFile as i32,
+                optional_payload: Some(OptionalPayload::StopPipePaneToFilePayload(
+                    terminal_pane_id,
+                )),
+            }),
             Action::ClosePluginPane(plugin_pane_id) => Ok(ProtobufAction {
                 name: ProtobufActionName::ClosePluginPane as i32,
                 optional_payload: Some(OptionalPayload::ClosePluginPanePayload(plugin_pane_id)),
@@ -1235,6 +1682,43 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::BreakPaneLeft as i32,
                 optional_payload: None,
             }),
+            Action::MoveTab(direction) => {
+                let direction: ProtobufResizeDirection = direction.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::MoveTab as i32,
+                    optional_payload: Some(OptionalPayload::MoveTabPayload(direction as i32)),
+                })
+            },
+            Action::MoveTabToIndex(index) => Ok(ProtobufAction {
+                name: ProtobufActionName::MoveTabToIndex as i32,
+                optional_payload: Some(OptionalPayload::MoveTabToIndexPayload(index as u32)),
+            }),
+            Action::SwapPanes(first_pane_id, second_pane_id) => Ok(ProtobufAction {
+                name: ProtobufActionName::SwapPanes as i32,
+                optional_payload: Some(OptionalPayload::SwapPanesPayload(swap_panes_payload(
+                    first_pane_id,
+                    second_pane_id,
+                ))),
+            }),
+            Action::SwapPaneWith(direction) => {
+                let direction: ProtobufResizeDirection = direction.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::SwapPaneWith as i32,
+                    optional_payload: Some(OptionalPayload::SwapPaneWithPayload(direction as i32)),
+                })
+            },
+            Action::ChangeFloatingPaneCoordinates(pane_id, coordinates) => Ok(ProtobufAction {
+                name: ProtobufActionName::ChangeFloatingPaneCoordinates as i32,
+                optional_payload: Some(OptionalPayload::ChangeFloatingPaneCoordinatesPayload(
+                    change_floating_pane_coordinates_payload(pane_id, coordinates),
+                )),
+            }),
+            Action::MovePaneToTab(pane_id, tab, should_float) => Ok(ProtobufAction {
+                name: ProtobufActionName::MovePaneToTab as i32,
+                optional_payload: Some(OptionalPayload::MovePaneToTabPayload(
+                    move_pane_to_tab_payload(pane_id, tab, should_float),
+                )),
+            }),
             Action::RenameSession(session_name) => Ok(ProtobufAction {
                 name: ProtobufActionName::RenameSession as i32,
                 optional_payload: Some(OptionalPayload::RenameSessionPayload(session_name)),
@@ -1245,8 +1729,11 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::NewInPlacePluginPane(..)
             | Action::Deny
             | Action::Copy
+            | Action::WriteKey(..)
             | Action::DumpLayout
             | Action::CliPipe { .. }
+            | Action::WaitForPane { .. }
+            | Action::SendKeyToPlugin(..)
             | Action::SkipConfirm(..) => Err("Unsupported action"),
         }
     }
@@ -1259,6 +1746,7 @@ impl TryFrom<ProtobufSearchOption> for SearchOption {
             ProtobufSearchOption::CaseSensitivity => Ok(SearchOption::CaseSensitivity),
             ProtobufSearchOption::WholeWord => Ok(SearchOption::WholeWord),
             ProtobufSearchOption::Wrap => Ok(SearchOption::Wrap),
+            ProtobufSearchOption::Regex => Ok(SearchOption::Regex),
         }
     }
 }
@@ -1270,6 +1758,7 @@ impl TryFrom<SearchOption> for ProtobufSearchOption {
             SearchOption::CaseSensitivity => Ok(ProtobufSearchOption::CaseSensitivity),
             SearchOption::WholeWord => Ok(ProtobufSearchOption::WholeWord),
             SearchOption::Wrap => Ok(ProtobufSearchOption::Wrap),
+            SearchOption::Regex => Ok(ProtobufSearchOption::Regex),
         }
     }
 }
@@ -1294,6 +1783,68 @@ impl TryFrom<SearchDirection> for ProtobufSearchDirection {
     }
 }
 
+impl TryFrom<ProtobufCopyModeMotion> for CopyModeMotion {
+    type Error = &'static str;
+    fn try_from(protobuf_copy_mode_motion: ProtobufCopyModeMotion) -> Result<Self, &'static str> {
+        match protobuf_copy_mode_motion {
+            ProtobufCopyModeMotion::CursorLeft => Ok(CopyModeMotion::Left),
+            ProtobufCopyModeMotion::CursorRight => Ok(CopyModeMotion::Right),
+            ProtobufCopyModeMotion::CursorUp => Ok(CopyModeMotion::Up),
+            ProtobufCopyModeMotion::CursorDown => Ok(CopyModeMotion::Down),
+            ProtobufCopyModeMotion::NextWord => Ok(CopyModeMotion::NextWord),
+            ProtobufCopyModeMotion::PreviousWord => Ok(CopyModeMotion::PreviousWord),
+            ProtobufCopyModeMotion::EndOfWord => Ok(CopyModeMotion::EndOfWord),
+            ProtobufCopyModeMotion::LineStart => Ok(CopyModeMotion::LineStart),
+            ProtobufCopyModeMotion::LineEnd => Ok(CopyModeMotion::LineEnd),
+            ProtobufCopyModeMotion::Top => Ok(CopyModeMotion::Top),
+            ProtobufCopyModeMotion::Bottom => Ok(CopyModeMotion::Bottom),
+            ProtobufCopyModeMotion::GoPrefix => Ok(CopyModeMotion::GoPrefix),
+        }
+    }
+}
+
+impl TryFrom<CopyModeMotion> for ProtobufCopyModeMotion {
+    type Error = &'static str;
+    fn try_from(copy_mode_motion: CopyModeMotion) -> Result<Self, &'static str> {
+        match copy_mode_motion {
+            CopyModeMotion::Left => Ok(ProtobufCopyModeMotion::CursorLeft),
+            CopyModeMotion::Right => Ok(ProtobufCopyModeMotion::CursorRight),
+            CopyModeMotion::Up => Ok(ProtobufCopyModeMotion::CursorUp),
+            CopyModeMotion::Down => Ok(ProtobufCopyModeMotion::CursorDown),
+            CopyModeMotion::NextWord => Ok(ProtobufCopyModeMotion::NextWord),
+            CopyModeMotion::PreviousWord => Ok(ProtobufCopyModeMotion::PreviousWord),
+            CopyModeMotion::EndOfWord => Ok(ProtobufCopyModeMotion::EndOfWord),
+            CopyModeMotion::LineStart => Ok(ProtobufCopyModeMotion::LineStart),
+            CopyModeMotion::LineEnd => Ok(ProtobufCopyModeMotion::LineEnd),
+            CopyModeMotion::Top => Ok(ProtobufCopyModeMotion::Top),
+            CopyModeMotion::Bottom => Ok(ProtobufCopyModeMotion::Bottom),
+            CopyModeMotion::GoPrefix => Ok(ProtobufCopyModeMotion::GoPrefix),
+        }
+    }
+}
+
+impl TryFrom<ProtobufCopyModeSelection> for CopyModeSelection {
+    type Error = &'static str;
+    fn try_from(
+        protobuf_copy_mode_selection: ProtobufCopyModeSelection,
+    ) -> Result<Self, &'static str> {
+        match protobuf_copy_mode_selection {
+            ProtobufCopyModeSelection::Character => Ok(CopyModeSelection::Character),
+            ProtobufCopyModeSelection::Line => Ok(CopyModeSelection::Line),
+        }
+    }
+}
+
+impl TryFrom<CopyModeSelection> for ProtobufCopyModeSelection {
+    type Error = &'static str;
+    fn try_from(copy_mode_selection: CopyModeSelection) -> Result<Self, &'static str> {
+        match copy_mode_selection {
+            CopyModeSelection::Character => Ok(ProtobufCopyModeSelection::Character),
+            CopyModeSelection::Line => Ok(ProtobufCopyModeSelection::Line),
+        }
+    }
+}
+
 impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
     type Error = &'static str;
     fn try_from(
@@ -1308,6 +1859,11 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let env = protobuf_run_command_action
+            .env_variables
+            .into_iter()
+            .map(|env_variable| (env_variable.name, env_variable.value))
+            .collect();
         Ok(RunCommandAction {
             command,
             args,
@@ -1315,6 +1871,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            env,
         })
     }
 }
@@ -1331,6 +1888,11 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let env_variables = run_command_action
+            .env
+            .into_iter()
+            .map(|(name, value)| ProtobufEnvVariable { name, value })
+            .collect();
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -1339,6 +1901,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             hold_on_close,
             hold_on_start,
             pane_name: None,
+            env_variables,
         })
     }
 }
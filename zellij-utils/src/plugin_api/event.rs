@@ -1,12 +1,12 @@
 pub use super::generated_api::api::{
     action::{Action as ProtobufAction, Position as ProtobufPosition},
     event::{
-        event::Payload as ProtobufEventPayload, CopyDestination as ProtobufCopyDestination,
-        Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
-        EventType as ProtobufEventType, InputModeKeybinds as ProtobufInputModeKeybinds,
-        KeyBind as ProtobufKeyBind, ModeUpdatePayload as ProtobufModeUpdatePayload,
-        PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
-        ResurrectableSession as ProtobufResurrectableSession,
+        event::Payload as ProtobufEventPayload, ActionJournalEntry as ProtobufActionJournalEntry,
+        CopyDestination as ProtobufCopyDestination, Event as ProtobufEvent,
+        EventNameList as ProtobufEventNameList, EventType as ProtobufEventType,
+        InputModeKeybinds as ProtobufInputModeKeybinds, KeyBind as ProtobufKeyBind,
+        ModeUpdatePayload as ProtobufModeUpdatePayload, PaneInfo as ProtobufPaneInfo,
+        PaneManifest as ProtobufPaneManifest, ResurrectableSession as ProtobufResurrectableSession,
         SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo, *,
     },
     input_mode::InputMode as ProtobufInputMode,
@@ -14,8 +14,8 @@ pub use super::generated_api::api::{
     style::Style as ProtobufStyle,
 };
 use crate::data::{
-    CopyDestination, Event, EventType, InputMode, Key, ModeInfo, Mouse, PaneInfo, PaneManifest,
-    PermissionStatus, PluginCapabilities, SessionInfo, Style, TabInfo,
+    CopyDestination, Event, EventType, InputMode, Key, ModeInfo, Mouse, PaneContents, PaneId,
+    PaneInfo, PaneManifest, PermissionStatus, PluginCapabilities, SessionInfo, Style, TabInfo,
 };
 
 use crate::errors::prelude::*;
@@ -228,6 +228,73 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for the WebRequestResult Event"),
             },
+            Some(ProtobufEventType::PaneContents) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneContentsPayload(pane_contents_payload)) => {
+                    let pane_id = if pane_contents_payload.is_plugin {
+                        PaneId::Plugin(pane_contents_payload.pane_id)
+                    } else {
+                        PaneId::Terminal(pane_contents_payload.pane_id)
+                    };
+                    Ok(Event::PaneContents(
+                        pane_id,
+                        PaneContents {
+                            viewport: pane_contents_payload.viewport,
+                            lines_above_viewport: pane_contents_payload.lines_above_viewport,
+                            lines_below_viewport: pane_contents_payload.lines_below_viewport,
+                        },
+                    ))
+                },
+                _ => Err("Malformed payload for the PaneContents Event"),
+            },
+            Some(ProtobufEventType::PaneOutputMatched) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneOutputMatchedPayload(
+                    pane_output_matched_payload,
+                )) => {
+                    let pane_id = if pane_output_matched_payload.is_plugin {
+                        PaneId::Plugin(pane_output_matched_payload.pane_id)
+                    } else {
+                        PaneId::Terminal(pane_output_matched_payload.pane_id)
+                    };
+                    Ok(Event::PaneOutputMatched(
+                        pane_id,
+                        pane_output_matched_payload.pattern,
+                        pane_output_matched_payload.line,
+                    ))
+                },
+                _ => Err("Malformed payload for the PaneOutputMatched Event"),
+            },
+            Some(ProtobufEventType::ActionJournal) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ActionJournalPayload(action_journal_payload)) => {
+                    Ok(Event::ActionJournal(
+                        action_journal_payload
+                            .entries
+                            .into_iter()
+                            .map(|e| e.into())
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the ActionJournal Event"),
+            },
+            Some(ProtobufEventType::PluginResponse) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PluginResponsePayload(plugin_response_payload)) => {
+                    Ok(Event::PluginResponse(plugin_response_payload.try_into()?))
+                },
+                _ => Err("Malformed payload for the PluginResponse Event"),
+            },
+            Some(ProtobufEventType::StoreValue) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::StoreValuePayload(store_entry_payload)) => {
+                    let (scope, key, value) = store_entry_from_payload(store_entry_payload)?;
+                    Ok(Event::StoreValue(scope, key, value))
+                },
+                _ => Err("Malformed payload for the StoreValue Event"),
+            },
+            Some(ProtobufEventType::StoreChanged) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::StoreChangedPayload(store_entry_payload)) => {
+                    let (scope, key, value) = store_entry_from_payload(store_entry_payload)?;
+                    Ok(Event::StoreChanged(scope, key, value))
+                },
+                _ => Err("Malformed payload for the StoreChanged Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -425,6 +492,169 @@ impl TryFrom<Event> for ProtobufEvent {
                     )),
                 })
             },
+            Event::PaneContents(pane_id, pane_contents) => {
+                let (pane_id, is_plugin) = match pane_id {
+                    PaneId::Terminal(id) => (id, false),
+                    PaneId::Plugin(id) => (id, true),
+                };
+                let pane_contents_payload = PaneContentsPayload {
+                    pane_id,
+                    is_plugin,
+                    viewport: pane_contents.viewport,
+                    lines_above_viewport: pane_contents.lines_above_viewport,
+                    lines_below_viewport: pane_contents.lines_below_viewport,
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PaneContents as i32,
+                    payload: Some(event::Payload::PaneContentsPayload(pane_contents_payload)),
+                })
+            },
+            Event::PaneOutputMatched(pane_id, pattern, line) => {
+                let (pane_id, is_plugin) = match pane_id {
+                    PaneId::Terminal(id) => (id, false),
+                    PaneId::Plugin(id) => (id, true),
+                };
+                let pane_output_matched_payload = PaneOutputMatchedPayload {
+                    pane_id,
+                    is_plugin,
+                    pattern,
+                    line,
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PaneOutputMatched as i32,
+                    payload: Some(event::Payload::PaneOutputMatchedPayload(
+                        pane_output_matched_payload,
+                    )),
+                })
+            },
+            Event::ActionJournal(entries) => Ok(ProtobufEvent {
+                name: ProtobufEventType::ActionJournal as i32,
+                payload: Some(event::Payload::ActionJournalPayload(ActionJournalPayload {
+                    entries: entries.into_iter().map(|e| e.into()).collect(),
+                })),
+            }),
+            Event::PluginResponse(plugin_response) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PluginResponse as i32,
+                payload: Some(event::Payload::PluginResponsePayload(
+                    plugin_response.into(),
+                )),
+            }),
+            Event::StoreValue(scope, key, value) => Ok(ProtobufEvent {
+                name: ProtobufEventType::StoreValue as i32,
+                payload: Some(event::Payload::StoreValuePayload(store_entry_payload(
+                    scope, key, value,
+                ))),
+            }),
+            Event::StoreChanged(scope, key, value) => Ok(ProtobufEvent {
+                name: ProtobufEventType::StoreChanged as i32,
+                payload: Some(event::Payload::StoreChangedPayload(store_entry_payload(
+                    scope, key, value,
+                ))),
+            }),
+        }
+    }
+}
+
+impl From<ProtobufActionJournalEntry> for crate::data::ActionJournalEntry {
+    fn from(protobuf_entry: ProtobufActionJournalEntry) -> Self {
+        crate::data::ActionJournalEntry {
+            action: protobuf_entry.action,
+            arguments: protobuf_entry.arguments,
+            tab_position: protobuf_entry.tab_position as usize,
+            client_id: protobuf_entry.client_id as u16,
+            timestamp: protobuf_entry.timestamp,
+        }
+    }
+}
+
+impl From<crate::data::ActionJournalEntry> for ProtobufActionJournalEntry {
+    fn from(entry: crate::data::ActionJournalEntry) -> Self {
+        ProtobufActionJournalEntry {
+            action: entry.action,
+            arguments: entry.arguments,
+            tab_position: entry.tab_position as u32,
+            client_id: entry.client_id as u32,
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+impl TryFrom<i32> for crate::data::StoreScope {
+    type Error = &'static str;
+    fn try_from(protobuf_store_scope: i32) -> Result<Self, &'static str> {
+        match StoreScope::from_i32(protobuf_store_scope) {
+            Some(StoreScope::Plugin) => Ok(crate::data::StoreScope::Plugin),
+            Some(StoreScope::Session) => Ok(crate::data::StoreScope::Session),
+            Some(StoreScope::Global) => Ok(crate::data::StoreScope::Global),
+            None => Err("Unknown StoreScope"),
+        }
+    }
+}
+
+impl From<crate::data::StoreScope> for StoreScope {
+    fn from(store_scope: crate::data::StoreScope) -> Self {
+        match store_scope {
+            crate::data::StoreScope::Plugin => StoreScope::Plugin,
+            crate::data::StoreScope::Session => StoreScope::Session,
+            crate::data::StoreScope::Global => StoreScope::Global,
+        }
+    }
+}
+
+pub fn store_entry_payload(
+    scope: crate::data::StoreScope,
+    key: String,
+    value: Option<String>,
+) -> StoreEntryPayload {
+    StoreEntryPayload {
+        scope: StoreScope::from(scope) as i32,
+        key,
+        value,
+    }
+}
+
+pub fn store_entry_from_payload(
+    store_entry_payload: StoreEntryPayload,
+) -> Result<(crate::data::StoreScope, String, Option<String>), &'static str> {
+    Ok((
+        store_entry_payload.scope.try_into()?,
+        store_entry_payload.key,
+        store_entry_payload.value,
+    ))
+}
+
+impl TryFrom<PluginResponsePayload> for crate::data::PluginResponse {
+    type Error = &'static str;
+    fn try_from(plugin_response_payload: PluginResponsePayload) -> Result<Self, &'static str> {
+        let status = match PluginResponseStatus::from_i32(plugin_response_payload.status) {
+            Some(PluginResponseStatus::Ok) => crate::data::PluginResponseStatus::Ok,
+            Some(PluginResponseStatus::Error) => crate::data::PluginResponseStatus::Error,
+            Some(PluginResponseStatus::TimedOut) => crate::data::PluginResponseStatus::TimedOut,
+            Some(PluginResponseStatus::Unavailable) => {
+                crate::data::PluginResponseStatus::Unavailable
+            },
+            None => return Err("Unknown PluginResponseStatus"),
+        };
+        Ok(crate::data::PluginResponse {
+            request_id: plugin_response_payload.request_id,
+            status,
+            payload: plugin_response_payload.payload,
+        })
+    }
+}
+
+impl From<crate::data::PluginResponse> for PluginResponsePayload {
+    fn from(plugin_response: crate::data::PluginResponse) -> Self {
+        let status = match plugin_response.status {
+            crate::data::PluginResponseStatus::Ok => PluginResponseStatus::Ok,
+            crate::data::PluginResponseStatus::Error => PluginResponseStatus::Error,
+            crate::data::PluginResponseStatus::TimedOut => PluginResponseStatus::TimedOut,
+            crate::data::PluginResponseStatus::Unavailable => PluginResponseStatus::Unavailable,
+        };
+        PluginResponsePayload {
+            request_id: plugin_response.request_id,
+            status: status as i32,
+            payload: plugin_response.payload,
         }
     }
 }
@@ -625,6 +855,8 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             is_focused: protobuf_pane_info.is_focused,
             is_fullscreen: protobuf_pane_info.is_fullscreen,
             is_floating: protobuf_pane_info.is_floating,
+            is_pinned: protobuf_pane_info.is_pinned,
+            is_in_group: protobuf_pane_info.is_in_group,
             is_suppressed: protobuf_pane_info.is_suppressed,
             title: protobuf_pane_info.title,
             exited: protobuf_pane_info.exited,
@@ -644,6 +876,10 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             terminal_command: protobuf_pane_info.terminal_command,
             plugin_url: protobuf_pane_info.plugin_url,
             is_selectable: protobuf_pane_info.is_selectable,
+            cwd: protobuf_pane_info.cwd.map(PathBuf::from),
+            running_command: protobuf_pane_info.running_command,
+            last_command_exit_code: protobuf_pane_info.last_command_exit_code,
+            output_log_path: protobuf_pane_info.output_log_path.map(PathBuf::from),
         })
     }
 }
@@ -657,6 +893,8 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             is_focused: pane_info.is_focused,
             is_fullscreen: pane_info.is_fullscreen,
             is_floating: pane_info.is_floating,
+            is_pinned: pane_info.is_pinned,
+            is_in_group: pane_info.is_in_group,
             is_suppressed: pane_info.is_suppressed,
             title: pane_info.title,
             exited: pane_info.exited,
@@ -679,6 +917,12 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             terminal_command: pane_info.terminal_command,
             plugin_url: pane_info.plugin_url,
             is_selectable: pane_info.is_selectable,
+            cwd: pane_info.cwd.map(|cwd| cwd.display().to_string()),
+            running_command: pane_info.running_command,
+            last_command_exit_code: pane_info.last_command_exit_code,
+            output_log_path: pane_info
+                .output_log_path
+                .map(|output_log_path| output_log_path.display().to_string()),
         })
     }
 }
@@ -773,6 +1017,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             style,
             capabilities,
             session_name,
+            is_read_only: protobuf_mode_update_payload.is_read_only,
         };
         Ok(mode_info)
     }
@@ -785,6 +1030,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
         let style: ProtobufStyle = mode_info.style.try_into()?;
         let arrow_fonts_support: bool = mode_info.capabilities.arrow_fonts;
         let session_name = mode_info.session_name;
+        let is_read_only = mode_info.is_read_only;
         let mut protobuf_input_mode_keybinds: Vec<ProtobufInputModeKeybinds> = vec![];
         for (input_mode, input_mode_keybinds) in mode_info.keybinds {
             let mode: ProtobufInputMode = input_mode.try_into()?;
@@ -815,6 +1061,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             keybinds: protobuf_input_mode_keybinds,
             arrow_fonts_support,
             session_name,
+            is_read_only,
         })
     }
 }
@@ -872,6 +1119,12 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::SessionUpdate => EventType::SessionUpdate,
             ProtobufEventType::RunCommandResult => EventType::RunCommandResult,
             ProtobufEventType::WebRequestResult => EventType::WebRequestResult,
+            ProtobufEventType::PaneContents => EventType::PaneContents,
+            ProtobufEventType::PaneOutputMatched => EventType::PaneOutputMatched,
+            ProtobufEventType::ActionJournal => EventType::ActionJournal,
+            ProtobufEventType::PluginResponse => EventType::PluginResponse,
+            ProtobufEventType::StoreValue => EventType::StoreValue,
+            ProtobufEventType::StoreChanged => EventType::StoreChanged,
         })
     }
 }
@@ -899,6 +1152,12 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::SessionUpdate => ProtobufEventType::SessionUpdate,
             EventType::RunCommandResult => ProtobufEventType::RunCommandResult,
             EventType::WebRequestResult => ProtobufEventType::WebRequestResult,
+            EventType::PaneContents => ProtobufEventType::PaneContents,
+            EventType::PaneOutputMatched => ProtobufEventType::PaneOutputMatched,
+            EventType::ActionJournal => ProtobufEventType::ActionJournal,
+            EventType::PluginResponse => ProtobufEventType::PluginResponse,
+            EventType::StoreValue => ProtobufEventType::StoreValue,
+            EventType::StoreChanged => ProtobufEventType::StoreChanged,
         })
     }
 }
@@ -998,6 +1257,7 @@ fn serialize_mode_update_event_with_non_default_values() {
         },
         capabilities: PluginCapabilities { arrow_fonts: false },
         session_name: Some("my awesome test session".to_owned()),
+        is_read_only: true,
     });
     let protobuf_event: ProtobufEvent = mode_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
@@ -1096,6 +1356,30 @@ fn serialize_key_event() {
     );
 }
 
+#[test]
+fn serialize_key_with_modifiers_event() {
+    use crate::data::{BareKey, KeyModifiers};
+    use prost::Message;
+    let key_event = Event::Key(Key::WithModifiers(
+        BareKey::Left,
+        KeyModifiers {
+            ctrl: true,
+            shift: true,
+            super_key: true,
+            ..Default::default()
+        },
+    ));
+    let protobuf_event: ProtobufEvent = key_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        key_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_mouse_event() {
     use prost::Message;
@@ -1333,6 +1617,8 @@ fn serialize_session_update_event_with_non_default_values() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
+            is_in_group: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
@@ -1350,6 +1636,10 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            cwd: Some(PathBuf::from("/home/aram/code")),
+            running_command: Some("vim foo.rs".to_owned()),
+            last_command_exit_code: Some(1),
+            output_log_path: Some(PathBuf::from("/tmp/pane-1.log")),
         },
         PaneInfo {
             id: 1,
@@ -1357,6 +1647,8 @@ fn serialize_session_update_event_with_non_default_values() {
             is_focused: true,
             is_fullscreen: true,
             is_floating: false,
+            is_pinned: false,
+            is_in_group: false,
             is_suppressed: false,
             title: "pane 1".to_owned(),
             exited: false,
@@ -1374,6 +1666,10 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            cwd: None,
+            running_command: None,
+            last_command_exit_code: None,
+            output_log_path: None,
         },
     ];
     panes.insert(0, panes_list);
@@ -1407,3 +1703,129 @@ fn serialize_session_update_event_with_non_default_values() {
         "Event properly serialized/deserialized without change"
     );
 }
+
+#[test]
+fn serialize_pane_contents_event() {
+    use prost::Message;
+    let pane_contents = PaneContents {
+        viewport: vec![
+            "$ cargo build".to_owned(),
+            "error[E0308]: mismatched types".to_owned(),
+        ],
+        lines_above_viewport: vec!["line above".to_owned()],
+        lines_below_viewport: vec![],
+    };
+    let pane_contents_event = Event::PaneContents(PaneId::Terminal(3), pane_contents);
+    let protobuf_event: ProtobufEvent = pane_contents_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_contents_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_output_matched_event() {
+    use prost::Message;
+    let pane_output_matched_event = Event::PaneOutputMatched(
+        PaneId::Terminal(2),
+        "listening on".to_owned(),
+        "server listening on 0.0.0.0:8080".to_owned(),
+    );
+    let protobuf_event: ProtobufEvent = pane_output_matched_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_output_matched_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_action_journal_event() {
+    use crate::data::ActionJournalEntry;
+    use prost::Message;
+    let action_journal_event = Event::ActionJournal(vec![
+        ActionJournalEntry {
+            action: "NewPane".to_owned(),
+            arguments: vec![],
+            tab_position: 0,
+            client_id: 1,
+            timestamp: 1700000000,
+        },
+        ActionJournalEntry {
+            action: "Resize".to_owned(),
+            arguments: vec!["Increase".to_owned(), "Left".to_owned()],
+            tab_position: 2,
+            client_id: 3,
+            timestamp: 1700000010,
+        },
+    ]);
+    let protobuf_event: ProtobufEvent = action_journal_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        action_journal_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_plugin_response_event() {
+    use crate::data::PluginResponse;
+    use prost::Message;
+    for plugin_response in [
+        PluginResponse::ok("git-status-1", Some("clean".to_owned())),
+        PluginResponse::error("git-status-2", "not a git repository"),
+        PluginResponse::timed_out("project-index-1"),
+        PluginResponse::unavailable("project-index-2"),
+    ] {
+        let plugin_response_event = Event::PluginResponse(plugin_response);
+        let protobuf_event: ProtobufEvent = plugin_response_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            plugin_response_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_store_events() {
+    use crate::data::StoreScope;
+    use prost::Message;
+    for store_event in [
+        Event::StoreValue(
+            StoreScope::Plugin,
+            "recent_files:/home/user/project".to_owned(),
+            Some("src/main.rs".to_owned()),
+        ),
+        Event::StoreValue(StoreScope::Session, "bookmarks".to_owned(), None),
+        Event::StoreChanged(
+            StoreScope::Global,
+            "theme".to_owned(),
+            Some("dracula".to_owned()),
+        ),
+        Event::StoreChanged(StoreScope::Global, "theme".to_owned(), None),
+    ] {
+        let protobuf_event: ProtobufEvent = store_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            store_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
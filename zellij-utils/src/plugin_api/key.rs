@@ -2,13 +2,36 @@ pub use super::generated_api::api::key::{
     key::{KeyModifier, MainKey, NamedKey},
     Key as ProtobufKey,
 };
-use crate::data::{CharOrArrow, Direction, Key};
+use crate::data::{BareKey, CharOrArrow, Direction, Key, KeyModifiers};
 
 use std::convert::TryFrom;
 
 impl TryFrom<ProtobufKey> for Key {
     type Error = &'static str;
     fn try_from(protobuf_key: ProtobufKey) -> Result<Self, &'static str> {
+        if !protobuf_key.modifiers.is_empty() {
+            let mut modifiers = KeyModifiers::default();
+            for modifier in &protobuf_key.modifiers {
+                match KeyModifier::from_i32(*modifier) {
+                    Some(KeyModifier::Ctrl) => modifiers.ctrl = true,
+                    Some(KeyModifier::Alt) => modifiers.alt = true,
+                    Some(KeyModifier::Shift) => modifiers.shift = true,
+                    Some(KeyModifier::Super) => modifiers.super_key = true,
+                    None => return Err("invalid key modifier"),
+                }
+            }
+            let bare_key = match protobuf_key.main_key.ok_or("invalid key")? {
+                MainKey::Char(encoded_key) => match char_index_to_char(encoded_key) {
+                    '\n' => BareKey::Enter,
+                    character => BareKey::Char(character),
+                },
+                MainKey::Key(key_index) => {
+                    let key = NamedKey::from_i32(key_index).ok_or("invalid_key")?;
+                    BareKey::try_from(named_key_to_key(key))?
+                },
+            };
+            return Ok(Key::with_modifiers(bare_key, modifiers));
+        }
         let key_modifier = parse_optional_modifier(&protobuf_key);
         match key_modifier {
             Some(KeyModifier::Ctrl) => {
@@ -19,6 +42,9 @@ impl TryFrom<ProtobufKey> for Key {
                 let char_or_arrow = CharOrArrow::from_main_key(protobuf_key.main_key)?;
                 Ok(Key::Alt(char_or_arrow))
             },
+            Some(KeyModifier::Shift) | Some(KeyModifier::Super) => {
+                Err("Shift and Super are only supported in the modifiers field")
+            },
             None => match protobuf_key.main_key.as_ref().ok_or("invalid key")? {
                 MainKey::Char(_key_index) => {
                     let character = char_from_main_key(protobuf_key.main_key)?;
@@ -39,46 +65,57 @@ impl TryFrom<Key> for ProtobufKey {
         match key {
             Key::PageDown => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::PageDown as i32)),
             }),
             Key::PageUp => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::PageUp as i32)),
             }),
             Key::Left => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::LeftArrow as i32)),
             }),
             Key::Down => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::DownArrow as i32)),
             }),
             Key::Up => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::UpArrow as i32)),
             }),
             Key::Right => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::RightArrow as i32)),
             }),
             Key::Home => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Home as i32)),
             }),
             Key::End => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::End as i32)),
             }),
             Key::Backspace => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Backspace as i32)),
             }),
             Key::Delete => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Delete as i32)),
             }),
             Key::Insert => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Insert as i32)),
             }),
             Key::F(index) => {
@@ -99,11 +136,13 @@ impl TryFrom<Key> for ProtobufKey {
                 };
                 Ok(ProtobufKey {
                     modifier: None,
+                    modifiers: vec![],
                     main_key,
                 })
             },
             Key::Char(character) => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Char((character as u8) as i32)),
             }),
             Key::Alt(char_or_arrow) => {
@@ -122,27 +161,48 @@ impl TryFrom<Key> for ProtobufKey {
                 };
                 Ok(ProtobufKey {
                     modifier: Some(KeyModifier::Alt as i32),
+                    modifiers: vec![],
                     main_key: Some(main_key),
                 })
             },
             Key::Ctrl(character) => Ok(ProtobufKey {
                 modifier: Some(KeyModifier::Ctrl as i32),
+                modifiers: vec![],
                 main_key: Some(MainKey::Char((character as u8) as i32)),
             }),
             Key::BackTab => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Tab as i32)),
             }),
             Key::Null => {
                 Ok(ProtobufKey {
                     modifier: None,
+                    modifiers: vec![],
                     main_key: None, // TODO: does this break deserialization?
                 })
             },
             Key::Esc => Ok(ProtobufKey {
                 modifier: None,
+                modifiers: vec![],
                 main_key: Some(MainKey::Key(NamedKey::Esc as i32)),
             }),
+            Key::WithModifiers(bare_key, modifiers) => {
+                let mut protobuf_key = ProtobufKey::try_from(Key::from(bare_key))?;
+                if modifiers.ctrl {
+                    protobuf_key.modifiers.push(KeyModifier::Ctrl as i32);
+                }
+                if modifiers.alt {
+                    protobuf_key.modifiers.push(KeyModifier::Alt as i32);
+                }
+                if modifiers.shift {
+                    protobuf_key.modifiers.push(KeyModifier::Shift as i32);
+                }
+                if modifiers.super_key {
+                    protobuf_key.modifiers.push(KeyModifier::Super as i32);
+                }
+                Ok(protobuf_key)
+            },
         }
     }
 }
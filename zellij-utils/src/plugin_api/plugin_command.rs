@@ -1,29 +1,40 @@
 pub use super::generated_api::api::{
-    action::{PaneIdAndShouldFloat, SwitchToModePayload},
-    event::{EventNameList as ProtobufEventNameList, Header},
+    action::{
+        MovePaneToTabPayload, PaneIdAndShouldFloat, PipePaneToFilePayload, SwitchToModePayload,
+    },
+    event::{EventNameList as ProtobufEventNameList, Header, StoreScope as ProtobufStoreScope},
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
         plugin_command::Payload, CliPipeOutputPayload, CommandName, ContextItem, EnvVariable,
-        ExecCmdPayload, HttpVerb as ProtobufHttpVerb, IdAndNewName, MessageToPluginPayload,
-        MovePayload, NewPluginArgs as ProtobufNewPluginArgs, OpenCommandPanePayload,
-        OpenFilePayload, PaneId as ProtobufPaneId, PaneType as ProtobufPaneType,
-        PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
-        RequestPluginPermissionPayload, ResizePayload, RunCommandPayload, SetTimeoutPayload,
-        SubscribePayload, SwitchSessionPayload, SwitchTabToPayload, UnsubscribePayload,
-        WebRequestPayload,
+        ExecCmdPayload, GetPaneScrollbackPayload, HttpVerb as ProtobufHttpVerb, IdAndNewName,
+        MessageToPluginPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
+        OpenCommandPanePayload, OpenFilePayload, PaneId as ProtobufPaneId,
+        PaneType as ProtobufPaneType, PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
+        RegisterKeybindPayload, ReplyToPluginRequestPayload, RequestPluginPermissionPayload,
+        ResizePayload, RunCommandPayload, SendPluginRequestPayload, SetTimeoutPayload,
+        StoreKeyPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToPayload,
+        UnsubscribePayload, WebRequestPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
 };
 
+use super::action::{
+    change_floating_pane_coordinates_from_payload, change_floating_pane_coordinates_payload,
+    floating_pane_coordinates_from_protobuf, floating_pane_coordinates_to_protobuf,
+    move_pane_to_tab_from_payload, move_pane_to_tab_payload, set_pane_pinned_from_payload,
+    set_pane_pinned_payload, swap_panes_from_payload, swap_panes_payload,
+};
+use super::event::{store_entry_from_payload, store_entry_payload};
 use crate::data::{
     ConnectToSession, HttpVerb, MessageToPlugin, NewPluginArgs, PaneId, PermissionType,
-    PluginCommand,
+    PluginCommand, PluginRequest,
 };
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::time::Duration;
 
 impl Into<HttpVerb> for ProtobufHttpVerb {
     fn into(self) -> HttpVerb {
@@ -134,9 +145,12 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
             Some(CommandName::OpenFileFloating) => match protobuf_plugin_command.payload {
                 Some(Payload::OpenFileFloatingPayload(file_to_open_payload)) => {
                     match file_to_open_payload.file_to_open {
-                        Some(file_to_open) => {
-                            Ok(PluginCommand::OpenFileFloating(file_to_open.try_into()?))
-                        },
+                        Some(file_to_open) => Ok(PluginCommand::OpenFileFloating(
+                            file_to_open.try_into()?,
+                            file_to_open_payload
+                                .floating_pane_coordinates
+                                .map(floating_pane_coordinates_from_protobuf),
+                        )),
                         None => Err("Malformed open file payload"),
                     }
                 },
@@ -158,6 +172,9 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                     match file_to_open_payload.file_to_open {
                         Some(file_to_open) => Ok(PluginCommand::OpenTerminalFloating(
                             file_to_open.try_into()?,
+                            file_to_open_payload
+                                .floating_pane_coordinates
+                                .map(floating_pane_coordinates_from_protobuf),
                         )),
                         None => Err("Malformed open terminal floating payload"),
                     }
@@ -180,6 +197,9 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                     match command_to_run_payload.command_to_run {
                         Some(command_to_run) => Ok(PluginCommand::OpenCommandPaneFloating(
                             command_to_run.try_into()?,
+                            command_to_run_payload
+                                .floating_pane_coordinates
+                                .map(floating_pane_coordinates_from_protobuf),
                         )),
                         None => Err("Malformed open command pane floating payload"),
                     }
@@ -692,43 +712,152 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 _ => Err("Mismatched payload for PipeOutput"),
             },
             Some(CommandName::MessageToPlugin) => match protobuf_plugin_command.payload {
-                Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
-                    plugin_url,
-                    plugin_config,
-                    message_name,
-                    message_payload,
-                    message_args,
-                    new_plugin_args,
-                })) => {
-                    let plugin_config: BTreeMap<String, String> = plugin_config
-                        .into_iter()
-                        .map(|e| (e.name, e.value))
-                        .collect();
-                    let message_args: BTreeMap<String, String> = message_args
-                        .into_iter()
-                        .map(|e| (e.name, e.value))
-                        .collect();
-                    Ok(PluginCommand::MessageToPlugin(MessageToPlugin {
-                        plugin_url,
-                        plugin_config,
-                        message_name,
-                        message_payload,
-                        message_args,
-                        new_plugin_args: new_plugin_args.and_then(|protobuf_new_plugin_args| {
-                            Some(NewPluginArgs {
-                                should_float: protobuf_new_plugin_args.should_float,
-                                pane_id_to_replace: protobuf_new_plugin_args
-                                    .pane_id_to_replace
-                                    .and_then(|p_id| PaneId::try_from(p_id).ok()),
-                                pane_title: protobuf_new_plugin_args.pane_title,
-                                cwd: protobuf_new_plugin_args.cwd.map(|cwd| PathBuf::from(cwd)),
-                                skip_cache: protobuf_new_plugin_args.skip_cache,
-                            })
-                        }),
-                    }))
-                },
+                Some(Payload::MessageToPluginPayload(message_to_plugin_payload)) => Ok(
+                    PluginCommand::MessageToPlugin(message_to_plugin_payload.into()),
+                ),
                 _ => Err("Mismatched payload for PipeOutput"),
             },
+            Some(CommandName::GetPaneScrollback) => match protobuf_plugin_command.payload {
+                Some(Payload::GetPaneScrollbackPayload(GetPaneScrollbackPayload {
+                    pane_id: Some(pane_id),
+                    scrollback_lines,
+                })) => Ok(PluginCommand::GetPaneScrollback(
+                    pane_id.try_into()?,
+                    scrollback_lines as usize,
+                )),
+                _ => Err("Mismatched payload for GetPaneScrollback"),
+            },
+            Some(CommandName::PipePaneToFile) => match protobuf_plugin_command.payload {
+                Some(Payload::PipePaneToFilePayload(payload)) => Ok(PluginCommand::PipePaneToFile(
+                    payload.pane_id,
+                    PathBuf::from(payload.path),
+                    payload.strip_ansi,
+                )),
+                _ => Err("Mismatched payload for PipePaneToFile"),
+            },
+            Some(CommandName::StopPipePaneToFile) => match protobuf_plugin_command.payload {
+                Some(Payload::StopPipePaneToFilePayload(pane_id)) => {
+                    Ok(PluginCommand::StopPipePaneToFile(pane_id))
+                },
+                _ => Err("Mismatched payload for StopPipePaneToFile"),
+            },
+            Some(CommandName::MoveTab) => match protobuf_plugin_command.payload {
+                Some(Payload::MoveTabPayload(move_payload)) => match move_payload.direction {
+                    Some(direction) => Ok(PluginCommand::MoveTab(direction.try_into()?)),
+                    None => Err("Malformed move tab payload"),
+                },
+                _ => Err("Mismatched payload for MoveTab"),
+            },
+            Some(CommandName::MoveTabToIndex) => match protobuf_plugin_command.payload {
+                Some(Payload::MoveTabToIndexPayload(tab_index)) => {
+                    Ok(PluginCommand::MoveTabToIndex(tab_index))
+                },
+                _ => Err("Mismatched payload for MoveTabToIndex"),
+            },
+            Some(CommandName::SwapPanes) => match protobuf_plugin_command.payload {
+                Some(Payload::SwapPanesPayload(payload)) => {
+                    let (first_pane_id, second_pane_id) = swap_panes_from_payload(payload);
+                    Ok(PluginCommand::SwapPanes(first_pane_id, second_pane_id))
+                },
+                _ => Err("Mismatched payload for SwapPanes"),
+            },
+            Some(CommandName::ChangeFloatingPaneCoordinates) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::ChangeFloatingPaneCoordinatesPayload(payload)) => {
+                        let (pane_id, coordinates) =
+                            change_floating_pane_coordinates_from_payload(payload)?;
+                        Ok(PluginCommand::ChangeFloatingPaneCoordinates(
+                            pane_id,
+                            coordinates,
+                        ))
+                    },
+                    _ => Err("Mismatched payload for ChangeFloatingPaneCoordinates"),
+                }
+            },
+            Some(CommandName::SetPanePinned) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPanePinnedPayload(payload)) => {
+                    let (pane_id, should_be_pinned) = set_pane_pinned_from_payload(payload);
+                    Ok(PluginCommand::SetPanePinned(pane_id, should_be_pinned))
+                },
+                _ => Err("Mismatched payload for SetPanePinned"),
+            },
+            Some(CommandName::GetActionJournal) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("GetActionJournal should not have a payload")
+                } else {
+                    Ok(PluginCommand::GetActionJournal)
+                }
+            },
+            Some(CommandName::SendPluginRequest) => match protobuf_plugin_command.payload {
+                Some(Payload::SendPluginRequestPayload(SendPluginRequestPayload {
+                    request_id,
+                    timeout_ms,
+                    message: Some(message),
+                })) => Ok(PluginCommand::SendPluginRequest(PluginRequest::new(
+                    request_id,
+                    message.into(),
+                    Duration::from_millis(timeout_ms),
+                ))),
+                _ => Err("Mismatched payload for SendPluginRequest"),
+            },
+            Some(CommandName::ReplyToPluginRequest) => match protobuf_plugin_command.payload {
+                Some(Payload::ReplyToPluginRequestPayload(ReplyToPluginRequestPayload {
+                    requester_plugin_id,
+                    response: Some(response),
+                })) => Ok(PluginCommand::ReplyToPluginRequest(
+                    requester_plugin_id,
+                    response.try_into()?,
+                )),
+                _ => Err("Mismatched payload for ReplyToPluginRequest"),
+            },
+            Some(CommandName::StoreSet) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreSetPayload(store_entry_payload)) => {
+                    let (scope, key, value) = store_entry_from_payload(store_entry_payload)?;
+                    Ok(PluginCommand::StoreSet(scope, key, value))
+                },
+                _ => Err("Mismatched payload for StoreSet"),
+            },
+            Some(CommandName::StoreGet) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreGetPayload(StoreKeyPayload { scope, key })) => {
+                    Ok(PluginCommand::StoreGet(scope.try_into()?, key))
+                },
+                _ => Err("Mismatched payload for StoreGet"),
+            },
+            Some(CommandName::StoreWatch) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreWatchPayload(StoreKeyPayload { scope, key })) => {
+                    Ok(PluginCommand::StoreWatch(scope.try_into()?, key))
+                },
+                _ => Err("Mismatched payload for StoreWatch"),
+            },
+            Some(CommandName::SwapPaneWith) => match protobuf_plugin_command.payload {
+                Some(Payload::SwapPaneWithPayload(move_payload)) => match move_payload.direction {
+                    Some(direction) => Ok(PluginCommand::SwapPaneWith(direction.try_into()?)),
+                    None => Err("Malformed swap pane with payload"),
+                },
+                _ => Err("Mismatched payload for SwapPaneWith"),
+            },
+            Some(CommandName::RegisterKeybind) => match protobuf_plugin_command.payload {
+                Some(Payload::RegisterKeybindPayload(payload)) => {
+                    match (ProtobufInputMode::from_i32(payload.input_mode), payload.key) {
+                        (Some(protobuf_input_mode), Some(key)) => {
+                            Ok(PluginCommand::RegisterKeybind(
+                                protobuf_input_mode.try_into()?,
+                                key.try_into()?,
+                                payload.intercept,
+                            ))
+                        },
+                        _ => Err("Malformed register keybind payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for RegisterKeybind"),
+            },
+            Some(CommandName::MovePaneToTab) => match protobuf_plugin_command.payload {
+                Some(Payload::MovePaneToTabPayload(payload)) => {
+                    let (pane_id, tab, should_float) = move_pane_to_tab_from_payload(payload)?;
+                    Ok(PluginCommand::MovePaneToTab(pane_id, tab, should_float))
+                },
+                _ => Err("Mismatched payload for MovePaneToTab"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -772,40 +901,53 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 name: CommandName::OpenFile as i32,
                 payload: Some(Payload::OpenFilePayload(OpenFilePayload {
                     file_to_open: Some(file_to_open.try_into()?),
+                    floating_pane_coordinates: None,
                 })),
             }),
-            PluginCommand::OpenFileFloating(file_to_open) => Ok(ProtobufPluginCommand {
-                name: CommandName::OpenFileFloating as i32,
-                payload: Some(Payload::OpenFileFloatingPayload(OpenFilePayload {
-                    file_to_open: Some(file_to_open.try_into()?),
-                })),
-            }),
+            PluginCommand::OpenFileFloating(file_to_open, coordinates) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::OpenFileFloating as i32,
+                    payload: Some(Payload::OpenFileFloatingPayload(OpenFilePayload {
+                        file_to_open: Some(file_to_open.try_into()?),
+                        floating_pane_coordinates: coordinates
+                            .map(floating_pane_coordinates_to_protobuf),
+                    })),
+                })
+            },
             PluginCommand::OpenTerminal(cwd) => Ok(ProtobufPluginCommand {
                 name: CommandName::OpenTerminal as i32,
                 payload: Some(Payload::OpenTerminalPayload(OpenFilePayload {
                     file_to_open: Some(cwd.try_into()?),
+                    floating_pane_coordinates: None,
                 })),
             }),
-            PluginCommand::OpenTerminalFloating(cwd) => Ok(ProtobufPluginCommand {
+            PluginCommand::OpenTerminalFloating(cwd, coordinates) => Ok(ProtobufPluginCommand {
                 name: CommandName::OpenTerminalFloating as i32,
                 payload: Some(Payload::OpenTerminalFloatingPayload(OpenFilePayload {
                     file_to_open: Some(cwd.try_into()?),
+                    floating_pane_coordinates: coordinates
+                        .map(floating_pane_coordinates_to_protobuf),
                 })),
             }),
             PluginCommand::OpenCommandPane(command_to_run) => Ok(ProtobufPluginCommand {
                 name: CommandName::OpenCommandPane as i32,
                 payload: Some(Payload::OpenCommandPanePayload(OpenCommandPanePayload {
                     command_to_run: Some(command_to_run.try_into()?),
+                    floating_pane_coordinates: None,
                 })),
             }),
-            PluginCommand::OpenCommandPaneFloating(command_to_run) => Ok(ProtobufPluginCommand {
-                name: CommandName::OpenCommandPaneFloating as i32,
-                payload: Some(Payload::OpenCommandPaneFloatingPayload(
-                    OpenCommandPanePayload {
-                        command_to_run: Some(command_to_run.try_into()?),
-                    },
-                )),
-            }),
+            PluginCommand::OpenCommandPaneFloating(command_to_run, coordinates) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::OpenCommandPaneFloating as i32,
+                    payload: Some(Payload::OpenCommandPaneFloatingPayload(
+                        OpenCommandPanePayload {
+                            command_to_run: Some(command_to_run.try_into()?),
+                            floating_pane_coordinates: coordinates
+                                .map(floating_pane_coordinates_to_protobuf),
+                        },
+                    )),
+                })
+            },
             PluginCommand::SwitchTabTo(tab_index) => Ok(ProtobufPluginCommand {
                 name: CommandName::SwitchTabTo as i32,
                 payload: Some(Payload::SwitchTabToPayload(SwitchTabToPayload {
@@ -1088,12 +1230,14 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 name: CommandName::OpenTerminalInPlace as i32,
                 payload: Some(Payload::OpenTerminalInPlacePayload(OpenFilePayload {
                     file_to_open: Some(cwd.try_into()?),
+                    floating_pane_coordinates: None,
                 })),
             }),
             PluginCommand::OpenFileInPlace(file_to_open) => Ok(ProtobufPluginCommand {
                 name: CommandName::OpenFileInPlace as i32,
                 payload: Some(Payload::OpenFileInPlacePayload(OpenFilePayload {
                     file_to_open: Some(file_to_open.try_into()?),
+                    floating_pane_coordinates: None,
                 })),
             }),
             PluginCommand::OpenCommandPaneInPlace(command_to_run) => Ok(ProtobufPluginCommand {
@@ -1101,6 +1245,7 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 payload: Some(Payload::OpenCommandPaneInPlacePayload(
                     OpenCommandPanePayload {
                         command_to_run: Some(command_to_run.try_into()?),
+                        floating_pane_coordinates: None,
                     },
                 )),
             }),
@@ -1172,39 +1317,275 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     output,
                 })),
             }),
-            PluginCommand::MessageToPlugin(message_to_plugin) => {
-                let plugin_config: Vec<_> = message_to_plugin
-                    .plugin_config
-                    .into_iter()
-                    .map(|(name, value)| ContextItem { name, value })
-                    .collect();
-                let message_args: Vec<_> = message_to_plugin
-                    .message_args
-                    .into_iter()
-                    .map(|(name, value)| ContextItem { name, value })
-                    .collect();
+            PluginCommand::MessageToPlugin(message_to_plugin) => Ok(ProtobufPluginCommand {
+                name: CommandName::MessageToPlugin as i32,
+                payload: Some(Payload::MessageToPluginPayload(message_to_plugin.into())),
+            }),
+            PluginCommand::GetPaneScrollback(pane_id, scrollback_lines) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::GetPaneScrollback as i32,
+                    payload: Some(Payload::GetPaneScrollbackPayload(
+                        GetPaneScrollbackPayload {
+                            pane_id: Some(pane_id.try_into()?),
+                            scrollback_lines: scrollback_lines as u32,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::PipePaneToFile(terminal_pane_id, path, strip_ansi) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::PipePaneToFile as i32,
+                    payload: Some(Payload::PipePaneToFilePayload(PipePaneToFilePayload {
+                        pane_id: terminal_pane_id,
+                        path: path.display().to_string(),
+                        strip_ansi,
+                    })),
+                })
+            },
+            PluginCommand::StopPipePaneToFile(terminal_pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::StopPipePaneToFile as i32,
+                payload: Some(Payload::StopPipePaneToFilePayload(terminal_pane_id)),
+            }),
+            PluginCommand::MoveTab(direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::MoveTab as i32,
+                payload: Some(Payload::MoveTabPayload(MovePayload {
+                    direction: Some(direction.try_into()?),
+                })),
+            }),
+            PluginCommand::MoveTabToIndex(tab_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::MoveTabToIndex as i32,
+                payload: Some(Payload::MoveTabToIndexPayload(tab_index)),
+            }),
+            PluginCommand::SwapPanes(first_pane_id, second_pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::SwapPanes as i32,
+                payload: Some(Payload::SwapPanesPayload(swap_panes_payload(
+                    first_pane_id,
+                    second_pane_id,
+                ))),
+            }),
+            PluginCommand::ChangeFloatingPaneCoordinates(pane_id, coordinates) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ChangeFloatingPaneCoordinates as i32,
+                    payload: Some(Payload::ChangeFloatingPaneCoordinatesPayload(
+                        change_floating_pane_coordinates_payload(pane_id, coordinates),
+                    )),
+                })
+            },
+            PluginCommand::SetPanePinned(pane_id, should_be_pinned) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetPanePinned as i32,
+                payload: Some(Payload::SetPanePinnedPayload(set_pane_pinned_payload(
+                    pane_id,
+                    should_be_pinned,
+                ))),
+            }),
+            PluginCommand::GetActionJournal => Ok(ProtobufPluginCommand {
+                name: CommandName::GetActionJournal as i32,
+                payload: None,
+            }),
+            PluginCommand::SendPluginRequest(plugin_request) => Ok(ProtobufPluginCommand {
+                name: CommandName::SendPluginRequest as i32,
+                payload: Some(Payload::SendPluginRequestPayload(
+                    SendPluginRequestPayload {
+                        request_id: plugin_request.request_id,
+                        timeout_ms: plugin_request.timeout.as_millis() as u64,
+                        message: Some(plugin_request.message.into()),
+                    },
+                )),
+            }),
+            PluginCommand::ReplyToPluginRequest(requester_plugin_id, response) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ReplyToPluginRequest as i32,
+                    payload: Some(Payload::ReplyToPluginRequestPayload(
+                        ReplyToPluginRequestPayload {
+                            requester_plugin_id,
+                            response: Some(response.into()),
+                        },
+                    )),
+                })
+            },
+            PluginCommand::StoreSet(scope, key, value) => Ok(ProtobufPluginCommand {
+                name: CommandName::StoreSet as i32,
+                payload: Some(Payload::StoreSetPayload(store_entry_payload(
+                    scope, key, value,
+                ))),
+            }),
+            PluginCommand::StoreGet(scope, key) => Ok(ProtobufPluginCommand {
+                name: CommandName::StoreGet as i32,
+                payload: Some(Payload::StoreGetPayload(StoreKeyPayload {
+                    scope: ProtobufStoreScope::from(scope) as i32,
+                    key,
+                })),
+            }),
+            PluginCommand::StoreWatch(scope, key) => Ok(ProtobufPluginCommand {
+                name: CommandName::StoreWatch as i32,
+                payload: Some(Payload::StoreWatchPayload(StoreKeyPayload {
+                    scope: ProtobufStoreScope::from(scope) as i32,
+                    key,
+                })),
+            }),
+            PluginCommand::SwapPaneWith(direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::SwapPaneWith as i32,
+                payload: Some(Payload::SwapPaneWithPayload(MovePayload {
+                    direction: Some(direction.try_into()?),
+                })),
+            }),
+            PluginCommand::RegisterKeybind(input_mode, key, intercept) => {
                 Ok(ProtobufPluginCommand {
-                    name: CommandName::MessageToPlugin as i32,
-                    payload: Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
-                        plugin_url: message_to_plugin.plugin_url,
-                        plugin_config,
-                        message_name: message_to_plugin.message_name,
-                        message_payload: message_to_plugin.message_payload,
-                        message_args,
-                        new_plugin_args: message_to_plugin.new_plugin_args.map(|m_t_p| {
-                            ProtobufNewPluginArgs {
-                                should_float: m_t_p.should_float,
-                                pane_id_to_replace: m_t_p
-                                    .pane_id_to_replace
-                                    .and_then(|p_id| ProtobufPaneId::try_from(p_id).ok()),
-                                pane_title: m_t_p.pane_title,
-                                cwd: m_t_p.cwd.map(|cwd| cwd.display().to_string()),
-                                skip_cache: m_t_p.skip_cache,
-                            }
-                        }),
+                    name: CommandName::RegisterKeybind as i32,
+                    payload: Some(Payload::RegisterKeybindPayload(RegisterKeybindPayload {
+                        input_mode: ProtobufInputMode::try_from(input_mode)? as i32,
+                        key: Some(key.try_into()?),
+                        intercept,
                     })),
                 })
             },
+            PluginCommand::MovePaneToTab(pane_id, tab, should_float) => Ok(ProtobufPluginCommand {
+                name: CommandName::MovePaneToTab as i32,
+                payload: Some(Payload::MovePaneToTabPayload(move_pane_to_tab_payload(
+                    pane_id,
+                    tab,
+                    should_float,
+                ))),
+            }),
+        }
+    }
+}
+
+impl From<MessageToPluginPayload> for MessageToPlugin {
+    fn from(message_to_plugin_payload: MessageToPluginPayload) -> Self {
+        let plugin_config: BTreeMap<String, String> = message_to_plugin_payload
+            .plugin_config
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        let message_args: BTreeMap<String, String> = message_to_plugin_payload
+            .message_args
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        MessageToPlugin {
+            plugin_url: message_to_plugin_payload.plugin_url,
+            plugin_config,
+            message_name: message_to_plugin_payload.message_name,
+            message_payload: message_to_plugin_payload.message_payload,
+            message_args,
+            new_plugin_args: message_to_plugin_payload.new_plugin_args.map(
+                |protobuf_new_plugin_args| NewPluginArgs {
+                    should_float: protobuf_new_plugin_args.should_float,
+                    pane_id_to_replace: protobuf_new_plugin_args
+                        .pane_id_to_replace
+                        .and_then(|p_id| PaneId::try_from(p_id).ok()),
+                    pane_title: protobuf_new_plugin_args.pane_title,
+                    cwd: protobuf_new_plugin_args.cwd.map(PathBuf::from),
+                    skip_cache: protobuf_new_plugin_args.skip_cache,
+                },
+            ),
+        }
+    }
+}
+
+impl From<MessageToPlugin> for MessageToPluginPayload {
+    fn from(message_to_plugin: MessageToPlugin) -> Self {
+        let plugin_config: Vec<_> = message_to_plugin
+            .plugin_config
+            .into_iter()
+            .map(|(name, value)| ContextItem { name, value })
+            .collect();
+        let message_args: Vec<_> = message_to_plugin
+            .message_args
+            .into_iter()
+            .map(|(name, value)| ContextItem { name, value })
+            .collect();
+        MessageToPluginPayload {
+            plugin_url: message_to_plugin.plugin_url,
+            plugin_config,
+            message_name: message_to_plugin.message_name,
+            message_payload: message_to_plugin.message_payload,
+            message_args,
+            new_plugin_args: message_to_plugin
+                .new_plugin_args
+                .map(|m_t_p| ProtobufNewPluginArgs {
+                    should_float: m_t_p.should_float,
+                    pane_id_to_replace: m_t_p
+                        .pane_id_to_replace
+                        .and_then(|p_id| ProtobufPaneId::try_from(p_id).ok()),
+                    pane_title: m_t_p.pane_title,
+                    cwd: m_t_p.cwd.map(|cwd| cwd.display().to_string()),
+                    skip_cache: m_t_p.skip_cache,
+                }),
+        }
+    }
+}
+
+#[test]
+fn serialize_send_plugin_request_plugin_command() {
+    use prost::Message;
+    let plugin_request = PluginRequest::new(
+        "git-status-1",
+        MessageToPlugin::new("status")
+            .with_plugin_url("file:/plugins/git-status.wasm")
+            .with_payload("/home/user/project")
+            .with_args(BTreeMap::from([("porcelain".to_owned(), "v2".to_owned())])),
+        Duration::from_millis(1500),
+    );
+    let protobuf_plugin_command: ProtobufPluginCommand =
+        PluginCommand::SendPluginRequest(plugin_request)
+            .try_into()
+            .unwrap();
+    let serialized_protobuf_plugin_command = protobuf_plugin_command.encode_to_vec();
+    let deserialized_protobuf_plugin_command: ProtobufPluginCommand =
+        Message::decode(serialized_protobuf_plugin_command.as_slice()).unwrap();
+    let deserialized_plugin_command: PluginCommand =
+        deserialized_protobuf_plugin_command.try_into().unwrap();
+    match deserialized_plugin_command {
+        PluginCommand::SendPluginRequest(plugin_request) => {
+            assert_eq!(plugin_request.request_id, "git-status-1");
+            assert_eq!(plugin_request.timeout, Duration::from_millis(1500));
+            assert_eq!(plugin_request.message.message_name, "status");
+            assert_eq!(
+                plugin_request.message.plugin_url,
+                Some("file:/plugins/git-status.wasm".to_owned())
+            );
+            assert_eq!(
+                plugin_request.message.message_payload,
+                Some("/home/user/project".to_owned())
+            );
+            assert_eq!(
+                plugin_request.message.message_args,
+                BTreeMap::from([("porcelain".to_owned(), "v2".to_owned())])
+            );
+        },
+        plugin_command => panic!("Unexpected plugin command: {:?}", plugin_command),
+    }
+}
+
+#[test]
+fn serialize_reply_to_plugin_request_plugin_command() {
+    use crate::data::PluginResponse;
+    use prost::Message;
+    for response in [
+        PluginResponse::ok("git-status-1", Some("clean".to_owned())),
+        PluginResponse::ok("git-status-2", None),
+        PluginResponse::error("git-status-3", "not a git repository"),
+    ] {
+        let protobuf_plugin_command: ProtobufPluginCommand =
+            PluginCommand::ReplyToPluginRequest(7, response.clone())
+                .try_into()
+                .unwrap();
+        let serialized_protobuf_plugin_command = protobuf_plugin_command.encode_to_vec();
+        let deserialized_protobuf_plugin_command: ProtobufPluginCommand =
+            Message::decode(serialized_protobuf_plugin_command.as_slice()).unwrap();
+        let deserialized_plugin_command: PluginCommand =
+            deserialized_protobuf_plugin_command.try_into().unwrap();
+        match deserialized_plugin_command {
+            PluginCommand::ReplyToPluginRequest(requester_plugin_id, deserialized_response) => {
+                assert_eq!(requester_plugin_id, 7);
+                assert_eq!(
+                    deserialized_response, response,
+                    "PluginCommand properly serialized/deserialized without change"
+                );
+            },
+            plugin_command => panic!("Unexpected plugin command: {:?}", plugin_command),
         }
     }
 }
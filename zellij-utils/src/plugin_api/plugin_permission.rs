@@ -24,6 +24,12 @@ impl TryFrom<ProtobufPermissionType> for PermissionType {
             ProtobufPermissionType::MessageAndLaunchOtherPlugins => {
                 Ok(PermissionType::MessageAndLaunchOtherPlugins)
             },
+            ProtobufPermissionType::ReadPaneContents => Ok(PermissionType::ReadPaneContents),
+            ProtobufPermissionType::InterceptKeys => Ok(PermissionType::InterceptKeys),
+            ProtobufPermissionType::WriteToFiles => Ok(PermissionType::WriteToFiles),
+            ProtobufPermissionType::ShareDataWithOtherPlugins => {
+                Ok(PermissionType::ShareDataWithOtherPlugins)
+            },
         }
     }
 }
@@ -49,6 +55,12 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
             PermissionType::MessageAndLaunchOtherPlugins => {
                 Ok(ProtobufPermissionType::MessageAndLaunchOtherPlugins)
             },
+            PermissionType::ReadPaneContents => Ok(ProtobufPermissionType::ReadPaneContents),
+            PermissionType::InterceptKeys => Ok(ProtobufPermissionType::InterceptKeys),
+            PermissionType::WriteToFiles => Ok(ProtobufPermissionType::WriteToFiles),
+            PermissionType::ShareDataWithOtherPlugins => {
+                Ok(ProtobufPermissionType::ShareDataWithOtherPlugins)
+            },
         }
     }
 }
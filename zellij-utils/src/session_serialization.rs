@@ -7,6 +7,7 @@
 //! }
 //! ```
 //!
+use kdl::KdlNode;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
@@ -20,6 +21,7 @@ use crate::{
 };
 
 const INDENT: &str = "    ";
+const PANE_SNAPSHOT_FILE_PREFIX: &str = "pane_snapshot_";
 const DOUBLE_INDENT: &str = "        ";
 const TRIPLE_INDENT: &str = "            ";
 
@@ -57,9 +59,22 @@ pub struct PaneLayoutManifest {
     pub run: Option<Run>,
     pub cwd: Option<PathBuf>,
     pub is_borderless: bool,
+    pub is_pinned: bool,
     pub title: Option<String>,
     pub is_focused: bool,
     pub pane_contents: Option<String>,
+    pub pane_snapshot_file: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+/// The name of the file (relative to the session layout) holding the binary snapshot of the
+/// contents of this terminal pane
+pub fn pane_snapshot_file_name(terminal_id: u32) -> String {
+    format!("{}{}", PANE_SNAPSHOT_FILE_PREFIX, terminal_id)
+}
+
+pub fn is_pane_snapshot_file_name(file_name: &str) -> bool {
+    file_name.starts_with(PANE_SNAPSHOT_FILE_PREFIX)
 }
 
 pub fn serialize_session_layout(
@@ -191,6 +206,7 @@ fn kdl_string_from_tiled_pane(
         cwd,
         layout.focus,
         &layout.pane_initial_contents,
+        &layout.pane_initial_snapshot_file,
         pane_contents,
         has_children,
     );
@@ -200,10 +216,12 @@ fn kdl_string_from_tiled_pane(
         || layout.external_children_index.is_some()
         || !args.is_empty()
         || plugin.is_some()
-        || command.is_some();
+        || command.is_some()
+        || !layout.env.is_empty();
     if has_child_attributes {
         kdl_string.push_str(" {\n");
         stringify_args(args, &mut kdl_string);
+        stringify_env(&layout.env, &mut kdl_string);
         stringify_start_suspended(&command, &mut kdl_string);
         stringify_plugin(plugin, plugin_config, &mut kdl_string);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
@@ -262,6 +280,7 @@ fn stringify_pane_title_and_attributes(
     cwd: Option<PathBuf>,
     focus: Option<bool>,
     initial_pane_contents: &Option<String>,
+    initial_pane_snapshot_file: &Option<PathBuf>,
     pane_contents: &mut BTreeMap<String, String>,
     has_children: bool,
 ) -> String {
@@ -282,7 +301,14 @@ fn stringify_pane_title_and_attributes(
     if focus.unwrap_or(false) {
         kdl_string.push_str(&" focus=true");
     }
-    if let Some(initial_pane_contents) = initial_pane_contents.as_ref() {
+    if let Some(initial_pane_snapshot_file) = initial_pane_snapshot_file.as_ref() {
+        if command.is_none() && edit.is_none() {
+            kdl_string.push_str(&format!(
+                " snapshot_file=\"{}\"",
+                initial_pane_snapshot_file.display()
+            ));
+        }
+    } else if let Some(initial_pane_contents) = initial_pane_contents.as_ref() {
         if command.is_none() && edit.is_none() {
             let file_name = format!("initial_contents_{}", pane_contents.keys().len() + 1);
             kdl_string.push_str(&format!(" contents_file=\"{}\"", file_name));
@@ -303,6 +329,19 @@ fn stringify_args(args: Vec<String>, kdl_string: &mut String) {
     }
 }
 
+fn stringify_env(env: &BTreeMap<String, String>, kdl_string: &mut String) {
+    if !env.is_empty() {
+        kdl_string.push_str(&indent("env {\n", INDENT));
+        for (name, value) in env {
+            // the kdl node takes care of quoting and escaping both the name and the value
+            let mut env_variable = KdlNode::new(name.as_str());
+            env_variable.push(value.as_str());
+            kdl_string.push_str(&indent(&format!("{}\n", env_variable), DOUBLE_INDENT));
+        }
+        kdl_string.push_str(&indent("}\n", INDENT));
+    }
+}
+
 fn stringify_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
@@ -399,6 +438,9 @@ fn stringify_floating_layout_attributes(layout: &FloatingPaneLayout, kdl_string:
         },
         None => {},
     }
+    if layout.pinned == Some(true) {
+        kdl_string.push_str(&indent("pinned true\n", INDENT));
+    }
 }
 
 fn stringify_start_suspended(command: &Option<String>, kdl_string: &mut String) {
@@ -561,6 +603,7 @@ fn kdl_string_from_floating_pane(
         cwd,
         layout.focus,
         &layout.pane_initial_contents,
+        &layout.pane_initial_snapshot_file,
         pane_contents,
         has_children,
     );
@@ -568,6 +611,7 @@ fn kdl_string_from_floating_pane(
     stringify_start_suspended(&command, &mut kdl_string);
     stringify_floating_layout_attributes(&layout, &mut kdl_string);
     stringify_args(args, &mut kdl_string);
+    stringify_env(&layout.env, &mut kdl_string);
     stringify_plugin(plugin, plugin_config, &mut kdl_string);
     kdl_string.push_str("}\n");
     kdl_string
@@ -577,7 +621,16 @@ fn tiled_pane_layout_from_manifest(
     manifest: Option<&PaneLayoutManifest>,
     split_size: Option<SplitSize>,
 ) -> TiledPaneLayout {
-    let (run, borderless, is_expanded_in_stack, name, focus, pane_initial_contents) = manifest
+    let (
+        run,
+        borderless,
+        is_expanded_in_stack,
+        name,
+        focus,
+        pane_initial_contents,
+        pane_initial_snapshot_file,
+        env,
+    ) = manifest
         .map(|g| {
             let mut run = g.run.clone();
             if let Some(cwd) = &g.cwd {
@@ -594,9 +647,11 @@ fn tiled_pane_layout_from_manifest(
                 g.title.clone(),
                 Some(g.is_focused),
                 g.pane_contents.clone(),
+                g.pane_snapshot_file.as_ref().map(PathBuf::from),
+                g.env.clone(),
             )
         })
-        .unwrap_or((None, false, false, None, None, None));
+        .unwrap_or((None, false, false, None, None, None, None, BTreeMap::new()));
     TiledPaneLayout {
         split_size,
         run,
@@ -605,6 +660,8 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        pane_initial_snapshot_file,
+        env,
         ..Default::default()
     }
 }
@@ -690,10 +747,13 @@ fn get_floating_panes_layout_from_panegeoms(
                 width: Some(m.geom.cols.into()),
                 x: Some(PercentOrFixed::Fixed(m.geom.x)),
                 y: Some(PercentOrFixed::Fixed(m.geom.y)),
+                pinned: Some(m.is_pinned),
                 run,
                 focus: Some(m.is_focused),
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
+                pane_initial_snapshot_file: m.pane_snapshot_file.as_ref().map(PathBuf::from),
+                env: m.env.clone(),
             }
         })
         .collect()
@@ -920,6 +980,7 @@ fn get_split_sizes(constraints: &Vec<Constraint>) -> Vec<Option<SplitSize>> {
 mod tests {
 
     use super::*;
+    use crate::input::command::RunCommand;
     use crate::pane_size::Dimension;
     use expect_test::expect;
     use serde_json::Value;
@@ -1123,6 +1184,205 @@ mod tests {
 }"#]]
         .assert_eq(&kdl.0);
     }
+    #[test]
+    fn pane_snapshot_files() {
+        let geoms: Vec<PaneLayoutManifest> = PANEGEOMS_JSON[0]
+            .iter()
+            .map(|pg| parse_panegeom_from_json(pg))
+            .map(|geom| PaneLayoutManifest {
+                geom,
+                ..Default::default()
+            })
+            .collect();
+        let mut tiled_panes = geoms.clone();
+        tiled_panes[0].pane_snapshot_file = Some(pane_snapshot_file_name(1));
+        // command panes are re-run rather than restored
+        tiled_panes[1].run = Some(Run::Command(RunCommand::new(PathBuf::from("htop"))));
+        tiled_panes[1].pane_snapshot_file = Some(pane_snapshot_file_name(2));
+        let floating_panes = vec![PaneLayoutManifest {
+            geom: geoms[0].geom,
+            pane_snapshot_file: Some(pane_snapshot_file_name(3)),
+            ..Default::default()
+        }];
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes,
+            floating_panes,
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let (kdl, pane_contents) = serialize_session_layout(global_layout_manifest).unwrap();
+        assert!(
+            pane_contents.is_empty(),
+            "snapshots are not written as pane contents"
+        );
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane command="htop" size=1 {
+                        start_suspended true
+                    }
+                    pane snapshot_file="pane_snapshot_1"
+                    pane size=2
+                    floating_panes {
+                        pane snapshot_file="pane_snapshot_3" {
+                            height "100%"
+                            width "100%"
+                            x 0
+                            y 1
+                        }
+                    }
+                }
+            }"#]]
+        .assert_eq(&kdl);
+
+        let layout = Layout::from_kdl(
+            &kdl,
+            "/tmp/session-folder/session-layout.kdl".to_owned(),
+            None,
+            None,
+        )
+        .unwrap();
+        let (_tab_name, tiled_panes, floating_panes) = layout.tabs().remove(0);
+        assert_eq!(tiled_panes.children[0].pane_initial_snapshot_file, None);
+        assert_eq!(
+            tiled_panes.children[1].pane_initial_snapshot_file,
+            Some(PathBuf::from("/tmp/session-folder/pane_snapshot_1")),
+            "snapshot files are relative to the layout"
+        );
+        assert_eq!(
+            floating_panes[0].pane_initial_snapshot_file,
+            Some(PathBuf::from("/tmp/session-folder/pane_snapshot_3")),
+        );
+    }
+    #[test]
+    fn env_variables() {
+        let mut env = BTreeMap::new();
+        env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+        env.insert("GREETING".to_owned(), "say \"hi\"".to_owned());
+        env.insert("with space".to_owned(), "a\\b".to_owned());
+        let geoms: Vec<PaneLayoutManifest> = PANEGEOMS_JSON[0]
+            .iter()
+            .map(|pg| parse_panegeom_from_json(pg))
+            .map(|geom| PaneLayoutManifest {
+                geom,
+                ..Default::default()
+            })
+            .collect();
+        let mut tiled_panes = geoms.clone();
+        tiled_panes[0].run = Some(Run::Command(RunCommand::new(PathBuf::from("htop"))));
+        tiled_panes[0].env = env.clone();
+        tiled_panes[1].env = env.clone();
+        let floating_panes = vec![PaneLayoutManifest {
+            geom: geoms[0].geom,
+            env,
+            ..Default::default()
+        }];
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes,
+            floating_panes,
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"layout {
+    tab name="Tab #1" {
+        pane size=1 {
+            env {
+                GREETING "say \"hi\""
+                RUST_LOG "debug"
+                "with space" "a\\b"
+            }
+        }
+        pane command="htop" {
+            env {
+                GREETING "say \"hi\""
+                RUST_LOG "debug"
+                "with space" "a\\b"
+            }
+            start_suspended true
+        }
+        pane size=2
+        floating_panes {
+            pane {
+                height "100%"
+                width "100%"
+                x 0
+                y 1
+                env {
+                    GREETING "say \"hi\""
+                    RUST_LOG "debug"
+                    "with space" "a\\b"
+                }
+            }
+        }
+    }
+}"#]]
+        .assert_eq(&kdl.0);
+    }
+    #[test]
+    fn pinned_floating_panes() {
+        let geoms: Vec<PaneLayoutManifest> = PANEGEOMS_JSON[0]
+            .iter()
+            .map(|pg| parse_panegeom_from_json(pg))
+            .map(|geom| PaneLayoutManifest {
+                geom,
+                ..Default::default()
+            })
+            .collect();
+        let floating_panes = vec![
+            PaneLayoutManifest {
+                geom: geoms[0].geom,
+                is_pinned: true,
+                ..Default::default()
+            },
+            PaneLayoutManifest {
+                geom: geoms[1].geom,
+                ..Default::default()
+            },
+        ];
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![geoms[0].clone()],
+            floating_panes,
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let (kdl, _pane_contents) = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    floating_panes {
+                        pane {
+                            height "100%"
+                            width "100%"
+                            x 0
+                            y 1
+                            pinned true
+                        }
+                        pane {
+                            height 1
+                            width "100%"
+                            x 0
+                            y 0
+                        }
+                    }
+                }
+            }"#]]
+        .assert_eq(&kdl);
+
+        let layout = Layout::from_kdl(&kdl, "session-layout.kdl".to_owned(), None, None).unwrap();
+        let (_tab_name, _tiled_panes, floating_panes) = layout.tabs().remove(0);
+        assert_eq!(floating_panes[0].pinned, Some(true));
+        assert_eq!(floating_panes[1].pinned, None);
+    }
     // utility functions
     fn parse_panegeom_from_json(data_str: &str) -> PaneGeom {
         //
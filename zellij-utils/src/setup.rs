@@ -361,16 +361,7 @@ impl Setup {
             Some(cli_config_options) => config.options.merge(cli_config_options),
             None => config.options.clone(),
         };
-
-        config.themes = config.themes.merge(get_default_themes());
-
-        let user_theme_dir = config_options.theme_dir.clone().or_else(|| {
-            get_theme_dir(cli_args.config_dir.clone().or_else(find_default_config_dir))
-                .filter(|dir| dir.exists())
-        });
-        if let Some(user_theme_dir) = user_theme_dir {
-            config.themes = config.themes.merge(Themes::from_dir(user_theme_dir)?);
-        }
+        Setup::merge_themes(&mut config, &config_options, cli_args)?;
 
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
             setup
@@ -386,6 +377,36 @@ impl Setup {
         Ok((config, layout, config_options))
     }
 
+    /// Re-reads the configuration file of a running client.
+    /// Unlike [`Setup::from_cli_args`], this neither runs setup commands nor reparses the layout,
+    /// so that it can be called any number of times while a session is running.
+    pub fn reload_config(cli_args: &CliArgs) -> Result<(Config, Options), ConfigError> {
+        let mut config = Config::try_from(cli_args)?;
+        let config_options = match cli_args.command.clone() {
+            Some(Command::Options(options)) => config.options.merge(options.into()),
+            _ => config.options.clone(),
+        };
+        Setup::merge_themes(&mut config, &config_options, cli_args)?;
+        Ok((config, config_options))
+    }
+
+    fn merge_themes(
+        config: &mut Config,
+        config_options: &Options,
+        cli_args: &CliArgs,
+    ) -> Result<(), ConfigError> {
+        config.themes = config.themes.merge(get_default_themes());
+
+        let user_theme_dir = config_options.theme_dir.clone().or_else(|| {
+            get_theme_dir(cli_args.config_dir.clone().or_else(find_default_config_dir))
+                .filter(|dir| dir.exists())
+        });
+        if let Some(user_theme_dir) = user_theme_dir {
+            config.themes = config.themes.merge(Themes::from_dir(user_theme_dir)?);
+        }
+        Ok(())
+    }
+
     /// General setup helpers
     pub fn from_cli(&self) -> Result<()> {
         if self.clean {